@@ -0,0 +1,69 @@
+// Keeps RustyNote usable without a connection. The build stamps VERSION and
+// PRECACHE (see stamp_service_worker.sh), so every build gets a fresh cache.
+const VERSION = "development";
+const CACHE = `rustynote-${VERSION}`;
+
+const PRECACHE = [
+  "./",
+  // __PRECACHE__
+];
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(
+    caches
+      .open(CACHE)
+      .then((cache) => cache.addAll(PRECACHE))
+      .then(() => self.skipWaiting()),
+  );
+});
+
+// Drops the caches of previous builds.
+self.addEventListener("activate", (event) => {
+  event.waitUntil(
+    caches
+      .keys()
+      .then((keys) =>
+        Promise.all(
+          keys
+            .filter((key) => key.startsWith("rustynote-") && key !== CACHE)
+            .map((key) => caches.delete(key)),
+        ),
+      )
+      .then(() => self.clients.claim()),
+  );
+});
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  if (request.method !== "GET" || new URL(request.url).origin !== self.location.origin) {
+    return;
+  }
+
+  // The page itself comes from the network when possible, so a new build is
+  // picked up right away, and from the cache when offline.
+  if (request.mode === "navigate") {
+    event.respondWith(
+      fetch(request)
+        .then((response) => store(request, response))
+        .catch(() =>
+          caches.match(request).then((cached) => cached || caches.match("./")),
+        ),
+    );
+    return;
+  }
+
+  // Everything else is versioned by the cache name, so the cache wins.
+  event.respondWith(
+    caches
+      .match(request)
+      .then((cached) => cached || fetch(request).then((response) => store(request, response))),
+  );
+});
+
+function store(request, response) {
+  if (response.ok) {
+    const copy = response.clone();
+    caches.open(CACHE).then((cache) => cache.put(request, copy));
+  }
+  return response;
+}
@@ -0,0 +1,23 @@
+#!/bin/sh
+# Trunk post-build hook: writes the build version and the list of built files
+# into the service worker, so browsers install it as an update and precache
+# everything the app needs offline.
+set -eu
+
+cd "$TRUNK_STAGING_DIR"
+
+worker=service-worker.js
+files=$(find . -type f ! -name "$worker" | sed 's|^\./||' | sort)
+version=$(cat $files | cksum | cut -d ' ' -f 1)
+
+list=$(mktemp)
+for file in $files; do
+  printf '  "%s",\n' "$file" >> "$list"
+done
+
+stamped=$(mktemp)
+sed "s|^const VERSION = .*|const VERSION = \"$version\";|" "$worker" |
+  awk -v list="$list" '/^ *\/\/ __PRECACHE__$/ { while ((getline line < list) > 0) print line; next } { print }' > "$stamped"
+
+cat "$stamped" > "$worker"
+rm "$list" "$stamped"
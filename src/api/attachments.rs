@@ -0,0 +1,358 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use gloo::file::{File, FileList, futures::read_as_bytes};
+use sha2::{Digest, Sha256};
+
+use comrak::{
+    Arena,
+    nodes::{AstNode, NodeValue},
+    parse_document,
+};
+
+use crate::api::{parser::Dialect, storage::PersistentStore};
+
+/// URL scheme used to reference locally stored attachments from markdown.
+pub const ATTACHMENT_SCHEME: &str = "attachment:";
+
+/// Prefix of the storage keys of attachments, followed by the id.
+pub const STORAGE_PREFIX: &str = "attachment:";
+const ID_LENGTH: usize = 32;
+
+/// An image that was pasted or dropped into the editor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attachment {
+    pub id: String,
+    pub name: String,
+    pub data_url: String,
+}
+
+impl Attachment {
+    /// Creates an attachment from raw file bytes, keyed by the hash of its content.
+    ///
+    /// ```rust
+    /// use rustynote::api::attachments::Attachment;
+    ///
+    /// let attachment = Attachment::from_bytes("dot.png", "image/png", &[1, 2, 3]);
+    /// assert!(attachment.data_url.starts_with("data:image/png;base64,"));
+    /// assert_eq!(attachment.url(), format!("attachment:{}", attachment.id));
+    /// ```
+    pub fn from_bytes(name: &str, mime_type: &str, bytes: &[u8]) -> Self {
+        Attachment {
+            id: content_hash(bytes),
+            name: name.to_string(),
+            data_url: format!("data:{};base64,{}", mime_type, STANDARD.encode(bytes)),
+        }
+    }
+
+    /// Returns the stable `attachment:` URL of this attachment.
+    pub fn url(&self) -> String {
+        format!("{ATTACHMENT_SCHEME}{}", self.id)
+    }
+
+    /// Returns the markdown image syntax that embeds this attachment.
+    pub fn to_markdown(&self) -> String {
+        format!("![{}]({})", alt_text(&self.name), self.url())
+    }
+}
+
+fn content_hash(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    let mut hash: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    hash.truncate(ID_LENGTH);
+    hash
+}
+
+fn alt_text(file_name: &str) -> String {
+    let stem = file_name
+        .rsplit_once('.')
+        .map(|(stem, _)| stem)
+        .unwrap_or(file_name);
+
+    stem.chars()
+        .filter(|c| !matches!(c, '[' | ']'))
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Lists the ids of all attachments referenced in the given text, without duplicates.
+///
+/// ```rust
+/// use rustynote::api::attachments::referenced_attachments;
+///
+/// let ids = referenced_attachments("![a](attachment:ab12) ![b](attachment:ab12)");
+/// assert_eq!(ids, vec![String::from("ab12")]);
+/// ```
+pub fn referenced_attachments(text: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+
+    for (index, _) in text.match_indices(ATTACHMENT_SCHEME) {
+        let id = attachment_id_at(text, index + ATTACHMENT_SCHEME.len());
+        if !id.is_empty() && !ids.iter().any(|known| known == id) {
+            ids.push(id.to_string());
+        }
+    }
+
+    ids
+}
+
+/// Replaces the `attachment:` destinations of images and links with the URL returned
+/// by `lookup`. Code and text that merely show the syntax keep it as written, and so do
+/// references that can't be resolved.
+///
+/// ```rust
+/// use rustynote::api::{attachments::resolve_attachments, parser::Dialect};
+///
+/// let markdown = "![dot](attachment:ab12) `![dot](attachment:ab12)`";
+/// let resolved = resolve_attachments(markdown, &Dialect::GitHub, |id| {
+///     (id == "ab12").then(|| String::from("data:image/png;base64,AAAA"))
+/// });
+/// assert_eq!(resolved, "![dot](data:image/png;base64,AAAA) `![dot](attachment:ab12)`");
+/// ```
+pub fn resolve_attachments(
+    markdown: &str,
+    dialect: &Dialect,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> String {
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &dialect.options());
+    let line_starts: Vec<usize> = [0]
+        .into_iter()
+        .chain(markdown.match_indices('\n').map(|(index, _)| index + 1))
+        .collect();
+    let offset = |line: usize, column: usize| {
+        let start = line_starts.get(line.checked_sub(1)?)?;
+        (start + column).checked_sub(1)
+    };
+
+    let mut replacements = Vec::new();
+    for node in root.descendants() {
+        let data = node.data.borrow();
+        let (NodeValue::Image(link) | NodeValue::Link(link)) = &data.value else {
+            continue;
+        };
+        let Some(resolved) = attachment_id(&link.url).and_then(&lookup) else {
+            continue;
+        };
+        let (Some(start), Some(end)) = (
+            offset(data.sourcepos.start.line, data.sourcepos.start.column),
+            offset(data.sourcepos.end.line, data.sourcepos.end.column + 1),
+        ) else {
+            continue;
+        };
+        // The destination comes first after the text of the link, a reference link has none.
+        let text_end = node
+            .last_child()
+            .and_then(|child| {
+                let end = child.data.borrow().sourcepos.end;
+                offset(end.line, end.column + 1)
+            })
+            .unwrap_or(start);
+        if let Some(url_start) = markdown
+            .get(text_end..end)
+            .and_then(|source| source.find(&link.url))
+        {
+            let url_start = text_end + url_start;
+            replacements.push((url_start..url_start + link.url.len(), resolved));
+        }
+    }
+
+    replacements.sort_by_key(|(range, _)| range.start);
+    replacements.dedup_by_key(|(range, _)| range.start);
+
+    let mut result = markdown.to_string();
+    for (range, resolved) in replacements.into_iter().rev() {
+        result.replace_range(range, &resolved);
+    }
+    result
+}
+
+/// Like [`resolve_attachments`], but on a parsed document right before it is rendered.
+pub fn resolve_attachment_urls<'a>(root: &'a AstNode<'a>, lookup: impl Fn(&str) -> Option<String>) {
+    for node in root.descendants() {
+        if let NodeValue::Image(link) | NodeValue::Link(link) = &mut node.data.borrow_mut().value
+            && let Some(resolved) = attachment_id(&link.url).and_then(&lookup)
+        {
+            link.url = resolved;
+        }
+    }
+}
+
+/// The id of an `attachment:` URL.
+fn attachment_id(url: &str) -> Option<&str> {
+    url.strip_prefix(ATTACHMENT_SCHEME)
+        .filter(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn attachment_id_at(text: &str, start: usize) -> &str {
+    let rest = &text[start..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_hexdigit())
+        .unwrap_or(rest.len());
+    &rest[..end]
+}
+
+/// Persists the attachment in the store, before the note references it.
+pub async fn store_attachment(
+    store: &PersistentStore,
+    attachment: &Attachment,
+) -> Result<(), String> {
+    store
+        .store(
+            &format!("{STORAGE_PREFIX}{}", attachment.id),
+            attachment.data_url.clone(),
+        )
+        .await
+        .map_err(|error| error.to_string())
+}
+
+/// Loads the data URL of a stored attachment. The store keeps all values in memory, so
+/// this doesn't wait for the browser storage.
+pub fn load_attachment(store: &PersistentStore, id: &str) -> Option<String> {
+    store.loaded(&format!("{STORAGE_PREFIX}{id}"))
+}
+
+/// Returns `true` when the file list contains at least one image.
+pub fn contains_images(files: &FileList) -> bool {
+    files.iter().any(is_image)
+}
+
+/// Reads every image of a pasted or dropped file list into an attachment.
+pub async fn read_image_files(files: FileList) -> Vec<Attachment> {
+    let mut attachments = Vec::new();
+
+    for file in files.iter().filter(|file| is_image(file)) {
+        if let Ok(bytes) = read_as_bytes(file).await {
+            attachments.push(Attachment::from_bytes(
+                &file.name(),
+                &file.raw_mime_type(),
+                &bytes,
+            ));
+        }
+    }
+
+    attachments
+}
+
+/// Image types whose data URLs the preview shows, comrak drops other `data:` URLs.
+const IMAGE_TYPES: [&str; 4] = ["image/png", "image/gif", "image/jpeg", "image/webp"];
+
+fn is_image(file: &File) -> bool {
+    IMAGE_TYPES.contains(&file.raw_mime_type().as_str())
+}
+
+/// Inlines all stored attachments as data URLs so the markdown can be used outside
+/// RustyNote.
+pub fn embed_attachments(store: &PersistentStore, markdown: &str, dialect: &Dialect) -> String {
+    resolve_attachments(markdown, dialect, |id| load_attachment(store, id))
+}
+
+/// Inlines all stored attachments of a parsed document as data URLs.
+pub fn embed_attachment_urls<'a>(store: &PersistentStore, root: &'a AstNode<'a>) {
+    resolve_attachment_urls(root, |id| load_attachment(store, id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_content_has_identical_id() {
+        let first = Attachment::from_bytes("a.png", "image/png", b"same");
+        let second = Attachment::from_bytes("b.png", "image/png", b"same");
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(first.id.len(), ID_LENGTH);
+    }
+
+    #[test]
+    fn test_different_content_has_different_id() {
+        let first = Attachment::from_bytes("a.png", "image/png", b"first");
+        let second = Attachment::from_bytes("a.png", "image/png", b"second");
+
+        assert_ne!(first.id, second.id);
+    }
+
+    #[test]
+    fn test_data_url() {
+        let attachment = Attachment::from_bytes("a.gif", "image/gif", b"GIF");
+
+        assert_eq!(attachment.data_url, "data:image/gif;base64,R0lG");
+    }
+
+    #[test]
+    fn test_markdown_uses_file_name_as_alt_text() {
+        let attachment = Attachment::from_bytes("Screenshot [1].png", "image/png", b"png");
+
+        assert_eq!(
+            attachment.to_markdown(),
+            format!("![Screenshot 1](attachment:{})", attachment.id)
+        );
+    }
+
+    #[test]
+    fn test_referenced_attachments() {
+        let markdown = "# Title\n![one](attachment:abc)\ntext\n![two](attachment:def \"title\")";
+
+        assert_eq!(
+            referenced_attachments(markdown),
+            vec![String::from("abc"), String::from("def")]
+        );
+    }
+
+    #[test]
+    fn test_referenced_attachments_ignores_empty_ids() {
+        assert!(referenced_attachments("attachment: nothing here").is_empty());
+    }
+
+    fn data_url(id: &str) -> Option<String> {
+        (id == "abc").then(|| String::from("data:image/png;base64,QQ=="))
+    }
+
+    #[test]
+    fn test_resolve_attachments_keeps_unknown_references() {
+        let markdown = "![one](attachment:abc) ![two](attachment:def)";
+
+        assert_eq!(
+            resolve_attachments(markdown, &Dialect::GitHub, data_url),
+            "![one](data:image/png;base64,QQ==) ![two](attachment:def)"
+        );
+    }
+
+    #[test]
+    fn test_resolve_attachments_with_multibyte_text() {
+        let markdown = "> - Äpfel ![🍎](attachment:abc \"attachment:abc\") Öl\n";
+
+        assert_eq!(
+            resolve_attachments(markdown, &Dialect::GitHub, data_url),
+            "> - Äpfel ![🍎](data:image/png;base64,QQ== \"attachment:abc\") Öl\n"
+        );
+    }
+
+    #[test]
+    fn test_resolve_attachments_skips_code_and_text() {
+        let markdown = "Write `![a](attachment:abc)` or attachment:abc\n\n\
+            ```md\n![a](attachment:abc)\n```\n\n[![a](attachment:abc)](attachment:abc)\n";
+
+        assert_eq!(
+            resolve_attachments(markdown, &Dialect::GitHub, data_url),
+            "Write `![a](attachment:abc)` or attachment:abc\n\n\
+            ```md\n![a](attachment:abc)\n```\n\n\
+            [![a](data:image/png;base64,QQ==)](data:image/png;base64,QQ==)\n"
+        );
+    }
+
+    #[test]
+    fn test_resolve_attachment_urls_in_syntax_tree() {
+        let arena = Arena::new();
+        let options = Dialect::GitHub.options();
+        let root = parse_document(&arena, "![a](attachment:abc) `attachment:abc`", &options);
+        resolve_attachment_urls(root, data_url);
+
+        let mut html = String::new();
+        comrak::format_html(root, &options, &mut html).unwrap();
+        assert_eq!(
+            html,
+            "<p><img src=\"data:image/png;base64,QQ==\" alt=\"a\" /> <code>attachment:abc</code></p>\n"
+        );
+    }
+}
@@ -0,0 +1,414 @@
+use std::sync::OnceLock;
+
+use syntect::parsing::SyntaxSet;
+
+use crate::api::{
+    emoji::{is_shortcode_char, search_shortcodes},
+    toc::heading_anchors,
+};
+
+/// Most completions offered at once.
+pub const MAX_COMPLETIONS: usize = 8;
+
+/// Fence languages listed first, in this order.
+const POPULAR_LANGUAGES: &[&str] = &[
+    "rust",
+    "python",
+    "javascript",
+    "bash",
+    "json",
+    "yaml",
+    "html",
+    "css",
+    "sql",
+    "java",
+    "go",
+    "c",
+    "c++",
+    "markdown",
+];
+
+/// What the word at the caret is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompletionKind {
+    /// The language after a code fence: ```` ```ru ````.
+    Language,
+    /// An emoji shortcode: `:ta`.
+    Emoji,
+    /// A heading anchor in a link: `](#us`.
+    Anchor,
+    /// The label of a reference link: `][do`.
+    Reference,
+}
+
+/// The partly typed word at the caret that can be completed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompletionQuery {
+    pub kind: CompletionKind,
+    /// Byte position where the typed word starts.
+    pub start: usize,
+    /// Byte position of the caret.
+    pub end: usize,
+    pub prefix: String,
+}
+
+/// A suggestion for a [`CompletionQuery`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Completion {
+    pub label: String,
+    /// Shown next to the label, e.g. the emoji or the heading.
+    pub detail: String,
+    /// Replaces the typed word.
+    pub insert: String,
+}
+
+/// A language known to the highlighter with the words that select it after a fence.
+struct Language {
+    name: String,
+    tags: Vec<String>,
+}
+
+fn languages() -> &'static [Language] {
+    static LANGUAGES: OnceLock<Vec<Language>> = OnceLock::new();
+    LANGUAGES.get_or_init(|| {
+        SyntaxSet::load_defaults_newlines()
+            .syntaxes()
+            .iter()
+            .filter(|syntax| !syntax.hidden)
+            .map(|syntax| {
+                let mut tags: Vec<String> = Vec::new();
+                let name = syntax.name.to_lowercase();
+                for tag in std::iter::once(&name).chain(&syntax.file_extensions) {
+                    let tag = tag.to_lowercase();
+                    if tag.chars().all(is_language_char) && !tags.contains(&tag) {
+                        tags.push(tag);
+                    }
+                }
+                Language {
+                    name: syntax.name.clone(),
+                    tags,
+                }
+            })
+            .filter(|language| !language.tags.is_empty())
+            .collect()
+    })
+}
+
+fn is_language_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '+' | '#' | '-' | '_' | '.')
+}
+
+/// Finds the word before the caret that can be completed.
+///
+/// ```rust
+/// use rustynote::api::completion::{CompletionKind, completion_query};
+///
+/// let query = completion_query("See [usage](#ins", 16).unwrap();
+/// assert_eq!((query.kind, query.prefix.as_str()), (CompletionKind::Anchor, "ins"));
+/// assert_eq!(completion_query("at 10:30", 8), None);
+/// ```
+pub fn completion_query(text: &str, caret: usize) -> Option<CompletionQuery> {
+    let line_start = text[..caret].rfind('\n').map_or(0, |index| index + 1);
+    let line = &text[line_start..caret];
+    let in_fence = inside_fence(&text[..line_start]);
+
+    let query = |kind, start: usize| {
+        Some(CompletionQuery {
+            kind,
+            start: line_start + start,
+            end: caret,
+            prefix: line[start..].to_string(),
+        })
+    };
+
+    if !in_fence && let Some(start) = fence_language_start(line) {
+        let line_end = text[caret..]
+            .find('\n')
+            .map_or(text.len(), |end| caret + end);
+        return if text[caret..line_end].trim().is_empty() {
+            query(CompletionKind::Language, start)
+        } else {
+            None
+        };
+    }
+    // Neither inside code blocks nor code spans.
+    if in_fence || line.matches('`').count() % 2 == 1 {
+        return None;
+    }
+
+    let word_start = line
+        .rfind(|c: char| !is_shortcode_char(c))
+        .map_or(0, |index| index + 1);
+    if line[..word_start].ends_with(':')
+        && line.len() - word_start >= 2
+        && !line[..word_start - 1]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == ':')
+    {
+        return query(CompletionKind::Emoji, word_start);
+    }
+
+    let anchor_start = line
+        .rfind(|c: char| c.is_whitespace() || "()#[]".contains(c))
+        .map_or(0, |index| index + 1);
+    if line[..anchor_start].ends_with("](#") {
+        return query(CompletionKind::Anchor, anchor_start);
+    }
+
+    let label_start = line.rfind(['[', ']']).map_or(0, |index| index + 1);
+    if line[..label_start].ends_with("][") {
+        return query(CompletionKind::Reference, label_start);
+    }
+
+    None
+}
+
+/// Whether the text ends inside a fenced code block.
+fn inside_fence(text: &str) -> bool {
+    let mut open: Option<(char, usize)> = None;
+    for line in text.lines() {
+        let Some((marker, length, info)) = fence(line) else {
+            continue;
+        };
+        match open {
+            None => open = Some((marker, length)),
+            Some((open_marker, open_length))
+                if marker == open_marker && length >= open_length && info.trim().is_empty() =>
+            {
+                open = None
+            }
+            Some(_) => {}
+        }
+    }
+    open.is_some()
+}
+
+/// Marker, length and info string of a code fence line.
+fn fence(line: &str) -> Option<(char, usize, &str)> {
+    let indentation = line.len() - line.trim_start_matches(' ').len();
+    let fence = &line[indentation..];
+    let marker = fence.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let length = fence.len() - fence.trim_start_matches(marker).len();
+    (indentation <= 3 && length >= 3).then_some((marker, length, &fence[length..]))
+}
+
+/// Byte position of the language in a line that opens a code block.
+fn fence_language_start(line: &str) -> Option<usize> {
+    let (_, _, language) = fence(line)?;
+    language
+        .chars()
+        .all(is_language_char)
+        .then_some(line.len() - language.len())
+}
+
+/// Suggestions for the word at the caret, the best first.
+///
+/// ```rust
+/// use rustynote::api::completion::{completion_query, completions};
+///
+/// let text = "# Install\n\nSee [steps](#in";
+/// let query = completion_query(text, text.len()).unwrap();
+/// assert_eq!(completions(text, &query)[0].insert, "install");
+/// ```
+pub fn completions(text: &str, query: &CompletionQuery) -> Vec<Completion> {
+    let prefix = query.prefix.to_lowercase();
+    let mut completions = match query.kind {
+        CompletionKind::Language => language_completions(&prefix),
+        CompletionKind::Emoji => search_shortcodes(&prefix)
+            .into_iter()
+            .map(|(shortcode, emoji)| Completion {
+                label: format!(":{shortcode}:"),
+                detail: emoji.to_string(),
+                insert: format!(":{shortcode}:"),
+            })
+            .collect(),
+        CompletionKind::Anchor => heading_anchors(text)
+            .into_iter()
+            .filter(|heading| {
+                heading.anchor.starts_with(&prefix) || heading.text.to_lowercase().contains(&prefix)
+            })
+            .map(|heading| Completion {
+                label: format!("#{}", heading.anchor),
+                detail: heading.text,
+                insert: heading.anchor,
+            })
+            .collect(),
+        CompletionKind::Reference => reference_definitions(text)
+            .into_iter()
+            .filter(|(label, _)| label.to_lowercase().contains(&prefix))
+            .map(|(label, destination)| Completion {
+                label: label.clone(),
+                detail: destination,
+                insert: label,
+            })
+            .collect(),
+    };
+    completions.truncate(MAX_COMPLETIONS);
+    completions
+}
+
+fn language_completions(prefix: &str) -> Vec<Completion> {
+    let popularity = |tag: &str| {
+        POPULAR_LANGUAGES
+            .iter()
+            .position(|popular| *popular == tag)
+            .unwrap_or(usize::MAX)
+    };
+
+    let mut matches: Vec<(&str, &str)> = languages()
+        .iter()
+        .filter_map(|language| {
+            let tag = language
+                .tags
+                .iter()
+                .filter(|tag| tag.starts_with(prefix))
+                .min_by_key(|tag| (popularity(tag) == usize::MAX, tag.len()))?;
+            Some((tag.as_str(), language.name.as_str()))
+        })
+        .collect();
+    matches.sort_by_key(|(tag, _)| (*tag != prefix, popularity(tag), tag.len(), *tag));
+
+    matches
+        .into_iter()
+        .map(|(tag, name)| Completion {
+            label: tag.to_string(),
+            detail: name.to_string(),
+            insert: tag.to_string(),
+        })
+        .collect()
+}
+
+/// Labels and destinations of the reference definitions like `[docs]: https://…`.
+/// Footnotes are left out.
+fn reference_definitions(text: &str) -> Vec<(String, String)> {
+    let mut definitions: Vec<(String, String)> = Vec::new();
+    for line in text.lines() {
+        let indentation = line.len() - line.trim_start_matches(' ').len();
+        let Some(definition) = line.trim_start_matches(' ').strip_prefix('[') else {
+            continue;
+        };
+        let Some((label, destination)) = definition.split_once("]:") else {
+            continue;
+        };
+        let is_duplicate = definitions
+            .iter()
+            .any(|(known, _)| known.eq_ignore_ascii_case(label));
+        if indentation > 3 || label.trim().is_empty() || label.starts_with('^') || is_duplicate {
+            continue;
+        }
+        let destination = destination.split_whitespace().next().unwrap_or_default();
+        definitions.push((label.to_string(), destination.to_string()));
+    }
+    definitions
+}
+
+/// Replaces the typed word with the completion and closes the syntax around it.
+/// Returns the new text and the caret position after the completion.
+pub fn apply_completion(
+    text: &str,
+    query: &CompletionQuery,
+    completion: &Completion,
+) -> (String, usize) {
+    let after = &text[query.end..];
+    let mut insert = completion.insert.clone();
+    let closing = match query.kind {
+        CompletionKind::Anchor => Some(')'),
+        CompletionKind::Reference => Some(']'),
+        CompletionKind::Language | CompletionKind::Emoji => None,
+    };
+    if let Some(closing) = closing
+        && !after.starts_with(closing)
+    {
+        insert.push(closing);
+    }
+
+    let mut new_text = text.to_string();
+    new_text.replace_range(query.start..query.end, &insert);
+    let mut caret = query.start + insert.len();
+    if closing.is_some_and(|closing| after.starts_with(closing)) {
+        caret += 1;
+    }
+    (new_text, caret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query_at_end(text: &str) -> Option<CompletionQuery> {
+        completion_query(text, text.len())
+    }
+
+    #[test]
+    fn test_language_after_opening_fence() {
+        let query = query_at_end("Intro\n```ru").unwrap();
+        assert_eq!(query.kind, CompletionKind::Language);
+        assert_eq!(query.prefix, "ru");
+        assert_eq!(completions("", &query)[0].label, "rust");
+    }
+
+    #[test]
+    fn test_no_language_on_closing_fence() {
+        assert_eq!(query_at_end("```rust\nfn main() {}\n```"), None);
+        assert!(query_at_end("```rust\nfn main() {}\n```\n\n~~~").is_some());
+    }
+
+    #[test]
+    fn test_popular_languages_come_first() {
+        let query = query_at_end("```").unwrap();
+        let labels: Vec<String> = completions("", &query)
+            .into_iter()
+            .map(|completion| completion.label)
+            .collect();
+        assert_eq!(&labels[..4], ["rust", "python", "javascript", "bash"]);
+        assert_eq!(labels.len(), MAX_COMPLETIONS);
+    }
+
+    #[test]
+    fn test_emoji_needs_two_letters_outside_words_and_code() {
+        assert_eq!(query_at_end(":t"), None);
+        assert_eq!(query_at_end("word:tad"), None);
+        assert_eq!(query_at_end("`code :tad"), None);
+        assert_eq!(query_at_end("```\n:tad"), None);
+
+        let query = query_at_end("Done :tad").unwrap();
+        assert_eq!(query.kind, CompletionKind::Emoji);
+        assert_eq!(completions("", &query)[0].insert, ":tada:");
+    }
+
+    #[test]
+    fn test_anchor_completion_closes_the_link() {
+        let text = "# Getting Started\n## FAQ\n\nRead [this](#get";
+        let query = query_at_end(text).unwrap();
+        let found = completions(text, &query);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].detail, "Getting Started");
+
+        let (new_text, caret) = apply_completion(text, &query, &found[0]);
+        assert!(new_text.ends_with("[this](#getting-started)"));
+        assert_eq!(caret, new_text.len());
+    }
+
+    #[test]
+    fn test_anchors_match_heading_text() {
+        let text = "## FAQ\n[x](#fa";
+        let query = query_at_end(text).unwrap();
+        assert_eq!(completions(text, &query)[0].insert, "faq");
+    }
+
+    #[test]
+    fn test_reference_labels() {
+        let text = "[Docs]: https://docs.rs \"Docs\"\n[^1]: note\n[docs]: dup\n\nSee [here][d";
+        let query = query_at_end(text).unwrap();
+        assert_eq!(query.kind, CompletionKind::Reference);
+        let found = completions(text, &query);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].detail, "https://docs.rs");
+
+        let with_bracket = format!("{text}]");
+        let (new_text, caret) = apply_completion(&with_bracket, &query, &found[0]);
+        assert!(new_text.ends_with("[here][Docs]"));
+        assert_eq!(caret, new_text.len());
+    }
+}
@@ -0,0 +1,558 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::{encryption::Sealed, parser::Dialect, wikilinks::rename_wikilinks};
+
+const UNTITLED: &str = "Untitled";
+
+/// A stored note. The content of the active document lives in the editor and is
+/// written back when another document is opened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Document {
+    pub id: u64,
+    pub name: String,
+    pub content: String,
+    /// Milliseconds since the Unix epoch.
+    pub modified: f64,
+    /// The file in an opened folder that the document is saved to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<LinkedFile>,
+    /// The encrypted text of a note protected by a passphrase. `content` stays empty
+    /// for such notes, so their text is never stored in the clear.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sealed: Option<Sealed>,
+    /// The dialect the document was last edited with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dialect: Option<Dialect>,
+}
+
+/// Links a document to a file of a folder opened through the File System Access API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkedFile {
+    /// Path relative to the opened folder, e.g. `notes/today.md`.
+    pub path: String,
+    /// The content of the file when it was last read or written, used to tell
+    /// external changes apart from edits in the editor.
+    pub base: String,
+    /// Last modification of the file in milliseconds since the Unix epoch.
+    pub disk_modified: f64,
+}
+
+impl Document {
+    /// Whether the document still has the name it was created with, e.g. `Untitled (2)`.
+    pub fn has_default_name(&self) -> bool {
+        self.name == UNTITLED
+            || self
+                .name
+                .strip_prefix(UNTITLED)
+                .and_then(|rest| rest.strip_prefix(" ("))
+                .and_then(|rest| rest.strip_suffix(')'))
+                .is_some_and(|number| number.parse::<u32>().is_ok())
+    }
+}
+
+/// All documents of the user, persisted as JSON.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DocumentLibrary {
+    pub documents: Vec<Document>,
+    pub active: u64,
+}
+
+impl DocumentLibrary {
+    pub fn active(&self) -> Option<&Document> {
+        self.get(self.active)
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Document> {
+        self.documents.iter().find(|document| document.id == id)
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<&Document> {
+        self.documents.iter().find(|document| document.name == name)
+    }
+
+    pub fn find_by_path(&self, path: &str) -> Option<&Document> {
+        self.documents
+            .iter()
+            .find(|document| document.file.as_ref().is_some_and(|file| file.path == path))
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.documents
+            .iter()
+            .map(|document| document.name.clone())
+            .collect()
+    }
+
+    /// Makes sure the content of the editor belongs to a document, e.g. on the first start.
+    pub fn ensure_active(&mut self, content: &str, now: f64) {
+        if self.active().is_none() {
+            let id = self.insert(UNTITLED, content, now);
+            self.active = id;
+        }
+    }
+
+    /// Writes the content of the editor back into the active document. Encrypted
+    /// documents are skipped, they are updated through [`DocumentLibrary::seal`].
+    pub fn store_active(&mut self, content: &str, now: f64) {
+        let active = self.active;
+        if let Some(document) = self
+            .documents
+            .iter_mut()
+            .find(|document| document.id == active)
+            && document.sealed.is_none()
+            && document.content != content
+        {
+            document.content = content.to_string();
+            document.modified = now;
+        }
+    }
+
+    /// Stores the current content and adds a new active document with the given content.
+    /// The name gets a number if another document already uses it.
+    ///
+    /// ```rust
+    /// use rustynote::api::documents::DocumentLibrary;
+    ///
+    /// let mut library = DocumentLibrary::default();
+    /// library.ensure_active("first", 0.0);
+    /// library.create("Notes", "second", "first", 1.0);
+    /// library.create("Notes", "third", "second", 2.0);
+    ///
+    /// assert_eq!(library.names(), vec!["Untitled", "Notes", "Notes (2)"]);
+    /// assert_eq!(library.active().unwrap().content, "third");
+    /// ```
+    pub fn create(&mut self, name: &str, content: &str, current_content: &str, now: f64) {
+        self.store_active(current_content, now);
+        let id = self.insert(name, content, now);
+        self.active = id;
+    }
+
+    /// Stores the current content and adds a new active document for a file of the
+    /// opened folder.
+    pub fn create_linked(&mut self, name: &str, file: LinkedFile, current_content: &str, now: f64) {
+        self.create(name, &file.base, current_content, now);
+        let active = self.active;
+        if let Some(document) = self
+            .documents
+            .iter_mut()
+            .find(|document| document.id == active)
+        {
+            document.file = Some(file);
+        }
+    }
+
+    /// Remembers the content and modification time of the linked file after it was read
+    /// or written.
+    pub fn update_link(&mut self, id: u64, base: &str, disk_modified: f64) {
+        if let Some(file) = self
+            .documents
+            .iter_mut()
+            .find(|document| document.id == id)
+            .and_then(|document| document.file.as_mut())
+        {
+            file.base = base.to_string();
+            file.disk_modified = disk_modified;
+        }
+    }
+
+    /// Stores the current content and adds a new active document that is encrypted.
+    pub fn create_sealed(&mut self, name: &str, sealed: Sealed, current_content: &str, now: f64) {
+        self.create(name, "", current_content, now);
+        self.seal(self.active, sealed, now);
+    }
+
+    /// Replaces the content of a document with its encrypted form.
+    pub fn seal(&mut self, id: u64, sealed: Sealed, now: f64) {
+        if let Some(document) = self.document_mut(id) {
+            document.content.clear();
+            document.sealed = Some(sealed);
+            document.modified = now;
+        }
+    }
+
+    /// Removes the encryption of a document and stores its text in the clear again.
+    pub fn unseal(&mut self, id: u64, content: &str, now: f64) {
+        if let Some(document) = self.document_mut(id) {
+            document.content = content.to_string();
+            document.sealed = None;
+            document.modified = now;
+        }
+    }
+
+    /// Remembers the dialect the active document is edited with.
+    pub fn set_dialect(&mut self, dialect: Dialect) {
+        let active = self.active;
+        if let Some(document) = self.document_mut(active) {
+            document.dialect = Some(dialect);
+        }
+    }
+
+    /// Stores the current content and switches to another document.
+    /// Returns the content of the opened document.
+    pub fn open(&mut self, id: u64, current_content: &str, now: f64) -> Option<String> {
+        let content = self.get(id)?.content.clone();
+        self.store_active(current_content, now);
+        self.active = id;
+        Some(content)
+    }
+
+    /// Renames the active document and updates the `[[links]]` of all notes that point to
+    /// it. Encrypted notes keep their links, their text can't be changed. Returns the
+    /// content of the active document, which changes when it links to itself.
+    pub fn rename_active(
+        &mut self,
+        name: &str,
+        current_content: &str,
+        now: f64,
+    ) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(String::from("The name can't be empty"));
+        }
+        let active = self.active;
+        if self.documents.iter().any(|document| {
+            document.id != active && document.name.to_lowercase() == name.to_lowercase()
+        }) {
+            return Err(format!("There already is a document called {name}"));
+        }
+
+        self.store_active(current_content, now);
+        let Some(old_name) = self.active().map(|document| document.name.clone()) else {
+            return Err(String::from("There is no document to rename"));
+        };
+
+        for document in &mut self.documents {
+            if document.sealed.is_some() {
+                continue;
+            }
+            let content = rename_wikilinks(&document.content, &old_name, name);
+            if content != document.content {
+                document.content = content;
+                document.modified = now;
+            }
+        }
+
+        let document = self
+            .document_mut(active)
+            .expect("the active document exists");
+        document.name = name.to_string();
+        document.modified = now;
+        Ok(document.content.clone())
+    }
+
+    /// Removes the active document and opens the most recently modified one,
+    /// or an empty document if it was the last one. Returns the content to show.
+    pub fn remove_active(&mut self, now: f64) -> String {
+        let active = self.active;
+        self.documents.retain(|document| document.id != active);
+
+        let next = self
+            .documents
+            .iter()
+            .max_by(|a, b| a.modified.total_cmp(&b.modified))
+            .map(|document| (document.id, document.content.clone()));
+
+        match next {
+            Some((id, content)) => {
+                self.active = id;
+                content
+            }
+            None => {
+                self.ensure_active("", now);
+                String::new()
+            }
+        }
+    }
+
+    fn insert(&mut self, name: &str, content: &str, now: f64) -> u64 {
+        let id = self
+            .documents
+            .iter()
+            .map(|document| document.id + 1)
+            .max()
+            .unwrap_or(1);
+
+        self.documents.push(Document {
+            id,
+            name: self.unique_name(name),
+            content: content.to_string(),
+            modified: now,
+            file: None,
+            sealed: None,
+            dialect: None,
+        });
+
+        id
+    }
+
+    fn document_mut(&mut self, id: u64) -> Option<&mut Document> {
+        self.documents.iter_mut().find(|document| document.id == id)
+    }
+
+    fn unique_name(&self, name: &str) -> String {
+        let name = match name.trim() {
+            "" => UNTITLED,
+            name => name,
+        };
+
+        (1..)
+            .map(|number| match number {
+                1 => name.to_string(),
+                _ => format!("{name} ({number})"),
+            })
+            .find(|candidate| self.find_by_name(candidate).is_none())
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for DocumentLibrary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).unwrap_or_default())
+    }
+}
+
+impl FromStr for DocumentLibrary {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library() -> DocumentLibrary {
+        let mut library = DocumentLibrary::default();
+        library.ensure_active("first", 0.0);
+        library
+    }
+
+    #[test]
+    fn test_ensure_active_creates_document_once() {
+        let mut library = library();
+        library.ensure_active("ignored", 1.0);
+
+        assert_eq!(library.documents.len(), 1);
+        assert_eq!(library.active().unwrap().content, "first");
+        assert_eq!(library.active().unwrap().name, "Untitled");
+    }
+
+    #[test]
+    fn test_create_stores_current_content() {
+        let mut library = library();
+        library.create("Imported", "new", "first edited", 5.0);
+
+        assert_eq!(library.documents[0].content, "first edited");
+        assert_eq!(library.documents[0].modified, 5.0);
+        assert_eq!(library.active().unwrap().name, "Imported");
+    }
+
+    #[test]
+    fn test_create_with_empty_name() {
+        let mut library = library();
+        library.create("  ", "", "first", 1.0);
+
+        assert_eq!(library.active().unwrap().name, "Untitled (2)");
+    }
+
+    #[test]
+    fn test_open_switches_document() {
+        let mut library = library();
+        let first = library.active;
+        library.create("Second", "second", "first", 1.0);
+
+        assert_eq!(
+            library.open(first, "second edited", 2.0),
+            Some(String::from("first"))
+        );
+        assert_eq!(library.active, first);
+        assert_eq!(library.documents[1].content, "second edited");
+    }
+
+    #[test]
+    fn test_open_unknown_document() {
+        let mut library = library();
+
+        assert_eq!(library.open(42, "first", 1.0), None);
+        assert_eq!(library.active().unwrap().content, "first");
+    }
+
+    #[test]
+    fn test_store_active_keeps_modified_without_change() {
+        let mut library = library();
+        library.store_active("first", 9.0);
+
+        assert_eq!(library.active().unwrap().modified, 0.0);
+    }
+
+    #[test]
+    fn test_remove_active_opens_most_recent() {
+        let mut library = library();
+        library.create("Second", "second", "first", 1.0);
+        library.create("Third", "third", "second", 3.0);
+        library.documents[0].modified = 10.0;
+
+        assert_eq!(library.remove_active(11.0), "first");
+        assert_eq!(library.names(), vec!["Untitled", "Second"]);
+    }
+
+    #[test]
+    fn test_remove_last_document() {
+        let mut library = library();
+
+        assert_eq!(library.remove_active(1.0), "");
+        assert_eq!(library.documents.len(), 1);
+        assert_eq!(library.active().unwrap().content, "");
+    }
+
+    #[test]
+    fn test_rename_active_updates_links() {
+        let mut library = library();
+        library.create("Plans", "See [[Ideas]]", "[[plans]] and [[Ideas]]", 1.0);
+        library.create("Ideas", "", "See [[Ideas]]", 2.0);
+
+        let content = library
+            .rename_active("Brainstorm", "Me: [[Ideas|this]]", 3.0)
+            .unwrap();
+
+        assert_eq!(content, "Me: [[Brainstorm|this]]");
+        assert_eq!(library.documents[0].content, "[[plans]] and [[Brainstorm]]");
+        assert_eq!(library.documents[1].content, "See [[Brainstorm]]");
+        assert_eq!(library.active().unwrap().name, "Brainstorm");
+    }
+
+    #[test]
+    fn test_rename_active_rejects_taken_names() {
+        let mut library = library();
+        library.create("Plans", "", "first", 1.0);
+
+        assert!(library.rename_active("untitled", "", 2.0).is_err());
+        assert!(library.rename_active("  ", "", 2.0).is_err());
+        assert!(library.rename_active("plans", "", 2.0).is_ok());
+        assert_eq!(library.active().unwrap().name, "plans");
+    }
+
+    #[test]
+    fn test_has_default_name() {
+        let mut library = library();
+        library.create("", "", "first", 1.0);
+        library.create("Untitled (draft)", "", "", 2.0);
+
+        assert!(library.documents[0].has_default_name());
+        assert!(library.documents[1].has_default_name());
+        assert!(!library.documents[2].has_default_name());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut library = library();
+        library.create("Second", "second", "first", 1.0);
+
+        assert_eq!(library.to_string().parse(), Ok(library));
+    }
+
+    fn linked_file(path: &str, base: &str) -> LinkedFile {
+        LinkedFile {
+            path: path.to_string(),
+            base: base.to_string(),
+            disk_modified: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_create_linked() {
+        let mut library = library();
+        library.create_linked(
+            "today",
+            linked_file("notes/today.md", "# Today"),
+            "first",
+            1.0,
+        );
+
+        let document = library.find_by_path("notes/today.md").unwrap();
+        assert_eq!(document.id, library.active);
+        assert_eq!(document.content, "# Today");
+        assert_eq!(library.find_by_path("today.md"), None);
+    }
+
+    #[test]
+    fn test_update_link() {
+        let mut library = library();
+        library.create_linked("today", linked_file("today.md", "old"), "first", 1.0);
+        library.update_link(library.active, "new", 200.0);
+
+        assert_eq!(
+            library.active().unwrap().file,
+            Some(LinkedFile {
+                path: String::from("today.md"),
+                base: String::from("new"),
+                disk_modified: 200.0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_documents_without_file_stay_compatible() {
+        let library: DocumentLibrary =
+            r#"{"documents":[{"id":1,"name":"A","content":"a","modified":0.0}],"active":1}"#
+                .parse()
+                .unwrap();
+
+        assert_eq!(library.active().unwrap().file, None);
+        assert_eq!(library.active().unwrap().sealed, None);
+        assert!(!library.to_string().contains("file"));
+        assert!(!library.to_string().contains("sealed"));
+    }
+
+    fn sealed() -> Sealed {
+        Sealed {
+            version: 1,
+            kdf: Default::default(),
+            salt: String::from("c2FsdA=="),
+            nonce: String::from("bm9uY2U="),
+            ciphertext: String::from("Y2lwaGVy"),
+        }
+    }
+
+    #[test]
+    fn test_sealed_document_keeps_no_clear_text() {
+        let mut library = library();
+        library.seal(library.active, sealed(), 1.0);
+        library.store_active("secret", 2.0);
+
+        let document = library.active().unwrap();
+        assert_eq!(document.content, "");
+        assert_eq!(document.sealed, Some(sealed()));
+        assert_eq!(document.modified, 1.0);
+        assert!(!library.to_string().contains("first"));
+    }
+
+    #[test]
+    fn test_open_from_sealed_document() {
+        let mut library = library();
+        let first = library.active;
+        library.create_sealed("Secret", sealed(), "first", 1.0);
+
+        assert_eq!(
+            library.open(first, "secret", 2.0),
+            Some(String::from("first"))
+        );
+        assert_eq!(library.documents[1].content, "");
+        assert_eq!(library.documents[1].name, "Secret");
+    }
+
+    #[test]
+    fn test_unseal() {
+        let mut library = library();
+        library.seal(library.active, sealed(), 1.0);
+        library.unseal(library.active, "decrypted", 2.0);
+
+        let document = library.active().unwrap();
+        assert_eq!(document.content, "decrypted");
+        assert_eq!(document.sealed, None);
+    }
+}
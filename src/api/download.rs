@@ -1,13 +1,32 @@
 use gloo::utils::window;
 use wasm_bindgen::JsCast;
-use web_sys::{HtmlAnchorElement, js_sys::Array};
+use web_sys::{
+    BlobPropertyBag, HtmlAnchorElement,
+    js_sys::{Array, Uint8Array},
+};
 
 pub fn download_file(content: String, filename: &str) {
     let blob_parts = Array::new();
     blob_parts.push(&content.into());
     let blob = web_sys::Blob::new_with_str_sequence(&blob_parts).unwrap();
 
-    let url = web_sys::Url::create_object_url_with_blob(&blob).unwrap();
+    download_blob(&blob, filename);
+}
+
+/// Downloads binary content, e.g. a zipped office document, with its MIME type.
+pub fn download_bytes(content: &[u8], filename: &str, mime_type: &str) {
+    let blob_parts = Array::new();
+    blob_parts.push(&Uint8Array::from(content));
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob =
+        web_sys::Blob::new_with_u8_array_sequence_and_options(&blob_parts, &options).unwrap();
+
+    download_blob(&blob, filename);
+}
+
+fn download_blob(blob: &web_sys::Blob, filename: &str) {
+    let url = web_sys::Url::create_object_url_with_blob(blob).unwrap();
 
     let document = window().document().unwrap();
     let a: HtmlAnchorElement = document.create_element("a").unwrap().dyn_into().unwrap();
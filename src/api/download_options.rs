@@ -0,0 +1,433 @@
+use std::{fmt, str::FromStr};
+
+use crate::api::export::{
+    DOCUMENT_FORMATS, DocumentFormat, EXPORT_FORMATS, ExportFormat, document_title,
+};
+
+/// Longest file name proposed for a download, without the extension.
+const MAX_SLUG_LENGTH: usize = 64;
+const FALLBACK_NAME: &str = "document";
+
+/// Everything the markdown can be downloaded as.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DownloadFormat {
+    Markdown,
+    Html,
+    Document(DocumentFormat),
+    Export(ExportFormat),
+}
+
+pub const DOWNLOAD_FORMATS: [DownloadFormat; 8] = [
+    DownloadFormat::Markdown,
+    DownloadFormat::Html,
+    DownloadFormat::Document(DOCUMENT_FORMATS[0]),
+    DownloadFormat::Document(DOCUMENT_FORMATS[1]),
+    DownloadFormat::Export(EXPORT_FORMATS[0]),
+    DownloadFormat::Export(EXPORT_FORMATS[1]),
+    DownloadFormat::Export(EXPORT_FORMATS[2]),
+    DownloadFormat::Export(EXPORT_FORMATS[3]),
+];
+
+impl DownloadFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            DownloadFormat::Markdown => "md",
+            DownloadFormat::Html => "html",
+            DownloadFormat::Document(format) => format.extension(),
+            DownloadFormat::Export(format) => format.extension(),
+        }
+    }
+
+    /// Line endings and the trailing newline only apply to text formats.
+    pub fn is_text(self) -> bool {
+        !matches!(self, DownloadFormat::Document(_))
+    }
+}
+
+impl fmt::Display for DownloadFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DownloadFormat::Markdown => write!(f, "Markdown"),
+            DownloadFormat::Html => write!(f, "HTML"),
+            DownloadFormat::Document(format) => write!(f, "{format}"),
+            DownloadFormat::Export(format) => write!(f, "{format}"),
+        }
+    }
+}
+
+/// Parses the label shown in the format select.
+impl FromStr for DownloadFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DOWNLOAD_FORMATS
+            .into_iter()
+            .find(|format| format.to_string() == s)
+            .ok_or(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineEnding {
+    /// The line endings of the editor are downloaded as they are.
+    #[default]
+    Keep,
+    Lf,
+    Crlf,
+}
+
+pub const LINE_ENDINGS: [LineEnding; 3] = [LineEnding::Keep, LineEnding::Lf, LineEnding::Crlf];
+
+impl fmt::Display for LineEnding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineEnding::Keep => write!(f, "Keep as they are"),
+            LineEnding::Lf => write!(f, "LF (Linux, macOS)"),
+            LineEnding::Crlf => write!(f, "CRLF (Windows)"),
+        }
+    }
+}
+
+impl FromStr for LineEnding {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LINE_ENDINGS
+            .into_iter()
+            .find(|line_ending| line_ending.to_string() == s)
+            .ok_or(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TrailingNewline {
+    /// The text is downloaded as it is.
+    #[default]
+    Keep,
+    /// The text ends with exactly one newline.
+    Ensure,
+    /// Newlines at the end are removed.
+    Remove,
+}
+
+pub const TRAILING_NEWLINES: [TrailingNewline; 3] = [
+    TrailingNewline::Keep,
+    TrailingNewline::Ensure,
+    TrailingNewline::Remove,
+];
+
+impl fmt::Display for TrailingNewline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrailingNewline::Keep => write!(f, "Keep as it is"),
+            TrailingNewline::Ensure => write!(f, "End with one newline"),
+            TrailingNewline::Remove => write!(f, "No newline at the end"),
+        }
+    }
+}
+
+impl FromStr for TrailingNewline {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TRAILING_NEWLINES
+            .into_iter()
+            .find(|policy| policy.to_string() == s)
+            .ok_or(())
+    }
+}
+
+/// The last choices of the download dialog, persisted as e.g. `md,crlf,ensure`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DownloadOptions {
+    pub format: DownloadFormat,
+    pub line_ending: LineEnding,
+    pub trailing_newline: TrailingNewline,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        DownloadOptions {
+            format: DownloadFormat::Markdown,
+            line_ending: LineEnding::default(),
+            trailing_newline: TrailingNewline::default(),
+        }
+    }
+}
+
+impl DownloadOptions {
+    /// Applies the trailing newline policy and the line endings to a text download. The
+    /// default options leave the text unchanged.
+    ///
+    /// ```rust
+    /// use rustynote::api::download_options::{DownloadOptions, LineEnding, TrailingNewline};
+    ///
+    /// let options = DownloadOptions {
+    ///     line_ending: LineEnding::Crlf,
+    ///     trailing_newline: TrailingNewline::Ensure,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(options.apply("# Title\ntext\n\n\n"), "# Title\r\ntext\r\n");
+    /// ```
+    pub fn apply(&self, text: &str) -> String {
+        let trimmed = text.trim_end_matches(['\r', '\n']);
+        let text = match self.trailing_newline {
+            TrailingNewline::Keep => text.to_string(),
+            TrailingNewline::Ensure if text.contains("\r\n") => format!("{trimmed}\r\n"),
+            TrailingNewline::Ensure => format!("{trimmed}\n"),
+            TrailingNewline::Remove => trimmed.to_string(),
+        };
+
+        match self.line_ending {
+            LineEnding::Keep => text,
+            LineEnding::Lf => text.replace("\r\n", "\n"),
+            LineEnding::Crlf => text.replace("\r\n", "\n").replace('\n', "\r\n"),
+        }
+    }
+}
+
+impl fmt::Display for DownloadOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line_ending = match self.line_ending {
+            LineEnding::Keep => "as-is",
+            LineEnding::Lf => "lf",
+            LineEnding::Crlf => "crlf",
+        };
+        let trailing_newline = match self.trailing_newline {
+            TrailingNewline::Keep => "keep",
+            TrailingNewline::Ensure => "ensure",
+            TrailingNewline::Remove => "remove",
+        };
+
+        write!(
+            f,
+            "{},{line_ending},{trailing_newline}",
+            self.format.extension()
+        )
+    }
+}
+
+impl FromStr for DownloadOptions {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut options = DownloadOptions::default();
+
+        for flag in s.split(',').map(str::trim) {
+            match flag {
+                "as-is" => options.line_ending = LineEnding::Keep,
+                "lf" => options.line_ending = LineEnding::Lf,
+                "crlf" => options.line_ending = LineEnding::Crlf,
+                "keep" => options.trailing_newline = TrailingNewline::Keep,
+                "ensure" => options.trailing_newline = TrailingNewline::Ensure,
+                "remove" => options.trailing_newline = TrailingNewline::Remove,
+                extension => {
+                    if let Some(format) = DOWNLOAD_FORMATS
+                        .into_iter()
+                        .find(|format| format.extension() == extension)
+                    {
+                        options.format = format;
+                    }
+                }
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Turns a title into a file name of lowercase words joined by dashes.
+///
+/// ```rust
+/// use rustynote::api::download_options::slugify;
+///
+/// assert_eq!(slugify("Meeting Notes: 3/4 Q&A!"), "meeting-notes-3-4-q-a");
+/// assert_eq!(slugify("Über Größe"), "über-größe");
+/// ```
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    let slug: String = slug.chars().take(MAX_SLUG_LENGTH).collect();
+    match slug.trim_end_matches('-') {
+        "" => String::from(FALLBACK_NAME),
+        slug => slug.to_string(),
+    }
+}
+
+/// Proposes a file name from the name of the document, or from the first heading
+/// when the document still has its default name.
+pub fn suggested_file_name(document_name: Option<&str>, markdown: &str) -> String {
+    match document_name {
+        Some(name) if !name.trim().is_empty() => slugify(name),
+        _ => slugify(&document_title(markdown)),
+    }
+}
+
+/// Completes an edited file name with the extension of the format, dropping
+/// characters that aren't allowed in file names.
+///
+/// ```rust
+/// use rustynote::api::download_options::file_name;
+///
+/// assert_eq!(file_name("notes", "md"), "notes.md");
+/// assert_eq!(file_name("notes.MD", "md"), "notes.MD");
+/// assert_eq!(file_name(" a/b: c ", "txt"), "a-b- c.txt");
+/// ```
+pub fn file_name(name: &str, extension: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect();
+    let name = name.trim().trim_matches('.').trim();
+
+    let has_extension = name.rsplit_once('.').is_some_and(|(stem, existing)| {
+        !stem.is_empty() && existing.eq_ignore_ascii_case(extension)
+    });
+
+    match (name, has_extension) {
+        ("", _) => format!("{FALLBACK_NAME}.{extension}"),
+        (name, true) => name.to_string(),
+        (name, false) => format!("{name}.{extension}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_collapses_separators() {
+        assert_eq!(slugify("  --Hello   World--  "), "hello-world");
+    }
+
+    #[test]
+    fn test_slugify_without_letters() {
+        assert_eq!(slugify("!!!"), "document");
+    }
+
+    #[test]
+    fn test_slugify_limits_length() {
+        let slug = slugify(&"word ".repeat(30));
+
+        assert!(slug.chars().count() <= MAX_SLUG_LENGTH);
+        assert!(!slug.ends_with('-'));
+    }
+
+    #[test]
+    fn test_suggested_file_name_prefers_document_name() {
+        assert_eq!(
+            suggested_file_name(Some("Project Plan"), "# Heading"),
+            "project-plan"
+        );
+    }
+
+    #[test]
+    fn test_suggested_file_name_uses_first_heading() {
+        assert_eq!(
+            suggested_file_name(None, "intro\n\n## Release *Notes*"),
+            "release-notes"
+        );
+        assert_eq!(suggested_file_name(None, "no heading"), "document");
+    }
+
+    #[test]
+    fn test_file_name_replaces_other_extension() {
+        assert_eq!(file_name("notes.md", "html"), "notes.md.html");
+    }
+
+    #[test]
+    fn test_file_name_fallback() {
+        assert_eq!(file_name(" .. ", "rst"), "document.rst");
+        assert_eq!(file_name(".md", "md"), "md.md");
+    }
+
+    #[test]
+    fn test_default_download_is_unchanged() {
+        let text = "# Title\r\ntext\n\n\nend \n\n";
+
+        assert_eq!(DownloadOptions::default().apply(text), text);
+        assert_eq!(DownloadOptions::default().apply("no newline"), "no newline");
+    }
+
+    #[test]
+    fn test_apply_keeps_text() {
+        let options = DownloadOptions {
+            line_ending: LineEnding::Lf,
+            trailing_newline: TrailingNewline::Keep,
+            ..Default::default()
+        };
+
+        assert_eq!(options.apply("a\r\nb\n\n"), "a\nb\n\n");
+    }
+
+    #[test]
+    fn test_ensure_keeps_windows_line_endings() {
+        let options = DownloadOptions {
+            trailing_newline: TrailingNewline::Ensure,
+            ..Default::default()
+        };
+
+        assert_eq!(options.apply("a\r\nb\r\n\r\n"), "a\r\nb\r\n");
+        assert_eq!(options.apply("a\nb"), "a\nb\n");
+    }
+
+    #[test]
+    fn test_apply_removes_trailing_newlines() {
+        let options = DownloadOptions {
+            trailing_newline: TrailingNewline::Remove,
+            ..Default::default()
+        };
+
+        assert_eq!(options.apply("a\n\n"), "a");
+    }
+
+    #[test]
+    fn test_apply_does_not_double_carriage_returns() {
+        let options = DownloadOptions {
+            line_ending: LineEnding::Crlf,
+            trailing_newline: TrailingNewline::Keep,
+            ..Default::default()
+        };
+
+        assert_eq!(options.apply("a\r\nb"), "a\r\nb");
+    }
+
+    #[test]
+    fn test_options_round_trip() {
+        let options = DownloadOptions {
+            format: DownloadFormat::Document(DocumentFormat::Odt),
+            line_ending: LineEnding::Crlf,
+            trailing_newline: TrailingNewline::Remove,
+        };
+
+        assert_eq!(options.to_string(), "odt,crlf,remove");
+        assert_eq!(options.to_string().parse(), Ok(options));
+    }
+
+    #[test]
+    fn test_options_ignore_unknown_flags() {
+        assert_eq!(
+            "pdf,crlf".parse::<DownloadOptions>().unwrap().format,
+            DownloadFormat::Markdown
+        );
+    }
+
+    #[test]
+    fn test_format_from_label() {
+        assert_eq!(
+            "AsciiDoc".parse(),
+            Ok(DownloadFormat::Export(ExportFormat::AsciiDoc))
+        );
+    }
+}
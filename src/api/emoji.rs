@@ -0,0 +1,54 @@
+/// GitHub emoji shortcodes that start with `prefix`, followed by the ones that only
+/// contain it, each in alphabetical order. They come from the same list that comrak
+/// renders, so every completion turns into an emoji.
+///
+/// ```rust
+/// use rustynote::api::emoji::search_shortcodes;
+///
+/// assert_eq!(search_shortcodes("tada"), vec![("tada", "🎉")]);
+/// ```
+pub fn search_shortcodes(prefix: &str) -> Vec<(&'static str, &'static str)> {
+    let mut matches: Vec<(&'static str, &'static str)> = emojis::iter()
+        .flat_map(|emoji| {
+            emoji
+                .shortcodes()
+                .filter(|code| code.contains(prefix))
+                .map(|code| (code, emoji.as_str()))
+        })
+        .collect();
+
+    matches.sort_by_key(|(code, _)| (!code.starts_with(prefix), *code));
+    matches
+}
+
+/// Whether `c` can be part of a shortcode.
+pub fn is_shortcode_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_lists_prefix_matches_first() {
+        let codes: Vec<&str> = search_shortcodes("heart")
+            .iter()
+            .map(|(code, _)| *code)
+            .collect();
+
+        assert_eq!(codes[0], "heart");
+        assert!(codes.contains(&"heart_eyes") && codes.contains(&"broken_heart"));
+        let first_containing = codes.iter().position(|code| !code.starts_with("heart"));
+        assert!(
+            codes[first_containing.unwrap()..]
+                .iter()
+                .all(|code| !code.starts_with("heart"))
+        );
+    }
+
+    #[test]
+    fn test_search_includes_signs() {
+        assert_eq!(search_shortcodes("+1"), vec![("+1", "👍")]);
+    }
+}
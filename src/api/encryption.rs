@@ -0,0 +1,627 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chacha20poly1305::{
+    Key, KeyInit, XChaCha20Poly1305, XNonce,
+    aead::{Aead, Payload},
+};
+use gloo::{timers::callback::Interval, utils::window};
+use leptoaster::ToasterContext;
+use leptos::{
+    ev::{keydown, pointerdown},
+    prelude::*,
+};
+use leptos_use::{use_document, use_event_listener};
+use serde::{Deserialize, Serialize};
+use web_sys::js_sys::Date;
+
+use crate::api::documents::DocumentLibrary;
+
+/// File extension of an encrypted note that was downloaded.
+pub const ENCRYPTED_EXTENSION: &str = "rnote";
+
+const FORMAT_VERSION: u8 = 1;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 24;
+/// Phrases shorter than this are rejected when a note or a share link is encrypted.
+pub const MIN_PASSPHRASE_LENGTH: usize = 8;
+
+/// Argon2id settings. They are stored with every note so they can be raised later
+/// without breaking older notes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+}
+
+impl Default for KdfParams {
+    /// The OWASP recommendation for Argon2id, about a second in the browser.
+    fn default() -> Self {
+        KdfParams {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+        }
+    }
+}
+
+/// How much stronger than the defaults the settings of a note may be. Notes and links
+/// can come from anyone, and larger settings would freeze or crash the tab.
+const MAX_KDF_FACTOR: u32 = 4;
+
+impl KdfParams {
+    /// Whether deriving a key with these settings stays within what the browser can do.
+    pub fn is_supported(&self) -> bool {
+        let limit = KdfParams::default();
+        self.memory_kib <= limit.memory_kib * MAX_KDF_FACTOR
+            && self.iterations <= limit.iterations * MAX_KDF_FACTOR
+    }
+}
+
+/// The encrypted content of a note. The document name and the salt stay readable, the
+/// text is sealed with XChaCha20-Poly1305.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sealed {
+    pub version: u8,
+    pub kdf: KdfParams,
+    /// Base64 encoded.
+    pub salt: String,
+    /// Base64 encoded.
+    pub nonce: String,
+    /// Base64 encoded, includes the authentication tag.
+    pub ciphertext: String,
+}
+
+impl Sealed {
+    /// Rejects notes whose key settings would take too long or too much memory to derive.
+    pub fn check(self) -> Result<Self, String> {
+        if self.kdf.is_supported() {
+            Ok(self)
+        } else {
+            Err(String::from("The encrypted note is damaged"))
+        }
+    }
+}
+
+/// A key derived from a passphrase, together with what is needed to derive it again.
+#[derive(Clone)]
+pub struct NoteKey {
+    key: Key,
+    kdf: KdfParams,
+    salt: [u8; SALT_LENGTH],
+}
+
+impl fmt::Debug for NoteKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NoteKey").finish_non_exhaustive()
+    }
+}
+
+impl NoteKey {
+    /// Derives a key with a fresh random salt, for a note that is encrypted the first time
+    /// or a link that is shared encrypted.
+    pub fn generate(passphrase: &str) -> Result<Self, String> {
+        if passphrase.chars().count() < MIN_PASSPHRASE_LENGTH {
+            return Err(format!(
+                "Use a passphrase of at least {MIN_PASSPHRASE_LENGTH} characters"
+            ));
+        }
+        Self::derive(passphrase, KdfParams::default(), random_bytes()?)
+    }
+
+    /// Derives the key of a sealed note. A wrong passphrase only shows up in [`NoteKey::open`].
+    pub fn for_sealed(passphrase: &str, sealed: &Sealed) -> Result<Self, String> {
+        let salt = decode(&sealed.salt)?
+            .try_into()
+            .map_err(|_| String::from("The encrypted note is damaged"))?;
+        Self::derive(passphrase, sealed.kdf, salt)
+    }
+
+    fn derive(passphrase: &str, kdf: KdfParams, salt: [u8; SALT_LENGTH]) -> Result<Self, String> {
+        if !kdf.is_supported() {
+            return Err(String::from("The encrypted note is damaged"));
+        }
+        let params = Params::new(kdf.memory_kib, kdf.iterations, 1, Some(32))
+            .map_err(|error| format!("Invalid key settings: {error}"))?;
+        let mut key = Key::default();
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|error| format!("Couldn't derive the key: {error}"))?;
+
+        Ok(NoteKey { key, kdf, salt })
+    }
+
+    /// Encrypts the text with a new random nonce.
+    pub fn seal(&self, text: &str) -> Result<Sealed, String> {
+        self.seal_with_nonce(text, random_bytes()?)
+    }
+
+    pub(crate) fn seal_with_nonce(
+        &self,
+        text: &str,
+        nonce: [u8; NONCE_LENGTH],
+    ) -> Result<Sealed, String> {
+        let salt = STANDARD.encode(self.salt);
+        let ciphertext = XChaCha20Poly1305::new(&self.key)
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: text.as_bytes(),
+                    aad: &associated_data(FORMAT_VERSION, &salt),
+                },
+            )
+            .map_err(|_| String::from("Couldn't encrypt the note"))?;
+
+        Ok(Sealed {
+            version: FORMAT_VERSION,
+            kdf: self.kdf,
+            salt,
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        })
+    }
+
+    /// Decrypts a note. Fails for a wrong passphrase and for any change to the sealed data.
+    pub fn open(&self, sealed: &Sealed) -> Result<String, String> {
+        if sealed.version != FORMAT_VERSION {
+            return Err(format!(
+                "The note was encrypted by a newer version (format {})",
+                sealed.version
+            ));
+        }
+
+        let nonce = decode(&sealed.nonce)?;
+        if nonce.len() != NONCE_LENGTH {
+            return Err(String::from("The encrypted note is damaged"));
+        }
+
+        let text = XChaCha20Poly1305::new(&self.key)
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &decode(&sealed.ciphertext)?,
+                    aad: &associated_data(sealed.version, &sealed.salt),
+                },
+            )
+            .map_err(|_| String::from("Wrong passphrase"))?;
+
+        String::from_utf8(text).map_err(|_| String::from("The encrypted note is damaged"))
+    }
+}
+
+/// Binds the format and the salt to the ciphertext, so neither can be swapped unnoticed.
+fn associated_data(version: u8, salt: &str) -> Vec<u8> {
+    format!("rustynote:{version}:{salt}").into_bytes()
+}
+
+fn decode(value: &str) -> Result<Vec<u8>, String> {
+    STANDARD
+        .decode(value)
+        .map_err(|_| String::from("The encrypted note is damaged"))
+}
+
+/// Random bytes from the browser's cryptographic random number generator.
+fn random_bytes<const N: usize>() -> Result<[u8; N], String> {
+    let mut bytes = [0; N];
+    window()
+        .crypto()
+        .and_then(|crypto| crypto.get_random_values_with_u8_array(&mut bytes))
+        .map_err(|_| String::from("Your browser can't generate secure random numbers"))?;
+    Ok(bytes)
+}
+
+/// A downloaded encrypted note, which can be opened again like a markdown file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncryptedFile {
+    pub name: String,
+    pub sealed: Sealed,
+}
+
+impl EncryptedFile {
+    pub fn is_encrypted_file(file_name: &str) -> bool {
+        file_name
+            .rsplit_once('.')
+            .is_some_and(|(_, extension)| extension.eq_ignore_ascii_case(ENCRYPTED_EXTENSION))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let file: Self = serde_json::from_str(json)
+            .map_err(|_| String::from("The encrypted note is damaged"))?;
+        Ok(EncryptedFile {
+            name: file.name,
+            sealed: file.sealed.check()?,
+        })
+    }
+}
+
+/// How long the editor may stay untouched before unlocked notes are locked again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoLock(pub Option<u32>);
+
+impl AutoLock {
+    pub const CHOICES: [AutoLock; 6] = [
+        AutoLock(Some(1)),
+        AutoLock(Some(5)),
+        AutoLock(Some(15)),
+        AutoLock(Some(30)),
+        AutoLock(Some(60)),
+        AutoLock(None),
+    ];
+
+    pub fn as_millis(&self) -> Option<f64> {
+        self.0.map(|minutes| f64::from(minutes) * 60_000.0)
+    }
+}
+
+impl Default for AutoLock {
+    fn default() -> Self {
+        AutoLock(Some(5))
+    }
+}
+
+impl fmt::Display for AutoLock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(1) => write!(f, "After 1 minute"),
+            Some(minutes) => write!(f, "After {minutes} minutes"),
+            None => write!(f, "Never"),
+        }
+    }
+}
+
+impl FromStr for AutoLock {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AutoLock::CHOICES
+            .into_iter()
+            .find(|choice| choice.to_string() == s)
+            .ok_or(())
+    }
+}
+
+/// The notes that are unlocked in this session. Keys only live in memory, reloading
+/// the page locks every note again.
+#[derive(Clone, Copy)]
+pub struct Vault {
+    pub auto_lock: RwSignal<AutoLock>,
+    unlocked: RwSignal<Vec<u64>>,
+    /// Milliseconds since the Unix epoch of the last input, for the auto-lock.
+    last_activity: RwSignal<f64>,
+    keys: StoredValue<HashMap<u64, NoteKey>, LocalStorage>,
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+}
+
+impl Vault {
+    pub fn new(
+        markdown: RwSignal<String>,
+        documents: RwSignal<DocumentLibrary>,
+        auto_lock: RwSignal<AutoLock>,
+    ) -> Self {
+        Vault {
+            auto_lock,
+            unlocked: RwSignal::new(Vec::new()),
+            last_activity: RwSignal::new(Date::now()),
+            keys: StoredValue::new_local(HashMap::new()),
+            markdown,
+            documents,
+        }
+    }
+
+    /// Whether the active document is encrypted, locked or not.
+    pub fn is_active_encrypted(&self) -> bool {
+        self.documents.with(|library| {
+            library
+                .active()
+                .is_some_and(|document| document.sealed.is_some())
+        })
+    }
+
+    /// Whether the active document is encrypted and waits for its passphrase.
+    pub fn is_active_locked(&self) -> bool {
+        let active = self.documents.with(|library| library.active);
+        self.is_active_encrypted() && !self.unlocked.read().contains(&active)
+    }
+
+    /// The key of the active document while it is unlocked.
+    pub fn active_key(&self) -> Option<NoteKey> {
+        let active = self.documents.with_untracked(|library| library.active);
+        self.keys.with_value(|keys| keys.get(&active).cloned())
+    }
+
+    /// Encrypts the active document. Its text stays in the editor until it is locked.
+    pub fn encrypt_active(&self, passphrase: &str) -> Result<(), String> {
+        if self.documents.with_untracked(|library| {
+            library
+                .active()
+                .is_some_and(|document| document.file.is_some())
+        }) {
+            return Err(String::from(
+                "Documents saved to a folder can't be encrypted, their file stays readable",
+            ));
+        }
+
+        let key = NoteKey::generate(passphrase)?;
+        let sealed = key.seal(&self.markdown.get_untracked())?;
+        let active = self.documents.with_untracked(|library| library.active);
+        self.documents
+            .update(|library| library.seal(active, sealed, Date::now()));
+        self.insert(active, key);
+        Ok(())
+    }
+
+    /// Unlocks the active document with its passphrase.
+    pub fn unlock_active(&self, passphrase: &str) -> Result<(), String> {
+        let Some((id, Some(sealed))) = self.documents.with_untracked(|library| {
+            library
+                .active()
+                .map(|document| (document.id, document.sealed.clone()))
+        }) else {
+            return Ok(());
+        };
+
+        let key = NoteKey::for_sealed(passphrase, &sealed)?;
+        // Fails for a wrong passphrase before the key is kept.
+        key.open(&sealed)?;
+        self.insert(id, key);
+        Ok(())
+    }
+
+    /// Keeps an already derived key, e.g. of a note that was opened from a link.
+    pub fn insert(&self, id: u64, key: NoteKey) {
+        self.keys.update_value(|keys| {
+            keys.insert(id, key);
+        });
+        self.unlocked.update(|unlocked| {
+            if !unlocked.contains(&id) {
+                unlocked.push(id);
+            }
+        });
+        self.last_activity.set(Date::now());
+    }
+
+    /// Stores the text of the active document in the clear again.
+    pub fn decrypt_active(&self) {
+        let active = self.documents.with_untracked(|library| library.active);
+        if !self.unlocked.read_untracked().contains(&active) {
+            return;
+        }
+
+        let content = self.markdown.get_untracked();
+        self.documents
+            .update(|library| library.unseal(active, &content, Date::now()));
+        self.forget(active);
+    }
+
+    /// Forgets every key and clears the editor if it shows an encrypted document.
+    pub fn lock_all(&self) {
+        let was_unlocked = self.is_active_encrypted() && !self.is_active_locked();
+        self.keys.update_value(HashMap::clear);
+        self.unlocked.set(Vec::new());
+        if was_unlocked {
+            self.markdown.set(String::new());
+        }
+    }
+
+    pub fn has_unlocked(&self) -> bool {
+        !self.unlocked.read().is_empty()
+    }
+
+    fn forget(&self, id: u64) {
+        self.keys.update_value(|keys| {
+            keys.remove(&id);
+        });
+        self.unlocked
+            .update(|unlocked| unlocked.retain(|unlocked| *unlocked != id));
+    }
+
+    /// Encrypts every change of an unlocked document and decrypts a document when it
+    /// is unlocked or opened. Returns the document and text that were handled last.
+    fn sync(&self, last: Option<(u64, String)>) -> Option<(u64, String)> {
+        let (id, sealed) = self.documents.with(|library| {
+            library
+                .active()
+                .and_then(|document| Some((document.id, document.sealed.clone()?)))
+        })?;
+        if !self.unlocked.read().contains(&id) {
+            return None;
+        }
+        let key = self.keys.with_value(|keys| keys.get(&id).cloned())?;
+        let text = self.markdown.get();
+
+        match last {
+            Some((last_id, last_text)) if last_id == id => {
+                if last_text != text {
+                    match key.seal(&text) {
+                        Ok(sealed) => self
+                            .documents
+                            .update(|library| library.seal(id, sealed, Date::now())),
+                        Err(error) => leptos::logging::error!("{error}"),
+                    }
+                }
+                Some((id, text))
+            }
+            // The document was just unlocked or opened, its text is only in the sealed form.
+            _ => {
+                let text = key.open(&sealed).ok()?;
+                self.markdown.set(text.clone());
+                Some((id, text))
+            }
+        }
+    }
+}
+
+/// How often the auto-lock checks for inactivity.
+const AUTO_LOCK_CHECK_MS: u32 = 10_000;
+
+/// Makes the vault available to the encryption controls, keeps encrypted documents
+/// sealed while they are edited and locks them after the chosen time without input.
+pub fn provide_vault(vault: Vault, toaster: ToasterContext) {
+    Effect::new(move |last: Option<Option<(u64, String)>>| vault.sync(last.flatten()));
+
+    let touch = move || vault.last_activity.set(Date::now());
+    let _ = use_event_listener(use_document(), keydown, move |_| touch());
+    let _ = use_event_listener(use_document(), pointerdown, move |_| touch());
+
+    Interval::new(AUTO_LOCK_CHECK_MS, move || {
+        let Some(timeout) = vault.auto_lock.get_untracked().as_millis() else {
+            return;
+        };
+        if vault.unlocked.read_untracked().is_empty()
+            || Date::now() - vault.last_activity.get_untracked() < timeout
+        {
+            return;
+        }
+
+        vault.lock_all();
+        toaster.info("Encrypted notes were locked after a period of inactivity");
+    })
+    .forget();
+
+    provide_context(vault);
+}
+
+/// A key with cheap settings, the defaults take seconds in a debug build.
+#[cfg(test)]
+pub(crate) fn test_key(passphrase: &str) -> NoteKey {
+    let kdf = KdfParams {
+        memory_kib: 64,
+        iterations: 1,
+    };
+    NoteKey::derive(passphrase, kdf, [7; SALT_LENGTH]).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(passphrase: &str) -> NoteKey {
+        test_key(passphrase)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let sealed = key("correct horse")
+            .seal_with_nonce("# Secret\n", [1; NONCE_LENGTH])
+            .unwrap();
+
+        assert!(!sealed.ciphertext.contains("Secret"));
+        assert_eq!(
+            NoteKey::for_sealed("correct horse", &sealed)
+                .unwrap()
+                .open(&sealed),
+            Ok(String::from("# Secret\n"))
+        );
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let sealed = key("correct horse")
+            .seal_with_nonce("text", [1; NONCE_LENGTH])
+            .unwrap();
+
+        assert_eq!(
+            key("battery staple").open(&sealed),
+            Err(String::from("Wrong passphrase"))
+        );
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let key = key("correct horse");
+        let sealed = key.seal_with_nonce("text", [1; NONCE_LENGTH]).unwrap();
+
+        let mut ciphertext = decode(&sealed.ciphertext).unwrap();
+        ciphertext[0] ^= 1;
+        let changed_text = Sealed {
+            ciphertext: STANDARD.encode(ciphertext),
+            ..sealed.clone()
+        };
+        assert!(key.open(&changed_text).is_err());
+
+        let changed_salt = Sealed {
+            salt: STANDARD.encode([8; SALT_LENGTH]),
+            ..sealed
+        };
+        assert!(key.open(&changed_salt).is_err());
+    }
+
+    #[test]
+    fn test_newer_format_is_rejected() {
+        let key = key("correct horse");
+        let sealed = Sealed {
+            version: 2,
+            ..key.seal_with_nonce("text", [1; NONCE_LENGTH]).unwrap()
+        };
+
+        assert!(key.open(&sealed).unwrap_err().contains("newer version"));
+    }
+
+    #[test]
+    fn test_encrypted_file() {
+        let file = EncryptedFile {
+            name: String::from("Incident"),
+            sealed: key("correct horse")
+                .seal_with_nonce("text", [1; NONCE_LENGTH])
+                .unwrap(),
+        };
+
+        assert!(EncryptedFile::is_encrypted_file("incident.RNOTE"));
+        assert!(!EncryptedFile::is_encrypted_file("incident.md"));
+        assert_eq!(EncryptedFile::from_json(&file.to_json()), Ok(file));
+        assert!(EncryptedFile::from_json("# Notes").is_err());
+    }
+
+    #[test]
+    fn test_excessive_key_settings_are_rejected() {
+        let sealed = key("correct horse")
+            .seal_with_nonce("text", [1; NONCE_LENGTH])
+            .unwrap();
+        let damaged = Err(String::from("The encrypted note is damaged"));
+
+        for kdf in [
+            KdfParams {
+                memory_kib: u32::MAX,
+                iterations: 2,
+            },
+            KdfParams {
+                memory_kib: 19 * 1024,
+                iterations: u32::MAX,
+            },
+        ] {
+            let sealed = Sealed {
+                kdf,
+                ..sealed.clone()
+            };
+            assert_eq!(
+                NoteKey::for_sealed("correct horse", &sealed).map(|_| ()),
+                damaged
+            );
+
+            let file = EncryptedFile {
+                name: String::from("Incident"),
+                sealed,
+            };
+            assert_eq!(
+                EncryptedFile::from_json(&file.to_json()).map(|_| ()),
+                damaged
+            );
+        }
+        assert!(KdfParams::default().is_supported());
+    }
+
+    #[test]
+    fn test_auto_lock_setting() {
+        for choice in AutoLock::CHOICES {
+            assert_eq!(choice.to_string().parse(), Ok(choice));
+        }
+        assert_eq!(AutoLock(Some(5)).as_millis(), Some(300_000.0));
+        assert_eq!(AutoLock(None).as_millis(), None);
+        assert_eq!("After 7 minutes".parse::<AutoLock>(), Err(()));
+    }
+}
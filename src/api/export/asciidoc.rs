@@ -0,0 +1,166 @@
+use comrak::nodes::{AstNode, ListType, NodeValue, TableAlignment};
+
+use crate::api::export::{finish, join_blocks, list_depth, lone_image, plain_text, task_state};
+
+/// Renders the document as AsciiDoc.
+pub fn render<'a>(root: &'a AstNode<'a>) -> String {
+    finish(blocks(root))
+}
+
+fn blocks<'a>(node: &'a AstNode<'a>) -> String {
+    join_blocks(node.children().map(block), "\n\n")
+}
+
+fn block<'a>(node: &'a AstNode<'a>) -> String {
+    match &node.data.borrow().value {
+        NodeValue::Paragraph => match lone_image(node) {
+            Some((url, alt)) => format!("image::{url}[{}]", escape_attribute(&alt)),
+            None => inlines(node),
+        },
+        NodeValue::Heading(heading) => {
+            format!("{} {}", "=".repeat(heading.level as usize), inlines(node))
+        }
+        NodeValue::BlockQuote | NodeValue::MultilineBlockQuote(_) | NodeValue::Alert(_) => {
+            format!("____\n{}\n____", blocks(node))
+        }
+        NodeValue::List(list) => {
+            let marker = match list.list_type {
+                ListType::Bullet => "*",
+                ListType::Ordered => ".",
+            }
+            .repeat(list_depth(node));
+
+            let start = match (list.list_type, list.start) {
+                (ListType::Ordered, start) if start != 1 => format!("[start={start}]\n"),
+                _ => String::new(),
+            };
+
+            let items = node
+                .children()
+                .map(|item| list_item(item, &marker))
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            format!("{start}{items}")
+        }
+        NodeValue::CodeBlock(code) => {
+            let literal = code.literal.trim_end_matches('\n');
+            match code.info.split_whitespace().next() {
+                Some(language) => format!("[source,{language}]\n----\n{literal}\n----"),
+                None => format!("----\n{literal}\n----"),
+            }
+        }
+        NodeValue::Table(table) => {
+            let columns = table
+                .alignments
+                .iter()
+                .map(|alignment| match alignment {
+                    TableAlignment::Center => "^1",
+                    TableAlignment::Right => ">1",
+                    _ => "<1",
+                })
+                .collect::<Vec<&str>>()
+                .join(",");
+
+            let has_header = node
+                .first_child()
+                .is_some_and(|row| matches!(row.data.borrow().value, NodeValue::TableRow(true)));
+            let options = if has_header {
+                ",options=\"header\""
+            } else {
+                ""
+            };
+
+            let rows = node
+                .children()
+                .map(|row| {
+                    row.children()
+                        .map(|cell| format!("| {}", inlines(cell).replace('|', "\\|")))
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            format!("[cols=\"{columns}\"{options}]\n|===\n{rows}\n|===")
+        }
+        NodeValue::ThematicBreak => String::from("'''"),
+        NodeValue::HtmlBlock(_) | NodeValue::FrontMatter(_) | NodeValue::Raw(_) => String::new(),
+        _ => blocks(node),
+    }
+}
+
+/// Renders an item; blocks after the first one are attached with a list continuation.
+fn list_item<'a>(item: &'a AstNode<'a>, marker: &str) -> String {
+    let checkbox = match task_state(item) {
+        Some(true) => "[x] ",
+        Some(false) => "[ ] ",
+        None => "",
+    };
+
+    let mut text = format!("{marker} {checkbox}");
+    for (index, child) in item.children().enumerate() {
+        let is_list = matches!(child.data.borrow().value, NodeValue::List(_));
+        let content = block(child);
+        match (index, is_list) {
+            (0, _) => text.push_str(&content),
+            (_, true) => text.push_str(&format!("\n{content}")),
+            _ => text.push_str(&format!("\n+\n{content}")),
+        }
+    }
+    text
+}
+
+fn inlines<'a>(node: &'a AstNode<'a>) -> String {
+    node.children().map(inline).collect()
+}
+
+fn inline<'a>(node: &'a AstNode<'a>) -> String {
+    match &node.data.borrow().value {
+        NodeValue::Text(text) => escape(text),
+        NodeValue::ShortCode(shortcode) => escape(&shortcode.emoji),
+        NodeValue::Code(code) => format!("`+{}+`", code.literal),
+        NodeValue::Emph => format!("_{}_", inlines(node)),
+        NodeValue::Strong => format!("*{}*", inlines(node)),
+        NodeValue::Strikethrough => format!("[.line-through]#{}#", inlines(node)),
+        NodeValue::SoftBreak => String::from("\n"),
+        NodeValue::LineBreak => String::from(" +\n"),
+        NodeValue::Link(link) => {
+            let text = plain_text(node);
+            if text == link.url {
+                link.url.clone()
+            } else {
+                format!("{}[{}]", link.url, escape_attribute(&inlines(node)))
+            }
+        }
+        NodeValue::Image(link) => {
+            format!(
+                "image:{}[{}]",
+                link.url,
+                escape_attribute(&plain_text(node))
+            )
+        }
+        NodeValue::HtmlInline(_) | NodeValue::Raw(_) => String::new(),
+        _ => inlines(node),
+    }
+}
+
+/// Replaces the characters of inline markup by attribute references.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '*' => escaped.push_str("{asterisk}"),
+            '`' => escaped.push_str("{backtick}"),
+            '^' => escaped.push_str("{caret}"),
+            '~' => escaped.push_str("{tilde}"),
+            '+' => escaped.push_str("{plus}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_attribute(text: &str) -> String {
+    text.replace(']', "\\]")
+}
@@ -0,0 +1,436 @@
+use comrak::nodes::{AstNode, ListType, NodeValue, TableAlignment};
+
+use crate::api::export::{
+    lone_image,
+    office::{EmbeddedImage, escape_xml, write_package},
+    plain_text, task_state,
+};
+
+/// English Metric Units per inch, the unit of drawing sizes.
+const EMU_PER_INCH: f64 = 914_400.0;
+/// Twentieths of a point a list level is indented by.
+const LIST_INDENT: usize = 720;
+/// Number of list levels Word supports.
+const LIST_LEVELS: usize = 9;
+const HEADING_STYLES: [&str; 6] = [
+    "Heading1", "Heading2", "Heading3", "Heading4", "Heading5", "Heading6",
+];
+
+const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+/// Numbering instance used by all bullet lists; ordered lists get their own to restart.
+const BULLET_NUMBERING: usize = 1;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Default Extension="png" ContentType="image/png"/><Default Extension="jpeg" ContentType="image/jpeg"/><Default Extension="gif" ContentType="image/gif"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/><Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/><Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/></Types>"#;
+
+const PACKAGE_RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/></Relationships>"#;
+
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri" w:eastAsia="Calibri" w:cs="Calibri"/><w:sz w:val="22"/><w:szCs w:val="22"/><w:lang w:val="en-US"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="160" w:line="259" w:lineRule="auto"/></w:pPr></w:pPrDefault></w:docDefaults><w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style><w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="360" w:after="120"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:sz w:val="36"/><w:szCs w:val="36"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="120"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:sz w:val="30"/><w:szCs w:val="30"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading3"><w:name w:val="heading 3"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="80"/><w:outlineLvl w:val="2"/></w:pPr><w:rPr><w:b/><w:sz w:val="26"/><w:szCs w:val="26"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading4"><w:name w:val="heading 4"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="200" w:after="80"/><w:outlineLvl w:val="3"/></w:pPr><w:rPr><w:b/><w:i/><w:sz w:val="24"/><w:szCs w:val="24"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading5"><w:name w:val="heading 5"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="200" w:after="80"/><w:outlineLvl w:val="4"/></w:pPr><w:rPr><w:b/><w:sz w:val="22"/><w:szCs w:val="22"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading6"><w:name w:val="heading 6"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="200" w:after="80"/><w:outlineLvl w:val="5"/></w:pPr><w:rPr><w:i/><w:sz w:val="22"/><w:szCs w:val="22"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Quote"><w:name w:val="Quote"/><w:basedOn w:val="Normal"/><w:qFormat/><w:pPr><w:pBdr><w:left w:val="single" w:sz="18" w:space="8" w:color="BFBFBF"/></w:pBdr><w:ind w:left="567"/></w:pPr><w:rPr><w:i/><w:color w:val="595959"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="SourceCode"><w:name w:val="Source Code"/><w:basedOn w:val="Normal"/><w:qFormat/><w:pPr><w:shd w:val="clear" w:color="auto" w:fill="F2F2F2"/><w:spacing w:line="240" w:lineRule="auto"/></w:pPr><w:rPr><w:rFonts w:ascii="Courier New" w:hAnsi="Courier New" w:cs="Courier New"/><w:sz w:val="20"/><w:szCs w:val="20"/></w:rPr></w:style><w:style w:type="character" w:styleId="Hyperlink"><w:name w:val="Hyperlink"/><w:rPr><w:color w:val="0563C1"/><w:u w:val="single"/></w:rPr></w:style><w:style w:type="table" w:styleId="TableGrid"><w:name w:val="Table Grid"/><w:pPr><w:spacing w:after="0"/></w:pPr><w:tblPr><w:tblBorders><w:top w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:left w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:bottom w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:right w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideH w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="auto"/></w:tblBorders><w:tblCellMar><w:left w:w="108" w:type="dxa"/><w:right w:w="108" w:type="dxa"/></w:tblCellMar></w:tblPr></w:style></w:styles>"#;
+
+const SECTION_PROPERTIES: &str = r#"<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440" w:header="708" w:footer="708" w:gutter="0"/></w:sectPr>"#;
+
+/// Where a paragraph is placed: its style and its list numbering or indentation.
+#[derive(Debug, Clone, Copy)]
+struct Context {
+    style: &'static str,
+    /// Numbering instance and level of the first paragraph of a list item.
+    numbering: Option<(usize, usize)>,
+    /// List levels the paragraph is indented by.
+    indent: usize,
+    task: Option<bool>,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Context {
+            style: "Normal",
+            numbering: None,
+            indent: 0,
+            task: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Format {
+    bold: bool,
+    italic: bool,
+    strike: bool,
+    code: bool,
+    link: bool,
+}
+
+/// Collects the relationships, images and list numberings while writing the body.
+#[derive(Default)]
+struct Writer {
+    relationships: Vec<String>,
+    media: Vec<(String, Vec<u8>)>,
+    /// Start values of the ordered lists, each list restarts its numbering.
+    ordered_lists: Vec<(usize, usize)>,
+    drawings: usize,
+}
+
+/// Renders the document as a Word document.
+pub fn render<'a>(root: &'a AstNode<'a>) -> Result<Vec<u8>, String> {
+    let mut writer = Writer::default();
+    let body = writer.blocks(root, Context::default());
+
+    let document = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:pic="http://schemas.openxmlformats.org/drawingml/2006/picture"><w:body>{body}{SECTION_PROPERTIES}</w:body></w:document>"#
+    );
+
+    let mut files = vec![
+        (String::from("[Content_Types].xml"), CONTENT_TYPES.into()),
+        (String::from("_rels/.rels"), PACKAGE_RELATIONSHIPS.into()),
+        (String::from("word/document.xml"), document.into_bytes()),
+        (String::from("word/styles.xml"), STYLES.into()),
+        (
+            String::from("word/numbering.xml"),
+            writer.numbering().into_bytes(),
+        ),
+        (
+            String::from("word/_rels/document.xml.rels"),
+            writer.document_relationships().into_bytes(),
+        ),
+    ];
+    files.append(&mut writer.media);
+
+    write_package(&files)
+}
+
+impl Writer {
+    fn blocks<'a>(&mut self, node: &'a AstNode<'a>, context: Context) -> String {
+        node.children()
+            .map(|child| self.block(child, context))
+            .collect()
+    }
+
+    fn block<'a>(&mut self, node: &'a AstNode<'a>, context: Context) -> String {
+        match &node.data.borrow().value {
+            NodeValue::Paragraph => {
+                let content = match lone_image(node) {
+                    Some((url, alt)) => self.image(&url, &alt, Format::default()),
+                    None => self.runs(node, Format::default()),
+                };
+                self.paragraph(context, "", &content)
+            }
+            NodeValue::Heading(heading) => {
+                let context = Context {
+                    style: HEADING_STYLES[(heading.level as usize).clamp(1, 6) - 1],
+                    ..context
+                };
+                let content = self.runs(node, Format::default());
+                self.paragraph(context, "", &content)
+            }
+            NodeValue::BlockQuote | NodeValue::MultilineBlockQuote(_) | NodeValue::Alert(_) => {
+                self.blocks(
+                    node,
+                    Context {
+                        style: "Quote",
+                        ..context
+                    },
+                )
+            }
+            NodeValue::List(list) => {
+                let level = context.numbering.map_or(context.indent, |(_, level)| level + 1);
+                let level = level.min(LIST_LEVELS - 1);
+                let numbering = match list.list_type {
+                    ListType::Bullet => BULLET_NUMBERING,
+                    ListType::Ordered => {
+                        self.ordered_lists.push((level, list.start));
+                        BULLET_NUMBERING + self.ordered_lists.len()
+                    }
+                };
+
+                let mut content = String::new();
+                for item in node.children() {
+                    for (index, child) in item.children().enumerate() {
+                        let item_context = match index {
+                            0 => Context {
+                                numbering: Some((numbering, level)),
+                                task: task_state(item),
+                                ..context
+                            },
+                            _ => Context {
+                                numbering: None,
+                                indent: level + 1,
+                                task: None,
+                                ..context
+                            },
+                        };
+                        content.push_str(&self.block(child, item_context));
+                    }
+                }
+                content
+            }
+            NodeValue::CodeBlock(code) => {
+                let content = code
+                    .literal
+                    .trim_end_matches('\n')
+                    .split('\n')
+                    .map(|line| text_run(line, Format::default()))
+                    .collect::<Vec<String>>()
+                    .join("<w:r><w:br/></w:r>");
+                let context = Context {
+                    style: "SourceCode",
+                    ..context
+                };
+                self.paragraph(context, "", &content)
+            }
+            NodeValue::Table(table) => self.table(node, &table.alignments),
+            NodeValue::ThematicBreak => self.paragraph(
+                context,
+                r#"<w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="auto"/></w:pBdr>"#,
+                "",
+            ),
+            NodeValue::HtmlBlock(_) | NodeValue::FrontMatter(_) | NodeValue::Raw(_) => {
+                String::new()
+            }
+            _ => self.blocks(node, context),
+        }
+    }
+
+    fn paragraph(&self, context: Context, borders: &str, content: &str) -> String {
+        let numbering = match context.numbering {
+            Some((numbering, level)) => format!(
+                r#"<w:numPr><w:ilvl w:val="{level}"/><w:numId w:val="{numbering}"/></w:numPr>"#
+            ),
+            None => String::new(),
+        };
+        let indent = match (context.numbering, context.indent) {
+            (None, indent) if indent > 0 => {
+                format!(r#"<w:ind w:left="{}"/>"#, LIST_INDENT * indent)
+            }
+            _ => String::new(),
+        };
+        let properties = format!(
+            r#"<w:pStyle w:val="{}"/>{numbering}{borders}{indent}"#,
+            context.style
+        );
+
+        let checkbox = match context.task {
+            Some(true) => text_run("☒ ", Format::default()),
+            Some(false) => text_run("☐ ", Format::default()),
+            None => String::new(),
+        };
+
+        format!("<w:p><w:pPr>{properties}</w:pPr>{checkbox}{content}</w:p>")
+    }
+
+    fn table<'a>(&mut self, node: &'a AstNode<'a>, alignments: &[TableAlignment]) -> String {
+        let grid: String = alignments
+            .iter()
+            .map(|_| r#"<w:gridCol w:w="2000"/>"#)
+            .collect();
+
+        let mut rows = String::new();
+        for row in node.children() {
+            let is_header = matches!(row.data.borrow().value, NodeValue::TableRow(true));
+            rows.push_str("<w:tr>");
+            if is_header {
+                rows.push_str("<w:trPr><w:tblHeader/></w:trPr>");
+            }
+            for (cell, alignment) in row.children().zip(alignments) {
+                let justification = match alignment {
+                    TableAlignment::Center => r#"<w:jc w:val="center"/>"#,
+                    TableAlignment::Right => r#"<w:jc w:val="right"/>"#,
+                    _ => "",
+                };
+                let format = Format {
+                    bold: is_header,
+                    ..Format::default()
+                };
+                let content = self.runs(cell, format);
+                rows.push_str(&format!(
+                    r#"<w:tc><w:tcPr><w:tcW w:w="0" w:type="auto"/></w:tcPr><w:p><w:pPr>{justification}</w:pPr>{content}</w:p></w:tc>"#
+                ));
+            }
+            rows.push_str("</w:tr>");
+        }
+
+        // An empty paragraph keeps consecutive tables apart.
+        format!(
+            r#"<w:tbl><w:tblPr><w:tblStyle w:val="TableGrid"/><w:tblW w:w="0" w:type="auto"/></w:tblPr><w:tblGrid>{grid}</w:tblGrid>{rows}</w:tbl><w:p/>"#
+        )
+    }
+
+    fn runs<'a>(&mut self, node: &'a AstNode<'a>, format: Format) -> String {
+        node.children()
+            .map(|child| self.run(child, format))
+            .collect()
+    }
+
+    fn run<'a>(&mut self, node: &'a AstNode<'a>, format: Format) -> String {
+        match &node.data.borrow().value {
+            NodeValue::Text(text) => text_run(text, format),
+            NodeValue::ShortCode(shortcode) => text_run(&shortcode.emoji, format),
+            NodeValue::Code(code) => text_run(
+                &code.literal,
+                Format {
+                    code: true,
+                    ..format
+                },
+            ),
+            NodeValue::Emph => self.runs(
+                node,
+                Format {
+                    italic: true,
+                    ..format
+                },
+            ),
+            NodeValue::Strong => self.runs(
+                node,
+                Format {
+                    bold: true,
+                    ..format
+                },
+            ),
+            NodeValue::Strikethrough => self.runs(
+                node,
+                Format {
+                    strike: true,
+                    ..format
+                },
+            ),
+            NodeValue::SoftBreak => text_run(" ", format),
+            NodeValue::LineBreak => String::from("<w:r><w:br/></w:r>"),
+            NodeValue::Link(link) if !format.link => {
+                let content = self.runs(
+                    node,
+                    Format {
+                        link: true,
+                        ..format
+                    },
+                );
+                self.hyperlink(&link.url, &content)
+            }
+            NodeValue::Image(link) => self.image(&link.url, &plain_text(node), format),
+            NodeValue::HtmlInline(_) | NodeValue::Raw(_) => String::new(),
+            _ => self.runs(node, format),
+        }
+    }
+
+    fn hyperlink(&mut self, url: &str, content: &str) -> String {
+        let id = self.relationship(
+            "hyperlink",
+            &format!(r#"Target="{}" TargetMode="External""#, escape_xml(url)),
+        );
+        format!(r#"<w:hyperlink r:id="{id}">{content}</w:hyperlink>"#)
+    }
+
+    /// Embeds images of `data:` URLs; other images become a link showing the alternative text.
+    fn image(&mut self, url: &str, alt: &str, format: Format) -> String {
+        let Some(image) = EmbeddedImage::from_data_url(url) else {
+            let text = if alt.is_empty() { url } else { alt };
+            return match format.link {
+                true => text_run(text, format),
+                false => {
+                    let content = text_run(
+                        text,
+                        Format {
+                            link: true,
+                            ..format
+                        },
+                    );
+                    self.hyperlink(url, &content)
+                }
+            };
+        };
+
+        self.drawings += 1;
+        let number = self.drawings;
+        let name = format!("image{number}.{}", image.extension);
+        let id = self.relationship("image", &format!(r#"Target="media/{name}""#));
+        self.media
+            .push((format!("word/media/{name}"), image.bytes.clone()));
+
+        let (width, height) = image.display_size();
+        let (width, height) = (
+            (width * EMU_PER_INCH).round() as u64,
+            (height * EMU_PER_INCH).round() as u64,
+        );
+        let alt = escape_xml(alt);
+
+        format!(
+            r#"<w:r><w:drawing><wp:inline distT="0" distB="0" distL="0" distR="0"><wp:extent cx="{width}" cy="{height}"/><wp:docPr id="{number}" name="Picture {number}" descr="{alt}"/><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/picture"><pic:pic><pic:nvPicPr><pic:cNvPr id="{number}" name="{name}" descr="{alt}"/><pic:cNvPicPr/></pic:nvPicPr><pic:blipFill><a:blip r:embed="{id}"/><a:stretch><a:fillRect/></a:stretch></pic:blipFill><pic:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="{width}" cy="{height}"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></pic:spPr></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>"#
+        )
+    }
+
+    /// Adds a relationship of the document and returns its id. The first two ids
+    /// belong to the styles and the numbering.
+    fn relationship(&mut self, kind: &str, target: &str) -> String {
+        let id = format!("rId{}", self.relationships.len() + 3);
+        self.relationships.push(format!(
+            r#"<Relationship Id="{id}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/{kind}" {target}/>"#
+        ));
+        id
+    }
+
+    fn document_relationships(&self) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/>{}</Relationships>"#,
+            self.relationships.concat()
+        )
+    }
+
+    fn numbering(&self) -> String {
+        let levels = |format: &dyn Fn(usize) -> (&'static str, String)| {
+            (0..LIST_LEVELS)
+                .map(|level| {
+                    let (number_format, text) = format(level);
+                    format!(
+                        r#"<w:lvl w:ilvl="{level}"><w:start w:val="1"/><w:numFmt w:val="{number_format}"/><w:lvlText w:val="{text}"/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="{}" w:hanging="360"/></w:pPr></w:lvl>"#,
+                        LIST_INDENT * (level + 1)
+                    )
+                })
+                .collect::<String>()
+        };
+        let bullets = levels(&|level| ("bullet", BULLETS[level % BULLETS.len()].to_string()));
+        let decimals = levels(&|level| ("decimal", format!("%{}.", level + 1)));
+
+        let ordered_lists: String = self
+            .ordered_lists
+            .iter()
+            .enumerate()
+            .map(|(index, (level, start))| {
+                format!(
+                    r#"<w:num w:numId="{}"><w:abstractNumId w:val="1"/><w:lvlOverride w:ilvl="{level}"><w:startOverride w:val="{start}"/></w:lvlOverride></w:num>"#,
+                    BULLET_NUMBERING + index + 1
+                )
+            })
+            .collect();
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:abstractNum w:abstractNumId="0"><w:multiLevelType w:val="hybridMultilevel"/>{bullets}</w:abstractNum><w:abstractNum w:abstractNumId="1"><w:multiLevelType w:val="hybridMultilevel"/>{decimals}</w:abstractNum><w:num w:numId="{BULLET_NUMBERING}"><w:abstractNumId w:val="0"/></w:num>{ordered_lists}</w:numbering>"#
+        )
+    }
+}
+
+fn text_run(text: &str, format: Format) -> String {
+    let mut properties = String::new();
+    if format.link {
+        properties.push_str(r#"<w:rStyle w:val="Hyperlink"/>"#);
+    }
+    if format.code {
+        properties.push_str(
+            r#"<w:rFonts w:ascii="Courier New" w:hAnsi="Courier New" w:cs="Courier New"/>"#,
+        );
+    }
+    if format.bold {
+        properties.push_str("<w:b/>");
+    }
+    if format.italic {
+        properties.push_str("<w:i/>");
+    }
+    if format.strike {
+        properties.push_str("<w:strike/>");
+    }
+
+    let text = escape_xml(text).replace('\t', "</w:t><w:tab/><w:t xml:space=\"preserve\">");
+    format!(r#"<w:r><w:rPr>{properties}</w:rPr><w:t xml:space="preserve">{text}</w:t></w:r>"#)
+}
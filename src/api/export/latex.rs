@@ -0,0 +1,197 @@
+use comrak::nodes::{AstNode, ListType, NodeValue, TableAlignment};
+
+use crate::api::export::{join_blocks, lone_image, plain_text, task_state};
+
+const PREAMBLE: &str = "\\documentclass{article}
+\\usepackage[utf8]{inputenc}
+\\usepackage[T1]{fontenc}
+\\usepackage{graphicx}
+\\usepackage[normalem]{ulem}
+\\usepackage{hyperref}
+
+\\begin{document}
+";
+
+const SECTIONS: [&str; 6] = [
+    "section",
+    "subsection",
+    "subsubsection",
+    "paragraph",
+    "subparagraph",
+    "subparagraph",
+];
+
+/// Item counters of the nested `enumerate` environments, from the outermost.
+const ENUMERATE_COUNTERS: [&str; 4] = ["enumi", "enumii", "enumiii", "enumiv"];
+
+/// Renders the document as a standalone LaTeX article.
+pub fn render<'a>(root: &'a AstNode<'a>) -> String {
+    let body = blocks(root);
+    match body.is_empty() {
+        true => format!("{PREAMBLE}\n\\end{{document}}\n"),
+        false => format!("{PREAMBLE}\n{body}\n\n\\end{{document}}\n"),
+    }
+}
+
+fn blocks<'a>(node: &'a AstNode<'a>) -> String {
+    join_blocks(node.children().map(block), "\n\n")
+}
+
+fn block<'a>(node: &'a AstNode<'a>) -> String {
+    match &node.data.borrow().value {
+        NodeValue::Paragraph => match lone_image(node) {
+            Some((url, alt)) => image(&url, &alt),
+            None => inlines(node),
+        },
+        NodeValue::Heading(heading) => format!(
+            "\\{}{{{}}}",
+            SECTIONS[(heading.level as usize).clamp(1, 6) - 1],
+            inlines(node)
+        ),
+        NodeValue::BlockQuote | NodeValue::MultilineBlockQuote(_) | NodeValue::Alert(_) => {
+            format!("\\begin{{quote}}\n{}\n\\end{{quote}}", blocks(node))
+        }
+        NodeValue::List(list) => {
+            let environment = match list.list_type {
+                ListType::Bullet => "itemize",
+                ListType::Ordered => "enumerate",
+            };
+            let start = match (list.list_type, list.start) {
+                (ListType::Ordered, start) if start != 1 => format!(
+                    "\n\\setcounter{{{}}}{{{}}}",
+                    ENUMERATE_COUNTERS[enumerate_depth(node).clamp(1, 4) - 1],
+                    start - 1
+                ),
+                _ => String::new(),
+            };
+
+            let items = node
+                .children()
+                .map(|item| {
+                    let label = match task_state(item) {
+                        Some(true) => "\\item[{[x]}]",
+                        Some(false) => "\\item[{[ ]}]",
+                        None => "\\item",
+                    };
+                    format!("{label} {}", blocks(item))
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            format!("\\begin{{{environment}}}{start}\n{items}\n\\end{{{environment}}}")
+        }
+        NodeValue::CodeBlock(code) => format!(
+            "\\begin{{verbatim}}\n{}\n\\end{{verbatim}}",
+            code.literal.trim_end_matches('\n')
+        ),
+        NodeValue::Table(table) => {
+            let columns = table
+                .alignments
+                .iter()
+                .map(|alignment| match alignment {
+                    TableAlignment::Center => "c",
+                    TableAlignment::Right => "r",
+                    _ => "l",
+                })
+                .collect::<String>();
+
+            let rows = node
+                .children()
+                .map(|row| {
+                    let cells = row
+                        .children()
+                        .map(inlines)
+                        .collect::<Vec<String>>()
+                        .join(" & ");
+                    match row.data.borrow().value {
+                        NodeValue::TableRow(true) => format!("{cells} \\\\\n\\hline"),
+                        _ => format!("{cells} \\\\"),
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            format!("\\begin{{tabular}}{{{columns}}}\n\\hline\n{rows}\n\\hline\n\\end{{tabular}}")
+        }
+        NodeValue::ThematicBreak => String::from("\\noindent\\rule{\\linewidth}{0.4pt}"),
+        NodeValue::HtmlBlock(_) | NodeValue::FrontMatter(_) | NodeValue::Raw(_) => String::new(),
+        _ => blocks(node),
+    }
+}
+
+/// Number of ordered lists the node is nested in, including its own list. Bullet
+/// lists don't count, as they don't nest `enumerate` environments.
+fn enumerate_depth<'a>(node: &'a AstNode<'a>) -> usize {
+    node.ancestors()
+        .filter(|ancestor| {
+            matches!(
+                &ancestor.data.borrow().value,
+                NodeValue::List(list) if list.list_type == ListType::Ordered
+            )
+        })
+        .count()
+}
+
+fn inlines<'a>(node: &'a AstNode<'a>) -> String {
+    node.children().map(inline).collect()
+}
+
+fn inline<'a>(node: &'a AstNode<'a>) -> String {
+    match &node.data.borrow().value {
+        NodeValue::Text(text) => escape(text),
+        NodeValue::ShortCode(shortcode) => escape(&shortcode.emoji),
+        NodeValue::Code(code) => format!("\\texttt{{{}}}", escape(&code.literal)),
+        NodeValue::Emph => format!("\\emph{{{}}}", inlines(node)),
+        NodeValue::Strong => format!("\\textbf{{{}}}", inlines(node)),
+        NodeValue::Strikethrough => format!("\\sout{{{}}}", inlines(node)),
+        NodeValue::SoftBreak => String::from("\n"),
+        NodeValue::LineBreak => String::from("\\\\\n"),
+        NodeValue::Link(link) => {
+            let url = escape_url(&link.url);
+            if plain_text(node) == link.url {
+                format!("\\url{{{url}}}")
+            } else {
+                format!("\\href{{{url}}}{{{}}}", inlines(node))
+            }
+        }
+        NodeValue::Image(link) => image(&link.url, &plain_text(node)),
+        NodeValue::HtmlInline(_) | NodeValue::Raw(_) => String::new(),
+        _ => inlines(node),
+    }
+}
+
+/// Includes the image file. LaTeX can't read `data:` URLs, e.g. of embedded
+/// attachments, so those images are replaced by their alternative text.
+fn image(url: &str, alt: &str) -> String {
+    match url.starts_with("data:") {
+        true => format!("\\emph{{{}}}", escape(alt)),
+        false => format!("\\includegraphics[width=\\linewidth]{{{url}}}"),
+    }
+}
+
+/// Escapes the characters with a special meaning in LaTeX.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '{' | '}' | '$' | '&' | '#' | '_' | '%' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// URLs are read verbatim by hyperref, except for these characters.
+fn escape_url(url: &str) -> String {
+    url.replace('\\', "\\\\")
+        .replace('#', "\\#")
+        .replace('%', "\\%")
+        .replace('{', "\\{")
+        .replace('}', "\\}")
+}
@@ -0,0 +1,611 @@
+use std::fmt;
+
+use comrak::{
+    Arena, Options,
+    nodes::{AstNode, ListType, NodeList, NodeValue},
+    parse_document,
+};
+
+use crate::api::{parser::Dialect, preview_styles::PreviewStyle};
+
+mod asciidoc;
+mod docx;
+mod latex;
+mod odt;
+mod office;
+mod plain_text;
+mod rst;
+
+/// Formats that are generated from the syntax tree of the markdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    PlainText,
+    ReStructuredText,
+    AsciiDoc,
+    LaTeX,
+}
+
+pub const EXPORT_FORMATS: [ExportFormat; 4] = [
+    ExportFormat::PlainText,
+    ExportFormat::ReStructuredText,
+    ExportFormat::AsciiDoc,
+    ExportFormat::LaTeX,
+];
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::PlainText => "txt",
+            ExportFormat::ReStructuredText => "rst",
+            ExportFormat::AsciiDoc => "adoc",
+            ExportFormat::LaTeX => "tex",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ExportFormat::PlainText => "Plain Text",
+                ExportFormat::ReStructuredText => "reStructuredText",
+                ExportFormat::AsciiDoc => "AsciiDoc",
+                ExportFormat::LaTeX => "LaTeX",
+            }
+        )
+    }
+}
+
+/// Office documents, which are zip packages of XML files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DocumentFormat {
+    Docx,
+    Odt,
+}
+
+pub const DOCUMENT_FORMATS: [DocumentFormat; 2] = [DocumentFormat::Docx, DocumentFormat::Odt];
+
+impl DocumentFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            DocumentFormat::Docx => "docx",
+            DocumentFormat::Odt => "odt",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            DocumentFormat::Docx => {
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+            }
+            DocumentFormat::Odt => "application/vnd.oasis.opendocument.text",
+        }
+    }
+}
+
+impl fmt::Display for DocumentFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DocumentFormat::Docx => "Word (DOCX)",
+                DocumentFormat::Odt => "OpenDocument (ODT)",
+            }
+        )
+    }
+}
+
+/// Converts the markdown into another markup language.
+///
+/// ```rust
+/// use rustynote::api::{export::{ExportFormat, export}, parser::Dialect};
+///
+/// assert_eq!(export("# Title", &Dialect::Common, ExportFormat::AsciiDoc), "= Title\n");
+/// ```
+pub fn export(markdown: &str, dialect: &Dialect, format: ExportFormat) -> String {
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &dialect.options());
+
+    match format {
+        ExportFormat::PlainText => plain_text::render(root),
+        ExportFormat::ReStructuredText => rst::render(root),
+        ExportFormat::AsciiDoc => asciidoc::render(root),
+        ExportFormat::LaTeX => latex::render(root),
+    }
+}
+
+/// Converts the markdown into an office document. Images of `data:` URLs, e.g. embedded
+/// attachments, are stored in the document, other images are linked.
+pub fn export_document(
+    markdown: &str,
+    dialect: &Dialect,
+    format: DocumentFormat,
+) -> Result<Vec<u8>, String> {
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &dialect.options());
+
+    match format {
+        DocumentFormat::Docx => docx::render(root),
+        DocumentFormat::Odt => odt::render(root),
+    }
+}
+
+/// Text of the first heading, used as the title of exported documents.
+///
+/// ```rust
+/// use rustynote::api::export::document_title;
+///
+/// assert_eq!(document_title("Intro\n\n## The *first* heading"), "The first heading");
+/// assert_eq!(document_title("No heading"), "Document");
+/// ```
+pub fn document_title(markdown: &str) -> String {
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &Options::default());
+
+    root.descendants()
+        .find(|node| matches!(node.data.borrow().value, NodeValue::Heading(_)))
+        .map(|heading| {
+            heading
+                .descendants()
+                .filter_map(|node| match &node.data.borrow().value {
+                    NodeValue::Text(text) => Some(text.to_string()),
+                    NodeValue::Code(code) => Some(code.literal.clone()),
+                    _ => None,
+                })
+                .collect::<String>()
+        })
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| String::from("Document"))
+}
+
+/// Wraps rendered markdown into a standalone HTML document using the preview style.
+pub fn html_document(title: &str, body: &str, style: PreviewStyle, custom_css: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n\
+         <html lang=\"en\">\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n\
+         <style>\n{}\n</style>\n\
+         </head>\n\
+         <body>\n\
+         <article class=\"{}\">\n{}</article>\n\
+         </body>\n\
+         </html>\n",
+        escape_html(title),
+        style.export_stylesheet(custom_css),
+        style.class(),
+        body,
+    )
+}
+
+/// Joins the rendered blocks, skipping blocks that render to nothing.
+fn join_blocks(blocks: impl Iterator<Item = String>, separator: &str) -> String {
+    blocks
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+/// Prefixes the first line with `first` and every other non-empty line with `rest`.
+fn indent(text: &str, first: &str, rest: &str) -> String {
+    text.split('\n')
+        .enumerate()
+        .map(|(index, line)| match (index, line.is_empty()) {
+            (0, _) => format!("{first}{line}"),
+            (_, true) => String::new(),
+            _ => format!("{rest}{line}"),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Ends the document with exactly one newline.
+fn finish(text: String) -> String {
+    let text = text.trim_end();
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!("{text}\n")
+    }
+}
+
+/// Literal text of the node, without any markup.
+fn plain_text<'a>(node: &'a AstNode<'a>) -> String {
+    node.descendants()
+        .filter_map(|node| match &node.data.borrow().value {
+            NodeValue::Text(text) => Some(text.to_string()),
+            NodeValue::ShortCode(shortcode) => Some(shortcode.emoji.clone()),
+            NodeValue::Code(code) => Some(code.literal.clone()),
+            NodeValue::SoftBreak | NodeValue::LineBreak => Some(String::from(" ")),
+            _ => None,
+        })
+        .collect()
+}
+
+/// URL and alternative text of a paragraph that only consists of an image.
+fn lone_image<'a>(paragraph: &'a AstNode<'a>) -> Option<(String, String)> {
+    let mut children = paragraph.children();
+    let image = children.next()?;
+    if children.next().is_some() {
+        return None;
+    }
+
+    match &image.data.borrow().value {
+        NodeValue::Image(link) => Some((link.url.clone(), plain_text(image))),
+        _ => None,
+    }
+}
+
+/// The markers of the items of a list, e.g. `1.`, `2.` for ordered lists.
+fn list_markers(list: &NodeList, bullet: &str, count: usize) -> Vec<String> {
+    (0..count)
+        .map(|index| match list.list_type {
+            ListType::Bullet => bullet.to_string(),
+            ListType::Ordered => format!("{}.", list.start + index),
+        })
+        .collect()
+}
+
+/// Checkbox state of a task list item, `None` for regular items.
+fn task_state<'a>(item: &'a AstNode<'a>) -> Option<bool> {
+    match item.data.borrow().value {
+        NodeValue::TaskItem(checked) => Some(checked.is_some()),
+        _ => None,
+    }
+}
+
+/// Number of lists the node is nested in, including its own list.
+fn list_depth<'a>(node: &'a AstNode<'a>) -> usize {
+    node.ancestors()
+        .filter(|ancestor| matches!(ancestor.data.borrow().value, NodeValue::List(_)))
+        .count()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_title_uses_first_heading() {
+        assert_eq!(document_title("# One\n\n# Two"), "One");
+    }
+
+    #[test]
+    fn test_document_title_includes_inline_code() {
+        assert_eq!(document_title("# The `main` function"), "The main function");
+    }
+
+    #[test]
+    fn test_document_title_falls_back_for_empty_heading() {
+        assert_eq!(document_title("#\n\ntext"), "Document");
+    }
+
+    #[test]
+    fn test_html_document_wraps_body() {
+        let html = html_document("A & B", "<p>Hi</p>\n", PreviewStyle::GitHub, "");
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>A &amp; B</title>"));
+        assert!(html.contains("<article class=\"preview-style-github\">\n<p>Hi</p>\n</article>"));
+        assert!(html.contains(".preview-style-github h1"));
+    }
+
+    #[test]
+    fn test_html_document_with_custom_css() {
+        let html = html_document("Notes", "", PreviewStyle::Custom, "h1 { color: red; }");
+
+        assert!(html.contains("<style>\nh1 { color: red; }\n</style>"));
+        assert!(html.contains("<article class=\"preview-style-custom\">"));
+    }
+
+    const GOLDEN_MARKDOWN: &str = include_str!("golden/sample.md");
+
+    fn golden(format: ExportFormat) -> String {
+        export(GOLDEN_MARKDOWN, &Dialect::GitHub, format)
+    }
+
+    #[test]
+    fn test_export_plain_text() {
+        assert_eq!(
+            golden(ExportFormat::PlainText),
+            include_str!("golden/sample.txt")
+        );
+    }
+
+    #[test]
+    fn test_export_rst() {
+        assert_eq!(
+            golden(ExportFormat::ReStructuredText),
+            include_str!("golden/sample.rst")
+        );
+    }
+
+    #[test]
+    fn test_export_rst_separates_quotes_from_indented_blocks() {
+        let rst = |markdown| export(markdown, &Dialect::GitHub, ExportFormat::ReStructuredText);
+
+        assert_eq!(rst("- item\n\n> quote"), "- item\n\n..\n\n    quote\n");
+        assert_eq!(rst("> one\n\n> two"), "    one\n\n..\n\n    two\n");
+        assert_eq!(rst("Text\n\n> quote"), "Text\n\n    quote\n");
+    }
+
+    #[test]
+    fn test_export_asciidoc() {
+        assert_eq!(
+            golden(ExportFormat::AsciiDoc),
+            include_str!("golden/sample.adoc")
+        );
+    }
+
+    #[test]
+    fn test_export_latex() {
+        assert_eq!(
+            golden(ExportFormat::LaTeX),
+            include_str!("golden/sample.tex")
+        );
+    }
+
+    #[test]
+    fn test_export_latex_nested_ordered_list() {
+        assert_eq!(
+            export(
+                include_str!("golden/nested_ordered.md"),
+                &Dialect::GitHub,
+                ExportFormat::LaTeX
+            ),
+            include_str!("golden/nested_ordered.tex")
+        );
+    }
+
+    #[test]
+    fn test_export_latex_replaces_embedded_images() {
+        let latex = export(
+            "![Chart](data:image/png;base64,AAAA)",
+            &Dialect::Common,
+            ExportFormat::LaTeX,
+        );
+
+        assert!(latex.contains("\\emph{Chart}"));
+        assert!(!latex.contains("data:"));
+    }
+
+    #[test]
+    fn test_export_renders_emoji_shortcodes() {
+        let markdown = "Shipped :rocket: at 10:30:00";
+
+        assert_eq!(
+            export(markdown, &Dialect::GitHub, ExportFormat::PlainText),
+            "Shipped 🚀 at 10:30:00\n"
+        );
+        assert!(export(markdown, &Dialect::Common, ExportFormat::PlainText).contains(":rocket:"));
+    }
+
+    #[test]
+    fn test_export_empty_document() {
+        assert_eq!(export("", &Dialect::Common, ExportFormat::PlainText), "");
+        assert_eq!(
+            export("", &Dialect::Common, ExportFormat::ReStructuredText),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_export_ordered_list_start() {
+        assert_eq!(
+            export(
+                "3. Three\n4. Four",
+                &Dialect::Common,
+                ExportFormat::PlainText
+            ),
+            "3. Three\n4. Four\n"
+        );
+        assert_eq!(
+            export("3. Three", &Dialect::Common, ExportFormat::AsciiDoc),
+            "[start=3]\n. Three\n"
+        );
+    }
+
+    #[test]
+    fn test_export_loose_list_item_continuation() {
+        assert_eq!(
+            export(
+                "- Item\n\n  More text",
+                &Dialect::Common,
+                ExportFormat::AsciiDoc
+            ),
+            "* Item\n+\nMore text\n"
+        );
+    }
+
+    #[test]
+    fn test_export_autolink_keeps_url() {
+        let markdown = "<https://example.com>";
+
+        assert_eq!(
+            export(markdown, &Dialect::Common, ExportFormat::ReStructuredText),
+            "https://example.com\n"
+        );
+        assert!(
+            export(markdown, &Dialect::Common, ExportFormat::LaTeX)
+                .contains("\\url{https://example.com}")
+        );
+    }
+
+    fn document_entry(package: Vec<u8>, name: &str) -> String {
+        use std::io::Read;
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(package)).unwrap();
+        let mut content = String::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        content
+    }
+
+    fn docx_body(markdown: &str) -> String {
+        let package = export_document(markdown, &Dialect::GitHub, DocumentFormat::Docx).unwrap();
+        document_entry(package, "word/document.xml")
+    }
+
+    fn odt_body(markdown: &str) -> String {
+        let package = export_document(markdown, &Dialect::GitHub, DocumentFormat::Odt).unwrap();
+        document_entry(package, "content.xml")
+    }
+
+    #[test]
+    fn test_docx_package() {
+        let package =
+            export_document(GOLDEN_MARKDOWN, &Dialect::GitHub, DocumentFormat::Docx).unwrap();
+
+        assert!(
+            document_entry(package.clone(), "[Content_Types].xml").contains("/word/document.xml")
+        );
+        assert!(
+            document_entry(package.clone(), "word/styles.xml").contains("w:styleId=\"Heading1\"")
+        );
+        assert!(
+            document_entry(package, "word/_rels/document.xml.rels")
+                .contains("Target=\"https://example.com\" TargetMode=\"External\"")
+        );
+    }
+
+    #[test]
+    fn test_docx_blocks() {
+        let body = docx_body(GOLDEN_MARKDOWN);
+
+        assert!(body.contains("<w:pStyle w:val=\"Heading1\"/></w:pPr><w:r><w:rPr></w:rPr><w:t xml:space=\"preserve\">Project Notes</w:t>"));
+        assert!(body.contains("<w:pStyle w:val=\"Quote\"/>"));
+        assert!(body.contains("<w:pStyle w:val=\"SourceCode\"/>"));
+        assert!(body.contains("<w:tblStyle w:val=\"TableGrid\"/>"));
+        assert!(body.contains("<w:jc w:val=\"right\"/>"));
+        assert!(body.contains("<w:numPr><w:ilvl w:val=\"1\"/><w:numId w:val=\"1\"/></w:numPr>"));
+        assert!(body.contains("<w:t xml:space=\"preserve\">☒ </w:t>"));
+    }
+
+    #[test]
+    fn test_docx_inlines() {
+        let body = docx_body("*a* **b** ~~c~~ `d` [e](https://example.com)");
+
+        assert!(body.contains("<w:rPr><w:i/></w:rPr><w:t xml:space=\"preserve\">a</w:t>"));
+        assert!(body.contains("<w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">b</w:t>"));
+        assert!(body.contains("<w:rPr><w:strike/></w:rPr><w:t xml:space=\"preserve\">c</w:t>"));
+        assert!(body.contains("<w:rFonts w:ascii=\"Courier New\""));
+        assert!(body.contains("<w:hyperlink r:id=\"rId3\"><w:r><w:rPr><w:rStyle w:val=\"Hyperlink\"/></w:rPr><w:t xml:space=\"preserve\">e</w:t></w:r></w:hyperlink>"));
+    }
+
+    #[test]
+    fn test_docx_ordered_lists_restart() {
+        let package = export_document(
+            "1. a\n\ntext\n\n3. b",
+            &Dialect::Common,
+            DocumentFormat::Docx,
+        )
+        .unwrap();
+        let numbering = document_entry(package, "word/numbering.xml");
+
+        assert!(numbering.contains("<w:num w:numId=\"2\"><w:abstractNumId w:val=\"1\"/><w:lvlOverride w:ilvl=\"0\"><w:startOverride w:val=\"1\"/>"));
+        assert!(numbering.contains("<w:num w:numId=\"3\"><w:abstractNumId w:val=\"1\"/><w:lvlOverride w:ilvl=\"0\"><w:startOverride w:val=\"3\"/>"));
+    }
+
+    #[test]
+    fn test_docx_embeds_data_url_images() {
+        let markdown = format!("![Dot](data:image/png;base64,{TINY_PNG})");
+        let package = export_document(&markdown, &Dialect::Common, DocumentFormat::Docx).unwrap();
+
+        assert!(
+            document_entry(package.clone(), "word/document.xml")
+                .contains("<a:blip r:embed=\"rId3\"/>")
+        );
+        assert!(
+            document_entry(package.clone(), "word/_rels/document.xml.rels")
+                .contains("Target=\"media/image1.png\"")
+        );
+        assert!(
+            zip::ZipArchive::new(std::io::Cursor::new(package))
+                .unwrap()
+                .by_name("word/media/image1.png")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_docx_links_remote_images() {
+        let body = docx_body("![Logo](logo.png)");
+
+        assert!(body.contains("<w:hyperlink r:id=\"rId3\">"));
+        assert!(body.contains(">Logo</w:t>"));
+        assert!(!body.contains("<w:drawing>"));
+    }
+
+    #[test]
+    fn test_odt_package() {
+        let package =
+            export_document(GOLDEN_MARKDOWN, &Dialect::GitHub, DocumentFormat::Odt).unwrap();
+
+        assert_eq!(&package[30..38], b"mimetype");
+        assert!(
+            document_entry(package.clone(), "META-INF/manifest.xml")
+                .contains("manifest:full-path=\"content.xml\"")
+        );
+        assert!(document_entry(package, "styles.xml").contains("style:name=\"Numbering_20_123\""));
+    }
+
+    #[test]
+    fn test_odt_blocks() {
+        let body = odt_body(GOLDEN_MARKDOWN);
+
+        assert!(body.contains("<text:h text:style-name=\"Heading_20_1\" text:outline-level=\"1\">Project Notes</text:h>"));
+        assert!(body.contains("<text:list text:style-name=\"List_20_1\"><text:list-item><text:p text:style-name=\"Text_20_body\">First item</text:p></text:list-item>"));
+        assert!(body.contains("<text:p text:style-name=\"Text_20_body\">☒ Done task</text:p>"));
+        assert!(body.contains("<text:list text:style-name=\"Numbering_20_123\"><text:list-item text:start-value=\"1\">"));
+        assert!(body.contains("<text:p text:style-name=\"Preformatted_20_Text\"><text:s text:c=\"4\"/>println!(&quot;Hello&quot;);</text:p>"));
+        assert!(body.contains("<text:p text:style-name=\"Quotations\">A quoted line.</text:p>"));
+        assert!(body.contains("<table:table-header-rows><table:table-row>"));
+        assert!(body.contains("<text:p text:style-name=\"Table_20_Contents_Right\">3</text:p>"));
+        assert!(body.contains("<text:p text:style-name=\"Horizontal_20_Line\"/>"));
+    }
+
+    #[test]
+    fn test_odt_inlines() {
+        let body = odt_body("*a* **b** `c  d` [e](https://example.com)");
+
+        assert!(body.contains("<text:span text:style-name=\"Emphasis\">a</text:span>"));
+        assert!(body.contains("<text:span text:style-name=\"Strong_20_Emphasis\">b</text:span>"));
+        assert!(
+            body.contains("<text:span text:style-name=\"Source_20_Text\">c <text:s/>d</text:span>")
+        );
+        assert!(body.contains("<text:a xlink:type=\"simple\" xlink:href=\"https://example.com\" text:style-name=\"Internet_20_link\">e</text:a>"));
+    }
+
+    #[test]
+    fn test_odt_embeds_data_url_images() {
+        let markdown = format!("![Dot](data:image/png;base64,{TINY_PNG})");
+        let package = export_document(&markdown, &Dialect::Common, DocumentFormat::Odt).unwrap();
+
+        assert!(
+            document_entry(package.clone(), "content.xml")
+                .contains("<draw:image xlink:href=\"Pictures/image1.png\"")
+        );
+        assert!(document_entry(package, "META-INF/manifest.xml").contains(
+            "manifest:full-path=\"Pictures/image1.png\" manifest:media-type=\"image/png\""
+        ));
+    }
+
+    /// Header of a 1×1 pixel PNG, which is all the export needs to size the image.
+    const TINY_PNG: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJ";
+}
@@ -0,0 +1,341 @@
+use comrak::nodes::{AstNode, ListType, NodeValue, TableAlignment};
+
+use crate::api::export::{
+    lone_image,
+    office::{EmbeddedImage, escape_xml, write_package},
+    plain_text, task_state,
+};
+
+const MIME_TYPE: &str = "application/vnd.oasis.opendocument.text";
+
+const NAMESPACES: &str = r#"xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:svg="urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0" office:version="1.3""#;
+
+const HEADING_STYLES: [&str; 6] = [
+    "Heading_20_1",
+    "Heading_20_2",
+    "Heading_20_3",
+    "Heading_20_4",
+    "Heading_20_5",
+    "Heading_20_6",
+];
+
+const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+
+const STYLES: &str = r##"<office:styles><style:default-style style:family="paragraph"><style:paragraph-properties fo:margin-top="0in" fo:margin-bottom="0.08in"/><style:text-properties style:font-name="Liberation Sans" fo:font-family="'Liberation Sans'" fo:font-size="11pt" fo:language="en" fo:country="US"/></style:default-style><style:style style:name="Standard" style:family="paragraph" style:class="text"/><style:style style:name="Text_20_body" style:display-name="Text body" style:family="paragraph" style:parent-style-name="Standard" style:class="text"/><style:style style:name="Heading" style:family="paragraph" style:parent-style-name="Standard" style:next-style-name="Text_20_body" style:class="text"><style:paragraph-properties fo:margin-top="0.17in" fo:margin-bottom="0.08in" fo:keep-with-next="always"/><style:text-properties fo:font-weight="bold"/></style:style><style:style style:name="Heading_20_1" style:display-name="Heading 1" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="1" style:class="text"><style:text-properties fo:font-size="18pt"/></style:style><style:style style:name="Heading_20_2" style:display-name="Heading 2" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="2" style:class="text"><style:text-properties fo:font-size="15pt"/></style:style><style:style style:name="Heading_20_3" style:display-name="Heading 3" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="3" style:class="text"><style:text-properties fo:font-size="13pt"/></style:style><style:style style:name="Heading_20_4" style:display-name="Heading 4" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="4" style:class="text"><style:text-properties fo:font-size="12pt" fo:font-style="italic"/></style:style><style:style style:name="Heading_20_5" style:display-name="Heading 5" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="5" style:class="text"><style:text-properties fo:font-size="11pt"/></style:style><style:style style:name="Heading_20_6" style:display-name="Heading 6" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="6" style:class="text"><style:text-properties fo:font-size="11pt" fo:font-style="italic" fo:font-weight="normal"/></style:style><style:style style:name="Quotations" style:family="paragraph" style:parent-style-name="Standard" style:class="html"><style:paragraph-properties fo:margin-left="0.39in" fo:padding-left="0.1in" fo:border-left="2.25pt solid #bfbfbf"/><style:text-properties fo:font-style="italic" fo:color="#595959"/></style:style><style:style style:name="Preformatted_20_Text" style:display-name="Preformatted Text" style:family="paragraph" style:parent-style-name="Standard" style:class="html"><style:paragraph-properties fo:background-color="#f2f2f2" fo:padding="0.04in"/><style:text-properties style:font-name="Liberation Mono" fo:font-family="'Liberation Mono'" fo:font-size="10pt"/></style:style><style:style style:name="Table_20_Contents" style:display-name="Table Contents" style:family="paragraph" style:parent-style-name="Standard" style:class="extra"/><style:style style:name="Table_20_Heading" style:display-name="Table Heading" style:family="paragraph" style:parent-style-name="Table_20_Contents" style:class="extra"><style:text-properties fo:font-weight="bold"/></style:style><style:style style:name="Horizontal_20_Line" style:display-name="Horizontal Line" style:family="paragraph" style:parent-style-name="Standard" style:class="html"><style:paragraph-properties fo:padding-bottom="0.02in" fo:border-bottom="0.5pt solid #000000"/></style:style><style:style style:name="Emphasis" style:family="text"><style:text-properties fo:font-style="italic"/></style:style><style:style style:name="Strong_20_Emphasis" style:display-name="Strong Emphasis" style:family="text"><style:text-properties fo:font-weight="bold"/></style:style><style:style style:name="Source_20_Text" style:display-name="Source Text" style:family="text"><style:text-properties style:font-name="Liberation Mono" fo:font-family="'Liberation Mono'"/></style:style><style:style style:name="Strikethrough" style:family="text"><style:text-properties style:text-line-through-style="solid" style:text-line-through-type="single"/></style:style><style:style style:name="Internet_20_link" style:display-name="Internet link" style:family="text"><style:text-properties fo:color="#0563c1" style:text-underline-style="solid" style:text-underline-width="auto" style:text-underline-color="font-color"/></style:style>"##;
+
+/// Paragraph styles of aligned table cells, named after the cell style and alignment.
+const AUTOMATIC_STYLES: &str = r##"<office:automatic-styles><style:style style:name="Table_20_Contents_Center" style:family="paragraph" style:parent-style-name="Table_20_Contents"><style:paragraph-properties fo:text-align="center"/></style:style><style:style style:name="Table_20_Contents_Right" style:family="paragraph" style:parent-style-name="Table_20_Contents"><style:paragraph-properties fo:text-align="end"/></style:style><style:style style:name="Table_20_Heading_Center" style:family="paragraph" style:parent-style-name="Table_20_Heading"><style:paragraph-properties fo:text-align="center"/></style:style><style:style style:name="Table_20_Heading_Right" style:family="paragraph" style:parent-style-name="Table_20_Heading"><style:paragraph-properties fo:text-align="end"/></style:style><style:style style:name="Table" style:family="table"><style:table-properties table:align="margins"/></style:style><style:style style:name="TableCell" style:family="table-cell"><style:table-cell-properties fo:padding="0.04in" fo:border="0.5pt solid #000000"/></style:style></office:automatic-styles>"##;
+
+/// Renders the document as an OpenDocument text.
+pub fn render<'a>(root: &'a AstNode<'a>) -> Result<Vec<u8>, String> {
+    let mut writer = Writer::default();
+    let body = writer.blocks(root, "Text_20_body");
+
+    let content = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content {NAMESPACES}>{AUTOMATIC_STYLES}<office:body><office:text>{body}</office:text></office:body></office:document-content>"#
+    );
+    let styles = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-styles {NAMESPACES}>{STYLES}{}</office:styles></office:document-styles>"#,
+        list_styles()
+    );
+
+    let mut files = vec![
+        (String::from("mimetype"), MIME_TYPE.into()),
+        (
+            String::from("META-INF/manifest.xml"),
+            writer.manifest().into_bytes(),
+        ),
+        (String::from("content.xml"), content.into_bytes()),
+        (String::from("styles.xml"), styles.into_bytes()),
+    ];
+    files.extend(
+        writer
+            .pictures
+            .into_iter()
+            .map(|(path, _, bytes)| (path, bytes)),
+    );
+
+    write_package(&files)
+}
+
+/// Collects the pictures while writing the body.
+#[derive(Default)]
+struct Writer {
+    /// Path, MIME type and content of the embedded pictures.
+    pictures: Vec<(String, &'static str, Vec<u8>)>,
+    tables: usize,
+}
+
+impl Writer {
+    fn blocks<'a>(&mut self, node: &'a AstNode<'a>, style: &str) -> String {
+        node.children()
+            .map(|child| self.block(child, style))
+            .collect()
+    }
+
+    fn block<'a>(&mut self, node: &'a AstNode<'a>, style: &str) -> String {
+        match &node.data.borrow().value {
+            NodeValue::Paragraph => self.paragraph(node, style, ""),
+            NodeValue::Heading(heading) => {
+                let level = (heading.level as usize).clamp(1, 6);
+                format!(
+                    r#"<text:h text:style-name="{}" text:outline-level="{level}">{}</text:h>"#,
+                    HEADING_STYLES[level - 1],
+                    self.inlines(node)
+                )
+            }
+            NodeValue::BlockQuote | NodeValue::MultilineBlockQuote(_) | NodeValue::Alert(_) => {
+                self.blocks(node, "Quotations")
+            }
+            NodeValue::List(list) => {
+                let list_style = match list.list_type {
+                    ListType::Bullet => "List_20_1",
+                    ListType::Ordered => "Numbering_20_123",
+                };
+
+                let items: String = node
+                    .children()
+                    .enumerate()
+                    .map(|(index, item)| {
+                        let start = match (index, list.list_type) {
+                            (0, ListType::Ordered) => {
+                                format!(r#" text:start-value="{}""#, list.start)
+                            }
+                            _ => String::new(),
+                        };
+                        let checkbox = match task_state(item) {
+                            Some(true) => "☒ ",
+                            Some(false) => "☐ ",
+                            None => "",
+                        };
+                        let content: String = item
+                            .children()
+                            .enumerate()
+                            .map(|(index, child)| {
+                                let is_paragraph =
+                                    matches!(child.data.borrow().value, NodeValue::Paragraph);
+                                match (index, is_paragraph) {
+                                    (0, true) => self.paragraph(child, style, checkbox),
+                                    _ => self.block(child, style),
+                                }
+                            })
+                            .collect();
+                        format!("<text:list-item{start}>{content}</text:list-item>")
+                    })
+                    .collect();
+
+                format!(r#"<text:list text:style-name="{list_style}">{items}</text:list>"#)
+            }
+            NodeValue::CodeBlock(code) => code
+                .literal
+                .trim_end_matches('\n')
+                .split('\n')
+                .map(|line| {
+                    format!(
+                        r#"<text:p text:style-name="Preformatted_20_Text">{}</text:p>"#,
+                        escape_text(line)
+                    )
+                })
+                .collect(),
+            NodeValue::Table(table) => self.table(node, &table.alignments),
+            NodeValue::ThematicBreak => {
+                String::from(r#"<text:p text:style-name="Horizontal_20_Line"/>"#)
+            }
+            NodeValue::HtmlBlock(_) | NodeValue::FrontMatter(_) | NodeValue::Raw(_) => {
+                String::new()
+            }
+            _ => self.blocks(node, style),
+        }
+    }
+
+    fn paragraph<'a>(&mut self, node: &'a AstNode<'a>, style: &str, prefix: &str) -> String {
+        let content = match lone_image(node) {
+            Some((url, alt)) => self.image(&url, &alt),
+            None => self.inlines(node),
+        };
+        format!(r#"<text:p text:style-name="{style}">{prefix}{content}</text:p>"#)
+    }
+
+    fn table<'a>(&mut self, node: &'a AstNode<'a>, alignments: &[TableAlignment]) -> String {
+        self.tables += 1;
+
+        let mut header = String::new();
+        let mut rows = String::new();
+        for row in node.children() {
+            let is_header = matches!(row.data.borrow().value, NodeValue::TableRow(true));
+            let cell_style = if is_header {
+                "Table_20_Heading"
+            } else {
+                "Table_20_Contents"
+            };
+
+            let cells: String = row
+                .children()
+                .zip(alignments)
+                .map(|(cell, alignment)| {
+                    let style = match alignment {
+                        TableAlignment::Center => format!("{cell_style}_Center"),
+                        TableAlignment::Right => format!("{cell_style}_Right"),
+                        _ => cell_style.to_string(),
+                    };
+                    format!(
+                        r#"<table:table-cell table:style-name="TableCell" office:value-type="string"><text:p text:style-name="{style}">{}</text:p></table:table-cell>"#,
+                        self.inlines(cell)
+                    )
+                })
+                .collect();
+
+            let row = format!("<table:table-row>{cells}</table:table-row>");
+            match is_header {
+                true => header.push_str(&row),
+                false => rows.push_str(&row),
+            }
+        }
+
+        if !header.is_empty() {
+            header = format!("<table:table-header-rows>{header}</table:table-header-rows>");
+        }
+
+        format!(
+            r#"<table:table table:name="Table{}" table:style-name="Table"><table:table-column table:number-columns-repeated="{}"/>{header}{rows}</table:table>"#,
+            self.tables,
+            alignments.len()
+        )
+    }
+
+    fn inlines<'a>(&mut self, node: &'a AstNode<'a>) -> String {
+        node.children().map(|child| self.inline(child)).collect()
+    }
+
+    fn inline<'a>(&mut self, node: &'a AstNode<'a>) -> String {
+        match &node.data.borrow().value {
+            NodeValue::Text(text) => escape_text(text),
+            NodeValue::ShortCode(shortcode) => escape_text(&shortcode.emoji),
+            NodeValue::Code(code) => span("Source_20_Text", &escape_text(&code.literal)),
+            NodeValue::Emph => span("Emphasis", &self.inlines(node)),
+            NodeValue::Strong => span("Strong_20_Emphasis", &self.inlines(node)),
+            NodeValue::Strikethrough => span("Strikethrough", &self.inlines(node)),
+            NodeValue::SoftBreak => String::from(" "),
+            NodeValue::LineBreak => String::from("<text:line-break/>"),
+            NodeValue::Link(link) => link_element(&link.url, &self.inlines(node)),
+            NodeValue::Image(link) => self.image(&link.url, &plain_text(node)),
+            NodeValue::HtmlInline(_) | NodeValue::Raw(_) => String::new(),
+            _ => self.inlines(node),
+        }
+    }
+
+    /// Embeds images of `data:` URLs; other images become a link showing the alternative text.
+    fn image(&mut self, url: &str, alt: &str) -> String {
+        let Some(image) = EmbeddedImage::from_data_url(url) else {
+            let text = if alt.is_empty() { url } else { alt };
+            return link_element(url, &escape_text(text));
+        };
+
+        let number = self.pictures.len() + 1;
+        let path = format!("Pictures/image{number}.{}", image.extension);
+        let (width, height) = image.display_size();
+        let frame = format!(
+            r#"<draw:frame draw:name="Image{number}" text:anchor-type="as-char" svg:width="{width:.3}in" svg:height="{height:.3}in"><draw:image xlink:href="{path}" xlink:type="simple" xlink:show="embed" xlink:actuate="onLoad"/><svg:desc>{}</svg:desc></draw:frame>"#,
+            escape_xml(alt)
+        );
+
+        self.pictures.push((path, image.mime_type, image.bytes));
+        frame
+    }
+
+    fn manifest(&self) -> String {
+        let pictures: String = self
+            .pictures
+            .iter()
+            .map(|(path, mime_type, _)| {
+                format!(
+                    r#"<manifest:file-entry manifest:full-path="{path}" manifest:media-type="{mime_type}"/>"#
+                )
+            })
+            .collect();
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.3"><manifest:file-entry manifest:full-path="/" manifest:version="1.3" manifest:media-type="{MIME_TYPE}"/><manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/><manifest:file-entry manifest:full-path="styles.xml" manifest:media-type="text/xml"/>{pictures}</manifest:manifest>"#
+        )
+    }
+}
+
+fn span(style: &str, content: &str) -> String {
+    format!(r#"<text:span text:style-name="{style}">{content}</text:span>"#)
+}
+
+fn link_element(url: &str, content: &str) -> String {
+    format!(
+        r#"<text:a xlink:type="simple" xlink:href="{}" text:style-name="Internet_20_link">{content}</text:a>"#,
+        escape_xml(url)
+    )
+}
+
+/// Bullet and numbering list styles with indented levels.
+fn list_styles() -> String {
+    let levels = |kind: &str, attributes: &dyn Fn(usize) -> String| {
+        (1..=10)
+            .map(|level| {
+                format!(
+                    r#"<text:list-level-style-{kind} text:level="{level}" {}><style:list-level-properties text:list-level-position-and-space-mode="label-alignment"><style:list-level-label-alignment text:label-followed-by="listtab" fo:text-indent="-0.25in" fo:margin-left="{:.2}in"/></style:list-level-properties></text:list-level-style-{kind}>"#,
+                    attributes(level),
+                    0.25 * (level + 1) as f64,
+                )
+            })
+            .collect::<String>()
+    };
+
+    let bullets = levels("bullet", &|level| {
+        format!(
+            r#"text:bullet-char="{}""#,
+            BULLETS[(level - 1) % BULLETS.len()]
+        )
+    });
+    let numbers = levels("number", &|_| {
+        String::from(r#"style:num-suffix="." style:num-format="1""#)
+    });
+
+    format!(
+        r#"<text:list-style style:name="List_20_1" style:display-name="List 1">{bullets}</text:list-style><text:list-style style:name="Numbering_20_123" style:display-name="Numbering 123">{numbers}</text:list-style>"#
+    )
+}
+
+/// Escapes text, keeping repeated and leading spaces and tabs that ODF would collapse.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut spaces = 0;
+    let mut previous_is_space = true;
+
+    for c in escape_xml(text).chars() {
+        match c {
+            ' ' if previous_is_space => spaces += 1,
+            ' ' => {
+                escaped.push(' ');
+                previous_is_space = true;
+            }
+            c => {
+                push_spaces(&mut escaped, &mut spaces);
+                match c {
+                    '\t' => escaped.push_str("<text:tab/>"),
+                    c => escaped.push(c),
+                }
+                previous_is_space = c == '\t';
+            }
+        }
+    }
+    push_spaces(&mut escaped, &mut spaces);
+
+    escaped
+}
+
+fn push_spaces(escaped: &mut String, spaces: &mut usize) {
+    match *spaces {
+        0 => {}
+        1 => escaped.push_str("<text:s/>"),
+        count => escaped.push_str(&format!(r#"<text:s text:c="{count}"/>"#)),
+    }
+    *spaces = 0;
+}
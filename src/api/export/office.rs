@@ -0,0 +1,225 @@
+use std::io::{Cursor, Write};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+/// Width of the text area images are scaled down to, in inches.
+const MAX_IMAGE_WIDTH: f64 = 6.0;
+/// Resolution used to convert pixels into a printed size.
+const PIXELS_PER_INCH: f64 = 96.0;
+
+/// An image of a `data:` URL, e.g. an embedded attachment, that is stored in the document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddedImage {
+    pub mime_type: &'static str,
+    pub extension: &'static str,
+    pub bytes: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl EmbeddedImage {
+    /// Decodes PNG, JPEG and GIF images; other URLs can't be embedded offline.
+    pub fn from_data_url(url: &str) -> Option<Self> {
+        let (header, data) = url.strip_prefix("data:")?.split_once(',')?;
+        let mime_type = header.strip_suffix(";base64")?;
+        let (mime_type, extension) = match mime_type {
+            "image/png" => ("image/png", "png"),
+            "image/jpeg" | "image/jpg" => ("image/jpeg", "jpeg"),
+            "image/gif" => ("image/gif", "gif"),
+            _ => return None,
+        };
+
+        let bytes = STANDARD.decode(data.trim()).ok()?;
+        let (width, height) = image_size(&bytes)?;
+
+        Some(EmbeddedImage {
+            mime_type,
+            extension,
+            bytes,
+            width,
+            height,
+        })
+    }
+
+    /// Printed width and height in inches, scaled down to fit the page.
+    pub fn display_size(&self) -> (f64, f64) {
+        let width = self.width as f64 / PIXELS_PER_INCH;
+        let height = self.height as f64 / PIXELS_PER_INCH;
+        let scale = (MAX_IMAGE_WIDTH / width).min(1.0);
+
+        (width * scale, height * scale)
+    }
+}
+
+/// Reads the pixel size from the header of a PNG, GIF or JPEG image.
+fn image_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    let u16_be = |at: usize| Some(u16::from_be_bytes([*bytes.get(at)?, *bytes.get(at + 1)?]));
+    let u16_le = |at: usize| Some(u16::from_le_bytes([*bytes.get(at)?, *bytes.get(at + 1)?]));
+    let u32_be = |at: usize| Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?));
+
+    let size = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        (u32_be(16)?, u32_be(20)?)
+    } else if bytes.starts_with(b"GIF8") {
+        (u16_le(6)? as u32, u16_le(8)? as u32)
+    } else if bytes.starts_with(&[0xFF, 0xD8]) {
+        let mut position = 2;
+        loop {
+            if *bytes.get(position)? != 0xFF {
+                return None;
+            }
+            let marker = *bytes.get(position + 1)?;
+            // Start of frame markers, excluding DHT, JPG and DAC.
+            if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+                break (u16_be(position + 7)? as u32, u16_be(position + 5)? as u32);
+            }
+            position += 2 + u16_be(position + 2)? as usize;
+        }
+    } else {
+        return None;
+    };
+
+    match size {
+        (0, _) | (_, 0) => None,
+        size => Some(size),
+    }
+}
+
+/// Zips the files of an office document. A `mimetype` file is stored uncompressed,
+/// as OpenDocument requires it to be readable at a fixed offset.
+pub fn write_package(files: &[(String, Vec<u8>)]) -> Result<Vec<u8>, String> {
+    let mut package = ZipWriter::new(Cursor::new(Vec::new()));
+
+    for (path, content) in files {
+        let compression = match path.as_str() {
+            "mimetype" => CompressionMethod::Stored,
+            _ => CompressionMethod::Deflated,
+        };
+        package
+            .start_file(
+                path,
+                SimpleFileOptions::default().compression_method(compression),
+            )
+            .map_err(|error| format!("Couldn't add {path} to the document: {error}"))?;
+        package
+            .write_all(content)
+            .map_err(|error| format!("Couldn't write {path}: {error}"))?;
+    }
+
+    package
+        .finish()
+        .map(Cursor::into_inner)
+        .map_err(|error| format!("Couldn't create the document: {error}"))
+}
+
+/// Escapes text for XML, dropping control characters XML doesn't allow.
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Read;
+
+    use zip::ZipArchive;
+
+    /// A 2×3 pixel PNG.
+    const PNG: &[u8] = &[
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, 0x08, 0x06, 0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn test_png_size() {
+        assert_eq!(image_size(PNG), Some((2, 3)));
+    }
+
+    #[test]
+    fn test_gif_size() {
+        assert_eq!(image_size(b"GIF89a\x10\x00\x20\x00"), Some((16, 32)));
+    }
+
+    #[test]
+    fn test_jpeg_size() {
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x4A, 0x46, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00,
+            0x40, 0x00, 0x80,
+        ];
+
+        assert_eq!(image_size(&jpeg), Some((128, 64)));
+    }
+
+    #[test]
+    fn test_unknown_image() {
+        assert_eq!(image_size(b"<svg></svg>"), None);
+    }
+
+    #[test]
+    fn test_image_from_data_url() {
+        let url = format!("data:image/png;base64,{}", STANDARD.encode(PNG));
+        let image = EmbeddedImage::from_data_url(&url).unwrap();
+
+        assert_eq!(image.extension, "png");
+        assert_eq!((image.width, image.height), (2, 3));
+        assert_eq!(
+            EmbeddedImage::from_data_url("https://example.com/a.png"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_large_image_is_scaled_down() {
+        let image = EmbeddedImage {
+            mime_type: "image/png",
+            extension: "png",
+            bytes: Vec::new(),
+            width: 1152,
+            height: 96,
+        };
+
+        assert_eq!(image.display_size(), (6.0, 0.5));
+    }
+
+    #[test]
+    fn test_package_stores_mimetype_uncompressed() {
+        let package = write_package(&[
+            (String::from("mimetype"), b"text/plain".to_vec()),
+            (String::from("content.xml"), b"<a/>".to_vec()),
+        ])
+        .unwrap();
+
+        assert_eq!(&package[30..38], b"mimetype");
+        assert_eq!(&package[38..48], b"text/plain");
+
+        let mut archive = ZipArchive::new(Cursor::new(package)).unwrap();
+        let mut content = String::new();
+        archive
+            .by_name("content.xml")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "<a/>");
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("a < b & \"c\"\u{7}"),
+            "a &lt; b &amp; &quot;c&quot;"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use comrak::nodes::{AstNode, NodeValue};
+
+use crate::api::export::{finish, indent, join_blocks, list_markers, task_state};
+
+/// Renders the text of the document with all markup removed.
+pub fn render<'a>(root: &'a AstNode<'a>) -> String {
+    finish(blocks(root, "\n\n"))
+}
+
+fn blocks<'a>(node: &'a AstNode<'a>, separator: &str) -> String {
+    join_blocks(node.children().map(block), separator)
+}
+
+fn block<'a>(node: &'a AstNode<'a>) -> String {
+    match &node.data.borrow().value {
+        NodeValue::Paragraph | NodeValue::Heading(_) => inlines(node),
+        NodeValue::BlockQuote | NodeValue::MultilineBlockQuote(_) | NodeValue::Alert(_) => {
+            indent(&blocks(node, "\n\n"), "  ", "  ")
+        }
+        NodeValue::List(list) => {
+            let items: Vec<&AstNode> = node.children().collect();
+            let markers = list_markers(list, "-", items.len());
+            let separator = if list.tight { "\n" } else { "\n\n" };
+
+            join_blocks(
+                items.into_iter().zip(markers).map(|(item, marker)| {
+                    let checkbox = match task_state(item) {
+                        Some(true) => "[x] ",
+                        Some(false) => "[ ] ",
+                        None => "",
+                    };
+                    let content = blocks(item, separator);
+                    let rest = " ".repeat(marker.len() + 1);
+                    indent(
+                        &format!("{checkbox}{content}"),
+                        &format!("{marker} "),
+                        &rest,
+                    )
+                }),
+                separator,
+            )
+        }
+        NodeValue::CodeBlock(code) => indent(code.literal.trim_end_matches('\n'), "    ", "    "),
+        NodeValue::Table(_) => join_blocks(
+            node.children().map(|row| {
+                row.children()
+                    .map(inlines)
+                    .collect::<Vec<String>>()
+                    .join("\t")
+            }),
+            "\n",
+        ),
+        NodeValue::ThematicBreak
+        | NodeValue::HtmlBlock(_)
+        | NodeValue::FrontMatter(_)
+        | NodeValue::Raw(_) => String::new(),
+        _ => blocks(node, "\n\n"),
+    }
+}
+
+fn inlines<'a>(node: &'a AstNode<'a>) -> String {
+    node.children().map(inline).collect()
+}
+
+fn inline<'a>(node: &'a AstNode<'a>) -> String {
+    match &node.data.borrow().value {
+        NodeValue::Text(text) => text.to_string(),
+        NodeValue::ShortCode(shortcode) => shortcode.emoji.clone(),
+        NodeValue::Code(code) => code.literal.clone(),
+        NodeValue::SoftBreak | NodeValue::LineBreak => String::from("\n"),
+        NodeValue::HtmlInline(_) | NodeValue::Raw(_) => String::new(),
+        _ => inlines(node),
+    }
+}
@@ -0,0 +1,146 @@
+use comrak::nodes::{AstNode, NodeValue};
+
+use crate::api::export::{
+    finish, indent, join_blocks, list_markers, lone_image, plain_text, task_state,
+};
+
+/// Underline characters of the section levels.
+const SECTION_ADORNMENTS: [char; 6] = ['=', '-', '~', '^', '"', '\''];
+
+/// Renders the document as reStructuredText.
+pub fn render<'a>(root: &'a AstNode<'a>) -> String {
+    finish(blocks(root))
+}
+
+fn blocks<'a>(node: &'a AstNode<'a>) -> String {
+    join_blocks(node.children().map(block), "\n\n")
+}
+
+fn block<'a>(node: &'a AstNode<'a>) -> String {
+    match &node.data.borrow().value {
+        NodeValue::Paragraph => match lone_image(node) {
+            Some((url, alt)) => format!(".. image:: {url}\n   :alt: {alt}"),
+            None => inlines(node),
+        },
+        NodeValue::Heading(heading) => {
+            let title = inlines(node);
+            let adornment = SECTION_ADORNMENTS[(heading.level as usize).clamp(1, 6) - 1];
+            let underline = adornment.to_string().repeat(title.chars().count().max(1));
+            format!("{title}\n{underline}")
+        }
+        NodeValue::BlockQuote | NodeValue::MultilineBlockQuote(_) | NodeValue::Alert(_) => {
+            let quote = indent(&blocks(node), "    ", "    ");
+            // An empty comment ends the indented block before, which would swallow the quote.
+            match node.previous_sibling().is_some_and(ends_indented) {
+                true => format!("..\n\n{quote}"),
+                false => quote,
+            }
+        }
+        NodeValue::List(list) => {
+            let items: Vec<&AstNode> = node.children().collect();
+            let markers = list_markers(list, "-", items.len());
+            let is_compact = list.tight && items.iter().all(|item| item.children().count() < 2);
+
+            join_blocks(
+                items.into_iter().zip(markers).map(|(item, marker)| {
+                    let checkbox = match task_state(item) {
+                        Some(true) => "[x] ",
+                        Some(false) => "[ ] ",
+                        None => "",
+                    };
+                    let rest = " ".repeat(marker.len() + 1);
+                    indent(
+                        &format!("{checkbox}{}", blocks(item)),
+                        &format!("{marker} "),
+                        &rest,
+                    )
+                }),
+                if is_compact { "\n" } else { "\n\n" },
+            )
+        }
+        NodeValue::CodeBlock(code) => {
+            let code_lines = indent(code.literal.trim_end_matches('\n'), "   ", "   ");
+            match code.info.split_whitespace().next() {
+                Some(language) => format!(".. code-block:: {language}\n\n{code_lines}"),
+                None => format!("::\n\n{code_lines}"),
+            }
+        }
+        NodeValue::Table(_) => {
+            let has_header = node
+                .first_child()
+                .is_some_and(|row| matches!(row.data.borrow().value, NodeValue::TableRow(true)));
+            let rows = node
+                .children()
+                .map(|row| {
+                    let cells = row
+                        .children()
+                        .map(|cell| format!("     - {}", inlines(cell)))
+                        .collect::<Vec<String>>()
+                        .join("\n");
+                    format!("   * {}", &cells[5..])
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            match has_header {
+                true => format!(".. list-table::\n   :header-rows: 1\n\n{rows}"),
+                false => format!(".. list-table::\n\n{rows}"),
+            }
+        }
+        NodeValue::ThematicBreak => String::from("----------"),
+        NodeValue::HtmlBlock(_) | NodeValue::FrontMatter(_) | NodeValue::Raw(_) => String::new(),
+        _ => blocks(node),
+    }
+}
+
+/// Whether the block ends with indented lines that a following indented block would
+/// continue: directives, literal blocks, lists and quotes.
+fn ends_indented<'a>(node: &'a AstNode<'a>) -> bool {
+    match &node.data.borrow().value {
+        NodeValue::Paragraph => lone_image(node).is_some(),
+        NodeValue::Heading(_) | NodeValue::ThematicBreak => false,
+        _ => true,
+    }
+}
+
+fn inlines<'a>(node: &'a AstNode<'a>) -> String {
+    node.children().map(inline).collect()
+}
+
+fn inline<'a>(node: &'a AstNode<'a>) -> String {
+    match &node.data.borrow().value {
+        NodeValue::Text(text) => escape(text),
+        NodeValue::ShortCode(shortcode) => escape(&shortcode.emoji),
+        NodeValue::Code(code) => format!("``{}``", code.literal),
+        NodeValue::Emph => format!("*{}*", inlines(node)),
+        NodeValue::Strong => format!("**{}**", inlines(node)),
+        NodeValue::SoftBreak | NodeValue::LineBreak => String::from("\n"),
+        NodeValue::Link(link) => {
+            let text = plain_text(node);
+            if text == link.url {
+                link.url.clone()
+            } else {
+                format!("`{} <{}>`__", escape_link_text(&text), link.url)
+            }
+        }
+        NodeValue::Image(_) => escape(&plain_text(node)),
+        NodeValue::HtmlInline(_) | NodeValue::Raw(_) => String::new(),
+        _ => inlines(node),
+    }
+}
+
+/// Escapes the characters that start inline markup.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '`' | '_' | '|') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn escape_link_text(text: &str) -> String {
+    text.replace('<', "\\<").replace('`', "\\`")
+}
@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+
+use gloo::{file::File, utils::window};
+use leptos::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    FileSystemDirectoryHandle, FileSystemFileHandle, FileSystemHandle, FileSystemHandleKind,
+    FileSystemWritableFileStream,
+    js_sys::{Function, IteratorNext, Object, Promise, Reflect},
+};
+
+use crate::api::import::{TextFormat, is_importable, read_text_file};
+
+/// Folders that never contain notes but can hold thousands of files.
+const SKIPPED_FOLDERS: [&str; 2] = ["node_modules", "target"];
+
+/// A markdown file of the opened folder as it was read from disk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskFile {
+    pub content: String,
+    /// The encoding, byte order mark and line endings to save the file with again.
+    pub format: TextFormat,
+    /// Milliseconds since the Unix epoch.
+    pub modified: f64,
+}
+
+/// A file that changed on disk while it was open in the editor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalChange {
+    pub document: u64,
+    pub path: String,
+    pub file: DiskFile,
+}
+
+/// A line of the folder tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeEntry {
+    pub name: String,
+    pub depth: usize,
+    /// The path of a file, `None` for folders.
+    pub path: Option<String>,
+}
+
+/// The folder opened in this session. The handles only live as long as the page, the
+/// documents remember the paths of their files.
+#[derive(Clone, Copy)]
+pub struct FolderSync {
+    pub name: RwSignal<Option<String>>,
+    pub files: RwSignal<Vec<String>>,
+    pub change: RwSignal<Option<ExternalChange>>,
+    root: StoredValue<Option<FileSystemDirectoryHandle>, LocalStorage>,
+    handles: StoredValue<HashMap<String, FileSystemFileHandle>, LocalStorage>,
+}
+
+impl Default for FolderSync {
+    fn default() -> Self {
+        FolderSync {
+            name: RwSignal::new(None),
+            files: RwSignal::new(Vec::new()),
+            change: RwSignal::new(None),
+            root: StoredValue::new_local(None),
+            handles: StoredValue::new_local(HashMap::new()),
+        }
+    }
+}
+
+impl FolderSync {
+    /// Asks the user for a folder. Returns `Ok(false)` when the picker was cancelled.
+    pub async fn open(&self) -> Result<bool, String> {
+        let Some(root) = pick_folder().await? else {
+            return Ok(false);
+        };
+        self.name.set(Some(root.name()));
+        self.root.set_value(Some(root));
+        self.refresh().await?;
+        Ok(true)
+    }
+
+    /// Lists the markdown files of the opened folder again.
+    pub async fn refresh(&self) -> Result<(), String> {
+        let Some(root) = self.root.get_value() else {
+            return Ok(());
+        };
+
+        let mut handles = HashMap::new();
+        collect_markdown_files(&root, &mut handles).await?;
+
+        let mut paths = handles.keys().cloned().collect::<Vec<_>>();
+        paths.sort_by_key(|path| sort_key(path));
+
+        self.handles.set_value(handles);
+        self.files.set(paths);
+        Ok(())
+    }
+
+    pub fn is_available(&self, path: &str) -> bool {
+        self.handles
+            .with_value(|handles| handles.contains_key(path))
+    }
+
+    pub async fn read(&self, path: &str) -> Result<DiskFile, String> {
+        let handle = self.handle(path)?;
+        let file = file_of(&handle).await?;
+        let modified = file.last_modified();
+        let imported = read_text_file(File::from(file)).await?;
+
+        Ok(DiskFile {
+            content: imported.content,
+            format: imported.format,
+            modified,
+        })
+    }
+
+    /// The modification time without reading the content, for cheap change checks.
+    pub async fn modified(&self, path: &str) -> Result<f64, String> {
+        Ok(file_of(&self.handle(path)?).await?.last_modified())
+    }
+
+    /// Replaces the content of the file, stored in the given format, and returns its new
+    /// modification time.
+    pub async fn write(
+        &self,
+        path: &str,
+        content: &str,
+        format: TextFormat,
+    ) -> Result<f64, String> {
+        let handle = self.handle(path)?;
+        let failed = |_| format!("Couldn't save {path}");
+
+        let stream: FileSystemWritableFileStream = await_promise(handle.create_writable())
+            .await
+            .map_err(failed)?
+            .unchecked_into();
+        stream
+            .write_with_u8_array(&format.encode(content))
+            .map(await_promise)
+            .map_err(failed)?
+            .await
+            .map_err(failed)?;
+        await_promise(stream.close()).await.map_err(failed)?;
+
+        Ok(file_of(&handle).await?.last_modified())
+    }
+
+    fn handle(&self, path: &str) -> Result<FileSystemFileHandle, String> {
+        self.handles
+            .with_value(|handles| handles.get(path).cloned())
+            .ok_or_else(|| format!("Open the folder that contains {path} to save it"))
+    }
+}
+
+/// Whether the browser can open folders. Others fall back to opening and downloading
+/// single files.
+pub fn is_supported() -> bool {
+    Reflect::has(&window(), &JsValue::from_str("showDirectoryPicker")).unwrap_or(false)
+}
+
+async fn pick_folder() -> Result<Option<FileSystemDirectoryHandle>, String> {
+    let picker = Reflect::get(&window(), &JsValue::from_str("showDirectoryPicker"))
+        .ok()
+        .and_then(|picker| picker.dyn_into::<Function>().ok())
+        .ok_or_else(|| String::from("Your browser can't open folders"))?;
+
+    let options = Object::new();
+    let _ = Reflect::set(&options, &"mode".into(), &"readwrite".into());
+    let promise = picker
+        .call1(&window(), &options)
+        .map_err(|_| String::from("Couldn't open the folder"))?;
+
+    match await_promise(promise.unchecked_into()).await {
+        Ok(handle) => Ok(Some(handle.unchecked_into())),
+        // The user closed the picker.
+        Err(error) if error_name(&error) == "AbortError" => Ok(None),
+        Err(_) => Err(String::from("Couldn't open the folder")),
+    }
+}
+
+/// Walks the folder and collects the markdown and text files by their relative path.
+async fn collect_markdown_files(
+    root: &FileSystemDirectoryHandle,
+    handles: &mut HashMap<String, FileSystemFileHandle>,
+) -> Result<(), String> {
+    let mut folders = vec![(root.clone(), String::new())];
+
+    while let Some((folder, prefix)) = folders.pop() {
+        let entries = folder.values();
+        loop {
+            let next: IteratorNext = await_promise(
+                entries
+                    .next()
+                    .map_err(|_| format!("Couldn't read {}", folder.name()))?,
+            )
+            .await
+            .map_err(|_| format!("Couldn't read {}", folder.name()))?
+            .unchecked_into();
+            if next.done() {
+                break;
+            }
+
+            let entry: FileSystemHandle = next.value().unchecked_into();
+            let name = entry.name();
+            if name.starts_with('.') {
+                continue;
+            }
+
+            let path = format!("{prefix}{name}");
+            match entry.kind() {
+                FileSystemHandleKind::Directory if !SKIPPED_FOLDERS.contains(&name.as_str()) => {
+                    folders.push((entry.unchecked_into(), format!("{path}/")));
+                }
+                FileSystemHandleKind::File if is_importable(&name) => {
+                    handles.insert(path, entry.unchecked_into());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn file_of(handle: &FileSystemFileHandle) -> Result<web_sys::File, String> {
+    await_promise(handle.get_file())
+        .await
+        .map(JsCast::unchecked_into)
+        .map_err(|_| format!("Couldn't read {}", handle.name()))
+}
+
+async fn await_promise(promise: Promise) -> Result<JsValue, JsValue> {
+    JsFuture::from(promise).await
+}
+
+fn error_name(error: &JsValue) -> String {
+    Reflect::get(error, &"name".into())
+        .ok()
+        .and_then(|name| name.as_string())
+        .unwrap_or_default()
+}
+
+/// Sorts folders before the files next to them, both alphabetically.
+fn sort_key(path: &str) -> Vec<(bool, String)> {
+    let mut parts = path.split('/').peekable();
+    let mut key = Vec::new();
+    while let Some(part) = parts.next() {
+        key.push((parts.peek().is_none(), part.to_lowercase()));
+    }
+    key
+}
+
+/// Turns sorted file paths into the lines of a tree, adding a line for every folder.
+///
+/// ```rust
+/// use rustynote::api::folder::file_tree;
+///
+/// let tree = file_tree(&[String::from("notes/a.md"), String::from("b.md")]);
+/// let names = tree.iter().map(|entry| entry.name.as_str()).collect::<Vec<_>>();
+///
+/// assert_eq!(names, vec!["notes", "a.md", "b.md"]);
+/// ```
+pub fn file_tree(paths: &[String]) -> Vec<TreeEntry> {
+    let mut entries = Vec::new();
+    let mut open_folders: Vec<&str> = Vec::new();
+
+    for path in paths {
+        let parts = path.split('/').collect::<Vec<_>>();
+        let (name, folders) = parts.split_last().unwrap_or((&"", &[]));
+
+        let shared = open_folders
+            .iter()
+            .zip(folders)
+            .take_while(|(open, folder)| open == folder)
+            .count();
+        open_folders.truncate(shared);
+
+        for folder in &folders[shared..] {
+            entries.push(TreeEntry {
+                name: folder.to_string(),
+                depth: open_folders.len(),
+                path: None,
+            });
+            open_folders.push(folder);
+        }
+
+        entries.push(TreeEntry {
+            name: name.to_string(),
+            depth: folders.len(),
+            path: Some(path.clone()),
+        });
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(paths: &[&str]) -> Vec<String> {
+        let mut paths = paths
+            .iter()
+            .map(|path| path.to_string())
+            .collect::<Vec<_>>();
+        paths.sort_by_key(|path| sort_key(path));
+        paths
+    }
+
+    fn entry(name: &str, depth: usize, path: Option<&str>) -> TreeEntry {
+        TreeEntry {
+            name: name.to_string(),
+            depth,
+            path: path.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_folders_come_first() {
+        assert_eq!(
+            paths(&[
+                "README.md",
+                "docs/setup.md",
+                "Notes.md",
+                "docs/api/index.md"
+            ]),
+            vec![
+                "docs/api/index.md",
+                "docs/setup.md",
+                "Notes.md",
+                "README.md"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_file_tree() {
+        let tree = file_tree(&paths(&[
+            "README.md",
+            "docs/setup.md",
+            "docs/api/index.md",
+            "journal/2024/jan.md",
+        ]));
+
+        assert_eq!(
+            tree,
+            vec![
+                entry("docs", 0, None),
+                entry("api", 1, None),
+                entry("index.md", 2, Some("docs/api/index.md")),
+                entry("setup.md", 1, Some("docs/setup.md")),
+                entry("journal", 0, None),
+                entry("2024", 1, None),
+                entry("jan.md", 2, Some("journal/2024/jan.md")),
+                entry("README.md", 0, Some("README.md")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_file_tree_with_same_folder_names() {
+        let tree = file_tree(&paths(&["a/notes/x.md", "b/notes/y.md"]));
+
+        assert_eq!(
+            tree.iter()
+                .map(|entry| (entry.name.as_str(), entry.depth))
+                .collect::<Vec<_>>(),
+            vec![
+                ("a", 0),
+                ("notes", 1),
+                ("x.md", 2),
+                ("b", 0),
+                ("notes", 1),
+                ("y.md", 2)
+            ]
+        );
+    }
+}
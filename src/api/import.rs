@@ -0,0 +1,296 @@
+use gloo::file::{File, FileList, futures::read_as_bytes};
+
+use crate::api::encryption::EncryptedFile;
+
+/// File extensions that can be opened in the editor.
+pub const IMPORT_EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+
+/// Characters of Windows-1252 for the bytes `0x80..=0x9F`, the rest matches Latin-1.
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+/// A text file the user wants to open.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedFile {
+    pub name: String,
+    pub content: String,
+    pub format: TextFormat,
+}
+
+/// How a text file was stored, so that saving it again doesn't change more than its text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextFormat {
+    pub encoding: TextEncoding,
+    /// Whether the file starts with a byte order mark.
+    pub bom: bool,
+    /// Whether lines end with `\r\n` instead of `\n`.
+    pub crlf: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextEncoding {
+    #[default]
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Windows1252,
+}
+
+impl TextFormat {
+    /// Encodes the text of the editor the way the file was stored. Text that Windows-1252
+    /// can't represent is saved as UTF-8 rather than losing characters.
+    ///
+    /// ```rust
+    /// use rustynote::api::import::{TextEncoding, TextFormat};
+    ///
+    /// let format = TextFormat { encoding: TextEncoding::Utf8, bom: true, crlf: true };
+    /// assert_eq!(format.encode("# Title\n"), b"\xEF\xBB\xBF# Title\r\n");
+    /// ```
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        let text = text.replace("\r\n", "\n");
+        let text = if self.crlf {
+            text.replace('\n', "\r\n")
+        } else {
+            text
+        };
+
+        match self.encoding {
+            TextEncoding::Utf8 => self.with_bom(b"\xEF\xBB\xBF", text.into_bytes()),
+            TextEncoding::Utf16Le => {
+                self.with_bom(b"\xFF\xFE", encode_utf16(&text, u16::to_le_bytes))
+            }
+            TextEncoding::Utf16Be => {
+                self.with_bom(b"\xFE\xFF", encode_utf16(&text, u16::to_be_bytes))
+            }
+            TextEncoding::Windows1252 => text
+                .chars()
+                .map(encode_windows_1252)
+                .collect::<Option<Vec<_>>>()
+                .unwrap_or_else(|| text.into_bytes()),
+        }
+    }
+
+    fn with_bom(&self, bom: &[u8], bytes: Vec<u8>) -> Vec<u8> {
+        if self.bom {
+            [bom, &bytes].concat()
+        } else {
+            bytes
+        }
+    }
+}
+
+/// Checks the extension of a file that should be opened.
+pub fn is_importable(file_name: &str) -> bool {
+    file_name.rsplit_once('.').is_some_and(|(_, extension)| {
+        IMPORT_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+    })
+}
+
+/// Name of the document for an opened file, i.e. the file name without extension.
+///
+/// ```rust
+/// use rustynote::api::import::document_name;
+///
+/// assert_eq!(document_name("meeting.notes.md"), "meeting.notes");
+/// assert_eq!(document_name("README"), "README");
+/// ```
+pub fn document_name(file_name: &str) -> String {
+    match file_name.rsplit_once('.') {
+        Some((name, _)) if !name.is_empty() => name.to_string(),
+        _ => file_name.to_string(),
+    }
+}
+
+/// Decodes a text file, detecting UTF-8 and UTF-16 by their byte order mark and falling
+/// back to Windows-1252 for files that aren't valid UTF-8. Line endings become `\n`.
+///
+/// ```rust
+/// use rustynote::api::import::decode_text;
+///
+/// assert_eq!(decode_text(b"\xEF\xBB\xBF# Title\r\n"), Ok(String::from("# Title\n")));
+/// assert_eq!(decode_text(b"caf\xE9"), Ok(String::from("café")));
+/// ```
+pub fn decode_text(bytes: &[u8]) -> Result<String, String> {
+    decode_text_with_format(bytes).map(|(text, _)| text)
+}
+
+/// Like [`decode_text`], but also tells how the file was stored so that
+/// [`TextFormat::encode`] can save it the same way.
+pub fn decode_text_with_format(bytes: &[u8]) -> Result<(String, TextFormat), String> {
+    let (text, encoding, bom) = if let Some(bytes) = bytes.strip_prefix(b"\xEF\xBB\xBF") {
+        (
+            String::from_utf8_lossy(bytes).into_owned(),
+            TextEncoding::Utf8,
+            true,
+        )
+    } else if let Some(bytes) = bytes.strip_prefix(b"\xFF\xFE") {
+        (
+            decode_utf16(bytes, u16::from_le_bytes),
+            TextEncoding::Utf16Le,
+            true,
+        )
+    } else if let Some(bytes) = bytes.strip_prefix(b"\xFE\xFF") {
+        (
+            decode_utf16(bytes, u16::from_be_bytes),
+            TextEncoding::Utf16Be,
+            true,
+        )
+    } else if bytes.contains(&0) {
+        return Err(String::from("The file doesn't look like a text file"));
+    } else {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => (text.to_string(), TextEncoding::Utf8, false),
+            Err(_) => (
+                bytes
+                    .iter()
+                    .map(|byte| decode_windows_1252(*byte))
+                    .collect(),
+                TextEncoding::Windows1252,
+                false,
+            ),
+        }
+    };
+
+    let format = TextFormat {
+        encoding,
+        bom,
+        crlf: text.contains("\r\n"),
+    };
+    Ok((text.replace("\r\n", "\n").replace('\r', "\n"), format))
+}
+
+/// The first markdown, text or encrypted note file of a picked or dropped file list.
+pub fn first_importable(files: &FileList) -> Option<File> {
+    files
+        .iter()
+        .find(|file| is_importable(&file.name()) || EncryptedFile::is_encrypted_file(&file.name()))
+        .cloned()
+}
+
+/// Reads and decodes a markdown or text file.
+pub async fn read_text_file(file: File) -> Result<ImportedFile, String> {
+    let bytes = read_as_bytes(&file)
+        .await
+        .map_err(|error| format!("Couldn't read {}: {error}", file.name()))?;
+
+    let (content, format) = decode_text_with_format(&bytes)?;
+
+    Ok(ImportedFile {
+        name: file.name(),
+        content,
+        format,
+    })
+}
+
+fn decode_utf16(bytes: &[u8], to_unit: fn([u8; 2]) -> u16) -> String {
+    let units = bytes
+        .chunks_exact(2)
+        .map(|pair| to_unit([pair[0], pair[1]]));
+
+    char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+fn encode_utf16(text: &str, to_bytes: fn(u16) -> [u8; 2]) -> Vec<u8> {
+    text.encode_utf16().flat_map(to_bytes).collect()
+}
+
+fn decode_windows_1252(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => WINDOWS_1252[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+fn encode_windows_1252(c: char) -> Option<u8> {
+    match c as u32 {
+        code @ (0..=0x7F | 0xA0..=0xFF) => Some(code as u8),
+        _ => WINDOWS_1252
+            .iter()
+            .position(|special| *special == c)
+            .map(|index| 0x80 + index as u8),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_importable() {
+        assert!(is_importable("notes.md"));
+        assert!(is_importable("Notes.MARKDOWN"));
+        assert!(is_importable("todo.txt"));
+        assert!(!is_importable("image.png"));
+        assert!(!is_importable("md"));
+    }
+
+    #[test]
+    fn test_document_name_of_hidden_file() {
+        assert_eq!(document_name(".md"), ".md");
+    }
+
+    #[test]
+    fn test_decode_utf8() {
+        assert_eq!(decode_text("Grüße".as_bytes()), Ok(String::from("Grüße")));
+    }
+
+    #[test]
+    fn test_decode_utf16_le() {
+        let bytes = [0xFF, 0xFE, b'H', 0, b'i', 0, 0xAC, 0x20];
+
+        assert_eq!(decode_text(&bytes), Ok(String::from("Hi€")));
+    }
+
+    #[test]
+    fn test_decode_utf16_be() {
+        let bytes = [0xFE, 0xFF, 0, b'#', 0, b' ', 0, b'A'];
+
+        assert_eq!(decode_text(&bytes), Ok(String::from("# A")));
+    }
+
+    #[test]
+    fn test_decode_windows_1252() {
+        assert_eq!(
+            decode_text(b"\x93quoted\x94 \x80 5"),
+            Ok(String::from("“quoted” € 5"))
+        );
+    }
+
+    #[test]
+    fn test_decode_normalizes_line_endings() {
+        assert_eq!(decode_text(b"a\r\nb\rc\n"), Ok(String::from("a\nb\nc\n")));
+    }
+
+    #[test]
+    fn test_encode_keeps_the_format() {
+        let files: [&[u8]; 5] = [
+            b"\xEF\xBB\xBF# Title\r\n\r\nText\r\n",
+            b"# Title\n",
+            b"\x93quoted\x94 \x80 5\r\n",
+            &[0xFF, 0xFE, b'H', 0, b'i', 0, b'\r', 0, b'\n', 0],
+            &[0xFE, 0xFF, 0, b'#', 0, b' ', 0, b'A'],
+        ];
+
+        for bytes in files {
+            let (text, format) = decode_text_with_format(bytes).unwrap();
+            assert_eq!(format.encode(&text), bytes);
+        }
+    }
+
+    #[test]
+    fn test_encode_windows_1252_falls_back_to_utf8() {
+        let (_, format) = decode_text_with_format(b"caf\xE9").unwrap();
+
+        assert_eq!(format.encode("café €"), b"caf\xE9 \x80");
+        assert_eq!(format.encode("café ✓"), "café ✓".as_bytes());
+    }
+
+    #[test]
+    fn test_decode_rejects_binary() {
+        assert!(decode_text(b"\x89PNG\r\n\x1a\n\0\0").is_err());
+    }
+}
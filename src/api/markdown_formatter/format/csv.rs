@@ -0,0 +1,271 @@
+use super::table_editor::{Alignment, MarkdownTable};
+
+/// Field separator of delimiter-separated data copied from spreadsheets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Delimiter {
+    Comma,
+    Tab,
+}
+
+impl Delimiter {
+    /// Guesses the delimiter from the first line: tabs win over commas.
+    pub fn detect(text: &str) -> Option<Self> {
+        let first_line = text.lines().next()?;
+
+        if first_line.contains('\t') {
+            Some(Delimiter::Tab)
+        } else if first_line.contains(',') {
+            Some(Delimiter::Comma)
+        } else {
+            None
+        }
+    }
+
+    fn as_char(self) -> char {
+        match self {
+            Delimiter::Comma => ',',
+            Delimiter::Tab => '\t',
+        }
+    }
+}
+
+/// Splits delimiter-separated text into records of fields.
+///
+/// Quoted fields may contain delimiters, line breaks and doubled quotes (`""`).
+///
+/// ```rust
+/// use rustynote::api::markdown_formatter::format::{Delimiter, parse_delimited};
+///
+/// let records = parse_delimited("name,quote\nAda,\"Hello, \"\"World\"\"\"", Delimiter::Comma);
+/// assert_eq!(records[1], vec!["Ada", "Hello, \"World\""]);
+/// ```
+pub fn parse_delimited(text: &str, delimiter: Delimiter) -> Vec<Vec<String>> {
+    let separator = delimiter.as_char();
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => in_quotes = true,
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c if c == separator && !in_quotes => record.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records
+}
+
+impl MarkdownTable {
+    /// Converts pasted spreadsheet data into a table, using the first record as header.
+    ///
+    /// Returns `None` unless the text has at least two records with the same number
+    /// (two or more) of fields, so ordinary prose isn't mistaken for a table.
+    ///
+    /// ```rust
+    /// use rustynote::api::markdown_formatter::format::MarkdownTable;
+    ///
+    /// let table = MarkdownTable::from_delimited("Name\tAge\nAda\t36\n").unwrap();
+    /// assert_eq!(table.header, vec!["Name", "Age"]);
+    /// assert_eq!(table.rows, vec![vec!["Ada", "36"]]);
+    /// ```
+    pub fn from_delimited(text: &str) -> Option<Self> {
+        if text.lines().any(|line| line.trim_start().starts_with('|')) {
+            return None;
+        }
+
+        let delimiter = Delimiter::detect(text)?;
+        let mut records: Vec<Vec<String>> = parse_delimited(text, delimiter)
+            .into_iter()
+            .filter(|record| record.iter().any(|field| !field.trim().is_empty()))
+            .map(|record| record.iter().map(|field| table_cell(field)).collect())
+            .collect();
+
+        let column_count = records.first()?.len();
+        if records.len() < 2
+            || column_count < 2
+            || records.iter().any(|record| record.len() != column_count)
+        {
+            return None;
+        }
+
+        let header = records.remove(0);
+        Some(MarkdownTable {
+            header,
+            alignments: vec![Alignment::None; column_count],
+            rows: records,
+        })
+    }
+
+    /// Renders the header and all rows as comma-separated values.
+    ///
+    /// ```rust
+    /// use rustynote::api::markdown_formatter::format::MarkdownTable;
+    ///
+    /// let table = MarkdownTable::parse("| City | Note |\n| --- | --- |\n| Bern | a, b |").unwrap();
+    /// assert_eq!(table.to_csv(), "City,Note\nBern,\"a, b\"\n");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        std::iter::once(&self.header)
+            .chain(&self.rows)
+            .map(|row| {
+                let fields: Vec<String> = row.iter().map(|cell| csv_field(cell)).collect();
+                format!("{}\n", fields.join(","))
+            })
+            .collect()
+    }
+}
+
+fn table_cell(field: &str) -> String {
+    field
+        .trim()
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+fn csv_field(cell: &str) -> String {
+    // Line breaks of a field were turned into `<br>` when the table was created.
+    let value = cell
+        .replace("\\|", "|")
+        .replace("<br />", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br>", "\n");
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_delimiter() {
+        assert_eq!(Delimiter::detect("a\tb, c\n1\t2"), Some(Delimiter::Tab));
+        assert_eq!(Delimiter::detect("a,b\n1,2"), Some(Delimiter::Comma));
+        assert_eq!(Delimiter::detect("just text"), None);
+        assert_eq!(Delimiter::detect(""), None);
+    }
+
+    #[test]
+    fn test_parse_simple_csv() {
+        let records = parse_delimited("a,b,c\n1,2,3", Delimiter::Comma);
+
+        assert_eq!(records, vec![vec!["a", "b", "c"], vec!["1", "2", "3"]]);
+    }
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        let records = parse_delimited(
+            "name,address\n\"Doe, Jane\",\"Main St \"\"5\"\"\"",
+            Delimiter::Comma,
+        );
+
+        assert_eq!(records[1], vec!["Doe, Jane", "Main St \"5\""]);
+    }
+
+    #[test]
+    fn test_parse_quoted_line_break() {
+        let records = parse_delimited("a,b\n\"line 1\nline 2\",x", Delimiter::Comma);
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1], vec!["line 1\nline 2", "x"]);
+    }
+
+    #[test]
+    fn test_parse_crlf_and_empty_fields() {
+        let records = parse_delimited("a\tb\r\n\t2\r\n", Delimiter::Tab);
+
+        assert_eq!(records, vec![vec!["a", "b"], vec!["", "2"]]);
+    }
+
+    #[test]
+    fn test_from_delimited_tsv() {
+        let table = MarkdownTable::from_delimited("Name\tAge\nAda\t36\nAlan\t41").unwrap();
+
+        assert_eq!(
+            table.to_markdown(),
+            "| Name | Age |\n| ---- | --- |\n| Ada  | 36  |\n| Alan | 41  |"
+        );
+    }
+
+    #[test]
+    fn test_from_delimited_escapes_pipes_and_line_breaks() {
+        let table = MarkdownTable::from_delimited("a,b\n\"x|y\",\"1\n2\"").unwrap();
+
+        assert_eq!(table.rows, vec![vec!["x\\|y", "1<br>2"]]);
+    }
+
+    #[test]
+    fn test_from_delimited_skips_blank_lines() {
+        let table = MarkdownTable::from_delimited("a,b\n\n1,2\n\n").unwrap();
+
+        assert_eq!(table.rows, vec![vec!["1", "2"]]);
+    }
+
+    #[test]
+    fn test_from_delimited_rejects_prose() {
+        assert!(MarkdownTable::from_delimited("Hello, world").is_none());
+        assert!(MarkdownTable::from_delimited("Hello, world\nno comma here").is_none());
+        assert!(MarkdownTable::from_delimited("one line\nanother line").is_none());
+    }
+
+    #[test]
+    fn test_from_delimited_rejects_markdown_tables() {
+        assert!(MarkdownTable::from_delimited("| a | b |\n| - | - |\n| 1, 2 | 3 |").is_none());
+    }
+
+    #[test]
+    fn test_to_csv_quotes_when_needed() {
+        let table = MarkdownTable {
+            header: vec![String::from("text"), String::from("pipe")],
+            alignments: vec![Alignment::None; 2],
+            rows: vec![vec![String::from("say \"hi\""), String::from("a \\| b")]],
+        };
+
+        assert_eq!(table.to_csv(), "text,pipe\n\"say \"\"hi\"\"\",a | b\n");
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let csv = "Name,Comment\nAda,\"First, programmer\"\n";
+        let table = MarkdownTable::from_delimited(csv).unwrap();
+        let parsed = MarkdownTable::parse(&table.to_markdown()).unwrap();
+
+        assert_eq!(parsed.to_csv(), csv);
+    }
+
+    #[test]
+    fn test_csv_round_trip_with_line_breaks() {
+        let csv = "Name,Lines\nAda,\"1\n2\"\n";
+        let table = MarkdownTable::from_delimited(csv).unwrap();
+        let parsed = MarkdownTable::parse(&table.to_markdown()).unwrap();
+
+        assert_eq!(parsed.rows, vec![vec!["Ada", "1<br>2"]]);
+        assert_eq!(parsed.to_csv(), csv);
+    }
+}
@@ -0,0 +1,506 @@
+use comrak::{Arena, ComrakOptions, nodes::NodeValue, parse_document};
+
+use super::SelectionFormatter;
+use crate::api::markdown_formatter::textarea::Selection;
+
+/// Edit of the headings in the selection or of the section under the caret.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeadingOperation {
+    /// Turns the selected lines into headings of this level, `0` into paragraphs.
+    SetLevel(u8),
+    /// Raises the heading of the section under the caret and its subheadings a level.
+    Promote,
+    /// Lowers the heading of the section under the caret and its subheadings a level.
+    Demote,
+    /// Switches the selected headings between `# ATX` and underlined Setext style.
+    ToggleStyle,
+}
+
+/// Formatter that sets heading levels, promotes or demotes sections and converts
+/// between ATX and Setext headings.
+///
+/// ```rust,ignore
+/// use rustynote::api::markdown_formatter::format::{HeadingEditor, HeadingOperation, SelectionFormatter};
+/// use rustynote::api::markdown_formatter::textarea::Selection;
+///
+/// // `Selection` is read from the textarea inside the crate.
+/// let selection = Selection {
+///     textarea_value: "# Title\nText".into(),
+///     selected_text: None,
+///     before_selection: "# Ti".into(),
+///     after_selection: "tle\nText".into(),
+///     start_index: 4,
+///     end_index: 4,
+/// };
+///
+/// let (text, start, _) = HeadingEditor::new(&selection, HeadingOperation::SetLevel(4)).format();
+/// assert_eq!(text, "#### Title\nText");
+/// assert_eq!(start, 7);
+/// ```
+pub struct HeadingEditor<'a> {
+    selection: &'a Selection,
+    operation: HeadingOperation,
+}
+
+/// A heading of the document by its lines.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct HeadingLines {
+    pub(super) first: usize,
+    /// The underline of a Setext heading, otherwise the same as `first`.
+    pub(super) last: usize,
+    pub(super) level: u8,
+    pub(super) setext: bool,
+}
+
+impl HeadingLines {
+    pub(super) fn contains(&self, line: usize) -> bool {
+        (self.first..=self.last).contains(&line)
+    }
+}
+
+/// How a line of the text changes.
+#[derive(Debug, Clone, PartialEq)]
+enum LineEdit {
+    Keep,
+    /// The line becomes `text`. Its first `old_prefix` bytes were markup, replaced by
+    /// `new_prefix` bytes of markup, so the caret can stay in the content.
+    Replace {
+        text: String,
+        old_prefix: usize,
+        new_prefix: usize,
+        underline: Option<String>,
+    },
+    Delete,
+}
+
+impl<'a> HeadingEditor<'a> {
+    /// Creates a formatter that applies `operation` to the headings at the selection.
+    pub fn new(selection: &'a Selection, operation: HeadingOperation) -> Self {
+        Self {
+            selection,
+            operation,
+        }
+    }
+
+    fn apply_heading_operation(&self) -> (String, u32, u32) {
+        let text = &self.selection.textarea_value;
+        let lines: Vec<&str> = text.split('\n').collect();
+        let headings = find_headings(text);
+
+        let first_line = self.selection.line_index_of(self.selection.start_index);
+        let mut last_line = self.selection.line_index_of(self.selection.end_index);
+        // A selection of whole lines ends at the start of the next one.
+        if last_line > first_line && text[..self.selection.end_index].ends_with('\n') {
+            last_line -= 1;
+        }
+
+        let changes = match self.operation {
+            HeadingOperation::SetLevel(level) => {
+                set_level(&lines, &headings, first_line, last_line, level.min(6))
+            }
+            HeadingOperation::Promote => shift_section(&headings, first_line, -1),
+            HeadingOperation::Demote => shift_section(&headings, first_line, 1),
+            HeadingOperation::ToggleStyle => toggle_style(&headings, first_line, last_line),
+        };
+
+        let mut edits = vec![LineEdit::Keep; lines.len()];
+        for (heading, level, setext) in changes {
+            for (line, edit) in restyle(&lines, heading, level, setext) {
+                edits[line] = edit;
+            }
+        }
+
+        self.rewrite(&lines, &edits)
+    }
+
+    /// Applies the line edits and moves the selection along with the content.
+    fn rewrite(&self, lines: &[&str], edits: &[LineEdit]) -> (String, u32, u32) {
+        // Where the content of every old line ended up: the new line and the prefixes,
+        // or the end of a new line for deleted lines.
+        let mut positions: Vec<(Option<usize>, usize, usize)> = Vec::with_capacity(lines.len());
+        let mut new_lines: Vec<String> = Vec::with_capacity(lines.len());
+
+        for (line, edit) in lines.iter().zip(edits) {
+            match edit {
+                LineEdit::Keep => {
+                    positions.push((Some(new_lines.len()), 0, 0));
+                    new_lines.push(line.to_string());
+                }
+                LineEdit::Replace {
+                    text,
+                    old_prefix,
+                    new_prefix,
+                    underline,
+                } => {
+                    positions.push((Some(new_lines.len()), *old_prefix, *new_prefix));
+                    new_lines.push(text.clone());
+                    new_lines.extend(underline.clone());
+                }
+                LineEdit::Delete => positions.push((new_lines.len().checked_sub(1), usize::MAX, 0)),
+            }
+        }
+
+        let map = |position: usize| {
+            let text = &self.selection.textarea_value;
+            let line = self.selection.line_index_of(position);
+            let column = position - text[..position].rfind('\n').map_or(0, |index| index + 1);
+
+            let (new_line, old_prefix, new_prefix) = positions[line];
+            let Some(new_line) = new_line else {
+                return 0;
+            };
+            let length = new_lines[new_line].len();
+            let column = if old_prefix == usize::MAX {
+                length
+            } else if column == 0 && !self.selection.is_empty() {
+                // Selected lines stay selected from their start.
+                0
+            } else {
+                (new_prefix + column.saturating_sub(old_prefix)).min(length)
+            };
+            let column = floor_char_boundary(&new_lines[new_line], column);
+
+            new_lines[..new_line]
+                .iter()
+                .map(|line| line.len() + 1)
+                .sum::<usize>()
+                + column
+        };
+
+        let start = map(self.selection.start_index);
+        let end = map(self.selection.end_index);
+        (new_lines.join("\n"), start as u32, end as u32)
+    }
+}
+
+impl<'a> SelectionFormatter for HeadingEditor<'a> {
+    fn format(&self) -> (String, u32, u32) {
+        self.apply_heading_operation()
+    }
+}
+
+/// Headings at the top level of the document, the ones in quotes, lists and code are
+/// left alone.
+pub(super) fn find_headings(text: &str) -> Vec<HeadingLines> {
+    let arena = Arena::new();
+    let root = parse_document(&arena, text, &ComrakOptions::default());
+
+    root.children()
+        .filter_map(|node| {
+            let data = node.data.borrow();
+            match &data.value {
+                NodeValue::Heading(heading) => Some(HeadingLines {
+                    first: data.sourcepos.start.line.saturating_sub(1),
+                    last: data.sourcepos.end.line.saturating_sub(1),
+                    level: heading.level,
+                    setext: heading.setext,
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// The headings (and lines that become headings) of the selected lines with their new
+/// level. Setext headings keep their style while the level allows it.
+fn set_level(
+    lines: &[&str],
+    headings: &[HeadingLines],
+    first_line: usize,
+    last_line: usize,
+    level: u8,
+) -> Vec<(HeadingLines, u8, bool)> {
+    let mut targets: Vec<HeadingLines> = Vec::new();
+    for (line, text) in lines
+        .iter()
+        .enumerate()
+        .take(last_line + 1)
+        .skip(first_line)
+    {
+        let target = match headings.iter().find(|heading| heading.contains(line)) {
+            Some(heading) => *heading,
+            None if first_line != last_line && text.trim().is_empty() => continue,
+            None => HeadingLines {
+                first: line,
+                last: line,
+                level: 0,
+                setext: false,
+            },
+        };
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+
+    targets
+        .into_iter()
+        .map(|heading| (heading, level, heading.setext))
+        .collect()
+}
+
+/// The heading of the section at `line` and its subheadings, shifted by `delta` levels.
+/// Nothing changes when a heading would leave the levels 1 to 6.
+fn shift_section(
+    headings: &[HeadingLines],
+    line: usize,
+    delta: i8,
+) -> Vec<(HeadingLines, u8, bool)> {
+    let Some(start) = headings.iter().rposition(|heading| heading.first <= line) else {
+        return Vec::new();
+    };
+    let level = headings[start].level;
+    let end = headings[start + 1..]
+        .iter()
+        .position(|heading| heading.level <= level)
+        .map_or(headings.len(), |offset| start + 1 + offset);
+
+    headings[start..end]
+        .iter()
+        .map(|heading| {
+            let level = heading.level as i8 + delta;
+            (1..=6)
+                .contains(&level)
+                .then_some((*heading, level as u8, heading.setext))
+        })
+        .collect::<Option<Vec<_>>>()
+        .unwrap_or_default()
+}
+
+/// The selected headings in the other style. ATX headings below level 2 have no Setext
+/// form and stay as they are.
+fn toggle_style(
+    headings: &[HeadingLines],
+    first_line: usize,
+    last_line: usize,
+) -> Vec<(HeadingLines, u8, bool)> {
+    headings
+        .iter()
+        .filter(|heading| heading.first <= last_line && heading.last >= first_line)
+        .filter(|heading| heading.setext || heading.level <= 2)
+        .map(|heading| (*heading, heading.level, !heading.setext))
+        .collect()
+}
+
+/// Line edits that give `heading` the `level` in the chosen style. Only levels 1 and 2
+/// can be Setext headings.
+fn restyle(
+    lines: &[&str],
+    heading: HeadingLines,
+    level: u8,
+    setext: bool,
+) -> Vec<(usize, LineEdit)> {
+    let setext = setext && (1..=2).contains(&level);
+
+    if heading.setext {
+        let content: Vec<&str> = lines[heading.first..heading.last]
+            .iter()
+            .map(|line| line.trim())
+            .collect();
+
+        if setext {
+            let width = content.iter().map(|line| line.chars().count()).max();
+            let underline = underline(level, width.unwrap_or_default());
+            return vec![(
+                heading.last,
+                LineEdit::Replace {
+                    old_prefix: 0,
+                    new_prefix: 0,
+                    text: underline,
+                    underline: None,
+                },
+            )];
+        }
+
+        let line = lines[heading.first];
+        let prefix = atx_prefix(level);
+        let mut edits = vec![(
+            heading.first,
+            LineEdit::Replace {
+                text: format!("{prefix}{}", content.join(" ")),
+                old_prefix: line.len() - line.trim_start().len(),
+                new_prefix: prefix.len(),
+                underline: None,
+            },
+        )];
+        edits.extend((heading.first + 1..=heading.last).map(|line| (line, LineEdit::Delete)));
+        return edits;
+    }
+
+    let line = lines[heading.first];
+    let old_prefix = atx_prefix_len(line);
+    let content = &line[old_prefix..];
+
+    let edit = if setext {
+        let content = strip_closing_sequence(content);
+        LineEdit::Replace {
+            text: content.to_string(),
+            old_prefix,
+            new_prefix: 0,
+            underline: Some(underline(level, content.chars().count())),
+        }
+    } else {
+        let indentation = &line[..line.len() - line.trim_start_matches(' ').len()];
+        let prefix = format!("{indentation}{}", atx_prefix(level));
+        LineEdit::Replace {
+            text: format!("{prefix}{content}"),
+            old_prefix,
+            new_prefix: prefix.len(),
+            underline: None,
+        }
+    };
+    vec![(heading.first, edit)]
+}
+
+fn atx_prefix(level: u8) -> String {
+    match level {
+        0 => String::new(),
+        level => format!("{} ", "#".repeat(level as usize)),
+    }
+}
+
+/// Length of the indentation, the `#` marker and the spaces after it.
+fn atx_prefix_len(line: &str) -> usize {
+    let indentation = line.len() - line.trim_start_matches(' ').len();
+    let rest = &line[indentation..];
+    let hashes = rest.len() - rest.trim_start_matches('#').len();
+    let after = &rest[hashes..];
+
+    if (1..=6).contains(&hashes) && (after.is_empty() || after.starts_with([' ', '\t'])) {
+        line.len() - after.trim_start().len()
+    } else {
+        indentation
+    }
+}
+
+/// Removes the optional closing `#`s of an ATX heading.
+fn strip_closing_sequence(content: &str) -> &str {
+    let content = content.trim_end();
+    let without = content.trim_end_matches('#');
+    if without.is_empty() {
+        ""
+    } else if without.ends_with([' ', '\t']) {
+        without.trim_end()
+    } else {
+        content
+    }
+}
+
+fn underline(level: u8, width: usize) -> String {
+    let marker = if level == 1 { "=" } else { "-" };
+    marker.repeat(width.max(3))
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(text: &str, caret: usize, operation: HeadingOperation) -> (String, u32) {
+        let selection = Selection::new_with_caret_position(text.to_string(), caret);
+        let (text, start, end) = HeadingEditor::new(&selection, operation).format();
+        assert_eq!(start, end);
+        (text, start)
+    }
+
+    #[test]
+    fn test_set_level_on_plain_line() {
+        assert_eq!(
+            apply("Intro\nTitle", 11, HeadingOperation::SetLevel(3)),
+            ("Intro\n### Title".into(), 15)
+        );
+    }
+
+    #[test]
+    fn test_set_level_zero_makes_a_paragraph() {
+        assert_eq!(
+            apply("## Title", 5, HeadingOperation::SetLevel(0)),
+            ("Title".into(), 2)
+        );
+    }
+
+    #[test]
+    fn test_set_level_keeps_setext_when_possible() {
+        assert_eq!(
+            apply("Title\n=====\nText", 2, HeadingOperation::SetLevel(2)).0,
+            "Title\n-----\nText"
+        );
+        assert_eq!(
+            apply("Title\n=====\nText", 2, HeadingOperation::SetLevel(3)),
+            ("### Title\nText".into(), 6)
+        );
+    }
+
+    #[test]
+    fn test_set_level_on_selected_lines_skips_blank_lines() {
+        let text = "One\n\nTwo";
+        let selection = Selection::new(text.to_string(), 0, text.len());
+        let (text, start, end) =
+            HeadingEditor::new(&selection, HeadingOperation::SetLevel(2)).format();
+        assert_eq!(text, "## One\n\n## Two");
+        assert_eq!((start, end), (0, text.len() as u32));
+    }
+
+    #[test]
+    fn test_promote_section_with_subheadings() {
+        let text = "# A\n## B\ntext\n### C\n## D";
+        assert_eq!(
+            apply(text, 10, HeadingOperation::Promote).0,
+            "# A\n# B\ntext\n## C\n## D"
+        );
+    }
+
+    #[test]
+    fn test_demote_section() {
+        let text = "# A\n## B\n# C";
+        assert_eq!(
+            apply(text, 1, HeadingOperation::Demote),
+            ("## A\n### B\n# C".into(), 3)
+        );
+    }
+
+    #[test]
+    fn test_shift_stops_at_the_outer_levels() {
+        assert_eq!(
+            apply("# A\ntext", 5, HeadingOperation::Promote).0,
+            "# A\ntext"
+        );
+        assert_eq!(
+            apply("##### A\n###### B", 0, HeadingOperation::Demote).0,
+            "##### A\n###### B"
+        );
+        assert_eq!(apply("text", 0, HeadingOperation::Demote).0, "text");
+    }
+
+    #[test]
+    fn test_toggle_atx_to_setext() {
+        assert_eq!(
+            apply("## Intro ##\nBody", 5, HeadingOperation::ToggleStyle),
+            ("Intro\n-----\nBody".into(), 2)
+        );
+        assert_eq!(
+            apply("### Deep", 5, HeadingOperation::ToggleStyle).0,
+            "### Deep"
+        );
+    }
+
+    #[test]
+    fn test_toggle_setext_to_atx() {
+        assert_eq!(
+            apply("Intro\n===\n", 8, HeadingOperation::ToggleStyle),
+            ("# Intro\n".into(), 7)
+        );
+    }
+
+    #[test]
+    fn test_headings_in_code_and_quotes_are_ignored() {
+        let text = "```\n# code\n```\n> # quote";
+        assert_eq!(apply(text, 6, HeadingOperation::Promote).0, text);
+        assert_eq!(apply(text, 20, HeadingOperation::ToggleStyle).0, text);
+    }
+}
@@ -2,15 +2,31 @@ use crate::api::markdown_formatter::textarea::{get_current_selection, set_cursor
 
 mod block_prefix;
 mod codeblock;
+mod csv;
 mod heading;
+mod heading_editor;
 mod horizontal_rule;
 mod inline;
+mod move_lines;
 mod ordered_list;
 mod table;
+mod table_editor;
+mod task;
 
+pub(crate) use self::table_editor::LocatedTable;
 pub use self::{
-    block_prefix::BlockPrefix, codeblock::CodeBlock, heading::Heading,
-    horizontal_rule::HorizontalRule, inline::Inline, ordered_list::OrderedList, table::Table,
+    block_prefix::BlockPrefix,
+    codeblock::CodeBlock,
+    csv::{Delimiter, parse_delimited},
+    heading::Heading,
+    heading_editor::{HeadingEditor, HeadingOperation},
+    horizontal_rule::HorizontalRule,
+    inline::Inline,
+    move_lines::{MoveDirection, MoveLines},
+    ordered_list::OrderedList,
+    table::Table,
+    table_editor::{Alignment, MarkdownTable, TableEditor, TableOperation},
+    task::TaskToggle,
 };
 
 /// Shared interface implemented by every formatter.
@@ -36,6 +52,7 @@ pub trait SelectionFormatter {
     fn format(&self) -> (String, u32, u32);
 }
 
+#[derive(Clone, Copy)]
 pub enum TextFormattingType {
     Inline {
         prefix: &'static str,
@@ -45,10 +62,14 @@ pub enum TextFormattingType {
         prefix: &'static str,
     },
     Heading,
+    HeadingOperation(HeadingOperation),
     CodeBlock,
     OrderedList,
     Table,
+    TableOperation(TableOperation),
+    Task,
     HorizontalRule,
+    MoveLines(MoveDirection),
 }
 
 impl TextFormattingType {
@@ -75,6 +96,10 @@ impl TextFormattingType {
 
             TextFormattingType::Heading => Heading::new(&selection).format(),
 
+            TextFormattingType::HeadingOperation(operation) => {
+                HeadingEditor::new(&selection, *operation).format()
+            }
+
             TextFormattingType::CodeBlock => CodeBlock::new(&selection).format(),
 
             TextFormattingType::HorizontalRule => HorizontalRule::new(&selection).format(),
@@ -82,6 +107,16 @@ impl TextFormattingType {
             TextFormattingType::OrderedList => OrderedList::new(&selection).format(),
 
             TextFormattingType::Table => Table::new(&selection).format(),
+
+            TextFormattingType::TableOperation(operation) => {
+                TableEditor::new(&selection, *operation).format()
+            }
+
+            TextFormattingType::Task => TaskToggle::new(&selection).format(),
+
+            TextFormattingType::MoveLines(direction) => {
+                MoveLines::new(&selection, *direction).format()
+            }
         };
 
         set_cursor(new_value, new_sel_start, new_sel_end)
@@ -0,0 +1,419 @@
+use std::ops::Range;
+
+use super::{
+    SelectionFormatter,
+    heading_editor::{HeadingLines, find_headings},
+};
+use crate::api::markdown_formatter::textarea::Selection;
+
+/// Direction in which [`MoveLines`] moves the text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+}
+
+/// Formatter that swaps the lines at the selection with the text above or below.
+///
+/// A caret on a heading moves the whole section past its sibling section, a caret on
+/// a list item moves the item with its nested children past its sibling item, and
+/// anything else moves line by line. Ordered lists are renumbered afterwards.
+///
+/// ```rust,ignore
+/// use rustynote::api::markdown_formatter::format::{MoveDirection, MoveLines, SelectionFormatter};
+/// use rustynote::api::markdown_formatter::textarea::Selection;
+///
+/// // `Selection` is read from the textarea inside the crate.
+/// let selection = Selection {
+///     textarea_value: "1. One\n2. Two".into(),
+///     selected_text: None,
+///     before_selection: "1. One\n2. T".into(),
+///     after_selection: "wo".into(),
+///     start_index: 11,
+///     end_index: 11,
+/// };
+///
+/// let (text, start, _) = MoveLines::new(&selection, MoveDirection::Up).format();
+/// assert_eq!(text, "1. Two\n2. One");
+/// assert_eq!(start, 4);
+/// ```
+pub struct MoveLines<'a> {
+    selection: &'a Selection,
+    direction: MoveDirection,
+}
+
+/// What moves together.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Unit {
+    Lines,
+    /// A list item at this indentation with its nested lines.
+    Item(usize),
+    /// A heading of this level with its body and subsections.
+    Section(u8),
+}
+
+/// The marker of a list item line.
+struct ListItem {
+    indent: usize,
+    /// Number of digits of an ordered list marker, `0` for bullets.
+    digits: usize,
+}
+
+impl<'a> MoveLines<'a> {
+    /// Creates a formatter that moves the selected text in `direction`.
+    pub fn new(selection: &'a Selection, direction: MoveDirection) -> Self {
+        Self {
+            selection,
+            direction,
+        }
+    }
+
+    fn apply_move(&self) -> (String, u32, u32) {
+        let unchanged = (
+            self.selection.textarea_value.clone(),
+            self.selection.start_index as u32,
+            self.selection.end_index as u32,
+        );
+
+        let text = &self.selection.textarea_value;
+        let lines: Vec<&str> = text.split('\n').collect();
+        let headings = find_headings(text);
+
+        let first_line = self.selection.line_index_of(self.selection.start_index);
+        let mut last_line = self.selection.line_index_of(self.selection.end_index);
+        // A selection of whole lines ends at the start of the next one.
+        if last_line > first_line && text[..self.selection.end_index].ends_with('\n') {
+            last_line -= 1;
+        }
+
+        let (unit, block) = unit_at(&lines, &headings, first_line, last_line);
+        let neighbour = match self.direction {
+            MoveDirection::Up => previous_unit(&lines, &headings, unit, &block),
+            MoveDirection::Down => next_unit(&lines, &headings, unit, &block),
+        };
+        let Some(neighbour) = neighbour else {
+            return unchanged;
+        };
+
+        let (upper, lower) = match self.direction {
+            MoveDirection::Up => (neighbour.clone(), block.clone()),
+            MoveDirection::Down => (block.clone(), neighbour.clone()),
+        };
+        let mut new_lines: Vec<String> = lines[..upper.start]
+            .iter()
+            .chain(&lines[lower.clone()])
+            .chain(&lines[upper.clone()])
+            .chain(&lines[lower.end..])
+            .map(|line| line.to_string())
+            .collect();
+
+        let shift: usize = lines[neighbour.clone()]
+            .iter()
+            .map(|line| line.len() + 1)
+            .sum();
+        let (mut start, mut end, moved_line) = match self.direction {
+            MoveDirection::Up => (
+                self.selection.start_index - shift,
+                self.selection.end_index - shift,
+                neighbour.start,
+            ),
+            MoveDirection::Down => (
+                self.selection.start_index + shift,
+                self.selection.end_index + shift,
+                block.start + neighbour.len(),
+            ),
+        };
+
+        if let Unit::Item(indent) = unit {
+            renumber(
+                &mut new_lines,
+                moved_line,
+                indent,
+                &mut [&mut start, &mut end],
+            );
+        }
+
+        (new_lines.join("\n"), start as u32, end as u32)
+    }
+}
+
+impl<'a> SelectionFormatter for MoveLines<'a> {
+    fn format(&self) -> (String, u32, u32) {
+        self.apply_move()
+    }
+}
+
+/// The unit at the selected lines and the lines it covers.
+fn unit_at(
+    lines: &[&str],
+    headings: &[HeadingLines],
+    first_line: usize,
+    last_line: usize,
+) -> (Unit, Range<usize>) {
+    if first_line == last_line
+        && let Some(index) = headings
+            .iter()
+            .position(|heading| heading.contains(first_line))
+    {
+        let level = headings[index].level;
+        let end = section_end(lines, headings, index);
+        return (Unit::Section(level), headings[index].first..end);
+    }
+
+    match list_item(lines[first_line]) {
+        Some(item) => (
+            Unit::Item(item.indent),
+            first_line..nested_end(lines, last_line, item.indent),
+        ),
+        None => (Unit::Lines, first_line..last_line + 1),
+    }
+}
+
+/// The unit right above `block` that it can swap with.
+fn previous_unit(
+    lines: &[&str],
+    headings: &[HeadingLines],
+    unit: Unit,
+    block: &Range<usize>,
+) -> Option<Range<usize>> {
+    match unit {
+        Unit::Lines => (block.start > 0).then(|| block.start - 1..block.start),
+        Unit::Item(indent) => {
+            for line in (0..block.start).rev() {
+                if lines[line].trim().is_empty() {
+                    return None;
+                }
+                if indentation(lines[line]) > indent {
+                    continue;
+                }
+                return list_item(lines[line])
+                    .filter(|item| item.indent == indent)
+                    .map(|_| line..block.start);
+            }
+            None
+        }
+        Unit::Section(level) => headings
+            .iter()
+            .rev()
+            .filter(|heading| heading.first < block.start)
+            .find(|heading| heading.level <= level)
+            .filter(|heading| heading.level == level)
+            .map(|heading| heading.first..block.start),
+    }
+}
+
+/// The unit right below `block` that it can swap with.
+fn next_unit(
+    lines: &[&str],
+    headings: &[HeadingLines],
+    unit: Unit,
+    block: &Range<usize>,
+) -> Option<Range<usize>> {
+    let line = block.end;
+    if line >= lines.len() {
+        return None;
+    }
+
+    match unit {
+        Unit::Lines => Some(line..line + 1),
+        Unit::Item(indent) => list_item(lines[line])
+            .filter(|item| item.indent == indent)
+            .map(|_| line..nested_end(lines, line, indent)),
+        Unit::Section(level) => {
+            let index = headings.iter().position(|heading| heading.first == line)?;
+            (headings[index].level == level).then(|| line..section_end(lines, headings, index))
+        }
+    }
+}
+
+/// The line after the section of the heading at `index`.
+fn section_end(lines: &[&str], headings: &[HeadingLines], index: usize) -> usize {
+    headings[index + 1..]
+        .iter()
+        .find(|heading| heading.level <= headings[index].level)
+        .map_or(lines.len(), |heading| heading.first)
+}
+
+/// The line after `line` and the lines nested deeper than `indent` below it.
+fn nested_end(lines: &[&str], line: usize, indent: usize) -> usize {
+    lines[line + 1..]
+        .iter()
+        .position(|line| line.trim().is_empty() || indentation(line) <= indent)
+        .map_or(lines.len(), |offset| line + 1 + offset)
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn list_item(line: &str) -> Option<ListItem> {
+    let indent = indentation(line);
+    let rest = &line[indent..];
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+    let after = match digits {
+        0 => rest.strip_prefix(['-', '*', '+'])?,
+        1..=9 => rest[digits..].strip_prefix(['.', ')'])?,
+        _ => return None,
+    };
+    (after.is_empty() || after.starts_with([' ', '\t'])).then_some(ListItem { indent, digits })
+}
+
+/// Numbers the ordered list around `line` from its lowest number again, and keeps the
+/// `positions` on the same text.
+fn renumber(lines: &mut [String], line: usize, indent: usize, positions: &mut [&mut usize]) {
+    let belongs = |line: &str| match list_item(line) {
+        Some(item) => item.indent > indent || (item.indent == indent && item.digits > 0),
+        None => !line.trim().is_empty() && indentation(line) > indent,
+    };
+    if list_item(&lines[line]).is_none_or(|item| item.digits == 0) {
+        return;
+    }
+
+    let first = (0..line)
+        .rev()
+        .find(|&index| !belongs(&lines[index]))
+        .map_or(0, |index| index + 1);
+    let end = (line..lines.len())
+        .find(|&index| !belongs(&lines[index]))
+        .unwrap_or(lines.len());
+
+    let items: Vec<usize> = (first..end)
+        .filter(|&index| list_item(&lines[index]).is_some_and(|item| item.indent == indent))
+        .collect();
+    let Some(start_number) = items
+        .iter()
+        .filter_map(|&index| {
+            lines[index][indent..]
+                .split(['.', ')'])
+                .next()?
+                .parse::<usize>()
+                .ok()
+        })
+        .min()
+    else {
+        return;
+    };
+
+    for (number, &index) in (start_number..).zip(&items) {
+        let digits = list_item(&lines[index]).map_or(0, |item| item.digits);
+        let number = number.to_string();
+        let line_start: usize = lines[..index].iter().map(|line| line.len() + 1).sum();
+        let digits_start = line_start + indent;
+
+        for position in positions.iter_mut() {
+            if **position >= digits_start + digits {
+                **position = **position + number.len() - digits;
+            } else if **position > digits_start + number.len() {
+                **position = digits_start + number.len();
+            }
+        }
+        lines[index].replace_range(indent..indent + digits, &number);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn move_lines(
+        text: &str,
+        start: usize,
+        end: usize,
+        direction: MoveDirection,
+    ) -> (String, u32, u32) {
+        let selection = Selection::new(text.to_string(), start, end);
+        MoveLines::new(&selection, direction).format()
+    }
+
+    #[test]
+    fn test_moves_the_current_line() {
+        assert_eq!(
+            move_lines("a\nb\nc", 3, 3, MoveDirection::Up),
+            ("b\na\nc".into(), 1, 1)
+        );
+        assert_eq!(
+            move_lines("a\nb\nc", 0, 0, MoveDirection::Down),
+            ("b\na\nc".into(), 2, 2)
+        );
+    }
+
+    #[test]
+    fn test_stops_at_the_ends() {
+        assert_eq!(
+            move_lines("a\nb", 0, 0, MoveDirection::Up),
+            ("a\nb".into(), 0, 0)
+        );
+        assert_eq!(
+            move_lines("a\nb", 3, 3, MoveDirection::Down),
+            ("a\nb".into(), 3, 3)
+        );
+    }
+
+    #[test]
+    fn test_moves_selected_lines() {
+        assert_eq!(
+            move_lines("a\nb\nc\nd", 0, 4, MoveDirection::Down),
+            ("c\na\nb\nd".into(), 2, 6)
+        );
+    }
+
+    #[test]
+    fn test_moves_list_item_with_children() {
+        assert_eq!(
+            move_lines("- a\n  - a1\n- b\nafter", 2, 2, MoveDirection::Down),
+            ("- b\n- a\n  - a1\nafter".into(), 6, 6)
+        );
+        assert_eq!(
+            move_lines("- a\n  - a1\n- b\nafter", 13, 13, MoveDirection::Up),
+            ("- b\n- a\n  - a1\nafter".into(), 2, 2)
+        );
+    }
+
+    #[test]
+    fn test_list_item_stays_in_its_list() {
+        let text = "- a\n- b\n\ntext";
+        assert_eq!(
+            move_lines(text, 6, 6, MoveDirection::Down),
+            (text.into(), 6, 6)
+        );
+        let text = "- a\n  - a1\n  - a2";
+        assert_eq!(
+            move_lines(text, 8, 8, MoveDirection::Up),
+            (text.into(), 8, 8)
+        );
+    }
+
+    #[test]
+    fn test_renumbers_ordered_lists() {
+        assert_eq!(
+            move_lines("1. one\n2. two\n3. three", 17, 17, MoveDirection::Up),
+            ("1. one\n2. three\n3. two".into(), 10, 10)
+        );
+        assert_eq!(
+            move_lines("9. a\n10. b", 9, 9, MoveDirection::Up),
+            ("9. b\n10. a".into(), 3, 3)
+        );
+    }
+
+    #[test]
+    fn test_moves_sections() {
+        assert_eq!(
+            move_lines("# A\na\n## A1\n# B\nb", 0, 0, MoveDirection::Down).0,
+            "# B\nb\n# A\na\n## A1"
+        );
+        assert_eq!(
+            move_lines("# A\na\n## A1\n# B\nb", 12, 12, MoveDirection::Up),
+            ("# B\nb\n# A\na\n## A1".into(), 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_section_stays_in_its_parent() {
+        let text = "# A\n## B\nb";
+        assert_eq!(
+            move_lines(text, 5, 5, MoveDirection::Up),
+            (text.into(), 5, 5)
+        );
+    }
+}
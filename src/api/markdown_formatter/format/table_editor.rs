@@ -0,0 +1,899 @@
+use comrak::{Arena, ComrakOptions, nodes::NodeValue, parse_document};
+use markdown_table_formatter::format_tables;
+
+use super::SelectionFormatter;
+use crate::api::markdown_formatter::textarea::{Selection, line_end_at, line_start_at};
+
+/// Column alignment as declared in the divider row of a GFM table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Alignment {
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+impl Alignment {
+    fn parse(cell: &str) -> Self {
+        let cell = cell.trim();
+        match (cell.starts_with(':'), cell.ends_with(':') && cell.len() > 1) {
+            (true, true) => Alignment::Center,
+            (true, false) => Alignment::Left,
+            (false, true) => Alignment::Right,
+            (false, false) => Alignment::None,
+        }
+    }
+
+    fn divider(self) -> &'static str {
+        match self {
+            Alignment::None => "---",
+            Alignment::Left => ":---",
+            Alignment::Center => ":---:",
+            Alignment::Right => "---:",
+        }
+    }
+}
+
+/// Structural edit that can be applied to the table under the caret.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TableOperation {
+    InsertRowAbove,
+    InsertRowBelow,
+    DeleteRow,
+    InsertColumnLeft,
+    InsertColumnRight,
+    DeleteColumn,
+    MoveRowUp,
+    MoveRowDown,
+    MoveColumnLeft,
+    MoveColumnRight,
+    Align(Alignment),
+    NextCell,
+    PreviousCell,
+    NewRow,
+}
+
+/// A parsed GFM table with a header row, column alignments and body rows.
+///
+/// ```rust
+/// use rustynote::api::markdown_formatter::format::MarkdownTable;
+///
+/// let table = MarkdownTable::parse("| Name | Age |\n|:---|---:|\n| Ada | 36 |").unwrap();
+/// assert_eq!(table.header, vec!["Name", "Age"]);
+/// assert_eq!(table.rows, vec![vec!["Ada", "36"]]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownTable {
+    pub header: Vec<String>,
+    pub alignments: Vec<Alignment>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl MarkdownTable {
+    /// Parses the given lines as a table. Returns `None` when the second line is no divider row.
+    pub fn parse(text: &str) -> Option<Self> {
+        let lines: Vec<&str> = text.lines().collect();
+        if lines.len() < 2 || !is_divider_row(lines[1]) {
+            return None;
+        }
+
+        let header = split_cells(lines[0]);
+        let column_count = header.len();
+        let mut alignments: Vec<Alignment> = split_cells(lines[1])
+            .iter()
+            .map(|cell| Alignment::parse(cell))
+            .collect();
+        alignments.resize(column_count, Alignment::None);
+
+        let rows = lines[2..]
+            .iter()
+            .map(|line| {
+                let mut cells = split_cells(line);
+                cells.resize(column_count, String::new());
+                cells
+            })
+            .collect();
+
+        Some(MarkdownTable {
+            header,
+            alignments,
+            rows,
+        })
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.header.len()
+    }
+
+    /// Renders the table as markdown, padded by the shared table formatter.
+    pub fn to_markdown(&self) -> String {
+        let mut text = render_row(&self.header);
+        text.push('\n');
+        text.push_str(&render_row(
+            &self
+                .alignments
+                .iter()
+                .map(|alignment| alignment.divider().to_string())
+                .collect::<Vec<String>>(),
+        ));
+        text.push('\n');
+
+        for row in &self.rows {
+            text.push_str(&render_row(row));
+            text.push('\n');
+        }
+
+        format_tables(text).trim_end_matches('\n').to_string()
+    }
+
+    fn empty_row(&self) -> Vec<String> {
+        vec![String::new(); self.column_count()]
+    }
+
+    fn apply(&mut self, operation: TableOperation, cell: Cell) -> Option<Cell> {
+        let Cell { row, column } = cell;
+        let last_column = self.column_count().saturating_sub(1);
+
+        match operation {
+            TableOperation::InsertRowAbove => {
+                let index = row.saturating_sub(1);
+                self.rows.insert(index, self.empty_row());
+                Some(Cell::new(index + 1, column))
+            }
+            TableOperation::InsertRowBelow => {
+                self.rows.insert(row, self.empty_row());
+                Some(Cell::new(row + 1, column))
+            }
+            TableOperation::DeleteRow => {
+                if row == 0 {
+                    return None;
+                }
+                self.rows.remove(row - 1);
+                Some(Cell::new(row.min(self.rows.len()), column))
+            }
+            TableOperation::InsertColumnLeft => {
+                self.insert_column(column);
+                Some(Cell::new(row, column))
+            }
+            TableOperation::InsertColumnRight => {
+                self.insert_column(column + 1);
+                Some(Cell::new(row, column + 1))
+            }
+            TableOperation::DeleteColumn => {
+                if self.column_count() <= 1 {
+                    return None;
+                }
+                self.header.remove(column);
+                self.alignments.remove(column);
+                for cells in &mut self.rows {
+                    cells.remove(column);
+                }
+                Some(Cell::new(row, column.min(self.column_count() - 1)))
+            }
+            TableOperation::MoveRowUp => {
+                if row < 2 {
+                    return None;
+                }
+                self.rows.swap(row - 1, row - 2);
+                Some(Cell::new(row - 1, column))
+            }
+            TableOperation::MoveRowDown => {
+                if row == 0 || row >= self.rows.len() {
+                    return None;
+                }
+                self.rows.swap(row - 1, row);
+                Some(Cell::new(row + 1, column))
+            }
+            TableOperation::MoveColumnLeft => {
+                if column == 0 {
+                    return None;
+                }
+                self.swap_columns(column, column - 1);
+                Some(Cell::new(row, column - 1))
+            }
+            TableOperation::MoveColumnRight => {
+                if column >= last_column {
+                    return None;
+                }
+                self.swap_columns(column, column + 1);
+                Some(Cell::new(row, column + 1))
+            }
+            TableOperation::Align(alignment) => {
+                self.alignments.resize(self.column_count(), Alignment::None);
+                *self.alignments.get_mut(column)? = alignment;
+                Some(cell)
+            }
+            TableOperation::NextCell => {
+                if column < last_column {
+                    Some(Cell::new(row, column + 1))
+                } else {
+                    if row >= self.rows.len() {
+                        self.rows.push(self.empty_row());
+                    }
+                    Some(Cell::new(row + 1, 0))
+                }
+            }
+            TableOperation::PreviousCell => match (row, column) {
+                (0, 0) => Some(cell),
+                (_, 0) => Some(Cell::new(row - 1, last_column)),
+                _ => Some(Cell::new(row, column - 1)),
+            },
+            TableOperation::NewRow => {
+                self.rows.insert(row, self.empty_row());
+                Some(Cell::new(row + 1, 0))
+            }
+        }
+    }
+
+    fn insert_column(&mut self, index: usize) {
+        self.header.insert(index, String::new());
+        self.alignments.insert(index, Alignment::None);
+        for cells in &mut self.rows {
+            cells.insert(index, String::new());
+        }
+    }
+
+    fn swap_columns(&mut self, first: usize, second: usize) {
+        self.header.swap(first, second);
+        self.alignments.swap(first, second);
+        for cells in &mut self.rows {
+            cells.swap(first, second);
+        }
+    }
+
+    fn is_empty_row(&self, row: usize) -> bool {
+        row > 0
+            && self
+                .rows
+                .get(row - 1)
+                .is_some_and(|cells| cells.iter().all(|cell| cell.is_empty()))
+    }
+}
+
+/// Position of a cell in a table. Row `0` is the header, body rows start at `1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cell {
+    row: usize,
+    column: usize,
+}
+
+impl Cell {
+    fn new(row: usize, column: usize) -> Self {
+        Cell { row, column }
+    }
+}
+
+/// Byte range of a table inside the textarea together with the cell under the caret.
+pub(crate) struct LocatedTable {
+    pub start: usize,
+    pub end: usize,
+    pub table: MarkdownTable,
+    cell: Cell,
+}
+
+impl LocatedTable {
+    /// Finds the GFM table that contains the given byte position.
+    pub(crate) fn at(text: &str, position: usize) -> Option<Self> {
+        let line_start = line_start_at(text, position);
+        let line_end = line_end_at(text, position);
+        let code_lines = code_block_lines(text);
+        let is_row = |line: usize, start: usize, end: usize| {
+            is_table_row(&text[start..end])
+                && !code_lines
+                    .iter()
+                    .any(|(first, last)| (*first..=*last).contains(&line))
+        };
+
+        let caret_line = text[..line_start].matches('\n').count();
+        if !is_row(caret_line, line_start, line_end) {
+            return None;
+        }
+
+        let mut start = line_start;
+        let mut line_index = 0;
+        while start > 0 {
+            let previous_start = line_start_at(text, start - 1);
+            if !is_row(caret_line - line_index - 1, previous_start, start - 1) {
+                break;
+            }
+            start = previous_start;
+            line_index += 1;
+        }
+
+        let mut end = line_end;
+        let mut next_line = caret_line + 1;
+        while end < text.len() {
+            let next_end = line_end_at(text, end + 1);
+            if !is_row(next_line, end + 1, next_end) {
+                break;
+            }
+            end = next_end;
+            next_line += 1;
+        }
+
+        let table = MarkdownTable::parse(&text[start..end])?;
+
+        let row = match line_index {
+            0 | 1 => 0,
+            index => index - 1,
+        };
+        let column = column_at(&text[line_start..line_end], position - line_start)
+            .min(table.column_count().saturating_sub(1));
+
+        Some(LocatedTable {
+            start,
+            end,
+            table,
+            cell: Cell::new(row, column),
+        })
+    }
+}
+
+/// Formatter that applies a [`TableOperation`] to the table under the caret.
+/// The text stays unchanged when the caret is not inside a table.
+///
+/// ```rust,ignore
+/// use rustynote::api::markdown_formatter::format::{SelectionFormatter, TableEditor, TableOperation};
+/// use rustynote::api::markdown_formatter::textarea::Selection;
+///
+/// let text = String::from("| A | B |\n| - | - |\n| 1 | 2 |");
+/// let selection = Selection {
+///     textarea_value: text.clone(),
+///     selected_text: None,
+///     before_selection: String::new(),
+///     after_selection: text,
+///     start_index: 0,
+///     end_index: 0,
+/// };
+///
+/// let (text, _, _) = TableEditor::new(&selection, TableOperation::DeleteColumn).format();
+/// assert_eq!(text, "| B   |\n| --- |\n| 2   |");
+/// ```
+pub struct TableEditor<'a> {
+    selection: &'a Selection,
+    operation: TableOperation,
+}
+
+impl<'a> TableEditor<'a> {
+    /// Creates a formatter that applies `operation` to the table under the caret.
+    pub fn new(selection: &'a Selection, operation: TableOperation) -> Self {
+        Self {
+            selection,
+            operation,
+        }
+    }
+
+    /// Returns `true` when the caret is placed inside a GFM table.
+    pub fn is_in_table(selection: &Selection) -> bool {
+        LocatedTable::at(&selection.textarea_value, selection.start_index).is_some()
+    }
+
+    fn apply_table_operation(&self) -> (String, u32, u32) {
+        let unchanged = (
+            self.selection.textarea_value.clone(),
+            self.selection.start_index as u32,
+            self.selection.end_index as u32,
+        );
+
+        let Some(LocatedTable {
+            start,
+            end,
+            mut table,
+            cell,
+        }) = LocatedTable::at(&self.selection.textarea_value, self.selection.start_index)
+        else {
+            return unchanged;
+        };
+
+        if self.operation == TableOperation::NewRow
+            && cell.row == table.rows.len()
+            && table.is_empty_row(cell.row)
+        {
+            return self.leave_table(start, &mut table);
+        }
+
+        let Some(target) = table.apply(self.operation, cell) else {
+            return unchanged;
+        };
+
+        let table_text = table.to_markdown();
+        let new_text = self.selection.replace_range(start, end, &table_text);
+        let (cell_start, cell_end) = cell_range(&table_text, target);
+
+        (
+            new_text,
+            (start + cell_start) as u32,
+            (start + cell_end) as u32,
+        )
+    }
+
+    fn leave_table(&self, start: usize, table: &mut MarkdownTable) -> (String, u32, u32) {
+        let text = &self.selection.textarea_value;
+        let (_, line_end) = self.selection.line_bounds();
+
+        table.rows.pop();
+        let mut table_text = table.to_markdown();
+        table_text.push('\n');
+
+        let after = text[line_end..]
+            .strip_prefix('\n')
+            .unwrap_or(&text[line_end..]);
+        if !after.starts_with('\n') {
+            table_text.push('\n');
+        }
+
+        let caret = (start + table_text.len()) as u32;
+        let new_text = format!("{}{}{}", &text[..start], table_text, after);
+
+        (new_text, caret, caret)
+    }
+}
+
+impl<'a> SelectionFormatter for TableEditor<'a> {
+    fn format(&self) -> (String, u32, u32) {
+        self.apply_table_operation()
+    }
+}
+
+/// Zero-based first and last lines of every code block, whose pipes never start a table.
+fn code_block_lines(text: &str) -> Vec<(usize, usize)> {
+    let arena = Arena::new();
+    let root = parse_document(&arena, text, &ComrakOptions::default());
+
+    root.descendants()
+        .filter_map(|node| {
+            let data = node.data.borrow();
+            matches!(data.value, NodeValue::CodeBlock(_)).then(|| {
+                (
+                    data.sourcepos.start.line.saturating_sub(1),
+                    data.sourcepos.end.line.saturating_sub(1),
+                )
+            })
+        })
+        .collect()
+}
+
+fn is_table_row(line: &str) -> bool {
+    !line.trim().is_empty() && line.contains('|')
+}
+
+fn is_divider_row(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.contains('-')
+        && trimmed
+            .chars()
+            .all(|c| matches!(c, '-' | ':' | '|' | ' ' | '\t'))
+}
+
+/// Byte offsets of all pipes that separate cells in the given line.
+fn cell_separators(line: &str) -> Vec<usize> {
+    let mut separators = Vec::new();
+    let mut escaped = false;
+    let mut in_code = false;
+
+    for (index, c) in line.char_indices() {
+        match c {
+            '\\' if !escaped => {
+                escaped = true;
+                continue;
+            }
+            '`' if !escaped => in_code = !in_code,
+            '|' if !escaped && !in_code => separators.push(index),
+            _ => {}
+        }
+        escaped = false;
+    }
+
+    separators
+}
+
+fn split_cells(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let offset = line.find(trimmed).unwrap_or(0);
+    let mut bounds = vec![offset];
+    bounds.extend(cell_separators(line).into_iter().flat_map(|i| [i, i + 1]));
+    bounds.push(offset + trimmed.len());
+
+    let mut cells: Vec<String> = bounds
+        .chunks(2)
+        .map(|pair| line[pair[0]..pair[1]].trim().to_string())
+        .collect();
+
+    if trimmed.starts_with('|') {
+        cells.remove(0);
+    }
+    if trimmed.ends_with('|') && !trimmed.ends_with("\\|") && !cells.is_empty() {
+        cells.pop();
+    }
+
+    cells
+}
+
+fn column_at(line: &str, offset: usize) -> usize {
+    let leading_pipe = line.trim_start().starts_with('|');
+    let pipes_before = cell_separators(line)
+        .into_iter()
+        .filter(|&index| index < offset)
+        .count();
+
+    if leading_pipe {
+        pipes_before.saturating_sub(1)
+    } else {
+        pipes_before
+    }
+}
+
+fn render_row(cells: &[String]) -> String {
+    let mut row = String::from("|");
+    for cell in cells {
+        row.push(' ');
+        row.push_str(cell);
+        row.push_str(" |");
+    }
+    row
+}
+
+/// Byte range of the cell content inside a formatted table.
+/// Empty cells collapse to a caret right after the opening pipe and its space.
+fn cell_range(table_text: &str, cell: Cell) -> (usize, usize) {
+    let line_index = if cell.row == 0 { 0 } else { cell.row + 1 };
+
+    let mut line_start = 0;
+    for line in table_text.split('\n').take(line_index) {
+        line_start += line.len() + 1;
+    }
+    let line = &table_text[line_start..line_end_at(table_text, line_start)];
+
+    let separators = cell_separators(line);
+    let (Some(&open), Some(&close)) =
+        (separators.get(cell.column), separators.get(cell.column + 1))
+    else {
+        return (line_start, line_start);
+    };
+
+    let content = &line[open + 1..close];
+    let trimmed = content.trim();
+
+    if trimmed.is_empty() {
+        let caret = line_start + (open + 2).min(close);
+        return (caret, caret);
+    }
+
+    let content_start = line_start + open + 1 + content.find(trimmed).unwrap_or(0);
+    (content_start, content_start + trimmed.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::markdown_formatter::format::SelectionFormatter;
+
+    const TABLE: &str = "| A   | B   |\n| --- | --- |\n| 1   | 2   |\n| 3   | 4   |";
+
+    fn apply(text: &str, caret: usize, operation: TableOperation) -> (String, u32, u32) {
+        let selection = Selection::new_with_caret_position(String::from(text), caret);
+        TableEditor::new(&selection, operation).format()
+    }
+
+    #[test]
+    fn test_parse_table_with_alignment() {
+        let table = MarkdownTable::parse("| A | B | C |\n|:--|:-:|--:|\n| 1 | 2 |").unwrap();
+
+        assert_eq!(
+            table.alignments,
+            vec![Alignment::Left, Alignment::Center, Alignment::Right]
+        );
+        assert_eq!(table.rows, vec![vec!["1", "2", ""]]);
+    }
+
+    #[test]
+    fn test_parse_table_with_escaped_pipe_and_code() {
+        let table = MarkdownTable::parse("| a \\| b | `c | d` |\n| - | - |").unwrap();
+
+        assert_eq!(table.header, vec!["a \\| b", "`c | d`"]);
+    }
+
+    #[test]
+    fn test_parse_rejects_text_without_divider() {
+        assert!(MarkdownTable::parse("| A | B |\n| 1 | 2 |").is_none());
+    }
+
+    #[test]
+    fn test_unchanged_outside_table() {
+        let text = "Just text";
+
+        let (new_text, start, end) = apply(text, 4, TableOperation::InsertRowBelow);
+
+        assert_eq!(new_text, text);
+        assert_eq!((start, end), (4, 4));
+    }
+
+    #[test]
+    fn test_insert_row_below() {
+        let (new_text, start, end) = apply(TABLE, 30, TableOperation::InsertRowBelow);
+
+        assert_eq!(
+            new_text,
+            "| A   | B   |\n| --- | --- |\n| 1   | 2   |\n|     |     |\n| 3   | 4   |"
+        );
+        assert_eq!((start, end), (44, 44));
+    }
+
+    #[test]
+    fn test_insert_row_above() {
+        let (new_text, start, end) = apply(TABLE, 30, TableOperation::InsertRowAbove);
+
+        assert_eq!(
+            new_text,
+            "| A   | B   |\n| --- | --- |\n|     |     |\n| 1   | 2   |\n| 3   | 4   |"
+        );
+        assert_eq!((start, end), (30, 30));
+    }
+
+    #[test]
+    fn test_insert_row_above_header_inserts_first_body_row() {
+        let (new_text, _, _) = apply(TABLE, 2, TableOperation::InsertRowAbove);
+
+        assert_eq!(
+            new_text,
+            "| A   | B   |\n| --- | --- |\n|     |     |\n| 1   | 2   |\n| 3   | 4   |"
+        );
+    }
+
+    #[test]
+    fn test_delete_row() {
+        let (new_text, start, end) = apply(TABLE, 30, TableOperation::DeleteRow);
+
+        assert_eq!(new_text, "| A   | B   |\n| --- | --- |\n| 3   | 4   |");
+        assert_eq!((start, end), (30, 31));
+    }
+
+    #[test]
+    fn test_delete_header_row_is_ignored() {
+        let (new_text, _, _) = apply(TABLE, 2, TableOperation::DeleteRow);
+
+        assert_eq!(new_text, TABLE);
+    }
+
+    #[test]
+    fn test_insert_column_right() {
+        let (new_text, start, end) = apply(TABLE, 2, TableOperation::InsertColumnRight);
+
+        assert_eq!(
+            new_text,
+            "| A   |     | B   |\n| --- | --- | --- |\n| 1   |     | 2   |\n| 3   |     | 4   |"
+        );
+        assert_eq!((start, end), (8, 8));
+    }
+
+    #[test]
+    fn test_insert_column_left() {
+        let (new_text, start, end) = apply(TABLE, 8, TableOperation::InsertColumnLeft);
+
+        assert_eq!(
+            new_text,
+            "| A   |     | B   |\n| --- | --- | --- |\n| 1   |     | 2   |\n| 3   |     | 4   |"
+        );
+        assert_eq!((start, end), (8, 8));
+    }
+
+    #[test]
+    fn test_delete_column() {
+        let (new_text, start, end) = apply(TABLE, 8, TableOperation::DeleteColumn);
+
+        assert_eq!(new_text, "| A   |\n| --- |\n| 1   |\n| 3   |");
+        assert_eq!((start, end), (2, 3));
+    }
+
+    #[test]
+    fn test_delete_last_column_is_ignored() {
+        let text = "| A |\n| - |";
+
+        let (new_text, _, _) = apply(text, 2, TableOperation::DeleteColumn);
+
+        assert_eq!(new_text, text);
+    }
+
+    #[test]
+    fn test_move_row_down() {
+        let (new_text, start, end) = apply(TABLE, 30, TableOperation::MoveRowDown);
+
+        assert_eq!(
+            new_text,
+            "| A   | B   |\n| --- | --- |\n| 3   | 4   |\n| 1   | 2   |"
+        );
+        assert_eq!((start, end), (44, 45));
+    }
+
+    #[test]
+    fn test_move_row_up() {
+        let (new_text, start, end) = apply(TABLE, 44, TableOperation::MoveRowUp);
+
+        assert_eq!(
+            new_text,
+            "| A   | B   |\n| --- | --- |\n| 3   | 4   |\n| 1   | 2   |"
+        );
+        assert_eq!((start, end), (30, 31));
+    }
+
+    #[test]
+    fn test_move_first_row_up_is_ignored() {
+        let (new_text, _, _) = apply(TABLE, 30, TableOperation::MoveRowUp);
+
+        assert_eq!(new_text, TABLE);
+    }
+
+    #[test]
+    fn test_move_column_right() {
+        let (new_text, start, end) = apply(TABLE, 2, TableOperation::MoveColumnRight);
+
+        assert_eq!(
+            new_text,
+            "| B   | A   |\n| --- | --- |\n| 2   | 1   |\n| 4   | 3   |"
+        );
+        assert_eq!((start, end), (8, 9));
+    }
+
+    #[test]
+    fn test_move_column_left() {
+        let (new_text, start, end) = apply(TABLE, 8, TableOperation::MoveColumnLeft);
+
+        assert_eq!(
+            new_text,
+            "| B   | A   |\n| --- | --- |\n| 2   | 1   |\n| 4   | 3   |"
+        );
+        assert_eq!((start, end), (2, 3));
+    }
+
+    #[test]
+    fn test_align_column_center() {
+        let (new_text, start, end) = apply(TABLE, 8, TableOperation::Align(Alignment::Center));
+
+        assert_eq!(
+            new_text,
+            "| A   |   B   |\n| --- | :---: |\n| 1   |   2   |\n| 3   |   4   |"
+        );
+        assert_eq!((start, end), (10, 11));
+    }
+
+    #[test]
+    fn test_align_column_right_from_body_row() {
+        let (new_text, _, _) = apply(TABLE, 30, TableOperation::Align(Alignment::Right));
+
+        assert_eq!(
+            new_text,
+            "|    A | B   |\n| ---: | --- |\n|    1 | 2   |\n|    3 | 4   |"
+        );
+    }
+
+    #[test]
+    fn test_next_cell() {
+        let (new_text, start, end) = apply(TABLE, 2, TableOperation::NextCell);
+
+        assert_eq!(new_text, TABLE);
+        assert_eq!((start, end), (8, 9));
+    }
+
+    #[test]
+    fn test_next_cell_wraps_to_next_row() {
+        let (new_text, start, end) = apply(TABLE, 8, TableOperation::NextCell);
+
+        assert_eq!(new_text, TABLE);
+        assert_eq!((start, end), (30, 31));
+    }
+
+    #[test]
+    fn test_next_cell_in_last_cell_appends_row() {
+        let (new_text, start, end) = apply(TABLE, 50, TableOperation::NextCell);
+
+        assert_eq!(new_text, format!("{TABLE}\n|     |     |"));
+        assert_eq!((start, end), (58, 58));
+    }
+
+    #[test]
+    fn test_previous_cell_wraps_to_previous_row() {
+        let (new_text, start, end) = apply(TABLE, 44, TableOperation::PreviousCell);
+
+        assert_eq!(new_text, TABLE);
+        assert_eq!((start, end), (36, 37));
+    }
+
+    #[test]
+    fn test_previous_cell_from_divider_moves_to_header() {
+        let (_, start, end) = apply(TABLE, 22, TableOperation::PreviousCell);
+
+        assert_eq!((start, end), (2, 3));
+    }
+
+    #[test]
+    fn test_new_row_adds_row_below() {
+        let (new_text, start, end) = apply(TABLE, 36, TableOperation::NewRow);
+
+        assert_eq!(
+            new_text,
+            "| A   | B   |\n| --- | --- |\n| 1   | 2   |\n|     |     |\n| 3   | 4   |"
+        );
+        assert_eq!((start, end), (44, 44));
+    }
+
+    #[test]
+    fn test_new_row_on_empty_last_row_leaves_table() {
+        let text = format!("{TABLE}\n|     |     |");
+
+        let (new_text, start, end) = apply(&text, 60, TableOperation::NewRow);
+
+        assert_eq!(new_text, format!("{TABLE}\n\n"));
+        assert_eq!((start, end), (57, 57));
+    }
+
+    #[test]
+    fn test_operation_keeps_surrounding_text() {
+        let text = format!("Intro\n\n{TABLE}\n\nOutro");
+
+        let (new_text, _, _) = apply(&text, 9, TableOperation::DeleteRow);
+
+        assert_eq!(new_text, text);
+
+        let (new_text, _, _) = apply(&text, 37, TableOperation::DeleteRow);
+
+        assert_eq!(
+            new_text,
+            "Intro\n\n| A   | B   |\n| --- | --- |\n| 3   | 4   |\n\nOutro"
+        );
+    }
+
+    #[test]
+    fn test_formats_messy_table() {
+        let text = "|Name|Age|\n|-|-|\n|Ada Lovelace|36|";
+
+        let (new_text, _, _) = apply(text, 1, TableOperation::Align(Alignment::Left));
+
+        assert_eq!(
+            new_text,
+            "| Name         | Age |\n| :----------- | --- |\n| Ada Lovelace | 36  |"
+        );
+    }
+
+    #[test]
+    fn test_table_with_multibyte_content() {
+        let text = "| Äpfel | Öl |\n| - | - |\n| 🍎 | 1 |";
+
+        let (new_text, _, _) = apply(text, 2, TableOperation::MoveColumnRight);
+
+        assert_eq!(
+            new_text,
+            "| Öl  | Äpfel |\n| --- | ----- |\n| 1   | 🍎    |"
+        );
+    }
+
+    #[test]
+    fn test_pipes_in_fenced_code_are_not_a_table() {
+        let text = "```\n| A | B |\n| - | - |\n| 1 | 2 |\n```";
+        let selection = Selection::new_with_caret_position(String::from(text), 6);
+
+        assert!(!TableEditor::is_in_table(&selection));
+        assert_eq!(apply(text, 6, TableOperation::DeleteColumn).0, text);
+    }
+
+    #[test]
+    fn test_table_stops_at_fenced_code() {
+        let text = "| A | B |\n| - | - |\n| 1 | 2 |\n```\n| x | y |\n```";
+
+        let (new_text, _, _) = apply(text, 2, TableOperation::DeleteColumn);
+
+        assert_eq!(new_text, "| B   |\n| --- |\n| 2   |\n```\n| x | y |\n```");
+    }
+
+    #[test]
+    fn test_align_fills_missing_alignments() {
+        let mut table = MarkdownTable::parse("| A | B |\n| - | - |").unwrap();
+        table.alignments.clear();
+
+        assert!(
+            table
+                .apply(TableOperation::Align(Alignment::Right), Cell::new(0, 1))
+                .is_some()
+        );
+        assert_eq!(table.alignments, vec![Alignment::None, Alignment::Right]);
+    }
+}
@@ -0,0 +1,83 @@
+use super::SelectionFormatter;
+use crate::api::{markdown_formatter::textarea::Selection, tasks::toggle_task_marker};
+
+/// Formatter that checks or unchecks the task list items of the selected lines.
+/// Lines without a task marker are left untouched.
+///
+/// ```rust,ignore
+/// use rustynote::api::markdown_formatter::format::{SelectionFormatter, TaskToggle};
+/// use rustynote::api::markdown_formatter::textarea::Selection;
+///
+/// let selection = Selection::new(String::from("- [ ] Milk"), 3, 3);
+///
+/// let (text, start, end) = TaskToggle::new(&selection).format();
+/// assert_eq!(text, "- [x] Milk");
+/// assert_eq!((start, end), (3, 3));
+/// ```
+pub struct TaskToggle<'a> {
+    selection: &'a Selection,
+}
+
+impl<'a> TaskToggle<'a> {
+    /// Creates a formatter that toggles the tasks on the selected lines.
+    pub fn new(selection: &'a Selection) -> Self {
+        Self { selection }
+    }
+
+    fn apply_task_toggle(&self) -> (String, u32, u32) {
+        let (start, end) = self.selection.line_bounds();
+
+        let toggled: Vec<String> = self
+            .selection
+            .selected_lines()
+            .into_iter()
+            .map(|line| toggle_task_marker(line).unwrap_or_else(|| line.to_string()))
+            .collect();
+
+        (
+            self.selection
+                .replace_range(start, end, &toggled.join("\n")),
+            self.selection.start_index as u32,
+            self.selection.end_index as u32,
+        )
+    }
+}
+
+impl<'a> SelectionFormatter for TaskToggle<'a> {
+    fn format(&self) -> (String, u32, u32) {
+        self.apply_task_toggle()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_task_under_caret() {
+        let selection = Selection::new(String::from("# Todo\n- [ ] Milk\n- [ ] Eggs"), 10, 10);
+
+        let (text, start, end) = TaskToggle::new(&selection).format();
+
+        assert_eq!(text, "# Todo\n- [x] Milk\n- [ ] Eggs");
+        assert_eq!((start, end), (10, 10));
+    }
+
+    #[test]
+    fn test_toggle_tasks_in_selection() {
+        let selection = Selection::new(String::from("- [x] Milk\ntext\n- [ ] Eggs"), 2, 20);
+
+        let (text, _, _) = TaskToggle::new(&selection).format();
+
+        assert_eq!(text, "- [ ] Milk\ntext\n- [x] Eggs");
+    }
+
+    #[test]
+    fn test_line_without_task_is_unchanged() {
+        let selection = Selection::new(String::from("- Milk"), 0, 0);
+
+        let (text, _, _) = TaskToggle::new(&selection).format();
+
+        assert_eq!(text, "- Milk");
+    }
+}
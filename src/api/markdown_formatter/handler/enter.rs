@@ -1,4 +1,7 @@
-use crate::api::markdown_formatter::textarea::{Selection, get_current_selection, set_cursor};
+use crate::api::markdown_formatter::{
+    format::{SelectionFormatter, TableEditor, TableOperation},
+    textarea::{Selection, get_current_selection, set_cursor},
+};
 
 /// Handles Enter key press for smart list continuation and formatting.
 ///
@@ -11,6 +14,7 @@ use crate::api::markdown_formatter::textarea::{Selection, get_current_selection,
 /// - List items: Continues the list with appropriate numbering/markers
 /// - Empty list items: Removes the list marker
 /// - Regular text: Inserts a newline
+/// - Table rows: Adds a new row below, or leaves the table on an empty last row
 ///
 /// # Examples
 ///
@@ -22,6 +26,12 @@ use crate::api::markdown_formatter::textarea::{Selection, get_current_selection,
 /// ```
 pub fn handle_enter_for_lists() -> String {
     let selection = get_current_selection();
+
+    if TableEditor::is_in_table(&selection) {
+        let (new_text, start, end) = TableEditor::new(&selection, TableOperation::NewRow).format();
+        return set_cursor(new_text, start, end);
+    }
+
     let (new_text, new_cursor_position) = handle_enter_with_selection(selection);
     set_cursor(
         new_text.clone(),
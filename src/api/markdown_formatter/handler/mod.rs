@@ -1,4 +1,7 @@
 mod enter;
+mod snippet;
 mod tab;
 
 pub use enter::handle_enter_for_lists;
+pub use snippet::handle_snippet;
+pub use tab::handle_tab;
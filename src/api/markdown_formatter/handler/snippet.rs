@@ -0,0 +1,31 @@
+use crate::api::{
+    markdown_formatter::textarea::{get_current_selection, set_cursor},
+    templates::{Placeholders, TemplateLibrary, expand_snippet},
+};
+
+/// Expands the snippet whose trigger, e.g. `/adr`, is right before the caret.
+///
+/// Returns `None` when there is no trigger, so Tab keeps its other uses.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use rustynote::api::{markdown_formatter::handle_snippet, templates::{Placeholders, TemplateLibrary}};
+///
+/// // Requires a browser environment with a textarea element.
+/// let expanded = handle_snippet(&TemplateLibrary::default(), &Placeholders::now("Notes"));
+/// ```
+pub fn handle_snippet(library: &TemplateLibrary, placeholders: &Placeholders) -> Option<String> {
+    let selection = get_current_selection();
+    if !selection.is_empty() {
+        return None;
+    }
+
+    let (new_text, caret) = expand_snippet(
+        &selection.textarea_value,
+        selection.start_index,
+        library,
+        placeholders,
+    )?;
+    Some(set_cursor(new_text, caret as u32, caret as u32))
+}
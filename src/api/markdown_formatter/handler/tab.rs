@@ -1 +1,36 @@
+use crate::api::markdown_formatter::{
+    format::{SelectionFormatter, TableEditor, TableOperation},
+    textarea::{get_current_selection, set_cursor},
+};
 
+/// Handles Tab and Shift+Tab key presses inside the textarea.
+///
+/// Inside a GFM table the caret jumps to the next or previous cell. Returns `None`
+/// when the key press should keep its default browser behavior.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use rustynote::api::markdown_formatter::handle_tab;
+///
+/// // Requires a browser environment with a textarea element.
+/// if let Some(markdown) = handle_tab(false) {
+///     println!("{markdown}");
+/// }
+/// ```
+pub fn handle_tab(shift: bool) -> Option<String> {
+    let selection = get_current_selection();
+
+    if !TableEditor::is_in_table(&selection) {
+        return None;
+    }
+
+    let operation = if shift {
+        TableOperation::PreviousCell
+    } else {
+        TableOperation::NextCell
+    };
+
+    let (new_text, start, end) = TableEditor::new(&selection, operation).format();
+    Some(set_cursor(new_text, start, end))
+}
@@ -1,10 +1,159 @@
 pub mod format;
 mod handler;
 mod shortcuts;
+mod slash;
 mod textarea;
 
-pub use handler::handle_enter_for_lists;
-pub use shortcuts::setup_shortcuts;
+pub use handler::{handle_enter_for_lists, handle_snippet, handle_tab};
+pub use shortcuts::{Shortcut, setup_shortcuts};
+pub use slash::{
+    Block, SLASH_COMMANDS, SlashCommand, SlashQuery, filter_slash_commands, run_slash_command,
+    slash_query,
+};
+
+use crate::api::{
+    completion::{Completion, CompletionQuery, apply_completion, completion_query},
+    parser::Dialect,
+    statistics::{CaretPosition, Statistics},
+    wikilinks::{complete_link, link_query},
+};
+use format::{LocatedTable, MarkdownTable};
+use textarea::{
+    byte_to_char_pos, caret_offset, find_textarea, get_current_selection, line_start_at, set_cursor,
+};
+
+/// Replaces the current textarea selection with `content` and places the caret after it.
+///
+/// ```rust,ignore
+/// use rustynote::api::markdown_formatter::insert_at_cursor;
+///
+/// // Requires a browser environment with a textarea element.
+/// let markdown = insert_at_cursor("![image](attachment:ab12)");
+/// ```
+pub fn insert_at_cursor(content: &str) -> String {
+    let selection = get_current_selection();
+    let new_text = selection.replace_range(selection.start_index, selection.end_index, content);
+    let caret = (selection.start_index + content.len()) as u32;
+
+    set_cursor(new_text, caret, caret)
+}
+
+/// Shows `text` in the editor with the caret at the byte position `caret`. Does nothing
+/// while the editor is hidden.
+pub fn place_caret(text: &str, caret: usize) {
+    if find_textarea().is_some() {
+        set_cursor(text.to_string(), caret as u32, caret as u32);
+    }
+}
+
+/// Returns the byte position where the current selection starts.
+pub fn selection_start() -> usize {
+    get_current_selection().start_index
+}
+
+/// Returns the caret position and, when text is selected, the statistics of the selection
+/// in `dialect`.
+pub fn selection_statistics(dialect: &Dialect) -> (CaretPosition, Option<Statistics>) {
+    let selection = get_current_selection();
+    let text = &selection.textarea_value;
+    let line_start = line_start_at(text, selection.end_index);
+
+    let caret = CaretPosition {
+        line: selection.line_index_of(selection.end_index) + 1,
+        column: byte_to_char_pos(&text[line_start..], selection.end_index - line_start) + 1,
+    };
+
+    (
+        caret,
+        selection
+            .selected_text
+            .as_deref()
+            .map(|text| Statistics::of(text, dialect)),
+    )
+}
+
+/// Returns the table under the caret, if there is one.
+pub fn table_under_cursor() -> Option<MarkdownTable> {
+    let selection = get_current_selection();
+    LocatedTable::at(&selection.textarea_value, selection.start_index).map(|located| located.table)
+}
+
+/// Replaces delimiter-separated data pasted at `start` with the equivalent markdown table.
+/// Returns `None` when the pasted text was edited in the meantime or isn't tabular.
+pub fn convert_pasted_table(start: usize, pasted: &str) -> Option<String> {
+    let selection = get_current_selection();
+    let pasted = pasted.replace("\r\n", "\n");
+    let end = start + pasted.len();
+
+    if selection.textarea_value.get(start..end) != Some(pasted.as_str()) {
+        return None;
+    }
+
+    let table = MarkdownTable::from_delimited(&pasted)?.to_markdown();
+    let new_text = selection.replace_range(start, end, &table);
+    let caret = (start + table.len()) as u32;
+
+    Some(set_cursor(new_text, caret, caret))
+}
+
+/// Returns the part of a `[[link]]` typed before the caret, if the caret is in one.
+pub fn wikilink_query() -> Option<String> {
+    let selection = get_current_selection();
+    if !selection.is_empty() {
+        return None;
+    }
+    link_query(selection.before()).map(String::from)
+}
+
+/// Completes the `[[link]]` at the caret with a note title.
+pub fn complete_wikilink(title: &str) -> Option<String> {
+    let selection = get_current_selection();
+    let (new_text, caret) = complete_link(&selection.textarea_value, selection.start_index, title)?;
+
+    Some(set_cursor(new_text, caret as u32, caret as u32))
+}
+
+/// Returns the `/command` typed at the caret, if there is one.
+pub fn slash_query_at_caret() -> Option<SlashQuery> {
+    let selection = get_current_selection();
+    if !selection.is_empty() {
+        return None;
+    }
+    slash_query(&selection.textarea_value, selection.start_index)
+}
+
+/// Returns the word at the caret that can be completed, if there is one.
+pub fn completion_query_at_caret() -> Option<CompletionQuery> {
+    let selection = get_current_selection();
+    if !selection.is_empty() {
+        return None;
+    }
+    completion_query(&selection.textarea_value, selection.start_index)
+}
+
+/// Replaces the word at the caret with `completion`.
+pub fn complete_at_caret(completion: &Completion) -> Option<String> {
+    let selection = get_current_selection();
+    let query = completion_query(&selection.textarea_value, selection.start_index)?;
+    let (new_text, caret) = apply_completion(&selection.textarea_value, &query, completion);
+
+    Some(set_cursor(new_text, caret as u32, caret as u32))
+}
+
+/// Returns the position right below the caret relative to the offset parent of the
+/// editor, to show menus at the caret.
+pub fn caret_position() -> Option<(f64, f64)> {
+    caret_offset()
+}
+
+/// Replaces the `/command` at the caret with the block of `command`.
+pub fn apply_slash_command(command: Block) -> Option<String> {
+    let selection = get_current_selection();
+    let query = slash_query(&selection.textarea_value, selection.start_index)?;
+    let (new_text, start, end) = run_slash_command(&selection.textarea_value, &query, command);
+
+    Some(set_cursor(new_text, start, end))
+}
 
 pub(crate) fn combine_text_slices(texts: Vec<&str>, capacity: usize) -> String {
     let mut result = String::with_capacity(capacity);
@@ -1,77 +1,55 @@
+use std::fmt;
+
 use leptos::{
     ev::keydown,
     prelude::{RwSignal, Set},
     *,
 };
 use leptos_use::{use_document, use_event_listener};
-use web_sys::KeyboardEvent;
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, KeyboardEvent};
 
-use crate::api::markdown_formatter::format::TextFormattingType;
+use crate::api::markdown_formatter::{format::TextFormattingType, textarea::find_textarea};
 
 /// Sets up keyboard shortcuts for markdown formatting.
 ///
-/// This function registers global keyboard event listeners that apply markdown
-/// formatting when the corresponding key combinations are pressed.
-///
-/// # Shortcuts
-///
-/// - `Ctrl+B`: Bold text (`**text**`)
-/// - `Ctrl+H`: Heading (cycles through heading levels)
-/// - `Ctrl+#`: Code block (```)
-/// - `Ctrl+M`: Monospace/inline code (`` `text` ``)
+/// This function registers a global keyboard event listener that applies the
+/// formatting bound to a key combination when it is pressed. The bindings come
+/// from the toolbar action registry, so buttons and shortcuts never disagree.
+/// Keys pressed outside the editor textarea are left to the browser, e.g. `Ctrl + 1`
+/// still switches tabs while the preview has focus.
 ///
 /// # Examples
 ///
 /// ```rust,ignore
-/// use markdown::api::markdown_formatter::setup_shortcuts;
+/// use rustynote::api::markdown_formatter::{Shortcut, format::TextFormattingType, setup_shortcuts};
 /// use leptos::prelude::*;
 ///
 /// // Requires a Leptos context and browser environment.
 /// let markdown = RwSignal::new(String::new());
-/// setup_shortcuts(markdown);
+/// let bold = TextFormattingType::Inline { prefix: "**", suffix: "**" };
+/// setup_shortcuts(markdown, vec![(Shortcut::ctrl("b"), bold)]);
 /// ```
-pub fn setup_shortcuts(markdown: RwSignal<String>) {
+pub fn setup_shortcuts(markdown: RwSignal<String>, bindings: Vec<(Shortcut, TextFormattingType)>) {
     let _ = use_event_listener(use_document(), keydown, move |ev: KeyboardEvent| {
-        for (shortcut, action) in SHORTCUTS {
-            if shortcut.matches(&ev) {
-                ev.prevent_default();
-                match action {
-                    ShortcutKey::Bold => markdown.set(
-                        TextFormattingType::Inline {
-                            prefix: "**",
-                            suffix: "**",
-                        }
-                        .apply_text_formatting(),
-                    ),
-                    ShortcutKey::Heading => {
-                        markdown.set(TextFormattingType::Heading.apply_text_formatting())
-                    }
-                    ShortcutKey::CodeBlock => {
-                        markdown.set(TextFormattingType::CodeBlock.apply_text_formatting())
-                    }
-                    ShortcutKey::Monospace => markdown.set(
-                        TextFormattingType::Inline {
-                            prefix: "`",
-                            suffix: "`",
-                        }
-                        .apply_text_formatting(),
-                    ),
-                }
-                break;
-            }
+        let in_editor = find_textarea().is_some_and(|textarea| {
+            ev.target()
+                .is_some_and(|target| target == *textarea.unchecked_ref::<EventTarget>())
+        });
+        if !in_editor {
+            return;
         }
-    });
-}
 
-enum ShortcutKey {
-    Bold,
-    Heading,
-    CodeBlock,
-    Monospace,
+        if let Some((_, formatting)) = bindings.iter().find(|(shortcut, _)| shortcut.matches(&ev)) {
+            ev.prevent_default();
+            markdown.set(formatting.apply_text_formatting());
+        }
+    });
 }
 
-#[derive(Clone)]
-struct Shortcut {
+/// A key combination that triggers a formatting action.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shortcut {
     key: &'static str,
     ctrl: bool,
     alt: bool,
@@ -79,7 +57,7 @@ struct Shortcut {
 }
 
 impl Shortcut {
-    const fn new(key: &'static str, ctrl: bool, alt: bool, shift: bool) -> Self {
+    pub const fn new(key: &'static str, ctrl: bool, alt: bool, shift: bool) -> Self {
         Self {
             key,
             ctrl,
@@ -88,6 +66,11 @@ impl Shortcut {
         }
     }
 
+    /// Shortcut for `Ctrl` plus the given key.
+    pub const fn ctrl(key: &'static str) -> Self {
+        Self::new(key, true, false, false)
+    }
+
     fn matches(&self, event: &KeyboardEvent) -> bool {
         event.key() == self.key
             && event.ctrl_key() == self.ctrl
@@ -96,18 +79,36 @@ impl Shortcut {
     }
 }
 
-const SHORTCUTS: &[(&Shortcut, ShortcutKey)] = &[
-    (&Shortcut::new("b", true, false, false), ShortcutKey::Bold),
-    (
-        &Shortcut::new("h", true, false, false),
-        ShortcutKey::Heading,
-    ),
-    (
-        &Shortcut::new("#", true, false, false),
-        ShortcutKey::CodeBlock,
-    ),
-    (
-        &Shortcut::new("m", true, false, false),
-        ShortcutKey::Monospace,
-    ),
-];
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifiers = [
+            (self.ctrl, "Ctrl"),
+            (self.alt, "Alt"),
+            (self.shift, "Shift"),
+        ];
+        for (_, name) in modifiers.iter().filter(|(active, _)| *active) {
+            write!(f, "{name} + ")?;
+        }
+
+        if self.key.chars().count() == 1 {
+            write!(f, "{}", self.key.to_uppercase())
+        } else {
+            write!(f, "{}", self.key)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_shortcut() {
+        assert_eq!(Shortcut::ctrl("b").to_string(), "Ctrl + B");
+        assert_eq!(Shortcut::ctrl("Enter").to_string(), "Ctrl + Enter");
+        assert_eq!(
+            Shortcut::new("ArrowUp", false, true, true).to_string(),
+            "Alt + Shift + ArrowUp"
+        );
+    }
+}
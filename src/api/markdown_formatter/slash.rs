@@ -0,0 +1,318 @@
+use crate::api::{
+    markdown_formatter::{
+        format::{
+            BlockPrefix, CodeBlock, HeadingEditor, HeadingOperation, HorizontalRule, OrderedList,
+            SelectionFormatter, Table,
+        },
+        textarea::{Selection, line_end_at, line_start_at},
+    },
+    toc::table_of_contents,
+};
+
+/// Block that a slash command inserts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Block {
+    Heading(u8),
+    BulletList,
+    OrderedList,
+    Checkbox,
+    Quote,
+    CodeBlock,
+    Table,
+    HorizontalRule,
+    Math,
+    TableOfContents,
+}
+
+/// An entry of the slash command menu.
+#[derive(Debug, PartialEq)]
+pub struct SlashCommand {
+    pub label: &'static str,
+    /// Further words the command is found by.
+    pub keywords: &'static [&'static str],
+    pub block: Block,
+}
+
+pub const SLASH_COMMANDS: &[SlashCommand] = &[
+    SlashCommand {
+        label: "Heading 1",
+        keywords: &["h1", "title"],
+        block: Block::Heading(1),
+    },
+    SlashCommand {
+        label: "Heading 2",
+        keywords: &["h2", "subtitle"],
+        block: Block::Heading(2),
+    },
+    SlashCommand {
+        label: "Heading 3",
+        keywords: &["h3"],
+        block: Block::Heading(3),
+    },
+    SlashCommand {
+        label: "Heading 4",
+        keywords: &["h4"],
+        block: Block::Heading(4),
+    },
+    SlashCommand {
+        label: "Heading 5",
+        keywords: &["h5"],
+        block: Block::Heading(5),
+    },
+    SlashCommand {
+        label: "Bulleted List",
+        keywords: &["ul", "unordered"],
+        block: Block::BulletList,
+    },
+    SlashCommand {
+        label: "Numbered List",
+        keywords: &["ol", "ordered"],
+        block: Block::OrderedList,
+    },
+    SlashCommand {
+        label: "Checkbox",
+        keywords: &["todo", "task"],
+        block: Block::Checkbox,
+    },
+    SlashCommand {
+        label: "Quote",
+        keywords: &["blockquote"],
+        block: Block::Quote,
+    },
+    SlashCommand {
+        label: "Code Block",
+        keywords: &["fence", "pre"],
+        block: Block::CodeBlock,
+    },
+    SlashCommand {
+        label: "Table",
+        keywords: &["grid"],
+        block: Block::Table,
+    },
+    SlashCommand {
+        label: "Horizontal Rule",
+        keywords: &["hr", "divider", "line", "separator"],
+        block: Block::HorizontalRule,
+    },
+    SlashCommand {
+        label: "Math Block",
+        keywords: &["equation", "formula", "latex"],
+        block: Block::Math,
+    },
+    SlashCommand {
+        label: "Table of Contents",
+        keywords: &["toc", "outline"],
+        block: Block::TableOfContents,
+    },
+];
+
+/// A `/command` typed alone on a line, e.g. `/code rust`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlashQuery {
+    /// Byte position of the `/`.
+    pub start: usize,
+    /// Byte position of the caret, where the query ends.
+    pub end: usize,
+    /// The word after the `/` that filters the commands.
+    pub filter: String,
+    /// An optional word after the filter, the language of a code block.
+    pub argument: String,
+}
+
+/// Finds the `/command` that is typed before the caret. The `/` has to start the line
+/// (after indentation) and nothing may follow the caret on that line.
+///
+/// ```rust
+/// use rustynote::api::markdown_formatter::slash_query;
+///
+/// let query = slash_query("Intro\n/code rust", 16).unwrap();
+/// assert_eq!((query.filter.as_str(), query.argument.as_str()), ("code", "rust"));
+/// assert_eq!(slash_query("a /code", 7), None);
+/// ```
+pub fn slash_query(text: &str, caret: usize) -> Option<SlashQuery> {
+    let line_start = line_start_at(text, caret);
+    if !text[caret..line_end_at(text, caret)].trim().is_empty() {
+        return None;
+    }
+
+    let typed = &text[line_start..caret];
+    let indentation = typed.len() - typed.trim_start().len();
+    let query = typed[indentation..].strip_prefix('/')?;
+
+    let (filter, argument) = query.split_once(' ').unwrap_or((query, ""));
+    let is_word = |word: &str| {
+        word.chars()
+            .all(|c| c.is_alphanumeric() || "-_+#".contains(c))
+    };
+    if !is_word(filter) || !is_word(argument) {
+        return None;
+    }
+
+    Some(SlashQuery {
+        start: line_start + indentation,
+        end: caret,
+        filter: filter.to_lowercase(),
+        argument: argument.to_string(),
+    })
+}
+
+/// The commands that match the typed filter, commands whose label matches come first.
+///
+/// ```rust
+/// use rustynote::api::markdown_formatter::filter_slash_commands;
+///
+/// let labels: Vec<&str> = filter_slash_commands("ta").iter().map(|command| command.label).collect();
+/// assert_eq!(labels, ["Table", "Table of Contents", "Checkbox"]);
+/// ```
+pub fn filter_slash_commands(filter: &str) -> Vec<&'static SlashCommand> {
+    let filter = filter.to_lowercase();
+    let label_matches = |command: &SlashCommand| {
+        command
+            .label
+            .to_lowercase()
+            .split(' ')
+            .any(|word| word.starts_with(&filter))
+    };
+    let keyword_matches = |command: &SlashCommand| {
+        command
+            .keywords
+            .iter()
+            .any(|keyword| keyword.starts_with(&filter))
+    };
+
+    let mut commands: Vec<&SlashCommand> = SLASH_COMMANDS
+        .iter()
+        .filter(|command| label_matches(command))
+        .collect();
+    commands.extend(
+        SLASH_COMMANDS
+            .iter()
+            .filter(|command| !label_matches(command) && keyword_matches(command)),
+    );
+    commands
+}
+
+/// Replaces the typed `/command` with the block of `command`.
+/// Returns the new text and the selection, like the formatters.
+pub fn run_slash_command(text: &str, query: &SlashQuery, command: Block) -> (String, u32, u32) {
+    let mut text = text.to_string();
+    text.replace_range(query.start..query.end, "");
+    let selection = Selection::new_with_caret_position(text, query.start);
+
+    match command {
+        Block::Heading(level) => {
+            HeadingEditor::new(&selection, HeadingOperation::SetLevel(level)).format()
+        }
+        Block::BulletList => BlockPrefix::new(&selection, "- ").format(),
+        Block::Checkbox => BlockPrefix::new(&selection, "- [ ] ").format(),
+        Block::Quote => BlockPrefix::new(&selection, "> ").format(),
+        Block::OrderedList => OrderedList::new(&selection).format(),
+        Block::Table => Table::new(&selection).format(),
+        Block::HorizontalRule => HorizontalRule::new(&selection).format(),
+        Block::CodeBlock => {
+            let (mut text, start, end) = CodeBlock::new(&selection).format();
+            let Some(fence) = text[..start as usize].rfind("```") else {
+                return (text, start, end);
+            };
+            text.insert_str(fence + 3, &query.argument);
+            let shift = query.argument.len() as u32;
+            (text, start + shift, end + shift)
+        }
+        Block::Math => insert_block(&selection, "$$\n", "\n$$"),
+        Block::TableOfContents => insert_block(
+            &selection,
+            &table_of_contents(&selection.textarea_value),
+            "",
+        ),
+    }
+}
+
+/// Inserts `before` and `after` at the caret and places the caret between them.
+fn insert_block(selection: &Selection, before: &str, after: &str) -> (String, u32, u32) {
+    let caret = selection.start_index;
+    let text = selection.replace_range(caret, caret, &format!("{before}{after}"));
+    let caret = (caret + before.len()) as u32;
+    (text, caret, caret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(text: &str, command: Block) -> (String, u32) {
+        let caret = line_end_at(text, text.rfind('/').expect("slash"));
+        let query = slash_query(text, caret).expect("slash query");
+        let (text, start, end) = run_slash_command(text, &query, command);
+        assert_eq!(start, end);
+        (text, start)
+    }
+
+    #[test]
+    fn test_slash_query_keeps_indentation() {
+        let query = slash_query("- item\n  /che", 13).unwrap();
+        assert_eq!(query.start, 9);
+        assert_eq!(query.filter, "che");
+        assert_eq!(query.argument, "");
+    }
+
+    #[test]
+    fn test_slash_query_rejects_paths_and_text_after_caret() {
+        assert_eq!(slash_query("/usr/bin", 8), None);
+        assert_eq!(slash_query("/code rust now", 14), None);
+        assert_eq!(slash_query("/code more", 5), None);
+        assert!(slash_query("/", 1).is_some());
+    }
+
+    #[test]
+    fn test_empty_filter_lists_every_command() {
+        assert_eq!(filter_slash_commands("").len(), SLASH_COMMANDS.len());
+        assert!(filter_slash_commands("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_keywords_find_commands() {
+        let blocks: Vec<Block> = filter_slash_commands("h2")
+            .iter()
+            .map(|command| command.block)
+            .collect();
+        assert_eq!(blocks, [Block::Heading(2)]);
+    }
+
+    #[test]
+    fn test_heading_levels() {
+        assert_eq!(
+            run("Intro\n/h1", Block::Heading(1)),
+            ("Intro\n# ".into(), 8)
+        );
+        assert_eq!(run("/h3", Block::Heading(3)), ("### ".into(), 4));
+        assert_eq!(
+            run("Intro\n/h6", Block::Heading(6)),
+            ("Intro\n###### ".into(), 13)
+        );
+    }
+
+    #[test]
+    fn test_line_prefixes() {
+        assert_eq!(run("/todo", Block::Checkbox), ("- [ ] ".into(), 6));
+        assert_eq!(run("/quote", Block::Quote), ("> ".into(), 2));
+        assert_eq!(run("/ul", Block::BulletList), ("- ".into(), 2));
+    }
+
+    #[test]
+    fn test_code_block_with_language() {
+        let (text, caret) = run("Intro\n\n/code rust", Block::CodeBlock);
+        assert_eq!(text, "Intro\n\n```rust\n\n```\n");
+        assert_eq!(&text[caret as usize..], "\n```\n");
+    }
+
+    #[test]
+    fn test_math_block() {
+        assert_eq!(run("/math", Block::Math), ("$$\n\n$$".into(), 3));
+    }
+
+    #[test]
+    fn test_table_of_contents() {
+        let (text, _) = run("/toc\n# One\n## Two", Block::TableOfContents);
+        assert_eq!(text, "- [One](#one)\n  - [Two](#two)\n\n# One\n## Two");
+    }
+}
@@ -1,14 +1,96 @@
 use wasm_bindgen::JsCast;
-use web_sys::HtmlTextAreaElement;
+use web_sys::{HtmlElement, HtmlTextAreaElement};
 
 use crate::api::markdown_formatter::combine_text_slices;
 
 fn get_textarea() -> HtmlTextAreaElement {
+    find_textarea().expect("Couldn't get textarea")
+}
+
+/// The editor textarea, `None` while only the preview is shown.
+pub(crate) fn find_textarea() -> Option<HtmlTextAreaElement> {
     web_sys::window()
         .and_then(|win| win.document())
         .and_then(|doc| doc.get_element_by_id("markdown-textarea"))
         .and_then(|el| el.dyn_into::<HtmlTextAreaElement>().ok())
-        .expect("Couldn't get textarea")
+}
+
+/// Styles that decide where the text of the textarea wraps.
+const MIRRORED_STYLES: &[&str] = &[
+    "box-sizing",
+    "width",
+    "font-family",
+    "font-size",
+    "font-style",
+    "font-weight",
+    "letter-spacing",
+    "line-height",
+    "tab-size",
+    "text-indent",
+    "word-spacing",
+    "padding-top",
+    "padding-right",
+    "padding-bottom",
+    "padding-left",
+    "border-top-width",
+    "border-right-width",
+    "border-bottom-width",
+    "border-left-width",
+];
+
+/// Position right below the caret, relative to the offset parent of the textarea, in pixels.
+///
+/// Textareas don't expose the caret position, so the text before the caret is laid out
+/// in a hidden copy that has the same styles.
+pub(crate) fn caret_offset() -> Option<(f64, f64)> {
+    let textarea = find_textarea()?;
+    let window = web_sys::window()?;
+    let document = window.document()?;
+    let style = window.get_computed_style(&textarea).ok()??;
+
+    let mirror = document
+        .create_element("div")
+        .ok()?
+        .dyn_into::<HtmlElement>()
+        .ok()?;
+    let mirror_style = mirror.style();
+    for property in MIRRORED_STYLES {
+        let value = style.get_property_value(property).ok()?;
+        mirror_style.set_property(property, &value).ok()?;
+    }
+    for (property, value) in [
+        ("position", "absolute"),
+        ("visibility", "hidden"),
+        ("overflow", "hidden"),
+        ("top", "0"),
+        ("left", "0"),
+        ("white-space", "pre-wrap"),
+        ("overflow-wrap", "break-word"),
+    ] {
+        mirror_style.set_property(property, value).ok()?;
+    }
+
+    let value = textarea.value();
+    let caret = textarea.selection_start().ok().flatten().unwrap_or(0) as usize;
+    mirror.set_text_content(Some(&value[..char_to_byte_pos_safe(&value, caret)]));
+
+    let marker = document
+        .create_element("span")
+        .ok()?
+        .dyn_into::<HtmlElement>()
+        .ok()?;
+    marker.set_text_content(Some("\u{200b}"));
+    mirror.append_child(&marker).ok()?;
+    document.body()?.append_child(&mirror).ok()?;
+
+    let left = marker.offset_left() - textarea.scroll_left();
+    let top = marker.offset_top() + marker.offset_height() - textarea.scroll_top();
+    mirror.remove();
+
+    Some((
+        (textarea.offset_left() + left) as f64,
+        (textarea.offset_top() + top) as f64,
+    ))
 }
 
 /// Represents the current textarea selection including the full text and cursor indices.
@@ -58,7 +140,6 @@ impl Selection {
         }
     }
 
-    #[allow(dead_code)]
     pub(crate) fn new_with_caret_position(textarea_value: String, caret_position: usize) -> Self {
         let before_selection = textarea_value[..caret_position].to_string();
         let after_selection = textarea_value[caret_position..].to_string();
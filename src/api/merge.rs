@@ -0,0 +1,359 @@
+/// Marks the start of the editor's side of a conflict.
+pub const CONFLICT_MINE: &str = "<<<<<<< Editor";
+pub const CONFLICT_SEPARATOR: &str = "=======";
+/// Marks the end of the file's side of a conflict.
+pub const CONFLICT_THEIRS: &str = ">>>>>>> File";
+
+/// The result of a three-way merge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Merge {
+    pub text: String,
+    /// Number of places where both sides changed the same lines differently. They are
+    /// kept in the text between conflict markers.
+    pub conflicts: usize,
+}
+
+/// Merges the changes of `mine` and `theirs` to their common `base` line by line.
+///
+/// ```rust
+/// use rustynote::api::merge::merge;
+///
+/// let merged = merge("a\nb\nc\n", "A\nb\nc\n", "a\nb\nC\n");
+/// assert_eq!(merged.text, "A\nb\nC\n");
+/// assert_eq!(merged.conflicts, 0);
+/// ```
+pub fn merge(base: &str, mine: &str, theirs: &str) -> Merge {
+    let base = lines(base);
+    let mine = lines(mine);
+    let theirs = lines(theirs);
+
+    let to_mine = matching_lines(&base, &mine);
+    let to_theirs = matching_lines(&base, &theirs);
+
+    let mut merged = Vec::new();
+    let mut conflicts = 0;
+    let (mut base_start, mut mine_start, mut theirs_start) = (0, 0, 0);
+
+    // Lines of the base that are unchanged on both sides anchor the merge, the
+    // chunks between two anchors are merged as a whole.
+    let anchors = (0..base.len())
+        .filter_map(|index| Some((index, to_mine[index]?, to_theirs[index]?)))
+        .chain([(base.len(), mine.len(), theirs.len())]);
+
+    for (base_end, mine_end, theirs_end) in anchors {
+        let base_chunk = &base[base_start..base_end];
+        let mine_chunk = &mine[mine_start..mine_end];
+        let theirs_chunk = &theirs[theirs_start..theirs_end];
+
+        if mine_chunk == base_chunk || mine_chunk == theirs_chunk {
+            merged.extend_from_slice(theirs_chunk);
+        } else if theirs_chunk == base_chunk {
+            merged.extend_from_slice(mine_chunk);
+        } else {
+            conflicts += 1;
+            push_conflict(&mut merged, mine_chunk, theirs_chunk);
+        }
+
+        if base_end < base.len() {
+            merged.push(base[base_end]);
+        }
+        (base_start, mine_start, theirs_start) = (base_end + 1, mine_end + 1, theirs_end + 1);
+    }
+
+    Merge {
+        text: merged.concat(),
+        conflicts,
+    }
+}
+
+fn lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+fn push_conflict<'a>(merged: &mut Vec<&'a str>, mine: &[&'a str], theirs: &[&'a str]) {
+    // Markers have to start on a line of their own.
+    if merged.last().is_some_and(|line| !line.ends_with('\n')) {
+        merged.push("\n");
+    }
+
+    merged.push(CONFLICT_MINE);
+    merged.push("\n");
+    push_lines(merged, mine);
+    merged.push(CONFLICT_SEPARATOR);
+    merged.push("\n");
+    push_lines(merged, theirs);
+    merged.push(CONFLICT_THEIRS);
+    merged.push("\n");
+}
+
+fn push_lines<'a>(merged: &mut Vec<&'a str>, lines: &[&'a str]) {
+    merged.extend_from_slice(lines);
+    if lines.last().is_some_and(|line| !line.ends_with('\n')) {
+        merged.push("\n");
+    }
+}
+
+/// For every line of `base`, the index of the same line in `other` according to a
+/// shortest edit script, or `None` when it was changed or removed.
+///
+/// This is Myers' diff in linear space: common lines at both ends are matched first,
+/// the rest is split at the middle of its shortest edit path until nothing is left.
+/// Mostly identical notes, the usual case when merging, take about linear time.
+fn matching_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; base.len()];
+    match_range(base, other, 0, 0, &mut matches);
+    matches
+}
+
+fn match_range(
+    base: &[&str],
+    other: &[&str],
+    base_offset: usize,
+    other_offset: usize,
+    matches: &mut [Option<usize>],
+) {
+    let prefix = base
+        .iter()
+        .zip(other)
+        .take_while(|(base, other)| base == other)
+        .count();
+    for index in 0..prefix {
+        matches[base_offset + index] = Some(other_offset + index);
+    }
+    let (base, other) = (&base[prefix..], &other[prefix..]);
+    let (base_offset, other_offset) = (base_offset + prefix, other_offset + prefix);
+
+    let suffix = base
+        .iter()
+        .rev()
+        .zip(other.iter().rev())
+        .take_while(|(base, other)| base == other)
+        .count();
+    let (base_end, other_end) = (base.len() - suffix, other.len() - suffix);
+    for index in 0..suffix {
+        matches[base_offset + base_end + index] = Some(other_offset + other_end + index);
+    }
+    let (base, other) = (&base[..base_end], &other[..other_end]);
+
+    if base.is_empty() || other.is_empty() {
+        return;
+    }
+    let Some((x, y)) = middle_snake(base, other) else {
+        return;
+    };
+    // Only a split inside both ranges shrinks the problem.
+    if (x, y) == (0, 0) || (x, y) == (base.len(), other.len()) {
+        return;
+    }
+
+    match_range(&base[..x], &other[..y], base_offset, other_offset, matches);
+    match_range(
+        &base[x..],
+        &other[y..],
+        base_offset + x,
+        other_offset + y,
+        matches,
+    );
+}
+
+/// Searches the shortest edit path from both ends at once and returns where the two
+/// searches meet, `None` when the ranges have no line in common.
+fn middle_snake(base: &[&str], other: &[&str]) -> Option<(usize, usize)> {
+    let (n, m) = (base.len() as isize, other.len() as isize);
+    let max_d = (n + m + 1) / 2;
+    let offset = max_d;
+    let length = 2 * max_d + 2;
+    let mut forward = vec![-1isize; length as usize];
+    let mut backward = vec![-1isize; length as usize];
+    forward[(offset + 1) as usize] = 0;
+    backward[(offset + 1) as usize] = 0;
+
+    let delta = n - m;
+    // With an odd difference the forward search meets the backward one, else the reverse.
+    let front = delta % 2 != 0;
+    let (mut k1_start, mut k1_end, mut k2_start, mut k2_end) = (0, 0, 0, 0);
+
+    for d in 0..max_d {
+        for k1 in (-d + k1_start..=d - k1_end).step_by(2) {
+            let k1_offset = (offset + k1) as usize;
+            let mut x1 = if k1 == -d || (k1 != d && forward[k1_offset - 1] < forward[k1_offset + 1])
+            {
+                forward[k1_offset + 1]
+            } else {
+                forward[k1_offset - 1] + 1
+            };
+            let mut y1 = x1 - k1;
+            while x1 < n && y1 < m && base[x1 as usize] == other[y1 as usize] {
+                x1 += 1;
+                y1 += 1;
+            }
+            forward[k1_offset] = x1;
+
+            if x1 > n {
+                k1_end += 2;
+            } else if y1 > m {
+                k1_start += 2;
+            } else if front {
+                let k2_offset = offset + delta - k1;
+                if (0..length).contains(&k2_offset)
+                    && backward[k2_offset as usize] != -1
+                    && x1 >= n - backward[k2_offset as usize]
+                {
+                    return Some((x1 as usize, y1 as usize));
+                }
+            }
+        }
+
+        for k2 in (-d + k2_start..=d - k2_end).step_by(2) {
+            let k2_offset = (offset + k2) as usize;
+            let mut x2 =
+                if k2 == -d || (k2 != d && backward[k2_offset - 1] < backward[k2_offset + 1]) {
+                    backward[k2_offset + 1]
+                } else {
+                    backward[k2_offset - 1] + 1
+                };
+            let mut y2 = x2 - k2;
+            while x2 < n && y2 < m && base[(n - x2 - 1) as usize] == other[(m - y2 - 1) as usize] {
+                x2 += 1;
+                y2 += 1;
+            }
+            backward[k2_offset] = x2;
+
+            if x2 > n {
+                k2_end += 2;
+            } else if y2 > m {
+                k2_start += 2;
+            } else if !front {
+                let k1_offset = offset + delta - k2;
+                if (0..length).contains(&k1_offset) && forward[k1_offset as usize] != -1 {
+                    let x1 = forward[k1_offset as usize];
+                    let y1 = offset + x1 - k1_offset;
+                    if x1 >= n - x2 {
+                        return Some((x1 as usize, y1 as usize));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unchanged() {
+        assert_eq!(
+            merge("a\nb\n", "a\nb\n", "a\nb\n"),
+            Merge {
+                text: String::from("a\nb\n"),
+                conflicts: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_takes_the_changed_side() {
+        assert_eq!(merge("a\nb\n", "a\nb\n", "a\nB\n").text, "a\nB\n");
+        assert_eq!(merge("a\nb\n", "a\nB\n", "a\nb\n").text, "a\nB\n");
+    }
+
+    #[test]
+    fn test_combines_separate_changes() {
+        let base = "# Title\n\nFirst\n\nSecond\n\nThird\n";
+        let mine = "# Title\n\nFirst, edited\n\nSecond\n\nThird\n";
+        let theirs = "# Title\n\nFirst\n\nSecond\n\nThird\n\nFourth\n";
+
+        assert_eq!(
+            merge(base, mine, theirs),
+            Merge {
+                text: String::from("# Title\n\nFirst, edited\n\nSecond\n\nThird\n\nFourth\n"),
+                conflicts: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_same_change_on_both_sides() {
+        assert_eq!(
+            merge("a\nb\nc\n", "a\nX\nc\n", "a\nX\nc\n"),
+            Merge {
+                text: String::from("a\nX\nc\n"),
+                conflicts: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_conflict_is_marked() {
+        assert_eq!(
+            merge("a\nb\nc\n", "a\nmine\nc\n", "a\ntheirs\nc\n"),
+            Merge {
+                text: String::from("a\n<<<<<<< Editor\nmine\n=======\ntheirs\n>>>>>>> File\nc\n"),
+                conflicts: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_conflict_without_trailing_newline() {
+        let merged = merge("a\nb", "a\nmine", "a\ntheirs");
+
+        assert_eq!(
+            merged.text,
+            "a\n<<<<<<< Editor\nmine\n=======\ntheirs\n>>>>>>> File\n"
+        );
+        assert_eq!(merged.conflicts, 1);
+    }
+
+    #[test]
+    fn test_deletion_and_insertion() {
+        assert_eq!(
+            merge("a\nb\nc\nd\n", "a\nc\nd\n", "a\nb\nc\nd\ne\n").text,
+            "a\nc\nd\ne\n"
+        );
+    }
+
+    #[test]
+    fn test_large_mostly_identical_documents() {
+        let base: String = (0..20_000).map(|line| format!("Line {line}\n")).collect();
+        let mine = base.replace("Line 100\n", "Line 100, edited\n");
+        let theirs = base.replace("Line 19900\n", "") + "Appended\n";
+
+        let merged = merge(&base, &mine, &theirs);
+
+        assert_eq!(merged.conflicts, 0);
+        assert_eq!(
+            merged.text,
+            base.replace("Line 100\n", "Line 100, edited\n")
+                .replace("Line 19900\n", "")
+                + "Appended\n"
+        );
+    }
+
+    #[test]
+    fn test_matching_lines_follow_a_shortest_edit() {
+        let base = ["a", "b", "c", "a", "b", "b", "a"];
+        let other = ["c", "b", "a", "b", "a", "c"];
+        let matches = matching_lines(&base, &other);
+
+        assert_eq!(matches.iter().flatten().count(), 4);
+        let matched: Vec<usize> = matches.iter().flatten().copied().collect();
+        assert!(matched.windows(2).all(|pair| pair[0] < pair[1]));
+        for (index, matched) in matches.iter().enumerate() {
+            if let Some(other_index) = matched {
+                assert_eq!(base[index], other[*other_index]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_base() {
+        let merged = merge("", "mine\n", "theirs\n");
+
+        assert_eq!(merged.conflicts, 1);
+        assert!(merged.text.contains("mine\n=======\ntheirs\n"));
+    }
+}
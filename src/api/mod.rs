@@ -1,4 +1,26 @@
+pub mod attachments;
+pub mod completion;
+pub mod documents;
 pub mod download;
-pub mod local_storage;
+pub mod download_options;
+pub mod emoji;
+pub mod encryption;
+pub mod export;
+pub mod folder;
+pub mod import;
 pub mod markdown_formatter;
+pub mod merge;
 pub mod parser;
+pub mod preview_styles;
+pub mod print;
+pub mod pwa;
+pub mod search;
+pub mod share;
+pub mod statistics;
+pub mod storage;
+pub mod tags;
+pub mod tasks;
+pub mod templates;
+pub mod themes;
+pub mod toc;
+pub mod wikilinks;
@@ -1,9 +1,13 @@
 use std::{fmt, str::FromStr};
 
-use comrak::{ComrakOptions, ExtensionOptions, ParseOptions, RenderOptions, markdown_to_html};
+use comrak::{
+    Arena, ComrakOptions, ExtensionOptions, ParseOptions, RenderOptions, format_html,
+    markdown_to_html, nodes::AstNode, parse_document,
+};
+use serde::{Deserialize, Serialize};
 
 /// Selects which markdown dialect should be used for parsing.
-#[derive(PartialEq, Clone, Debug, Default)]
+#[derive(PartialEq, Clone, Debug, Default, Serialize, Deserialize)]
 pub enum Dialect {
     #[default]
     Common,
@@ -20,33 +24,51 @@ impl Dialect {
     /// assert!(html.contains("<h1>Title</h1>"));
     /// ```
     pub fn parse_markdown_to_html(&self, input: &str) -> String {
-        match self {
-            Self::Common => {
-                let options = ComrakOptions::default();
-                markdown_to_html(input, &options)
-            }
-            Self::GitHub => {
-                let options = ComrakOptions {
-                    extension: ExtensionOptions {
-                        strikethrough: true,
-                        table: true,
-                        autolink: true,
-                        tasklist: true,
-                        tagfilter: true,
-                        ..Default::default()
-                    },
-                    parse: ParseOptions {
-                        ..Default::default()
-                    },
-                    render: RenderOptions {
-                        github_pre_lang: true,
-                        gfm_quirks: true,
-                        ..Default::default()
-                    },
-                };
+        markdown_to_html(input, &self.options())
+    }
 
-                markdown_to_html(input, &options)
-            }
+    /// Like [`Self::parse_markdown_to_html`], but `edit` can change the syntax tree
+    /// before it is rendered.
+    pub fn parse_markdown_to_html_with(
+        &self,
+        input: &str,
+        edit: impl for<'a> FnOnce(&'a Arena<AstNode<'a>>, &'a AstNode<'a>),
+    ) -> String {
+        let arena = Arena::new();
+        let options = self.options();
+        let root = parse_document(&arena, input, &options);
+        edit(&arena, root);
+
+        let mut html = String::new();
+        // Writing into a `String` can't fail.
+        let _ = format_html(root, &options, &mut html);
+        html
+    }
+
+    /// Comrak options for the dialect, for callers that work on the syntax tree.
+    pub fn options(&self) -> ComrakOptions<'static> {
+        match self {
+            Self::Common => ComrakOptions::default(),
+            Self::GitHub => ComrakOptions {
+                extension: ExtensionOptions {
+                    strikethrough: true,
+                    table: true,
+                    autolink: true,
+                    tasklist: true,
+                    tagfilter: true,
+                    // Like on GitHub, `:emoji:` shortcodes turn into emoji.
+                    shortcodes: true,
+                    ..Default::default()
+                },
+                parse: ParseOptions {
+                    ..Default::default()
+                },
+                render: RenderOptions {
+                    github_pre_lang: true,
+                    gfm_quirks: true,
+                    ..Default::default()
+                },
+            },
         }
     }
 }
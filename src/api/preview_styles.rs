@@ -0,0 +1,288 @@
+use std::{fmt, str::FromStr};
+
+/// Stylesheet used to render the preview and the HTML export.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum PreviewStyle {
+    /// The Bulma `content` styles of the app.
+    #[default]
+    Default,
+    GitHub,
+    Academic,
+    Slides,
+    Print,
+    /// A CSS file supplied by the user.
+    Custom,
+}
+
+pub const PREVIEW_STYLES: [PreviewStyle; 6] = [
+    PreviewStyle::Default,
+    PreviewStyle::GitHub,
+    PreviewStyle::Academic,
+    PreviewStyle::Slides,
+    PreviewStyle::Print,
+    PreviewStyle::Custom,
+];
+
+const DEFAULT_CSS: &str = "
+.preview-style-default { font-family: system-ui, sans-serif; line-height: 1.6; color: #303030; max-width: 52rem; margin: 0 auto; }
+.preview-style-default pre { background: #f5f5f5; padding: 1rem; overflow-x: auto; }
+.preview-style-default code { font-family: monospace; }
+.preview-style-default table { border-collapse: collapse; }
+.preview-style-default th, .preview-style-default td { border: 1px solid #dbdbdb; padding: 0.4rem 0.75rem; }
+.preview-style-default blockquote { border-left: 4px solid #dbdbdb; margin-left: 0; padding-left: 1rem; }
+.preview-style-default img { max-width: 100%; }
+";
+
+const GITHUB_CSS: &str = "
+.preview-style-github { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', 'Noto Sans', Helvetica, Arial, sans-serif; font-size: 16px; line-height: 1.5; color: #1f2328; background: #ffffff; }
+.preview-style-github h1, .preview-style-github h2 { border-bottom: 1px solid #d1d9e0; padding-bottom: 0.3em; }
+.preview-style-github h1, .preview-style-github h2, .preview-style-github h3, .preview-style-github h4, .preview-style-github h5, .preview-style-github h6 { margin: 24px 0 16px; font-weight: 600; line-height: 1.25; color: #1f2328; }
+.preview-style-github h1 { font-size: 2em; }
+.preview-style-github h2 { font-size: 1.5em; }
+.preview-style-github h3 { font-size: 1.25em; }
+.preview-style-github p, .preview-style-github ul, .preview-style-github ol, .preview-style-github blockquote, .preview-style-github pre, .preview-style-github table { margin: 0 0 16px; }
+.preview-style-github ul, .preview-style-github ol { padding-left: 2em; list-style: revert; }
+.preview-style-github a { color: #0969da; text-decoration: none; }
+.preview-style-github code { font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 85%; background: #818b981f; border-radius: 6px; padding: 0.2em 0.4em; color: inherit; }
+.preview-style-github pre { background: #f6f8fa; border-radius: 6px; padding: 16px; overflow: auto; }
+.preview-style-github pre code { background: transparent; padding: 0; font-size: 85%; }
+.preview-style-github blockquote { border-left: 0.25em solid #d1d9e0; color: #59636e; padding: 0 1em; }
+.preview-style-github table { border-collapse: collapse; display: block; overflow: auto; }
+.preview-style-github th, .preview-style-github td { border: 1px solid #d1d9e0; padding: 6px 13px; }
+.preview-style-github th { font-weight: 600; }
+.preview-style-github tr:nth-child(2n) { background: #f6f8fa; }
+.preview-style-github hr { height: 0.25em; background: #d1d9e0; border: 0; margin: 24px 0; }
+.preview-style-github img { max-width: 100%; }
+";
+
+const ACADEMIC_CSS: &str = "
+.preview-style-academic { font-family: 'Latin Modern Roman', 'Computer Modern', Georgia, 'Times New Roman', serif; font-size: 17px; line-height: 1.7; color: #111111; background: #ffffff; text-align: justify; hyphens: auto; max-width: 42rem; margin: 0 auto; }
+.preview-style-academic h1 { font-size: 1.8em; text-align: center; margin: 1em 0; font-weight: normal; }
+.preview-style-academic h2 { font-size: 1.3em; margin: 1.5em 0 0.5em; counter-increment: section; }
+.preview-style-academic h3 { font-size: 1.1em; font-style: italic; margin: 1.2em 0 0.4em; }
+.preview-style-academic h1, .preview-style-academic h2, .preview-style-academic h3, .preview-style-academic h4 { color: #111111; }
+.preview-style-academic p { margin: 0 0 0.8em; text-indent: 1.5em; }
+.preview-style-academic h1 + p, .preview-style-academic h2 + p, .preview-style-academic h3 + p { text-indent: 0; }
+.preview-style-academic ul, .preview-style-academic ol { padding-left: 2em; list-style: revert; }
+.preview-style-academic blockquote { margin: 1em 2.5em; font-size: 0.95em; }
+.preview-style-academic code { font-family: 'Latin Modern Mono', 'Courier New', monospace; font-size: 0.9em; background: none; color: inherit; }
+.preview-style-academic pre { border-top: 1px solid #111111; border-bottom: 1px solid #111111; padding: 0.5em 0; overflow-x: auto; background: none; }
+.preview-style-academic table { border-collapse: collapse; margin: 1em auto; border-top: 2px solid #111111; border-bottom: 2px solid #111111; }
+.preview-style-academic th { border-bottom: 1px solid #111111; }
+.preview-style-academic th, .preview-style-academic td { padding: 0.3em 0.8em; border-left: none; border-right: none; }
+.preview-style-academic img { display: block; max-width: 100%; margin: 1em auto; }
+";
+
+const SLIDES_CSS: &str = "
+.preview-style-slides { font-family: 'Helvetica Neue', Arial, sans-serif; font-size: 22px; line-height: 1.4; color: #f5f5f5; background: #1e2430; }
+.preview-style-slides h1, .preview-style-slides h2 { color: #ffb86c; page-break-before: always; break-before: page; border-top: 2px solid #3b4252; padding-top: 1.5em; margin-top: 1.5em; }
+.preview-style-slides h1:first-child, .preview-style-slides h2:first-child { border-top: none; padding-top: 0; margin-top: 0; }
+.preview-style-slides h1 { font-size: 2.4em; text-align: center; }
+.preview-style-slides h2 { font-size: 1.8em; }
+.preview-style-slides h3, .preview-style-slides h4 { color: #8be9fd; }
+.preview-style-slides ul, .preview-style-slides ol { padding-left: 1.5em; list-style: revert; }
+.preview-style-slides li { margin: 0.4em 0; }
+.preview-style-slides a { color: #8be9fd; }
+.preview-style-slides code { font-family: 'Fira Code', monospace; background: #2e3440; color: #a3be8c; padding: 0.1em 0.3em; border-radius: 4px; }
+.preview-style-slides pre { background: #2e3440; padding: 1em; border-radius: 8px; overflow-x: auto; }
+.preview-style-slides blockquote { border-left: 4px solid #ffb86c; padding-left: 1em; font-style: italic; color: #d8dee9; }
+.preview-style-slides table { border-collapse: collapse; margin: 1em auto; }
+.preview-style-slides th, .preview-style-slides td { border: 1px solid #4c566a; padding: 0.4em 0.8em; color: inherit; }
+.preview-style-slides img { display: block; max-width: 100%; margin: 0 auto; }
+";
+
+const PRINT_CSS: &str = "
+.preview-style-print { font-family: Georgia, 'Times New Roman', serif; font-size: 12pt; line-height: 1.5; color: #000000; background: #ffffff; }
+.preview-style-print h1, .preview-style-print h2, .preview-style-print h3, .preview-style-print h4 { font-family: 'Helvetica Neue', Arial, sans-serif; color: #000000; margin: 1.2em 0 0.4em; break-after: avoid; }
+.preview-style-print p { margin: 0 0 0.6em; orphans: 3; widows: 3; }
+.preview-style-print ul, .preview-style-print ol { padding-left: 1.5em; list-style: revert; }
+.preview-style-print a { color: #000000; text-decoration: underline; }
+.preview-style-print code { font-family: 'Courier New', monospace; background: none; color: inherit; }
+.preview-style-print pre { border: 1px solid #999999; padding: 0.5em; white-space: pre-wrap; background: none; break-inside: avoid; }
+.preview-style-print blockquote { border-left: 2px solid #000000; padding-left: 1em; margin-left: 0; }
+.preview-style-print table { border-collapse: collapse; break-inside: avoid; }
+.preview-style-print th, .preview-style-print td { border: 1px solid #000000; padding: 0.25em 0.5em; }
+.preview-style-print img { max-width: 100%; break-inside: avoid; }
+";
+
+impl PreviewStyle {
+    /// Class of the element wrapping the rendered markdown.
+    pub fn class(self) -> String {
+        format!("preview-style-{}", self.key())
+    }
+
+    /// Whether the preview keeps the Bulma `content` styles of the app.
+    pub fn uses_app_styles(self) -> bool {
+        self == PreviewStyle::Default
+    }
+
+    /// Stylesheet for the preview inside the app. The rules only apply to elements
+    /// within [`PreviewStyle::class`], so the app itself keeps its styles.
+    ///
+    /// ```rust
+    /// use rustynote::api::preview_styles::PreviewStyle;
+    ///
+    /// let css = PreviewStyle::Custom.preview_stylesheet("h1 { color: red; }");
+    /// assert_eq!(css, ".preview-style-custom {\nh1 { color: red; }\n}");
+    /// ```
+    pub fn preview_stylesheet(self, custom_css: &str) -> String {
+        match self {
+            PreviewStyle::Default => String::new(),
+            PreviewStyle::Custom => format!(
+                ".{} {{\n{}\n}}",
+                self.class(),
+                sanitize_css(custom_css).trim()
+            ),
+            _ => String::from(self.preset_css()),
+        }
+    }
+
+    /// Stylesheet for a standalone HTML document. Custom CSS is included as it is.
+    pub fn export_stylesheet(self, custom_css: &str) -> String {
+        match self {
+            PreviewStyle::Custom => sanitize_css(custom_css),
+            _ => String::from(self.preset_css().trim()),
+        }
+    }
+
+    fn preset_css(self) -> &'static str {
+        match self {
+            PreviewStyle::Default | PreviewStyle::Custom => DEFAULT_CSS,
+            PreviewStyle::GitHub => GITHUB_CSS,
+            PreviewStyle::Academic => ACADEMIC_CSS,
+            PreviewStyle::Slides => SLIDES_CSS,
+            PreviewStyle::Print => PRINT_CSS,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            PreviewStyle::Default => "default",
+            PreviewStyle::GitHub => "github",
+            PreviewStyle::Academic => "academic",
+            PreviewStyle::Slides => "slides",
+            PreviewStyle::Print => "print",
+            PreviewStyle::Custom => "custom",
+        }
+    }
+}
+
+/// Keeps user supplied CSS from closing the surrounding `<style>` element.
+fn sanitize_css(css: &str) -> String {
+    let lowercase = css.to_lowercase();
+    let mut sanitized = String::with_capacity(css.len());
+    let mut last = 0;
+
+    for (index, _) in lowercase.match_indices("</style") {
+        sanitized.push_str(&css[last..index]);
+        sanitized.push_str("<\\/style");
+        last = index + "</style".len();
+    }
+    sanitized.push_str(&css[last..]);
+
+    sanitized
+}
+
+impl fmt::Display for PreviewStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                PreviewStyle::Default => "Default",
+                PreviewStyle::GitHub => "GitHub README",
+                PreviewStyle::Academic => "Academic Paper",
+                PreviewStyle::Slides => "Slides",
+                PreviewStyle::Print => "Minimal Print",
+                PreviewStyle::Custom => "Custom CSS",
+            }
+        )
+    }
+}
+
+impl FromStr for PreviewStyle {
+    type Err = ();
+
+    /// Accepts the display names as well as the keys used for the CSS classes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PREVIEW_STYLES
+            .into_iter()
+            .find(|style| {
+                style.to_string().eq_ignore_ascii_case(s.trim()) || style.key() == s.trim()
+            })
+            .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for style in PREVIEW_STYLES {
+            assert_eq!(style.to_string().parse::<PreviewStyle>(), Ok(style));
+        }
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!("github".parse::<PreviewStyle>(), Ok(PreviewStyle::GitHub));
+        assert!("fancy".parse::<PreviewStyle>().is_err());
+    }
+
+    #[test]
+    fn test_presets_are_scoped_to_their_class() {
+        for style in PREVIEW_STYLES {
+            if style == PreviewStyle::Default || style == PreviewStyle::Custom {
+                continue;
+            }
+
+            let css = style.preview_stylesheet("");
+            let selector = format!(".{}", style.class());
+            for rule in css.lines().filter(|line| !line.trim().is_empty()) {
+                let selectors = rule.split('{').next().unwrap();
+                for selector_part in selectors.split(',') {
+                    assert!(
+                        selector_part.trim().starts_with(&selector),
+                        "{style}: {selector_part}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_preview_uses_app_styles() {
+        assert!(PreviewStyle::Default.uses_app_styles());
+        assert_eq!(PreviewStyle::Default.preview_stylesheet(""), "");
+        assert!(
+            PreviewStyle::Default
+                .export_stylesheet("")
+                .contains(".preview-style-default")
+        );
+    }
+
+    #[test]
+    fn test_custom_css_is_nested_in_preview() {
+        let css = PreviewStyle::Custom.preview_stylesheet("p { margin: 0; }\n");
+
+        assert_eq!(css, ".preview-style-custom {\np { margin: 0; }\n}");
+    }
+
+    #[test]
+    fn test_custom_css_is_exported_unchanged() {
+        assert_eq!(
+            PreviewStyle::Custom.export_stylesheet("body { color: red; }"),
+            "body { color: red; }"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_css_escapes_closing_style_tag() {
+        assert_eq!(
+            sanitize_css("p {} </STYLE><script>"),
+            "p {} <\\/style><script>"
+        );
+    }
+}
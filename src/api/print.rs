@@ -0,0 +1,214 @@
+use std::{fmt, str::FromStr};
+
+use comrak::{Arena, format_html, nodes::NodeValue, parse_document};
+
+use crate::api::parser::Dialect;
+
+/// Marker that forces a page break when printing.
+pub const PAGE_BREAK_MARKER: &str = "<!-- pagebreak -->";
+
+const PAGE_BREAK_HTML: &str = "<div class=\"page-break\"></div>\n";
+
+/// Layout options for printing and saving as PDF, persisted as e.g. `rules,header`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrintOptions {
+    /// Horizontal rules (`---`) start a new page.
+    pub break_on_rules: bool,
+    /// Every page gets the document title as header and the page number as footer.
+    pub header_footer: bool,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions {
+            break_on_rules: false,
+            header_footer: true,
+        }
+    }
+}
+
+impl fmt::Display for PrintOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut flags = Vec::new();
+        if self.break_on_rules {
+            flags.push("rules");
+        }
+        if self.header_footer {
+            flags.push("header");
+        }
+
+        write!(f, "{}", flags.join(","))
+    }
+}
+
+impl FromStr for PrintOptions {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let flags: Vec<&str> = s.split(',').map(str::trim).collect();
+
+        Ok(PrintOptions {
+            break_on_rules: flags.contains(&"rules"),
+            header_footer: flags.contains(&"header"),
+        })
+    }
+}
+
+/// Renders the markdown for printing, turning page break markers, and horizontal rules
+/// if enabled, into elements that end the page.
+///
+/// ```rust
+/// use rustynote::api::{parser::Dialect, print::{PrintOptions, render_for_print}};
+///
+/// let html = render_for_print("One\n\n<!-- pagebreak -->\n\nTwo", &Dialect::Common, PrintOptions::default());
+/// assert_eq!(html, "<p>One</p>\n<div class=\"page-break\"></div>\n<p>Two</p>\n");
+/// ```
+pub fn render_for_print(markdown: &str, dialect: &Dialect, options: PrintOptions) -> String {
+    let arena = Arena::new();
+    let comrak_options = dialect.options();
+    let root = parse_document(&arena, markdown, &comrak_options);
+
+    for node in root.descendants() {
+        let is_page_break = match &node.data.borrow().value {
+            NodeValue::HtmlBlock(block) => is_page_break_marker(&block.literal),
+            NodeValue::ThematicBreak => options.break_on_rules,
+            _ => false,
+        };
+
+        if is_page_break {
+            node.data.borrow_mut().value = NodeValue::Raw(String::from(PAGE_BREAK_HTML));
+        }
+    }
+
+    let mut html = String::new();
+    format_html(root, &comrak_options, &mut html).unwrap_or_default();
+    html
+}
+
+/// Paged media rules for the printed document: margins and, if enabled, the title
+/// in the page header and the page number in the footer.
+pub fn page_stylesheet(title: &str, options: PrintOptions) -> String {
+    if !options.header_footer {
+        return String::from("@page { margin: 2cm; }");
+    }
+
+    format!(
+        "@page {{ margin: 2cm; \
+         @top-center {{ content: {}; font-size: 9pt; color: #666666; }} \
+         @bottom-center {{ content: counter(page) \" / \" counter(pages); font-size: 9pt; color: #666666; }} }}",
+        css_string(title)
+    )
+}
+
+fn is_page_break_marker(literal: &str) -> bool {
+    let marker: String = literal.chars().filter(|c| !c.is_whitespace()).collect();
+    marker.eq_ignore_ascii_case("<!--pagebreak-->")
+}
+
+/// Quotes text as a CSS string that is also safe inside a `<style>` element.
+fn css_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '<' => quoted.push_str("\\3c "),
+            '\n' | '\r' => quoted.push(' '),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_round_trip() {
+        for break_on_rules in [false, true] {
+            for header_footer in [false, true] {
+                let options = PrintOptions {
+                    break_on_rules,
+                    header_footer,
+                };
+                assert_eq!(options.to_string().parse(), Ok(options));
+            }
+        }
+    }
+
+    #[test]
+    fn test_marker_variants() {
+        assert!(is_page_break_marker("<!-- pagebreak -->\n"));
+        assert!(is_page_break_marker("<!--PageBreak-->"));
+        assert!(!is_page_break_marker("<!-- comment -->"));
+    }
+
+    #[test]
+    fn test_rules_stay_without_option() {
+        let html = render_for_print(
+            "One\n\n---\n\nTwo",
+            &Dialect::Common,
+            PrintOptions::default(),
+        );
+
+        assert_eq!(html, "<p>One</p>\n<hr />\n<p>Two</p>\n");
+    }
+
+    #[test]
+    fn test_rules_break_pages_with_option() {
+        let options = PrintOptions {
+            break_on_rules: true,
+            header_footer: false,
+        };
+        let html = render_for_print("One\n\n---\n\nTwo", &Dialect::GitHub, options);
+
+        assert_eq!(
+            html,
+            "<p>One</p>\n<div class=\"page-break\"></div>\n<p>Two</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_marker_in_code_block_is_kept() {
+        let html = render_for_print(
+            "```\n<!-- pagebreak -->\n```",
+            &Dialect::Common,
+            PrintOptions::default(),
+        );
+
+        assert!(!html.contains("page-break\""));
+        assert!(html.contains("&lt;!-- pagebreak --&gt;"));
+    }
+
+    #[test]
+    fn test_other_html_is_still_omitted() {
+        let html = render_for_print("<div>raw</div>", &Dialect::Common, PrintOptions::default());
+
+        assert_eq!(html, "<!-- raw HTML omitted -->\n");
+    }
+
+    #[test]
+    fn test_page_stylesheet_with_header_footer() {
+        let css = page_stylesheet("Notes \"2026\"", PrintOptions::default());
+
+        assert!(css.contains("@top-center { content: \"Notes \\\"2026\\\"\";"));
+        assert!(css.contains("counter(page)"));
+    }
+
+    #[test]
+    fn test_page_stylesheet_without_header_footer() {
+        let options = PrintOptions {
+            break_on_rules: false,
+            header_footer: false,
+        };
+
+        assert_eq!(page_stylesheet("Notes", options), "@page { margin: 2cm; }");
+    }
+
+    #[test]
+    fn test_css_string_escapes_style_end() {
+        assert_eq!(css_string("a</style>"), "\"a\\3c /style>\"");
+    }
+}
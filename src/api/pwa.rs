@@ -0,0 +1,57 @@
+use gloo::utils::window;
+use leptoaster::{ToastBuilder, ToastLevel, ToasterContext};
+use wasm_bindgen::{JsCast, closure::Closure};
+use wasm_bindgen_futures::{JsFuture, spawn_local};
+use web_sys::{ServiceWorkerRegistration, ServiceWorkerState, js_sys::Reflect};
+
+/// Served next to `index.html`, so it controls the whole app.
+const SERVICE_WORKER: &str = "service-worker.js";
+
+const UPDATE_MESSAGE: &str = "A new version of RustyNote is available. Reload the page to update.";
+
+/// Registers the service worker that caches the app for offline use and shows a toast
+/// once a new build has been installed. Browsers without service workers, e.g. on
+/// plain HTTP, keep working online only.
+pub fn register_service_worker(toaster: ToasterContext) {
+    let navigator = window().navigator();
+    if !Reflect::has(&navigator, &"serviceWorker".into()).unwrap_or(false) {
+        return;
+    }
+
+    let container = navigator.service_worker();
+    spawn_local(async move {
+        if let Ok(registration) = JsFuture::from(container.register(SERVICE_WORKER)).await {
+            notify_about_updates(registration.unchecked_into(), toaster);
+        }
+    });
+}
+
+fn notify_about_updates(registration: ServiceWorkerRegistration, toaster: ToasterContext) {
+    let on_update_found = Closure::<dyn FnMut()>::new({
+        let registration = registration.clone();
+        move || {
+            let Some(worker) = registration.installing() else {
+                return;
+            };
+
+            let toaster = toaster.clone();
+            let installing = worker.clone();
+            let on_state_change = Closure::<dyn FnMut()>::new(move || {
+                // Without a controller this is the first install rather than an update.
+                let is_update = window().navigator().service_worker().controller().is_some();
+                if installing.state() == ServiceWorkerState::Installed && is_update {
+                    toaster.toast(
+                        ToastBuilder::new(UPDATE_MESSAGE)
+                            .with_level(ToastLevel::Info)
+                            .with_expiry(None),
+                    );
+                }
+            });
+            worker.set_onstatechange(Some(on_state_change.as_ref().unchecked_ref()));
+            on_state_change.forget();
+        }
+    });
+
+    registration.set_onupdatefound(Some(on_update_found.as_ref().unchecked_ref()));
+    on_update_found.forget();
+}
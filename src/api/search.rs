@@ -0,0 +1,570 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::api::{documents::DocumentLibrary, parser::Dialect, tags::extract_tags};
+
+/// BM25 saturation of repeated terms.
+const K1: f64 = 1.2;
+/// BM25 normalisation by document length.
+const B: f64 = 0.75;
+/// A word of the document name counts as often as this many words of the text.
+const NAME_WEIGHT: u32 = 3;
+/// Characters of context shown around the first match.
+const SNIPPET_BEFORE: usize = 40;
+const SNIPPET_LENGTH: usize = 160;
+
+/// Limits search results to documents with a tag, a recent change or a dialect.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchFilter {
+    pub tag: Option<String>,
+    pub modified: ModifiedWithin,
+    pub dialect: Option<Dialect>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModifiedWithin {
+    #[default]
+    AnyTime,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl ModifiedWithin {
+    pub const CHOICES: [ModifiedWithin; 5] = [
+        ModifiedWithin::AnyTime,
+        ModifiedWithin::Day,
+        ModifiedWithin::Week,
+        ModifiedWithin::Month,
+        ModifiedWithin::Year,
+    ];
+
+    fn as_millis(&self) -> Option<f64> {
+        let days = match self {
+            ModifiedWithin::AnyTime => return None,
+            ModifiedWithin::Day => 1.0,
+            ModifiedWithin::Week => 7.0,
+            ModifiedWithin::Month => 30.0,
+            ModifiedWithin::Year => 365.0,
+        };
+        Some(days * 24.0 * 60.0 * 60.0 * 1000.0)
+    }
+}
+
+impl fmt::Display for ModifiedWithin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ModifiedWithin::AnyTime => "Any time",
+                ModifiedWithin::Day => "Last 24 hours",
+                ModifiedWithin::Week => "Last 7 days",
+                ModifiedWithin::Month => "Last 30 days",
+                ModifiedWithin::Year => "Last year",
+            }
+        )
+    }
+}
+
+impl FromStr for ModifiedWithin {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ModifiedWithin::CHOICES
+            .into_iter()
+            .find(|choice| choice.to_string() == s)
+            .ok_or(())
+    }
+}
+
+/// A part of a document around the first match. `highlights` are byte ranges of
+/// `text` that matched the query.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snippet {
+    pub text: String,
+    pub highlights: Vec<(usize, usize)>,
+}
+
+impl Snippet {
+    /// The snippet as escaped HTML with the matches in `<mark>` elements.
+    ///
+    /// ```rust
+    /// use rustynote::api::search::Snippet;
+    ///
+    /// let snippet = Snippet { text: String::from("a <b> c"), highlights: vec![(0, 1)] };
+    /// assert_eq!(snippet.to_html(), "<mark>a</mark> &lt;b&gt; c");
+    /// ```
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let mut position = 0;
+
+        for (start, end) in &self.highlights {
+            html.push_str(&escape(&self.text[position..*start]));
+            html.push_str("<mark>");
+            html.push_str(&escape(&self.text[*start..*end]));
+            html.push_str("</mark>");
+            position = *end;
+        }
+        html.push_str(&escape(&self.text[position..]));
+
+        html
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub id: u64,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub modified: f64,
+    pub score: f64,
+    pub snippet: Snippet,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct IndexedDocument {
+    id: u64,
+    name: String,
+    content: String,
+    tags: Vec<String>,
+    modified: f64,
+    dialect: Option<Dialect>,
+    length: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Posting {
+    /// Position in `SearchIndex::documents`.
+    document: usize,
+    frequency: u32,
+}
+
+/// An inverted index over the stored documents, ranked with BM25.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchIndex {
+    documents: Vec<IndexedDocument>,
+    postings: HashMap<String, Vec<Posting>>,
+    average_length: f64,
+}
+
+impl SearchIndex {
+    /// Indexes every document. The active document is read from `active_content`, as the
+    /// library only gets its content when another document is opened. The text of
+    /// encrypted documents is not indexed, only their name.
+    pub fn build(library: &DocumentLibrary, active_content: &str) -> Self {
+        let mut index = SearchIndex::default();
+
+        for document in &library.documents {
+            let content = if document.sealed.is_some() {
+                ""
+            } else if document.id == library.active {
+                active_content
+            } else {
+                &document.content
+            };
+
+            let mut frequencies: HashMap<String, u32> = HashMap::new();
+            for (_, word) in words(&document.name) {
+                *frequencies.entry(word.to_lowercase()).or_default() += NAME_WEIGHT;
+            }
+            let mut length = 0;
+            for (_, word) in words(content) {
+                *frequencies.entry(word.to_lowercase()).or_default() += 1;
+                length += 1;
+            }
+
+            let position = index.documents.len();
+            for (term, frequency) in frequencies {
+                index.postings.entry(term).or_default().push(Posting {
+                    document: position,
+                    frequency,
+                });
+            }
+
+            index.documents.push(IndexedDocument {
+                id: document.id,
+                name: document.name.clone(),
+                content: content.to_string(),
+                tags: extract_tags(content),
+                modified: document.modified,
+                dialect: document.dialect.clone(),
+                length,
+            });
+        }
+
+        let total: usize = index.documents.iter().map(|document| document.length).sum();
+        index.average_length = total as f64 / index.documents.len().max(1) as f64;
+        index
+    }
+
+    /// All tags of the indexed documents, sorted.
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags = self
+            .documents
+            .iter()
+            .flat_map(|document| document.tags.iter().cloned())
+            .collect::<Vec<_>>();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    /// Finds the documents that contain every word of the query, best matches first.
+    /// The last word also matches as a prefix, so results show up while typing. An
+    /// empty query lists the filtered documents, most recently modified first.
+    pub fn search(&self, query: &str, filter: &SearchFilter, now: f64) -> Vec<SearchResult> {
+        let terms = query_terms(query);
+
+        let mut scores: Vec<Option<f64>> = self
+            .documents
+            .iter()
+            .map(|document| matches_filter(document, filter, now).then_some(0.0))
+            .collect();
+
+        for term in &terms {
+            let mut term_scores = vec![0.0; self.documents.len()];
+            for postings in self.matching_postings(term) {
+                let idf = self.idf(postings.len());
+                for posting in postings {
+                    term_scores[posting.document] += idf * self.saturation(posting);
+                }
+            }
+
+            for (score, term_score) in scores.iter_mut().zip(term_scores) {
+                *score = match *score {
+                    Some(score) if term_score > 0.0 => Some(score + term_score),
+                    _ => None,
+                };
+            }
+        }
+
+        let mut results = scores
+            .into_iter()
+            .zip(&self.documents)
+            .filter_map(|(score, document)| {
+                Some(SearchResult {
+                    id: document.id,
+                    name: document.name.clone(),
+                    tags: document.tags.clone(),
+                    modified: document.modified,
+                    score: score?,
+                    snippet: snippet(&document.content, &terms),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(b.modified.total_cmp(&a.modified))
+        });
+        results
+    }
+
+    fn matching_postings<'a>(&'a self, term: &'a Term) -> Vec<&'a Vec<Posting>> {
+        if term.is_prefix {
+            self.postings
+                .iter()
+                .filter(|(indexed, _)| indexed.starts_with(&term.text))
+                .map(|(_, postings)| postings)
+                .collect()
+        } else {
+            self.postings.get(&term.text).into_iter().collect()
+        }
+    }
+
+    fn idf(&self, document_frequency: usize) -> f64 {
+        let total = self.documents.len() as f64;
+        let frequency = document_frequency as f64;
+        (1.0 + (total - frequency + 0.5) / (frequency + 0.5)).ln()
+    }
+
+    fn saturation(&self, posting: &Posting) -> f64 {
+        let frequency = f64::from(posting.frequency);
+        let length = self.documents[posting.document].length as f64;
+        let normalization = 1.0 - B + B * length / self.average_length.max(1.0);
+        frequency * (K1 + 1.0) / (frequency + K1 * normalization)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    text: String,
+    is_prefix: bool,
+}
+
+impl Term {
+    fn matches(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        if self.is_prefix {
+            word.starts_with(&self.text)
+        } else {
+            word == self.text
+        }
+    }
+}
+
+fn query_terms(query: &str) -> Vec<Term> {
+    let completed = query.ends_with(char::is_whitespace);
+    let words = words(query).collect::<Vec<_>>();
+
+    words
+        .iter()
+        .enumerate()
+        .map(|(index, (_, word))| Term {
+            text: word.to_lowercase(),
+            is_prefix: !completed && index == words.len() - 1,
+        })
+        .collect()
+}
+
+fn matches_filter(document: &IndexedDocument, filter: &SearchFilter, now: f64) -> bool {
+    filter
+        .tag
+        .as_ref()
+        .is_none_or(|tag| document.tags.contains(tag))
+        && filter
+            .modified
+            .as_millis()
+            .is_none_or(|age| now - document.modified <= age)
+        && filter.dialect.as_ref().is_none_or(|dialect| {
+            document.dialect.as_ref().unwrap_or(&Dialect::default()) == dialect
+        })
+}
+
+/// Words of a text with their byte offset.
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+}
+
+/// The text around the first match on one line, or the beginning of the document.
+fn snippet(content: &str, terms: &[Term]) -> Snippet {
+    let first_match = words(content)
+        .find(|(_, word)| terms.iter().any(|term| term.matches(word)))
+        .map(|(offset, _)| offset);
+
+    let start = first_match
+        .map(|offset| floor_char_boundary(content, offset.saturating_sub(SNIPPET_BEFORE)))
+        .unwrap_or(0);
+    // Start at a word, not in the middle of one.
+    let start = match content[..start].chars().next_back() {
+        Some(c) if c.is_alphanumeric() => content[start..]
+            .find(|c: char| !c.is_alphanumeric())
+            .map(|skip| start + skip)
+            .filter(|start| first_match.is_none_or(|offset| *start <= offset))
+            .unwrap_or(start),
+        _ => start,
+    };
+    let end = floor_char_boundary(content, start + SNIPPET_LENGTH);
+
+    let prefix = if start > 0 { "…" } else { "" };
+    let suffix = if end < content.len() { "…" } else { "" };
+    let excerpt = &content[start..end];
+
+    let highlights = words(excerpt)
+        .filter(|(_, word)| terms.iter().any(|term| term.matches(word)))
+        .map(|(offset, word)| (prefix.len() + offset, prefix.len() + offset + word.len()))
+        .collect();
+
+    // Keep the byte offsets valid: whitespace characters are replaced one by one.
+    let excerpt = excerpt
+        .chars()
+        .map(|c| if c == '\n' || c == '\t' { ' ' } else { c })
+        .collect::<String>();
+
+    Snippet {
+        text: format!("{prefix}{excerpt}{suffix}"),
+        highlights,
+    }
+}
+
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+    fn library(documents: &[(&str, &str, f64)]) -> DocumentLibrary {
+        let mut library = DocumentLibrary::default();
+        let mut current = String::new();
+        for (name, content, modified) in documents {
+            library.create(name, content, &current, *modified);
+            current = content.to_string();
+        }
+        for (document, (_, _, modified)) in library.documents.iter_mut().zip(documents) {
+            document.modified = *modified;
+        }
+        library
+    }
+
+    fn names(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|result| result.name.as_str()).collect()
+    }
+
+    fn search(library: &DocumentLibrary, query: &str) -> Vec<SearchResult> {
+        let active = library.active().unwrap().content.clone();
+        SearchIndex::build(library, &active).search(query, &SearchFilter::default(), 10.0 * DAY)
+    }
+
+    #[test]
+    fn test_ranks_by_relevance() {
+        let library = library(&[
+            ("Groceries", "milk, bread, rust remover", 1.0),
+            (
+                "Rust notes",
+                "Rust ownership and borrowing. Rust traits.",
+                2.0,
+            ),
+            ("Journal", "Learned some rust today", 3.0),
+        ]);
+
+        assert_eq!(
+            names(&search(&library, "rust ")),
+            vec!["Rust notes", "Journal", "Groceries"]
+        );
+    }
+
+    #[test]
+    fn test_all_words_must_match() {
+        let library = library(&[("A", "rust and wasm", 1.0), ("B", "rust only", 2.0)]);
+
+        assert_eq!(names(&search(&library, "wasm rust ")), vec!["A"]);
+    }
+
+    #[test]
+    fn test_last_word_matches_as_prefix() {
+        let library = library(&[("A", "Borrowing rules", 1.0), ("B", "Borders", 2.0)]);
+
+        assert_eq!(names(&search(&library, "borrow")), vec!["A"]);
+        assert!(search(&library, "borrow ").is_empty());
+        assert_eq!(search(&library, "bor").len(), 2);
+    }
+
+    #[test]
+    fn test_empty_query_lists_recent_first() {
+        let library = library(&[("Old", "a", 1.0), ("New", "b", 2.0)]);
+
+        assert_eq!(names(&search(&library, "  ")), vec!["New", "Old"]);
+    }
+
+    #[test]
+    fn test_uses_the_editor_content_of_the_active_document() {
+        let library = library(&[("A", "stale", 1.0)]);
+        let index = SearchIndex::build(&library, "fresh text");
+
+        assert_eq!(
+            index.search("fresh", &SearchFilter::default(), 0.0).len(),
+            1
+        );
+        assert!(
+            index
+                .search("stale", &SearchFilter::default(), 0.0)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_encrypted_text_is_not_indexed() {
+        let mut library = library(&[("Secret plans", "launch codes", 1.0)]);
+        library.documents[0].sealed = Some(crate::api::encryption::Sealed {
+            version: 1,
+            kdf: Default::default(),
+            salt: String::new(),
+            nonce: String::new(),
+            ciphertext: String::new(),
+        });
+
+        assert!(search(&library, "launch").is_empty());
+        assert_eq!(names(&search(&library, "plans")), vec!["Secret plans"]);
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut library = library(&[
+            ("Work", "#project meeting", 9.5 * DAY),
+            ("Home", "#garden meeting", 1.0 * DAY),
+        ]);
+        library.documents[1].dialect = Some(Dialect::GitHub);
+        let index = SearchIndex::build(&library, "#garden meeting");
+        let now = 10.0 * DAY;
+
+        assert_eq!(index.tags(), vec!["garden", "project"]);
+
+        let by_tag = SearchFilter {
+            tag: Some(String::from("project")),
+            ..Default::default()
+        };
+        assert_eq!(names(&index.search("meeting", &by_tag, now)), vec!["Work"]);
+
+        let by_date = SearchFilter {
+            modified: ModifiedWithin::Week,
+            ..Default::default()
+        };
+        assert_eq!(names(&index.search("", &by_date, now)), vec!["Work"]);
+
+        let by_dialect = SearchFilter {
+            dialect: Some(Dialect::GitHub),
+            ..Default::default()
+        };
+        assert_eq!(names(&index.search("", &by_dialect, now)), vec!["Home"]);
+    }
+
+    #[test]
+    fn test_snippet_highlights_matches() {
+        let content = format!(
+            "{}The borrow checker checks borrows.\nNext line",
+            "x ".repeat(40)
+        );
+        let snippet = snippet(&content, &query_terms("borrow"));
+
+        assert!(snippet.text.starts_with('…'));
+        assert!(!snippet.text.contains('\n'));
+        let highlighted = snippet
+            .highlights
+            .iter()
+            .map(|(start, end)| &snippet.text[*start..*end])
+            .collect::<Vec<_>>();
+        assert_eq!(highlighted, vec!["borrow", "borrows"]);
+        assert!(snippet.to_html().contains("<mark>borrow</mark> checker"));
+    }
+
+    #[test]
+    fn test_snippet_with_multibyte_text() {
+        let content = "ü".repeat(100) + " straße";
+        let snippet = snippet(&content, &query_terms("straße "));
+
+        assert_eq!(
+            snippet
+                .highlights
+                .iter()
+                .map(|(start, end)| &snippet.text[*start..*end])
+                .collect::<Vec<_>>(),
+            vec!["straße"]
+        );
+    }
+
+    #[test]
+    fn test_modified_within_choices() {
+        for choice in ModifiedWithin::CHOICES {
+            assert_eq!(choice.to_string().parse(), Ok(choice));
+        }
+    }
+}
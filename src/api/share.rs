@@ -0,0 +1,262 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec_with_limit};
+use serde::{Deserialize, Serialize};
+
+use crate::api::{
+    encryption::{NoteKey, Sealed},
+    parser::Dialect,
+};
+
+/// Prefix of URL fragments that carry a shared note.
+pub const SHARE_PREFIX: &str = "#share=";
+/// Prefix of URL fragments that carry a note encrypted with a passphrase.
+pub const SEALED_SHARE_PREFIX: &str = "#sealed=";
+
+/// Links longer than this may be cut off by chat apps and mail clients.
+pub const LONG_LINK_LENGTH: usize = 8_000;
+
+const FORMAT_VERSION: u32 = 1;
+const COMPRESSION_LEVEL: u8 = 9;
+/// Upper bound for the decompressed note, so a crafted link can't exhaust memory.
+const MAX_NOTE_SIZE: usize = 10 * 1024 * 1024;
+
+/// A note that travels inside a link instead of a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedNote {
+    pub name: Option<String>,
+    pub dialect: Dialect,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Payload {
+    version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    dialect: String,
+    content: String,
+}
+
+impl SharedNote {
+    /// Compresses the note into the URL fragment of a share link, including the `#`.
+    ///
+    /// ```rust
+    /// use rustynote::api::{parser::Dialect, share::SharedNote};
+    ///
+    /// let note = SharedNote { name: None, dialect: Dialect::GitHub, content: String::from("# Hi") };
+    /// let fragment = note.to_fragment();
+    ///
+    /// assert!(fragment.starts_with("#share="));
+    /// assert_eq!(SharedNote::from_fragment(&fragment), Some(Ok(note)));
+    /// ```
+    pub fn to_fragment(&self) -> String {
+        format!("{SHARE_PREFIX}{}", self.encode())
+    }
+
+    /// Encrypts the note for a link that only opens with `passphrase`. The link gets a key
+    /// and salt of its own, so sharing it never gives away the passphrase of the note.
+    pub fn to_sealed_fragment(&self, passphrase: &str) -> Result<String, String> {
+        let key = NoteKey::generate(passphrase)?;
+        Ok(sealed_fragment(&key.seal(&self.encode())?))
+    }
+
+    fn encode(&self) -> String {
+        let payload = Payload {
+            version: FORMAT_VERSION,
+            name: self.name.clone(),
+            dialect: self.dialect.to_string(),
+            content: self.content.clone(),
+        };
+        let json = serde_json::to_vec(&payload).unwrap_or_default();
+
+        URL_SAFE_NO_PAD.encode(compress_to_vec(&json, COMPRESSION_LEVEL))
+    }
+
+    /// Reads a shared note from a URL fragment. Returns `None` when the fragment
+    /// isn't a share link and an error when the link is damaged.
+    pub fn from_fragment(fragment: &str) -> Option<Result<Self, String>> {
+        let encoded = fragment.strip_prefix(SHARE_PREFIX)?;
+        Some(decode(encoded))
+    }
+
+    /// Reads the encrypted note of a URL fragment, which is opened with
+    /// [`SharedNote::open_sealed`] once the passphrase is known.
+    pub fn from_sealed_fragment(fragment: &str) -> Option<Result<Sealed, String>> {
+        let encoded = fragment.strip_prefix(SEALED_SHARE_PREFIX)?;
+        let sealed = URL_SAFE_NO_PAD
+            .decode(encoded.trim().trim_end_matches('='))
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(|| String::from("The shared link is incomplete or damaged"))
+            .and_then(Sealed::check);
+        Some(sealed)
+    }
+
+    pub fn open_sealed(sealed: &Sealed, key: &NoteKey) -> Result<Self, String> {
+        decode(&key.open(sealed)?)
+    }
+}
+
+fn sealed_fragment(sealed: &Sealed) -> String {
+    let json = serde_json::to_vec(sealed).unwrap_or_default();
+    format!("{SEALED_SHARE_PREFIX}{}", URL_SAFE_NO_PAD.encode(json))
+}
+
+fn decode(encoded: &str) -> Result<SharedNote, String> {
+    let damaged = || String::from("The shared link is incomplete or damaged");
+
+    let compressed = URL_SAFE_NO_PAD
+        .decode(encoded.trim().trim_end_matches('='))
+        .map_err(|_| damaged())?;
+    let json = decompress_to_vec_with_limit(&compressed, MAX_NOTE_SIZE).map_err(|_| damaged())?;
+    let payload: Payload = serde_json::from_slice(&json).map_err(|_| damaged())?;
+
+    if payload.version > FORMAT_VERSION {
+        return Err(String::from(
+            "The shared link was created by a newer version of RustyNote",
+        ));
+    }
+
+    Ok(SharedNote {
+        name: payload.name.filter(|name| !name.trim().is_empty()),
+        dialect: payload.dialect.parse().unwrap_or_default(),
+        content: payload.content,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::encryption::{KdfParams, test_key};
+
+    fn note(content: &str) -> SharedNote {
+        SharedNote {
+            name: Some(String::from("Meeting")),
+            dialect: Dialect::GitHub,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let note = note("# Agenda\n\n- [ ] Ünïcödé ✓\n");
+        assert_eq!(
+            SharedNote::from_fragment(&note.to_fragment()),
+            Some(Ok(note))
+        );
+    }
+
+    #[test]
+    fn test_fragment_is_url_safe() {
+        let fragment = note(&"?&#/+=".repeat(50)).to_fragment();
+        let encoded = fragment.strip_prefix(SHARE_PREFIX).unwrap();
+
+        assert!(
+            encoded
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        );
+    }
+
+    #[test]
+    fn test_compresses_repetitive_content() {
+        let content = "| a | b |\n|---|---|\n".repeat(200);
+        assert!(note(&content).to_fragment().len() < content.len() / 4);
+    }
+
+    #[test]
+    fn test_other_fragments_are_ignored() {
+        assert_eq!(SharedNote::from_fragment(""), None);
+        assert_eq!(SharedNote::from_fragment("#heading"), None);
+    }
+
+    #[test]
+    fn test_damaged_link() {
+        let fragment = note("Some text").to_fragment();
+        let truncated = &fragment[..fragment.len() - 5];
+
+        assert!(matches!(SharedNote::from_fragment(truncated), Some(Err(_))));
+        assert!(matches!(
+            SharedNote::from_fragment("#share=not*base64"),
+            Some(Err(_))
+        ));
+    }
+
+    #[test]
+    fn test_unknown_dialect_and_blank_name() {
+        let payload = r#"{"version":1,"name":" ","dialect":"Other","content":"x"}"#;
+        let fragment = format!(
+            "{SHARE_PREFIX}{}",
+            URL_SAFE_NO_PAD.encode(compress_to_vec(payload.as_bytes(), 6))
+        );
+
+        assert_eq!(
+            SharedNote::from_fragment(&fragment),
+            Some(Ok(SharedNote {
+                name: None,
+                dialect: Dialect::Common,
+                content: String::from("x"),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_sealed_round_trip() {
+        let note = note("# Incident\n\nPassword: hunter2\n");
+        let key = test_key("correct horse");
+        let fragment = sealed_fragment(&key.seal_with_nonce(&note.encode(), [1; 24]).unwrap());
+
+        assert!(fragment.starts_with(SEALED_SHARE_PREFIX));
+        assert_eq!(SharedNote::from_fragment(&fragment), None);
+
+        let sealed = SharedNote::from_sealed_fragment(&fragment)
+            .unwrap()
+            .unwrap();
+        assert_eq!(SharedNote::open_sealed(&sealed, &key), Ok(note));
+        assert!(SharedNote::open_sealed(&sealed, &test_key("wrong")).is_err());
+    }
+
+    #[test]
+    fn test_sealed_link_needs_a_long_passphrase() {
+        assert!(note("x").to_sealed_fragment("short").is_err());
+    }
+
+    #[test]
+    fn test_sealed_link_with_excessive_key_settings() {
+        let sealed = test_key("correct horse")
+            .seal_with_nonce(&note("x").encode(), [1; 24])
+            .unwrap();
+        let fragment = sealed_fragment(&Sealed {
+            kdf: KdfParams {
+                memory_kib: 4 * 1024 * 1024,
+                iterations: 10_000,
+            },
+            ..sealed
+        });
+
+        assert!(matches!(
+            SharedNote::from_sealed_fragment(&fragment),
+            Some(Err(_))
+        ));
+    }
+
+    #[test]
+    fn test_damaged_sealed_link() {
+        assert_eq!(SharedNote::from_sealed_fragment("#share=abc"), None);
+        assert!(matches!(
+            SharedNote::from_sealed_fragment("#sealed=bm90IGpzb24"),
+            Some(Err(_))
+        ));
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let payload = r#"{"version":2,"dialect":"Common","content":"x"}"#;
+        let fragment = format!(
+            "{SHARE_PREFIX}{}",
+            URL_SAFE_NO_PAD.encode(compress_to_vec(payload.as_bytes(), 6))
+        );
+
+        assert!(matches!(SharedNote::from_fragment(&fragment), Some(Err(_))));
+    }
+}
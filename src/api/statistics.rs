@@ -0,0 +1,191 @@
+use comrak::{Arena, nodes::NodeValue, parse_document};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::api::parser::Dialect;
+
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Size statistics of a markdown text.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Statistics {
+    /// User-perceived characters (grapheme clusters), including whitespace.
+    pub characters: usize,
+    /// Words of the rendered text, markdown syntax is not counted.
+    pub words: usize,
+    pub lines: usize,
+    pub paragraphs: usize,
+    pub headings: usize,
+}
+
+impl Statistics {
+    /// Computes the statistics of the given markdown text, parsed in `dialect`.
+    ///
+    /// ```rust
+    /// use rustynote::api::{parser::Dialect, statistics::Statistics};
+    ///
+    /// let statistics = Statistics::of(
+    ///     "# Hello\n\nA **bold** [link](https://example.com).",
+    ///     &Dialect::GitHub,
+    /// );
+    /// assert_eq!(statistics.words, 4);
+    /// assert_eq!(statistics.lines, 3);
+    /// assert_eq!(statistics.paragraphs, 1);
+    /// assert_eq!(statistics.headings, 1);
+    /// ```
+    pub fn of(markdown: &str, dialect: &Dialect) -> Self {
+        let arena = Arena::new();
+        let root = parse_document(&arena, markdown, &dialect.options());
+
+        let mut plain_text = String::with_capacity(markdown.len());
+        let mut paragraphs = 0;
+        let mut headings = 0;
+
+        for node in root.descendants() {
+            let value = &node.data.borrow().value;
+
+            match value {
+                NodeValue::Text(text) => plain_text.push_str(text),
+                NodeValue::Code(code) => plain_text.push_str(&code.literal),
+                NodeValue::CodeBlock(block) => plain_text.push_str(&block.literal),
+                NodeValue::SoftBreak | NodeValue::LineBreak => plain_text.push(' '),
+                NodeValue::Paragraph => paragraphs += 1,
+                NodeValue::Heading(_) => headings += 1,
+                _ => {}
+            }
+
+            if value.block() {
+                plain_text.push(' ');
+            }
+        }
+
+        Statistics {
+            characters: markdown.graphemes(true).count(),
+            words: plain_text.unicode_words().count(),
+            lines: markdown.split('\n').count(),
+            paragraphs,
+            headings,
+        }
+    }
+
+    /// Estimated reading time in whole minutes, at least one minute for any text.
+    ///
+    /// ```rust
+    /// use rustynote::api::{parser::Dialect, statistics::Statistics};
+    ///
+    /// assert_eq!(Statistics::of("", &Dialect::Common).reading_minutes(), 0);
+    /// assert_eq!(Statistics::of("Hello", &Dialect::Common).reading_minutes(), 1);
+    /// ```
+    pub fn reading_minutes(&self) -> usize {
+        self.words.div_ceil(WORDS_PER_MINUTE)
+    }
+}
+
+/// One based line and column of the caret, columns are counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaretPosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Default for CaretPosition {
+    fn default() -> Self {
+        CaretPosition { line: 1, column: 1 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_text() {
+        let statistics = Statistics::of("", &Dialect::GitHub);
+
+        assert_eq!(
+            statistics,
+            Statistics {
+                characters: 0,
+                words: 0,
+                lines: 1,
+                paragraphs: 0,
+                headings: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_words_ignore_markdown_syntax() {
+        let statistics = Statistics::of(
+            "## Title\n\n- [ ] *one* **two**\n- `three`\n\n> four ~~five~~\n\n| six | seven |\n| --- | --- |\n| eight | nine |\n\n---",
+            &Dialect::GitHub,
+        );
+
+        assert_eq!(statistics.words, 10);
+        assert_eq!(statistics.headings, 1);
+    }
+
+    #[test]
+    fn test_link_urls_are_not_words() {
+        assert_eq!(
+            Statistics::of("[click here](https://example.com/a/b)", &Dialect::GitHub).words,
+            2
+        );
+        assert_eq!(Statistics::of("![](image.png)", &Dialect::GitHub).words, 0);
+    }
+
+    #[test]
+    fn test_line_breaks_separate_words() {
+        assert_eq!(
+            Statistics::of("one\ntwo\n\nthree", &Dialect::GitHub).words,
+            3
+        );
+        assert_eq!(Statistics::of("# one\ntwo", &Dialect::GitHub).words, 2);
+    }
+
+    #[test]
+    fn test_inline_formatting_does_not_split_words() {
+        assert_eq!(
+            Statistics::of("un**believ**able", &Dialect::GitHub).words,
+            1
+        );
+    }
+
+    #[test]
+    fn test_code_blocks_count_words() {
+        assert_eq!(
+            Statistics::of("```rust\nlet answer = 42;\n```", &Dialect::GitHub).words,
+            3
+        );
+    }
+
+    #[test]
+    fn test_unicode_characters() {
+        let statistics = Statistics::of("Grüße 👋🏽 e\u{301}", &Dialect::GitHub);
+
+        assert_eq!(statistics.characters, 9);
+        assert_eq!(statistics.words, 2);
+    }
+
+    #[test]
+    fn test_lines_and_paragraphs() {
+        let statistics = Statistics::of("first\nstill first\n\nsecond\n", &Dialect::GitHub);
+
+        assert_eq!(statistics.lines, 5);
+        assert_eq!(statistics.paragraphs, 2);
+    }
+
+    #[test]
+    fn test_dialect_decides_what_is_a_paragraph() {
+        let table = "| one | two |\n| --- | --- |";
+
+        assert_eq!(Statistics::of(table, &Dialect::Common).paragraphs, 1);
+        assert_eq!(Statistics::of(table, &Dialect::GitHub).paragraphs, 0);
+    }
+
+    #[test]
+    fn test_reading_minutes() {
+        let text = "word ".repeat(401);
+
+        assert_eq!(Statistics::of(&text, &Dialect::GitHub).reading_minutes(), 3);
+    }
+}
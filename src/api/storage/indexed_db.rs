@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use gloo::utils::window;
+use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    IdbDatabase, IdbObjectStore, IdbOpenDbRequest, IdbRequest, IdbTransaction, IdbTransactionMode,
+    js_sys::{Array, Promise},
+};
+
+use crate::api::storage::{StorageBackend, StorageError, StorageFuture};
+
+const DATABASE: &str = "rustynote";
+const VERSION: u32 = 1;
+const STORE: &str = "values";
+
+/// An IndexedDB object store with one string value per key. Writes run in the
+/// background and the quota is far larger than that of `localStorage`.
+pub struct IndexedDbBackend {
+    database: IdbDatabase,
+}
+
+impl IndexedDbBackend {
+    pub async fn open() -> Result<Self, StorageError> {
+        let factory = window()
+            .indexed_db()
+            .map_err(StorageError::from_js)?
+            .ok_or_else(|| StorageError::Unavailable(String::from("IndexedDB is disabled")))?;
+        let request = factory
+            .open_with_u32(DATABASE, VERSION)
+            .map_err(StorageError::from_js)?;
+
+        let on_upgrade_needed = Closure::<dyn FnMut()>::new({
+            let request = request.clone();
+            move || create_store(&request)
+        });
+        request.set_onupgradeneeded(Some(on_upgrade_needed.as_ref().unchecked_ref()));
+
+        let database = wait(&request).await;
+        request.set_onupgradeneeded(None);
+
+        Ok(IndexedDbBackend {
+            database: database?.unchecked_into(),
+        })
+    }
+
+    fn store(
+        &self,
+        mode: IdbTransactionMode,
+    ) -> Result<(IdbTransaction, IdbObjectStore), StorageError> {
+        let transaction = self
+            .database
+            .transaction_with_str_and_mode(STORE, mode)
+            .map_err(StorageError::from_js)?;
+        let store = transaction
+            .object_store(STORE)
+            .map_err(StorageError::from_js)?;
+        Ok((transaction, store))
+    }
+
+    async fn read(&self, key: &str) -> Result<Option<String>, StorageError> {
+        let (_, store) = self.store(IdbTransactionMode::Readonly)?;
+        let request = store
+            .get(&JsValue::from_str(key))
+            .map_err(StorageError::from_js)?;
+        Ok(wait(&request).await?.as_string())
+    }
+
+    async fn read_all(&self) -> Result<HashMap<String, String>, StorageError> {
+        let (_, store) = self.store(IdbTransactionMode::Readonly)?;
+        let keys = store.get_all_keys().map_err(StorageError::from_js)?;
+        let values = store.get_all().map_err(StorageError::from_js)?;
+        // Subscribe to both before awaiting, or the second could finish unnoticed.
+        let keys_settled = settled(&keys);
+        let values_settled = settled(&values);
+
+        // Both lists are sorted by key, so they line up.
+        let keys: Array = result(&keys, keys_settled).await?.unchecked_into();
+        let values: Array = result(&values, values_settled).await?.unchecked_into();
+
+        Ok(keys
+            .iter()
+            .zip(values.iter())
+            .filter_map(|(key, value)| Some((key.as_string()?, value.as_string()?)))
+            .collect())
+    }
+
+    async fn write(&self, key: &str, value: Option<String>) -> Result<(), StorageError> {
+        let (transaction, store) = self.store(IdbTransactionMode::Readwrite)?;
+        let key = JsValue::from_str(key);
+
+        match value {
+            Some(value) => store.put_with_key(&JsValue::from_str(&value), &key),
+            None => store.delete(&key),
+        }
+        .map_err(StorageError::from_js)?;
+
+        // A full disk only shows up when the transaction is committed.
+        commit(&transaction).await
+    }
+}
+
+impl StorageBackend for IndexedDbBackend {
+    fn get<'a>(&'a self, key: &'a str) -> StorageFuture<'a, Option<String>> {
+        Box::pin(self.read(key))
+    }
+
+    fn get_all(&self) -> StorageFuture<'_, HashMap<String, String>> {
+        Box::pin(self.read_all())
+    }
+
+    fn set<'a>(&'a self, key: &'a str, value: String) -> StorageFuture<'a, ()> {
+        Box::pin(self.write(key, Some(value)))
+    }
+
+    fn remove<'a>(&'a self, key: &'a str) -> StorageFuture<'a, ()> {
+        Box::pin(self.write(key, None))
+    }
+}
+
+fn create_store(request: &IdbOpenDbRequest) {
+    let Ok(database) = request.result() else {
+        return;
+    };
+    let database: IdbDatabase = database.unchecked_into();
+
+    if !database.object_store_names().contains(STORE) {
+        let _ = database.create_object_store(STORE);
+    }
+}
+
+/// Waits for a request and returns its result.
+async fn wait(request: &IdbRequest) -> Result<JsValue, StorageError> {
+    result(request, settled(request)).await
+}
+
+/// Resolves once the request succeeded or failed.
+fn settled(request: &IdbRequest) -> JsFuture {
+    JsFuture::from(Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    }))
+}
+
+async fn result(request: &IdbRequest, settled: JsFuture) -> Result<JsValue, StorageError> {
+    let outcome = settled.await;
+    request.set_onsuccess(None);
+    request.set_onerror(None);
+
+    match outcome {
+        Ok(_) => request.result().map_err(StorageError::from_js),
+        Err(_) => Err(request_error(request)),
+    }
+}
+
+/// Waits until a transaction is committed.
+async fn commit(transaction: &IdbTransaction) -> Result<(), StorageError> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        transaction.set_oncomplete(Some(&resolve));
+        transaction.set_onabort(Some(&reject));
+        transaction.set_onerror(Some(&reject));
+    });
+
+    let outcome = JsFuture::from(promise).await;
+    transaction.set_oncomplete(None);
+    transaction.set_onabort(None);
+    transaction.set_onerror(None);
+
+    outcome.map(|_| ()).map_err(|_| {
+        transaction
+            .error()
+            .map(|error| StorageError::from_js(error.into()))
+            .unwrap_or_else(|| StorageError::Failed(String::from("The change was not saved")))
+    })
+}
+
+fn request_error(request: &IdbRequest) -> StorageError {
+    match request.error() {
+        Ok(Some(error)) => StorageError::from_js(error.into()),
+        _ => StorageError::Failed(String::from("The request failed")),
+    }
+}
@@ -0,0 +1,63 @@
+use std::{collections::HashMap, future::ready};
+
+use gloo::utils::window;
+use web_sys::Storage;
+
+use crate::api::storage::{StorageBackend, StorageError, StorageFuture};
+
+/// The browser's `localStorage`. It is synchronous and limited to a few megabytes,
+/// so it is only used when IndexedDB isn't available.
+pub struct LocalStorageBackend {
+    storage: Storage,
+}
+
+impl LocalStorageBackend {
+    pub fn open() -> Result<Self, StorageError> {
+        window()
+            .local_storage()
+            .map_err(StorageError::from_js)?
+            .map(|storage| LocalStorageBackend { storage })
+            .ok_or_else(|| StorageError::Unavailable(String::from("localStorage is disabled")))
+    }
+
+    fn entries(&self) -> Result<HashMap<String, String>, StorageError> {
+        let length = self.storage.length().map_err(StorageError::from_js)?;
+        let mut entries = HashMap::new();
+
+        for index in 0..length {
+            if let Some(key) = self.storage.key(index).map_err(StorageError::from_js)?
+                && let Some(value) = self.storage.get_item(&key).map_err(StorageError::from_js)?
+            {
+                entries.insert(key, value);
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+impl StorageBackend for LocalStorageBackend {
+    fn get<'a>(&'a self, key: &'a str) -> StorageFuture<'a, Option<String>> {
+        Box::pin(ready(
+            self.storage.get_item(key).map_err(StorageError::from_js),
+        ))
+    }
+
+    fn get_all(&self) -> StorageFuture<'_, HashMap<String, String>> {
+        Box::pin(ready(self.entries()))
+    }
+
+    fn set<'a>(&'a self, key: &'a str, value: String) -> StorageFuture<'a, ()> {
+        Box::pin(ready(
+            self.storage
+                .set_item(key, &value)
+                .map_err(StorageError::from_js),
+        ))
+    }
+
+    fn remove<'a>(&'a self, key: &'a str) -> StorageFuture<'a, ()> {
+        Box::pin(ready(
+            self.storage.remove_item(key).map_err(StorageError::from_js),
+        ))
+    }
+}
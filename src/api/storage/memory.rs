@@ -0,0 +1,58 @@
+use std::{cell::RefCell, collections::HashMap, future::ready};
+
+use crate::api::storage::{StorageBackend, StorageError, StorageFuture};
+
+/// Keeps the values only as long as the page is open, for browsers that block
+/// every other storage.
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    values: RefCell<HashMap<String, String>>,
+    /// Maximum number of bytes of all values together.
+    quota: Option<usize>,
+}
+
+impl MemoryBackend {
+    pub fn with_quota(quota: usize) -> Self {
+        MemoryBackend {
+            quota: Some(quota),
+            ..Default::default()
+        }
+    }
+
+    fn insert(&self, key: &str, value: String) -> Result<(), StorageError> {
+        let mut values = self.values.borrow_mut();
+
+        if let Some(quota) = self.quota {
+            let used: usize = values
+                .iter()
+                .filter(|(stored_key, _)| stored_key.as_str() != key)
+                .map(|(_, stored)| stored.len())
+                .sum();
+            if used + value.len() > quota {
+                return Err(StorageError::QuotaExceeded);
+            }
+        }
+
+        values.insert(key.to_string(), value);
+        Ok(())
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn get<'a>(&'a self, key: &'a str) -> StorageFuture<'a, Option<String>> {
+        Box::pin(ready(Ok(self.values.borrow().get(key).cloned())))
+    }
+
+    fn get_all(&self) -> StorageFuture<'_, HashMap<String, String>> {
+        Box::pin(ready(Ok(self.values.borrow().clone())))
+    }
+
+    fn set<'a>(&'a self, key: &'a str, value: String) -> StorageFuture<'a, ()> {
+        Box::pin(ready(self.insert(key, value)))
+    }
+
+    fn remove<'a>(&'a self, key: &'a str) -> StorageFuture<'a, ()> {
+        self.values.borrow_mut().remove(key);
+        Box::pin(ready(Ok(())))
+    }
+}
@@ -0,0 +1,400 @@
+mod indexed_db;
+mod local;
+mod memory;
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    fmt,
+    pin::Pin,
+    rc::Rc,
+    str::FromStr,
+};
+
+use gloo::timers::callback::Timeout;
+use leptoaster::ToasterContext;
+use leptos::{
+    ev::{pagehide, visibilitychange},
+    prelude::*,
+    task::spawn_local,
+};
+use leptos_use::{use_document, use_event_listener, use_window};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::DomException;
+
+use crate::api::attachments::STORAGE_PREFIX;
+
+pub use indexed_db::IndexedDbBackend;
+pub use local::LocalStorageBackend;
+pub use memory::MemoryBackend;
+
+/// Keys that were kept in `localStorage` before the store existed. They are moved
+/// into IndexedDB on the first start.
+pub const LEGACY_KEYS: [&str; 10] = [
+    "markdown_content",
+    "markdown_parser",
+    "theme",
+    "documents",
+    "toolbar_layout",
+    "custom_themes",
+    "preview_style",
+    "preview_custom_css",
+    "print_options",
+    "download_options",
+];
+
+/// Prefixes of keys that were kept in `localStorage` under many names, e.g. one per
+/// image. They are moved along with [`LEGACY_KEYS`].
+pub const LEGACY_PREFIXES: [&str; 1] = [STORAGE_PREFIX];
+
+/// Changes are collected for this long before they are written.
+const WRITE_DELAY_MS: u32 = 400;
+
+pub type StorageFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, StorageError>> + 'a>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorageError {
+    QuotaExceeded,
+    Unavailable(String),
+    Failed(String),
+}
+
+impl StorageError {
+    /// Maps an exception thrown by the browser, recognising a full storage.
+    fn from_js(error: JsValue) -> Self {
+        match error.dyn_ref::<DomException>() {
+            Some(exception) if exception.name() == "QuotaExceededError" => Self::QuotaExceeded,
+            Some(exception) => Self::Failed(exception.message()),
+            None => Self::Failed(error.as_string().unwrap_or_default()),
+        }
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::QuotaExceeded => write!(
+                f,
+                "The browser storage is full. Delete documents or images you no longer need to keep saving."
+            ),
+            Self::Unavailable(reason) => write!(f, "Storage is not available: {reason}"),
+            Self::Failed(reason) => write!(f, "Saving failed: {reason}"),
+        }
+    }
+}
+
+/// A key-value store for the persisted state of the app.
+pub trait StorageBackend {
+    fn get<'a>(&'a self, key: &'a str) -> StorageFuture<'a, Option<String>>;
+
+    fn get_all(&self) -> StorageFuture<'_, HashMap<String, String>>;
+
+    fn set<'a>(&'a self, key: &'a str, value: String) -> StorageFuture<'a, ()>;
+
+    fn remove<'a>(&'a self, key: &'a str) -> StorageFuture<'a, ()>;
+}
+
+/// The [`LEGACY_KEYS`] and the keys with one of the [`LEGACY_PREFIXES`] that `from`
+/// holds.
+pub async fn legacy_keys(from: &dyn StorageBackend) -> Result<Vec<String>, StorageError> {
+    let mut keys: Vec<String> = LEGACY_KEYS.map(String::from).to_vec();
+    keys.extend(
+        from.get_all()
+            .await?
+            .into_keys()
+            .filter(|key| LEGACY_PREFIXES.iter().any(|prefix| key.starts_with(prefix))),
+    );
+    Ok(keys)
+}
+
+/// Copies the keys that `to` doesn't know yet from `from` and removes them from `from`
+/// once they are stored. Returns the number of copied keys.
+pub async fn migrate(
+    from: &dyn StorageBackend,
+    to: &dyn StorageBackend,
+    keys: &[impl AsRef<str>],
+) -> Result<usize, StorageError> {
+    let mut migrated = 0;
+
+    for key in keys {
+        let key = key.as_ref();
+        let Some(value) = from.get(key).await? else {
+            continue;
+        };
+        if to.get(key).await?.is_none() {
+            to.set(key, value).await?;
+            migrated += 1;
+        }
+        from.remove(key).await?;
+    }
+
+    Ok(migrated)
+}
+
+/// Opens IndexedDB and moves the legacy keys into it. Falls back to `localStorage` and
+/// finally to memory, which loses the notes on reload, so the user is told about it.
+async fn open_backend(toaster: &ToasterContext) -> Rc<dyn StorageBackend> {
+    let local = LocalStorageBackend::open();
+
+    match IndexedDbBackend::open().await {
+        Ok(indexed_db) => {
+            let migrated = match &local {
+                Ok(local) => match legacy_keys(local).await {
+                    Ok(keys) => migrate(local, &indexed_db, &keys).await.map(|_| ()),
+                    Err(error) => Err(error),
+                },
+                Err(_) => Ok(()),
+            };
+            if let Err(error) = migrated {
+                toaster.error(error.to_string());
+            }
+            return Rc::new(indexed_db);
+        }
+        Err(error) => leptos::logging::warn!("IndexedDB is not available: {error}"),
+    }
+
+    match local {
+        Ok(local) => Rc::new(local),
+        Err(error) => {
+            toaster.warn(format!(
+                "{error}. Your notes will be lost when the page is closed."
+            ));
+            Rc::new(MemoryBackend::default())
+        }
+    }
+}
+
+/// The values loaded on start and the writes that are waiting for the debounce delay.
+#[derive(Clone)]
+pub struct PersistentStore {
+    backend: Rc<dyn StorageBackend>,
+    loaded: Rc<RefCell<HashMap<String, String>>>,
+    pending: Rc<RefCell<BTreeMap<String, String>>>,
+    timer: Rc<RefCell<Option<Timeout>>>,
+    toaster: ToasterContext,
+}
+
+impl PersistentStore {
+    pub async fn open(toaster: ToasterContext) -> Self {
+        let backend = open_backend(&toaster).await;
+        let loaded = backend.get_all().await.unwrap_or_else(|error| {
+            toaster.error(error.to_string());
+            HashMap::new()
+        });
+
+        PersistentStore {
+            backend,
+            loaded: Rc::new(RefCell::new(loaded)),
+            pending: Rc::default(),
+            timer: Rc::default(),
+            toaster,
+        }
+    }
+
+    /// The value of `key` as it was loaded on start or stored with [`Self::store`].
+    pub fn loaded(&self, key: &str) -> Option<String> {
+        self.loaded.borrow().get(key).cloned()
+    }
+
+    /// Writes the value right away instead of after the delay, e.g. an image that has
+    /// to be stored before a note references it. It is kept for [`Self::loaded`].
+    pub async fn store(&self, key: &str, value: String) -> Result<(), StorageError> {
+        self.backend.set(key, value.clone()).await?;
+        self.loaded.borrow_mut().insert(key.to_string(), value);
+        Ok(())
+    }
+
+    /// Queues a write. Repeated writes to a key within the delay are stored once.
+    fn write(&self, key: &str, value: String) {
+        self.pending.borrow_mut().insert(key.to_string(), value);
+
+        let store = self.clone();
+        // Replacing the timer cancels the previous one.
+        *self.timer.borrow_mut() = Some(Timeout::new(WRITE_DELAY_MS, move || store.flush()));
+    }
+
+    /// Writes everything that is queued, e.g. before the page is hidden.
+    pub fn flush_now(&self) {
+        self.timer.borrow_mut().take();
+        self.flush();
+    }
+
+    fn flush(&self) {
+        let writes = std::mem::take(&mut *self.pending.borrow_mut());
+        if writes.is_empty() {
+            return;
+        }
+
+        let backend = self.backend.clone();
+        let toaster = self.toaster.clone();
+        spawn_local(async move {
+            for (key, value) in writes {
+                if let Err(error) = backend.set(&key, value).await {
+                    toaster.error(error.to_string());
+                    break;
+                }
+            }
+        });
+    }
+}
+
+/// Makes the store available to `use_persistent_signal` and writes pending changes
+/// when the page is hidden or closed.
+pub fn provide_persistent_store(store: PersistentStore) {
+    let store = StoredValue::new_local(store);
+
+    let _ = use_event_listener(use_document(), visibilitychange, move |_| {
+        store.with_value(PersistentStore::flush_now)
+    });
+    let _ = use_event_listener(use_window(), pagehide, move |_| {
+        store.with_value(PersistentStore::flush_now)
+    });
+
+    provide_context(store);
+}
+
+/// The store provided by [`provide_persistent_store`].
+pub fn use_persistent_store() -> StoredValue<PersistentStore, LocalStorage> {
+    expect_context()
+}
+
+/// Creates a reactive signal that persists its value in the store.
+///
+/// ```rust,ignore
+/// use rustynote::api::storage::use_persistent_signal;
+///
+/// let signal = use_persistent_signal::<String>("draft".into());
+/// signal.set("Hello".into());
+/// ```
+pub fn use_persistent_signal<T>(key: String) -> RwSignal<T>
+where
+    T: Clone + Default + PartialEq + ToString + FromStr + Send + Sync + 'static,
+{
+    use_persistent_signal_with(key, T::to_string)
+}
+
+/// Like [`use_persistent_signal`], but `to_stored` decides what is written, e.g. to
+/// keep the text of an encrypted note out of the store.
+pub fn use_persistent_signal_with<T>(
+    key: String,
+    to_stored: impl Fn(&T) -> String + 'static,
+) -> RwSignal<T>
+where
+    T: Clone + Default + PartialEq + FromStr + Send + Sync + 'static,
+{
+    let store = use_persistent_store();
+
+    let loaded = store.with_value(|store| store.loaded(&key));
+    let initial = loaded
+        .as_deref()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default();
+    let signal = RwSignal::new(initial);
+
+    Effect::new(move |stored: Option<Option<String>>| {
+        let value = signal.with(&to_stored);
+        // Skips the write when the value is what the store already holds.
+        if stored.unwrap_or_else(|| loaded.clone()).as_ref() != Some(&value) {
+            store.with_value(|store| store.write(&key, value.clone()));
+        }
+        Some(value)
+    });
+
+    signal
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use super::*;
+
+    /// The memory backend finishes right away, so polling once is enough.
+    fn block_on<T>(future: impl Future<Output = T>) -> T {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(value) => value,
+            Poll::Pending => panic!("the future did not complete"),
+        }
+    }
+
+    fn backend(entries: &[(&str, &str)]) -> MemoryBackend {
+        let backend = MemoryBackend::default();
+        for (key, value) in entries {
+            block_on(backend.set(key, value.to_string())).unwrap();
+        }
+        backend
+    }
+
+    #[test]
+    fn test_memory_backend() {
+        let backend = backend(&[("theme", "Dark"), ("markdown_parser", "GitHub")]);
+
+        assert_eq!(
+            block_on(backend.get("theme")),
+            Ok(Some(String::from("Dark")))
+        );
+        block_on(backend.remove("theme")).unwrap();
+        assert_eq!(block_on(backend.get("theme")), Ok(None));
+        assert_eq!(
+            block_on(backend.get_all()),
+            Ok(HashMap::from([(
+                String::from("markdown_parser"),
+                String::from("GitHub")
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_migrate_moves_legacy_keys() {
+        let local = backend(&[
+            ("markdown_content", "# Notes"),
+            ("theme", "Dark"),
+            ("attachment:abc", "data:image/png;base64,"),
+        ]);
+        let indexed_db = MemoryBackend::default();
+        let keys = block_on(legacy_keys(&local)).unwrap();
+
+        assert_eq!(block_on(migrate(&local, &indexed_db, &keys)), Ok(3));
+        assert_eq!(
+            block_on(indexed_db.get("markdown_content")),
+            Ok(Some(String::from("# Notes")))
+        );
+        assert_eq!(block_on(local.get("markdown_content")), Ok(None));
+        assert_eq!(
+            block_on(indexed_db.get("attachment:abc")),
+            Ok(Some(String::from("data:image/png;base64,")))
+        );
+        assert_eq!(block_on(local.get("attachment:abc")), Ok(None));
+    }
+
+    #[test]
+    fn test_migrate_keeps_newer_values() {
+        let local = backend(&[("theme", "Light")]);
+        let indexed_db = backend(&[("theme", "Dark")]);
+
+        assert_eq!(block_on(migrate(&local, &indexed_db, &LEGACY_KEYS)), Ok(0));
+        assert_eq!(
+            block_on(indexed_db.get("theme")),
+            Ok(Some(String::from("Dark")))
+        );
+        assert_eq!(block_on(local.get("theme")), Ok(None));
+    }
+
+    #[test]
+    fn test_migrate_stops_on_full_storage() {
+        let local = backend(&[("markdown_content", "# Notes")]);
+        let indexed_db = MemoryBackend::with_quota(4);
+
+        assert_eq!(
+            block_on(migrate(&local, &indexed_db, &LEGACY_KEYS)),
+            Err(StorageError::QuotaExceeded)
+        );
+        assert_eq!(
+            block_on(local.get("markdown_content")),
+            Ok(Some(String::from("# Notes")))
+        );
+    }
+}
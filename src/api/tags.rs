@@ -0,0 +1,205 @@
+use std::collections::BTreeSet;
+
+/// Collects the tags of a note from its front matter (`tags: [work, ideas]`) and from
+/// `#tag` words in the text. Tags are lowercase, sorted and unique. Headings, code and
+/// numbers like `#42` are not tags.
+///
+/// ```rust
+/// use rustynote::api::tags::extract_tags;
+///
+/// let note = "---\ntags: [Work]\n---\n# Plan\n\nCall Bob #todo #work, see #42";
+/// assert_eq!(extract_tags(note), vec!["todo", "work"]);
+/// ```
+pub fn extract_tags(markdown: &str) -> Vec<String> {
+    let (front_matter, body) = split_front_matter(markdown);
+    let mut tags = BTreeSet::new();
+
+    if let Some(front_matter) = front_matter {
+        tags.extend(front_matter_tags(front_matter));
+    }
+
+    let mut fence: Option<&str> = None;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+            continue;
+        }
+        if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+            continue;
+        }
+        // Indented code blocks.
+        if line.starts_with("    ") || line.starts_with('\t') {
+            continue;
+        }
+
+        tags.extend(inline_tags(line));
+    }
+
+    tags.into_iter().collect()
+}
+
+/// Splits a leading YAML front matter block from the note.
+///
+/// ```rust
+/// use rustynote::api::tags::split_front_matter;
+///
+/// assert_eq!(split_front_matter("---\ntitle: A\n---\nText"), (Some("title: A"), "Text"));
+/// assert_eq!(split_front_matter("Text"), (None, "Text"));
+/// ```
+pub fn split_front_matter(markdown: &str) -> (Option<&str>, &str) {
+    let Some(rest) = markdown.strip_prefix("---\n") else {
+        return (None, markdown);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if matches!(line.trim_end(), "---" | "...") {
+            let front_matter = rest[..offset].trim_end_matches('\n');
+            return (Some(front_matter), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+
+    (None, markdown)
+}
+
+/// Reads `tags: [a, b]`, `tags: a, b` and a `tags:` list with one `- a` per line.
+fn front_matter_tags(front_matter: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut in_list = false;
+
+    for line in front_matter.lines() {
+        if in_list {
+            match line.trim_start().strip_prefix('-') {
+                Some(item) if line.starts_with([' ', '\t', '-']) => {
+                    tags.extend(normalize(item));
+                    continue;
+                }
+                _ => in_list = false,
+            }
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if !matches!(key.trim().to_lowercase().as_str(), "tags" | "tag") {
+            continue;
+        }
+
+        let value = value.trim();
+        if value.is_empty() {
+            in_list = true;
+        } else {
+            let value = value
+                .strip_prefix('[')
+                .and_then(|value| value.strip_suffix(']'))
+                .unwrap_or(value);
+            tags.extend(value.split(',').filter_map(normalize));
+        }
+    }
+
+    tags
+}
+
+fn inline_tags(line: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut in_code = false;
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        if c == '`' {
+            in_code = !in_code;
+        } else if c == '#' && !in_code && previous.is_whitespace() {
+            let rest = &line[index + 1..];
+            let length = rest.find(|c: char| !is_tag_char(c)).unwrap_or(rest.len());
+            let tag = rest[..length].trim_end_matches(['-', '/', '_']);
+
+            if tag.chars().any(char::is_alphabetic) {
+                tags.push(tag.to_lowercase());
+            }
+        }
+        previous = c;
+    }
+
+    tags
+}
+
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | '/')
+}
+
+fn normalize(tag: &str) -> Option<String> {
+    let tag = tag
+        .trim()
+        .trim_matches(['"', '\''])
+        .trim_start_matches('#')
+        .trim();
+    (!tag.is_empty()).then(|| tag.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_tags() {
+        assert_eq!(
+            extract_tags("#idea at the start, one in the #middle and #Nested/Tag."),
+            vec!["idea", "middle", "nested/tag"]
+        );
+    }
+
+    #[test]
+    fn test_headings_and_numbers_are_no_tags() {
+        assert_eq!(
+            extract_tags("# Title\n## Section\nIssue #42 and C#"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_links_and_anchors_are_no_tags() {
+        assert_eq!(
+            extract_tags("[Jump](#section) https://example.com/#anchor"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_code_is_skipped() {
+        let note = "Use `#define` here #real\n\n```c\n#include <stdio.h>\n```\n\n    #indented\n";
+        assert_eq!(extract_tags(note), vec!["real"]);
+    }
+
+    #[test]
+    fn test_front_matter_forms() {
+        assert_eq!(
+            extract_tags("---\ntitle: A\ntags: [Work, \"Q3 Plan\"]\n---\nText"),
+            vec!["q3 plan", "work"]
+        );
+        assert_eq!(
+            extract_tags("---\ntags: alpha, beta\n---\n"),
+            vec!["alpha", "beta"]
+        );
+        assert_eq!(
+            extract_tags("---\ntags:\n  - one\n  - '#two'\nauthor: me\n---\n"),
+            vec!["one", "two"]
+        );
+    }
+
+    #[test]
+    fn test_unclosed_front_matter_is_text() {
+        assert_eq!(
+            split_front_matter("---\ntags: [a]\n"),
+            (None, "---\ntags: [a]\n")
+        );
+        assert_eq!(extract_tags("---\ntags: [a]\n"), Vec::<String>::new());
+    }
+}
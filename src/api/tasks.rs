@@ -0,0 +1,483 @@
+use std::{cell::RefCell, fmt};
+
+use comrak::{
+    Arena,
+    nodes::{Ast, AstNode, NodeValue},
+    parse_document,
+};
+
+use crate::api::parser::Dialect;
+
+const CHECKBOX: &str = "<input type=\"checkbox\"";
+const DISABLED: &str = " disabled=\"\"";
+
+/// A task list item of the document.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Task {
+    /// Zero based line of the task marker.
+    pub line: usize,
+    pub checked: bool,
+}
+
+/// Number of completed tasks out of all tasks.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Progress {
+    pub done: usize,
+    pub total: usize,
+}
+
+impl Progress {
+    fn of(tasks: &[Task]) -> Self {
+        Progress {
+            done: tasks.iter().filter(|task| task.checked).count(),
+            total: tasks.len(),
+        }
+    }
+
+    /// Share of completed tasks between `0.0` and `1.0`.
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.done as f64 / self.total as f64
+        }
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{} done", self.done, self.total)
+    }
+}
+
+/// Task progress of everything below a heading, including its subsections.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionProgress {
+    pub heading: String,
+    pub level: u8,
+    pub progress: Progress,
+}
+
+struct Heading<'a> {
+    node: &'a AstNode<'a>,
+    line: usize,
+    level: u8,
+}
+
+/// Lists all task list items in document order, the same order the preview renders them in.
+/// Dialects without task lists have none.
+///
+/// ```rust
+/// use rustynote::api::{parser::Dialect, tasks::tasks};
+///
+/// let tasks = tasks("- [x] done\n- [ ] open\n- no task", &Dialect::GitHub);
+/// assert_eq!(tasks.len(), 2);
+/// assert!(tasks[0].checked);
+/// assert_eq!(tasks[1].line, 1);
+/// ```
+pub fn tasks(markdown: &str, dialect: &Dialect) -> Vec<Task> {
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &dialect.options());
+    let (tasks, _) = collect(root);
+    tasks
+}
+
+/// Progress over all tasks of the document.
+pub fn document_progress(markdown: &str, dialect: &Dialect) -> Progress {
+    Progress::of(&tasks(markdown, dialect))
+}
+
+/// Progress for every heading of the document, in document order.
+///
+/// ```rust
+/// use rustynote::api::{parser::Dialect, tasks::section_progress};
+///
+/// let sections = section_progress("# Trip\n- [x] Book\n## Packing\n- [ ] Socks", &Dialect::GitHub);
+/// assert_eq!(sections[0].progress.to_string(), "1/2 done");
+/// assert_eq!(sections[1].progress.to_string(), "0/1 done");
+/// ```
+pub fn section_progress(markdown: &str, dialect: &Dialect) -> Vec<SectionProgress> {
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &dialect.options());
+    let (tasks, headings) = collect(root);
+
+    headings
+        .iter()
+        .zip(progress_of_sections(&tasks, &headings))
+        .map(|(heading, progress)| SectionProgress {
+            heading: plain_text(heading.node),
+            level: heading.level,
+            progress,
+        })
+        .collect()
+}
+
+/// Appends the task progress to every heading of the syntax tree that has tasks below
+/// it. Meant for [`Dialect::parse_markdown_to_html_with`].
+pub fn add_heading_progress<'a>(arena: &'a Arena<AstNode<'a>>, root: &'a AstNode<'a>) {
+    let (tasks, headings) = collect(root);
+
+    for (heading, progress) in headings.iter().zip(progress_of_sections(&tasks, &headings)) {
+        if progress.total == 0 {
+            continue;
+        }
+        let badge = format!(" <span class=\"tag task-progress\">{progress}</span>");
+        let position = heading.node.data.borrow().sourcepos.end;
+        heading
+            .node
+            .append(arena.alloc(AstNode::new(RefCell::new(Ast::new(
+                NodeValue::Raw(badge),
+                position,
+            )))));
+    }
+}
+
+/// Progress of the tasks below each heading, up to the next heading of the same or a
+/// higher level.
+fn progress_of_sections(tasks: &[Task], headings: &[Heading]) -> Vec<Progress> {
+    headings
+        .iter()
+        .enumerate()
+        .map(|(index, heading)| {
+            let end = headings[index + 1..]
+                .iter()
+                .find(|next| next.level <= heading.level)
+                .map(|next| next.line)
+                .unwrap_or(usize::MAX);
+
+            let section_tasks: Vec<Task> = tasks
+                .iter()
+                .filter(|task| task.line > heading.line && task.line < end)
+                .copied()
+                .collect();
+
+            Progress::of(&section_tasks)
+        })
+        .collect()
+}
+
+fn collect<'a>(root: &'a AstNode<'a>) -> (Vec<Task>, Vec<Heading<'a>>) {
+    let mut tasks = Vec::new();
+    let mut headings = Vec::new();
+
+    for node in root.descendants() {
+        let data = node.data.borrow();
+        let line = data.sourcepos.start.line.saturating_sub(1);
+
+        match &data.value {
+            NodeValue::TaskItem(symbol) => tasks.push(Task {
+                line,
+                checked: symbol.is_some(),
+            }),
+            NodeValue::Heading(heading) => headings.push(Heading {
+                node,
+                line,
+                level: heading.level,
+            }),
+            _ => {}
+        }
+    }
+
+    (tasks, headings)
+}
+
+fn plain_text<'a>(node: &'a AstNode<'a>) -> String {
+    node.descendants()
+        .filter_map(|child| match &child.data.borrow().value {
+            NodeValue::Text(text) => Some(text.to_string()),
+            NodeValue::Code(code) => Some(code.literal.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Flips the task marker (`[ ]` ↔ `[x]`) of a single line.
+/// Returns `None` when the line is no task list item.
+///
+/// ```rust
+/// use rustynote::api::tasks::toggle_task_marker;
+///
+/// assert_eq!(toggle_task_marker("- [ ] Milk").as_deref(), Some("- [x] Milk"));
+/// assert_eq!(toggle_task_marker("> 1. [X] Eggs").as_deref(), Some("> 1. [ ] Eggs"));
+/// assert_eq!(toggle_task_marker("[ ] no list"), None);
+/// ```
+pub fn toggle_task_marker(line: &str) -> Option<String> {
+    let offset = task_marker_offset(line)?;
+    let replacement = if &line[offset..offset + 3] == "[ ]" {
+        "[x]"
+    } else {
+        "[ ]"
+    };
+
+    Some(format!(
+        "{}{}{}",
+        &line[..offset],
+        replacement,
+        &line[offset + 3..]
+    ))
+}
+
+/// Byte offset of the `[ ]`/`[x]` marker in a task list line.
+fn task_marker_offset(line: &str) -> Option<usize> {
+    let offset = line.find('[')?;
+    let prefix = &line[..offset];
+    let marker = line.get(offset..offset + 3)?;
+    let after = &line[offset + 3..];
+
+    let is_list_prefix = prefix.ends_with([' ', '\t'])
+        && prefix.trim_end().ends_with(['-', '*', '+', '.', ')'])
+        && prefix.chars().all(|c| {
+            matches!(c, '>' | '-' | '*' | '+' | '.' | ')' | ' ' | '\t') || c.is_ascii_digit()
+        });
+    let is_marker = matches!(marker, "[ ]" | "[x]" | "[X]");
+    let is_followed_by_space = after.is_empty() || after.starts_with([' ', '\t']);
+
+    (is_list_prefix && is_marker && is_followed_by_space).then_some(offset)
+}
+
+/// Toggles the task marker on the given zero based line.
+pub fn toggle_task_at_line(markdown: &str, line: usize) -> Option<String> {
+    let mut start = 0;
+    for _ in 0..line {
+        start += markdown[start..].find('\n')? + 1;
+    }
+    let end = markdown[start..]
+        .find('\n')
+        .map(|index| start + index)
+        .unwrap_or(markdown.len());
+
+    let toggled = toggle_task_marker(markdown[start..end].trim_end_matches('\r'))?;
+    let line_end = start + markdown[start..end].trim_end_matches('\r').len();
+
+    Some(format!(
+        "{}{}{}",
+        &markdown[..start],
+        toggled,
+        &markdown[line_end..]
+    ))
+}
+
+/// Toggles the task with the given index, counted in document order.
+///
+/// ```rust
+/// use rustynote::api::{parser::Dialect, tasks::toggle_task};
+///
+/// let markdown = "- [ ] first\n- [ ] second";
+/// assert_eq!(
+///     toggle_task(markdown, &Dialect::GitHub, 1).as_deref(),
+///     Some("- [ ] first\n- [x] second")
+/// );
+/// ```
+pub fn toggle_task(markdown: &str, dialect: &Dialect, index: usize) -> Option<String> {
+    let task = *tasks(markdown, dialect).get(index)?;
+    toggle_task_at_line(markdown, task.line)
+}
+
+/// Makes the task checkboxes of rendered HTML clickable and numbers them with
+/// a `data-task` attribute, matching the indices of [`tasks`].
+///
+/// ```rust
+/// use rustynote::api::tasks::enable_task_checkboxes;
+///
+/// let html = enable_task_checkboxes("<li><input type=\"checkbox\" disabled=\"\" /> a</li>");
+/// assert_eq!(html, "<li><input type=\"checkbox\" data-task=\"0\" /> a</li>");
+/// ```
+pub fn enable_task_checkboxes(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+    let mut index = 0;
+
+    while let Some(position) = rest.find(CHECKBOX) {
+        let input_end = position + rest[position..].find('>').unwrap_or(rest.len() - position);
+        let input = &rest[position..input_end].replacen(DISABLED, "", 1);
+
+        result.push_str(&rest[..position]);
+        result.push_str(CHECKBOX);
+        result.push_str(&format!(" data-task=\"{index}\""));
+        result.push_str(&input[CHECKBOX.len()..]);
+
+        rest = &rest[input_end..];
+        index += 1;
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GITHUB: &Dialect = &Dialect::GitHub;
+
+    const TODO: &str = "# Trip\n\
+        - [x] Book train\n\
+        - [ ] Book hotel\n\
+        \n\
+        ## Packing\n\
+        - [ ] Socks\n\
+        - [X] Charger\n\
+        \n\
+        # Notes\n\
+        Nothing to do\n";
+
+    #[test]
+    fn test_tasks_in_document_order() {
+        let tasks = tasks(TODO, GITHUB);
+
+        assert_eq!(
+            tasks,
+            vec![
+                Task {
+                    line: 1,
+                    checked: true
+                },
+                Task {
+                    line: 2,
+                    checked: false
+                },
+                Task {
+                    line: 5,
+                    checked: false
+                },
+                Task {
+                    line: 6,
+                    checked: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tasks_ignore_code_blocks() {
+        let markdown = "```\n- [ ] not a task\n```\n- [ ] task";
+
+        assert_eq!(
+            tasks(markdown, GITHUB),
+            vec![Task {
+                line: 3,
+                checked: false
+            }]
+        );
+    }
+
+    #[test]
+    fn test_document_progress() {
+        let progress = document_progress(TODO, GITHUB);
+
+        assert_eq!(progress, Progress { done: 2, total: 4 });
+        assert_eq!(progress.to_string(), "2/4 done");
+        assert_eq!(progress.ratio(), 0.5);
+    }
+
+    #[test]
+    fn test_empty_progress() {
+        let progress = document_progress("no tasks", GITHUB);
+
+        assert_eq!(progress.total, 0);
+        assert_eq!(progress.ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_section_progress_includes_subsections() {
+        let sections = section_progress(TODO, GITHUB);
+
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].heading, "Trip");
+        assert_eq!(sections[0].progress, Progress { done: 2, total: 4 });
+        assert_eq!(sections[1].heading, "Packing");
+        assert_eq!(sections[1].level, 2);
+        assert_eq!(sections[1].progress, Progress { done: 1, total: 2 });
+        assert_eq!(sections[2].progress, Progress { done: 0, total: 0 });
+    }
+
+    #[test]
+    fn test_toggle_task_marker() {
+        assert_eq!(toggle_task_marker("- [ ]").as_deref(), Some("- [x]"));
+        assert_eq!(
+            toggle_task_marker("  * [x] nested").as_deref(),
+            Some("  * [ ] nested")
+        );
+        assert_eq!(
+            toggle_task_marker("3) [ ] numbered").as_deref(),
+            Some("3) [x] numbered")
+        );
+    }
+
+    #[test]
+    fn test_toggle_task_marker_ignores_non_tasks() {
+        assert_eq!(toggle_task_marker("- [link](url)"), None);
+        assert_eq!(toggle_task_marker("- [ ]text"), None);
+        assert_eq!(toggle_task_marker("text [ ] text"), None);
+        assert_eq!(toggle_task_marker("- plain item"), None);
+    }
+
+    #[test]
+    fn test_toggle_task_at_line_keeps_crlf() {
+        let markdown = "# Todo\r\n- [ ] a\r\n- [ ] b\r\n";
+
+        assert_eq!(
+            toggle_task_at_line(markdown, 2).as_deref(),
+            Some("# Todo\r\n- [ ] a\r\n- [x] b\r\n")
+        );
+    }
+
+    #[test]
+    fn test_toggle_task_by_index() {
+        let toggled = toggle_task(TODO, GITHUB, 2).unwrap();
+
+        assert!(toggled.contains("- [x] Socks"));
+        assert_eq!(document_progress(&toggled, GITHUB).done, 3);
+    }
+
+    #[test]
+    fn test_toggle_task_out_of_range() {
+        assert_eq!(toggle_task(TODO, GITHUB, 4), None);
+    }
+
+    #[test]
+    fn test_enable_task_checkboxes_numbers_inputs() {
+        let html = "<li><input type=\"checkbox\" checked=\"\" disabled=\"\" /> a</li>\n\
+                    <li><input type=\"checkbox\" disabled=\"\" /> b</li>";
+
+        assert_eq!(
+            enable_task_checkboxes(html),
+            "<li><input type=\"checkbox\" data-task=\"0\" checked=\"\" /> a</li>\n\
+             <li><input type=\"checkbox\" data-task=\"1\" /> b</li>"
+        );
+    }
+
+    #[test]
+    fn test_rendered_checkboxes_match_tasks() {
+        let html = Dialect::GitHub.parse_markdown_to_html(TODO);
+
+        assert_eq!(
+            enable_task_checkboxes(&html).matches("data-task=").count(),
+            tasks(TODO, GITHUB).len()
+        );
+    }
+
+    #[test]
+    fn test_heading_progress_follows_the_syntax_tree() {
+        let markdown = "<h2>Raw</h2>\n\n# Trip\n- [x] Book\n\n`</h1>`\n\n## Packing\n- [ ] Socks";
+        let html = Dialect::GitHub.parse_markdown_to_html_with(markdown, add_heading_progress);
+
+        assert!(html.contains("<h1>Trip <span class=\"tag task-progress\">1/2 done</span></h1>"));
+        assert!(
+            html.contains("<h2>Packing <span class=\"tag task-progress\">0/1 done</span></h2>")
+        );
+        assert_eq!(html.matches("task-progress").count(), 2);
+    }
+
+    #[test]
+    fn test_no_progress_without_task_lists() {
+        let markdown = "# Trip\n- [x] Book";
+
+        assert_eq!(document_progress(markdown, &Dialect::Common).total, 0);
+        assert!(
+            !Dialect::Common
+                .parse_markdown_to_html_with(markdown, add_heading_progress)
+                .contains("task-progress")
+        );
+    }
+}
@@ -0,0 +1,478 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use web_sys::js_sys::Date;
+
+/// Where the caret is placed after a template or snippet was inserted.
+pub const CURSOR_MARKER: &str = "{{cursor}}";
+const SNIPPET_PREFIX: char = '/';
+
+/// A document skeleton for a new note.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Template {
+    pub name: String,
+    pub content: String,
+}
+
+impl Template {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err(String::from("The template needs a name"));
+        }
+        Ok(())
+    }
+}
+
+/// Text that replaces its trigger, e.g. `/adr`, when Tab is pressed after it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snippet {
+    pub trigger: String,
+    pub content: String,
+}
+
+impl Snippet {
+    pub fn validate(&self) -> Result<(), String> {
+        let valid = self
+            .trigger
+            .strip_prefix(SNIPPET_PREFIX)
+            .is_some_and(|name| !name.is_empty() && name.chars().all(is_trigger_char));
+        if !valid {
+            return Err(String::from(
+                "The trigger has to start with / followed by letters, digits, - or _",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Values for the placeholders of templates and snippets.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Placeholders {
+    /// `{{title}}`, the name of the document.
+    pub title: String,
+    /// `{{date}}` as `YYYY-MM-DD`.
+    pub date: String,
+    /// `{{time}}` as `HH:MM`.
+    pub time: String,
+}
+
+impl Placeholders {
+    /// The placeholders for a document with the current local date and time.
+    pub fn now(title: &str) -> Self {
+        let now = Date::new_0();
+        Placeholders {
+            title: title.to_string(),
+            date: format!(
+                "{:04}-{:02}-{:02}",
+                now.get_full_year(),
+                now.get_month() + 1,
+                now.get_date()
+            ),
+            time: format!("{:02}:{:02}", now.get_hours(), now.get_minutes()),
+        }
+    }
+}
+
+/// A filled in template or snippet. `cursor` is the byte position of the first
+/// cursor marker or the end of the text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expansion {
+    pub text: String,
+    pub cursor: usize,
+}
+
+/// Fills in the placeholders. Unknown placeholders are kept as they are.
+///
+/// ```rust
+/// use rustynote::api::templates::{Placeholders, expand};
+///
+/// let placeholders = Placeholders {
+///     title: String::from("Standup"),
+///     date: String::from("2024-05-01"),
+///     time: String::from("09:30"),
+/// };
+/// let expansion = expand("# {{title}} {{date}}\n\n- {{cursor}}", &placeholders);
+///
+/// assert_eq!(expansion.text, "# Standup 2024-05-01\n\n- ");
+/// assert_eq!(expansion.cursor, expansion.text.len());
+/// ```
+pub fn expand(content: &str, placeholders: &Placeholders) -> Expansion {
+    let filled = content
+        .replace("{{title}}", &placeholders.title)
+        .replace("{{date}}", &placeholders.date)
+        .replace("{{time}}", &placeholders.time);
+
+    let cursor = filled.find(CURSOR_MARKER);
+    let text = filled.replace(CURSOR_MARKER, "");
+
+    Expansion {
+        cursor: cursor.unwrap_or(text.len()),
+        text,
+    }
+}
+
+/// Replaces the snippet trigger right before `caret` with the snippet. Lines after the
+/// first get the indentation of the line the trigger is on. Returns the new text and
+/// caret, or `None` when there is no known trigger before the caret.
+pub fn expand_snippet(
+    text: &str,
+    caret: usize,
+    library: &TemplateLibrary,
+    placeholders: &Placeholders,
+) -> Option<(String, usize)> {
+    let before = &text[..caret];
+    let line_start = before.rfind('\n').map_or(0, |start| start + 1);
+    let line = &before[line_start..];
+    let trigger_start = line
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map_or(0, |(index, c)| index + c.len_utf8());
+
+    let snippet = library.snippet(&line[trigger_start..])?;
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let expansion = expand(&snippet.content, placeholders);
+
+    let indented = |text: &str| text.replace('\n', &format!("\n{indent}"));
+    let start = line_start + trigger_start;
+    let new_caret = start + indented(&expansion.text[..expansion.cursor]).len();
+
+    let mut result = String::with_capacity(text.len() + expansion.text.len());
+    result.push_str(&text[..start]);
+    result.push_str(&indented(&expansion.text));
+    result.push_str(&text[caret..]);
+
+    Some((result, new_caret))
+}
+
+fn is_trigger_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_')
+}
+
+/// The templates and snippets of the user, persisted as JSON. They are listed together
+/// with the built-in ones, a user template or snippet with the same name replaces the
+/// built-in one.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TemplateLibrary {
+    #[serde(default)]
+    pub templates: Vec<Template>,
+    #[serde(default)]
+    pub snippets: Vec<Snippet>,
+}
+
+impl TemplateLibrary {
+    /// Built-in and user templates.
+    pub fn templates(&self) -> Vec<Template> {
+        merge(builtin_templates(), &self.templates, |template| {
+            &template.name
+        })
+    }
+
+    pub fn template(&self, name: &str) -> Option<Template> {
+        self.templates()
+            .into_iter()
+            .find(|template| template.name == name)
+    }
+
+    /// Built-in and user snippets.
+    pub fn snippets(&self) -> Vec<Snippet> {
+        merge(builtin_snippets(), &self.snippets, |snippet| {
+            &snippet.trigger
+        })
+    }
+
+    pub fn snippet(&self, trigger: &str) -> Option<Snippet> {
+        self.snippets()
+            .into_iter()
+            .find(|snippet| snippet.trigger == trigger)
+    }
+
+    pub fn is_custom_template(&self, name: &str) -> bool {
+        self.templates.iter().any(|template| template.name == name)
+    }
+
+    pub fn is_custom_snippet(&self, trigger: &str) -> bool {
+        self.snippets
+            .iter()
+            .any(|snippet| snippet.trigger == trigger)
+    }
+
+    /// Adds the template or replaces the user template with the same name.
+    pub fn save_template(&mut self, template: Template) {
+        match self
+            .templates
+            .iter_mut()
+            .find(|known| known.name == template.name)
+        {
+            Some(known) => *known = template,
+            None => self.templates.push(template),
+        }
+    }
+
+    /// Removes a user template. A built-in template it replaced shows up again.
+    pub fn remove_template(&mut self, name: &str) {
+        self.templates.retain(|template| template.name != name);
+    }
+
+    /// Adds the snippet or replaces the user snippet with the same trigger.
+    pub fn save_snippet(&mut self, snippet: Snippet) {
+        match self
+            .snippets
+            .iter_mut()
+            .find(|known| known.trigger == snippet.trigger)
+        {
+            Some(known) => *known = snippet,
+            None => self.snippets.push(snippet),
+        }
+    }
+
+    /// Removes a user snippet. A built-in snippet it replaced shows up again.
+    pub fn remove_snippet(&mut self, trigger: &str) {
+        self.snippets.retain(|snippet| snippet.trigger != trigger);
+    }
+}
+
+impl fmt::Display for TemplateLibrary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?
+        )
+    }
+}
+
+impl FromStr for TemplateLibrary {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(|_| ())
+    }
+}
+
+/// The built-in entries in their order, replaced by user entries with the same key,
+/// followed by the other user entries.
+fn merge<T: Clone>(builtin: Vec<T>, custom: &[T], key: impl Fn(&T) -> &String) -> Vec<T> {
+    let mut merged = builtin
+        .into_iter()
+        .map(|entry| {
+            custom
+                .iter()
+                .find(|custom| key(custom) == key(&entry))
+                .cloned()
+                .unwrap_or(entry)
+        })
+        .collect::<Vec<_>>();
+
+    for entry in custom {
+        if !merged.iter().any(|known| key(known) == key(entry)) {
+            merged.push(entry.clone());
+        }
+    }
+    merged
+}
+
+fn template(name: &str, content: &str) -> Template {
+    Template {
+        name: name.to_string(),
+        content: content.to_string(),
+    }
+}
+
+fn snippet(trigger: &str, content: &str) -> Snippet {
+    Snippet {
+        trigger: trigger.to_string(),
+        content: content.to_string(),
+    }
+}
+
+pub fn builtin_templates() -> Vec<Template> {
+    vec![
+        template(
+            "Meeting Notes",
+            "# {{title}}\n\n\
+             **Date:** {{date}}  \n\
+             **Attendees:** {{cursor}}\n\n\
+             ## Agenda\n\n- \n\n\
+             ## Notes\n\n\
+             ## Action Items\n\n- [ ] \n",
+        ),
+        template(
+            "Incident Report",
+            "# Incident: {{title}}\n\n\
+             **Date:** {{date}}  \n\
+             **Severity:** {{cursor}}  \n\
+             **Status:** Investigating\n\n\
+             ## Summary\n\n\
+             ## Timeline\n\n- {{time}} – Incident detected\n\n\
+             ## Impact\n\n\
+             ## Root Cause\n\n\
+             ## Resolution\n\n\
+             ## Follow-up\n\n- [ ] \n",
+        ),
+        template(
+            "Architecture Decision Record",
+            "# ADR: {{title}}\n\n\
+             **Date:** {{date}}  \n\
+             **Status:** Proposed\n\n\
+             ## Context\n\n{{cursor}}\n\n\
+             ## Decision\n\n\
+             ## Consequences\n",
+        ),
+        template(
+            "Daily Note",
+            "# {{date}}\n\n\
+             ## Tasks\n\n- [ ] {{cursor}}\n\n\
+             ## Notes\n",
+        ),
+    ]
+}
+
+pub fn builtin_snippets() -> Vec<Snippet> {
+    vec![
+        snippet(
+            "/adr",
+            "## Decision: {{cursor}}\n\n\
+             **Date:** {{date}}  \n\
+             **Status:** Proposed\n\n\
+             ### Context\n\n\
+             ### Decision\n\n\
+             ### Consequences\n",
+        ),
+        snippet(
+            "/meeting",
+            "## Meeting {{date}}\n\n**Attendees:** {{cursor}}\n\n### Action Items\n\n- [ ] \n",
+        ),
+        snippet("/todo", "- [ ] {{cursor}}"),
+        snippet("/date", "{{date}}"),
+        snippet("/time", "{{time}}"),
+        snippet(
+            "/table",
+            "| {{cursor}} | Column |\n| ------ | ------ |\n|        |        |",
+        ),
+        snippet("/code", "```\n{{cursor}}\n```"),
+        snippet(
+            "/details",
+            "<details>\n<summary>{{cursor}}</summary>\n\n</details>",
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders() -> Placeholders {
+        Placeholders {
+            title: String::from("Outage"),
+            date: String::from("2024-05-01"),
+            time: String::from("09:30"),
+        }
+    }
+
+    #[test]
+    fn test_expand_without_cursor_marker_ends_at_text_end() {
+        let expansion = expand("{{date}} {{unknown}}", &placeholders());
+
+        assert_eq!(expansion.text, "2024-05-01 {{unknown}}");
+        assert_eq!(expansion.cursor, expansion.text.len());
+    }
+
+    #[test]
+    fn test_expand_uses_first_cursor_marker() {
+        let expansion = expand("a{{cursor}}b{{cursor}}c", &placeholders());
+
+        assert_eq!(expansion.text, "abc");
+        assert_eq!(expansion.cursor, 1);
+    }
+
+    #[test]
+    fn test_expand_snippet_replaces_trigger() {
+        let text = "Done: /todo";
+        let (new_text, caret) = expand_snippet(
+            text,
+            text.len(),
+            &TemplateLibrary::default(),
+            &placeholders(),
+        )
+        .unwrap();
+
+        assert_eq!(new_text, "Done: - [ ] ");
+        assert_eq!(caret, new_text.len());
+    }
+
+    #[test]
+    fn test_expand_snippet_keeps_indentation_and_following_text() {
+        let text = "- item\n  /code\nrest";
+        let (new_text, caret) =
+            expand_snippet(text, 14, &TemplateLibrary::default(), &placeholders()).unwrap();
+
+        assert_eq!(new_text, "- item\n  ```\n  \n  ```\nrest");
+        assert_eq!(&new_text[..caret], "- item\n  ```\n  ");
+    }
+
+    #[test]
+    fn test_unknown_or_partial_trigger_is_ignored() {
+        let library = TemplateLibrary::default();
+
+        assert_eq!(
+            expand_snippet("/nothing", 8, &library, &placeholders()),
+            None
+        );
+        assert_eq!(
+            expand_snippet("path/todo", 9, &library, &placeholders()),
+            None
+        );
+        assert_eq!(expand_snippet("/tod", 4, &library, &placeholders()), None);
+    }
+
+    #[test]
+    fn test_custom_entries_replace_builtin_ones() {
+        let mut library = TemplateLibrary::default();
+        library.save_snippet(snippet("/todo", "TODO: {{cursor}}"));
+        library.save_snippet(snippet("/sig", "Cheers"));
+        library.save_template(template("Daily Note", "# Today"));
+
+        assert_eq!(
+            library.snippet("/todo").unwrap().content,
+            "TODO: {{cursor}}"
+        );
+        assert_eq!(library.snippets().last().unwrap().trigger, "/sig");
+        assert_eq!(library.snippets().len(), builtin_snippets().len() + 1);
+        assert_eq!(library.template("Daily Note").unwrap().content, "# Today");
+
+        library.remove_template("Daily Note");
+        assert_eq!(
+            library.template("Daily Note"),
+            builtin_templates()
+                .into_iter()
+                .find(|t| t.name == "Daily Note")
+        );
+    }
+
+    #[test]
+    fn test_snippet_trigger_validation() {
+        assert!(snippet("/adr-2", "").validate().is_ok());
+        assert!(snippet("adr", "").validate().is_err());
+        assert!(snippet("/", "").validate().is_err());
+        assert!(snippet("/two words", "").validate().is_err());
+    }
+
+    #[test]
+    fn test_builtin_entries_are_valid() {
+        for template in builtin_templates() {
+            assert!(template.validate().is_ok());
+        }
+        for snippet in builtin_snippets() {
+            assert!(snippet.validate().is_ok(), "{}", snippet.trigger);
+        }
+    }
+
+    #[test]
+    fn test_library_round_trip() {
+        let mut library = TemplateLibrary::default();
+        library.save_template(template("Weekly", "# Week of {{date}}"));
+
+        assert_eq!(library.to_string().parse(), Ok(library));
+    }
+}
@@ -0,0 +1,522 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Built-in colour scheme a custom theme is based on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BaseScheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl BaseScheme {
+    /// Value for the `data-theme` attribute of the document.
+    pub fn data_theme(self) -> &'static str {
+        match self {
+            BaseScheme::Light => "light",
+            BaseScheme::Dark => "dark",
+        }
+    }
+}
+
+/// A user-defined theme for the editor and the preview.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomTheme {
+    pub name: String,
+    pub base: BaseScheme,
+    pub accent: String,
+    pub editor_background: String,
+    pub editor_text: String,
+    pub preview_background: String,
+    pub preview_text: String,
+    pub font_family: String,
+    pub editor_font_family: String,
+    /// Font size in pixels.
+    pub font_size: f32,
+    pub line_height: f32,
+}
+
+impl Default for CustomTheme {
+    fn default() -> Self {
+        CustomTheme {
+            name: String::from("My Theme"),
+            base: BaseScheme::Light,
+            accent: String::from("#ee5c13"),
+            editor_background: String::from("#ffffff"),
+            editor_text: String::from("#303030"),
+            preview_background: String::from("#ffffff"),
+            preview_text: String::from("#303030"),
+            font_family: String::from("sans-serif"),
+            editor_font_family: String::from("monospace"),
+            font_size: 16.0,
+            line_height: 1.5,
+        }
+    }
+}
+
+/// Names reserved for the built-in themes.
+const RESERVED_NAMES: &[&str] = &["light", "dark", "system"];
+
+impl CustomTheme {
+    /// Checks that the theme can be stored and safely turned into CSS.
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err(String::from("The theme needs a name"));
+        }
+        if RESERVED_NAMES.contains(&name.to_lowercase().as_str()) {
+            return Err(format!("\"{name}\" is the name of a built-in theme"));
+        }
+
+        for (label, color) in [
+            ("accent", &self.accent),
+            ("editor background", &self.editor_background),
+            ("editor text", &self.editor_text),
+            ("preview background", &self.preview_background),
+            ("preview text", &self.preview_text),
+        ] {
+            if parse_hex_color(color).is_none() {
+                return Err(format!("The {label} colour \"{color}\" is no hex colour"));
+            }
+        }
+
+        for font in [&self.font_family, &self.editor_font_family] {
+            if !is_safe_font_family(font) {
+                return Err(format!("The font \"{font}\" contains invalid characters"));
+            }
+        }
+
+        if !(8.0..=48.0).contains(&self.font_size) {
+            return Err(String::from(
+                "The font size must be between 8 and 48 pixels",
+            ));
+        }
+        if !(1.0..=3.0).contains(&self.line_height) {
+            return Err(String::from("The line height must be between 1 and 3"));
+        }
+
+        Ok(())
+    }
+
+    /// Renders the theme as CSS custom properties for the `style` attribute of the document.
+    ///
+    /// ```rust
+    /// use rustynote::api::themes::CustomTheme;
+    ///
+    /// let css = CustomTheme::default().to_css_variables();
+    /// assert!(css.contains("--theme-editor-background: #ffffff;"));
+    /// assert!(css.contains("--bulma-primary-h: 20deg;"));
+    /// ```
+    pub fn to_css_variables(&self) -> String {
+        let mut css = String::new();
+
+        if let Some((hue, saturation, lightness)) = parse_hex_color(&self.accent).map(to_hsl) {
+            css.push_str(&format!(
+                "--bulma-primary-h: {hue:.0}deg; --bulma-primary-s: {saturation:.0}%; --bulma-primary-l: {lightness:.0}%; "
+            ));
+        }
+
+        css.push_str(&format!(
+            "--theme-editor-background: {}; --theme-editor-text: {}; \
+             --theme-preview-background: {}; --theme-preview-text: {}; \
+             --theme-font-family: {}; --theme-editor-font-family: {}; \
+             --theme-font-size: {}px; --theme-line-height: {};",
+            self.editor_background,
+            self.editor_text,
+            self.preview_background,
+            self.preview_text,
+            self.font_family,
+            self.editor_font_family,
+            self.font_size,
+            self.line_height,
+        ));
+
+        css
+    }
+
+    /// Serialises the theme as pretty printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Serialises the theme as a flat TOML document.
+    ///
+    /// ```rust
+    /// use rustynote::api::themes::CustomTheme;
+    ///
+    /// let theme = CustomTheme::default();
+    /// assert_eq!(CustomTheme::from_toml(&theme.to_toml()), Ok(theme));
+    /// ```
+    pub fn to_toml(&self) -> String {
+        let strings = [
+            ("name", &self.name),
+            ("base", &self.base.data_theme().to_string()),
+            ("accent", &self.accent),
+            ("editor_background", &self.editor_background),
+            ("editor_text", &self.editor_text),
+            ("preview_background", &self.preview_background),
+            ("preview_text", &self.preview_text),
+            ("font_family", &self.font_family),
+            ("editor_font_family", &self.editor_font_family),
+        ];
+
+        let mut toml: String = strings
+            .iter()
+            .map(|(key, value)| format!("{key} = {}\n", toml_string(value)))
+            .collect();
+        toml.push_str(&format!("font_size = {:?}\n", self.font_size));
+        toml.push_str(&format!("line_height = {:?}\n", self.line_height));
+        toml
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        serde_json::from_str::<CustomTheme>(text)
+            .map_err(|error| format!("Invalid theme file: {error}"))
+            .and_then(Self::validated)
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        toml::from_str::<CustomTheme>(text)
+            .map_err(|error| format!("Invalid theme file: {error}"))
+            .and_then(Self::validated)
+    }
+
+    /// Imports a theme file, choosing the format by the file extension.
+    pub fn from_file(file_name: &str, text: &str) -> Result<Self, String> {
+        if file_name.to_lowercase().ends_with(".toml") {
+            Self::from_toml(text)
+        } else {
+            Self::from_json(text)
+        }
+    }
+
+    /// File name used when exporting the theme, e.g. `my-theme.json`.
+    pub fn file_name(&self, extension: &str) -> String {
+        let slug: String = self
+            .name
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect();
+        let slug = slug
+            .split('-')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<&str>>()
+            .join("-");
+
+        format!(
+            "{}.{extension}",
+            if slug.is_empty() { "theme" } else { &slug }
+        )
+    }
+
+    fn validated(self) -> Result<Self, String> {
+        self.validate().map(|()| self)
+    }
+}
+
+/// All custom themes of the user, persisted as JSON.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ThemeLibrary {
+    pub themes: Vec<CustomTheme>,
+}
+
+impl ThemeLibrary {
+    pub fn get(&self, name: &str) -> Option<&CustomTheme> {
+        self.themes.iter().find(|theme| theme.name == name)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.themes.iter().map(|theme| theme.name.clone()).collect()
+    }
+
+    /// Adds the theme or replaces the theme with the same name.
+    pub fn save(&mut self, theme: CustomTheme) {
+        match self
+            .themes
+            .iter_mut()
+            .find(|known| known.name == theme.name)
+        {
+            Some(known) => *known = theme,
+            None => self.themes.push(theme),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.themes.retain(|theme| theme.name != name);
+    }
+}
+
+impl fmt::Display for ThemeLibrary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?
+        )
+    }
+}
+
+impl FromStr for ThemeLibrary {
+    type Err = ();
+
+    /// Themes that fail validation are dropped instead of breaking the whole library.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let library: ThemeLibrary = serde_json::from_str(s).map_err(|_| ())?;
+
+        Ok(ThemeLibrary {
+            themes: library
+                .themes
+                .into_iter()
+                .filter(|theme| theme.validate().is_ok())
+                .collect(),
+        })
+    }
+}
+
+/// Parses `#rgb` and `#rrggbb` colours.
+fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+    match hex.len() {
+        3 => {
+            let doubled: Vec<String> = hex.chars().map(|c| format!("{c}{c}")).collect();
+            Some((
+                channel(&doubled[0])?,
+                channel(&doubled[1])?,
+                channel(&doubled[2])?,
+            ))
+        }
+        6 => Some((
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        )),
+        _ => None,
+    }
+}
+
+/// Converts an RGB colour to hue (degrees), saturation and lightness (percent).
+fn to_hsl((red, green, blue): (u8, u8, u8)) -> (f32, f32, f32) {
+    let (r, g, b) = (
+        red as f32 / 255.0,
+        green as f32 / 255.0,
+        blue as f32 / 255.0,
+    );
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness * 100.0);
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+
+    (hue, saturation * 100.0, lightness * 100.0)
+}
+
+fn is_safe_font_family(font: &str) -> bool {
+    !font.trim().is_empty()
+        && font
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | ',' | '-' | '_' | '"' | '\''))
+}
+
+fn toml_string(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dark_theme() -> CustomTheme {
+        CustomTheme {
+            name: String::from("Night Owl"),
+            base: BaseScheme::Dark,
+            accent: String::from("#7fdbca"),
+            editor_background: String::from("#011627"),
+            editor_text: String::from("#d6deeb"),
+            preview_background: String::from("#0b2942"),
+            preview_text: String::from("#fff"),
+            font_family: String::from("\"Fira Sans\", sans-serif"),
+            editor_font_family: String::from("Fira Code, monospace"),
+            font_size: 15.0,
+            line_height: 1.6,
+        }
+    }
+
+    #[test]
+    fn test_default_theme_is_valid() {
+        assert_eq!(CustomTheme::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let theme = dark_theme();
+
+        assert_eq!(CustomTheme::from_json(&theme.to_json()), Ok(theme));
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let theme = dark_theme();
+
+        assert_eq!(CustomTheme::from_toml(&theme.to_toml()), Ok(theme));
+    }
+
+    #[test]
+    fn test_toml_export() {
+        let toml = dark_theme().to_toml();
+
+        assert!(toml.starts_with("name = \"Night Owl\"\nbase = \"dark\"\n"));
+        assert!(toml.contains("font_family = \"\\\"Fira Sans\\\", sans-serif\"\n"));
+        assert!(toml.ends_with("font_size = 15.0\nline_height = 1.6\n"));
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let theme =
+            CustomTheme::from_json(r##"{ "name": "Minimal", "accent": "#123456" }"##).unwrap();
+
+        assert_eq!(theme.accent, "#123456");
+        assert_eq!(theme.font_size, CustomTheme::default().font_size);
+    }
+
+    #[test]
+    fn test_from_file_chooses_format_by_extension() {
+        let theme = dark_theme();
+
+        assert_eq!(
+            CustomTheme::from_file("night.TOML", &theme.to_toml()),
+            Ok(theme.clone())
+        );
+        assert_eq!(
+            CustomTheme::from_file("night.json", &theme.to_json()),
+            Ok(theme)
+        );
+        assert!(CustomTheme::from_file("night.json", "name = 'x'").is_err());
+    }
+
+    #[test]
+    fn test_rejects_css_injection() {
+        let mut theme = dark_theme();
+        theme.editor_text = String::from("red; background: url(evil)");
+        assert!(theme.validate().is_err());
+
+        let mut theme = dark_theme();
+        theme.font_family = String::from("serif; } body { display: none");
+        assert!(theme.validate().is_err());
+    }
+
+    #[test]
+    fn test_rejects_reserved_and_empty_names() {
+        let mut theme = dark_theme();
+        theme.name = String::from("Dark");
+        assert!(theme.validate().is_err());
+
+        theme.name = String::from("  ");
+        assert!(theme.validate().is_err());
+    }
+
+    #[test]
+    fn test_rejects_out_of_range_sizes() {
+        let mut theme = dark_theme();
+        theme.font_size = 100.0;
+        assert!(theme.validate().is_err());
+
+        let mut theme = dark_theme();
+        theme.line_height = 0.5;
+        assert!(theme.validate().is_err());
+    }
+
+    #[test]
+    fn test_css_variables() {
+        let css = dark_theme().to_css_variables();
+
+        assert!(css.contains("--theme-editor-background: #011627;"));
+        assert!(css.contains("--theme-font-size: 15px;"));
+        assert!(css.contains("--theme-line-height: 1.6;"));
+        assert!(css.contains("--bulma-primary-h: 169deg;"));
+    }
+
+    #[test]
+    fn test_hex_colors() {
+        assert_eq!(parse_hex_color("#fff"), Some((255, 255, 255)));
+        assert_eq!(parse_hex_color("#EE5C13"), Some((238, 92, 19)));
+        assert_eq!(parse_hex_color("fff"), None);
+        assert_eq!(parse_hex_color("#ggg"), None);
+        assert_eq!(parse_hex_color("#ffff"), None);
+    }
+
+    #[test]
+    fn test_hsl_conversion() {
+        assert_eq!(to_hsl((255, 0, 0)), (0.0, 100.0, 50.0));
+        assert_eq!(to_hsl((0, 0, 255)), (240.0, 100.0, 50.0));
+        assert_eq!(to_hsl((128, 128, 128)).1, 0.0);
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(dark_theme().file_name("toml"), "night-owl.toml");
+
+        let mut theme = dark_theme();
+        theme.name = String::from("!!!");
+        assert_eq!(theme.file_name("json"), "theme.json");
+    }
+
+    #[test]
+    fn test_library_save_replaces_theme_with_same_name() {
+        let mut library = ThemeLibrary::default();
+        library.save(dark_theme());
+
+        let mut updated = dark_theme();
+        updated.font_size = 18.0;
+        library.save(updated);
+
+        assert_eq!(library.themes.len(), 1);
+        assert_eq!(library.get("Night Owl").unwrap().font_size, 18.0);
+    }
+
+    #[test]
+    fn test_library_round_trip_drops_invalid_themes() {
+        let mut invalid = dark_theme();
+        invalid.name = String::from("Broken");
+        invalid.accent = String::from("blue");
+
+        let library = ThemeLibrary {
+            themes: vec![dark_theme(), invalid],
+        };
+        let parsed: ThemeLibrary = library.to_string().parse().unwrap();
+
+        assert_eq!(parsed.names(), vec![String::from("Night Owl")]);
+    }
+
+    #[test]
+    fn test_library_remove() {
+        let mut library = ThemeLibrary {
+            themes: vec![dark_theme(), CustomTheme::default()],
+        };
+
+        library.remove("Night Owl");
+
+        assert_eq!(library.names(), vec![String::from("My Theme")]);
+    }
+}
@@ -0,0 +1,176 @@
+use comrak::{
+    Anchorizer, Arena, ComrakOptions, ExtensionOptions,
+    nodes::{AstNode, NodeValue},
+    parse_document,
+};
+
+/// A heading of a document with the anchor the preview gives it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeadingAnchor {
+    pub level: u8,
+    pub text: String,
+    pub anchor: String,
+}
+
+/// Lists the headings of a document in order, with GitHub-style anchors.
+/// Repeated titles get the anchors `title`, `title-1`, `title-2`, ….
+///
+/// ```rust
+/// use rustynote::api::toc::heading_anchors;
+///
+/// let headings = heading_anchors("# Intro\n## Set up `cargo`\n# Intro");
+/// let anchors: Vec<&str> = headings.iter().map(|heading| heading.anchor.as_str()).collect();
+/// assert_eq!(anchors, ["intro", "set-up-cargo", "intro-1"]);
+/// ```
+pub fn heading_anchors(markdown: &str) -> Vec<HeadingAnchor> {
+    let arena = Arena::new();
+    let options = ComrakOptions {
+        extension: ExtensionOptions {
+            table: true,
+            tasklist: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let root = parse_document(&arena, markdown, &options);
+    let mut anchorizer = Anchorizer::new();
+
+    root.descendants()
+        .filter_map(|node| {
+            let level = match &node.data.borrow().value {
+                NodeValue::Heading(heading) => heading.level,
+                _ => return None,
+            };
+            let text = plain_text(node);
+            Some(HeadingAnchor {
+                level,
+                anchor: anchorizer.anchorize(&text),
+                text,
+            })
+        })
+        .collect()
+}
+
+fn plain_text<'a>(node: &'a AstNode<'a>) -> String {
+    node.descendants()
+        .filter_map(|child| match &child.data.borrow().value {
+            NodeValue::Text(text) => Some(text.to_string()),
+            NodeValue::Code(code) => Some(code.literal.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Builds a nested list of links to the headings of a document.
+/// Returns an empty string when the document has no headings.
+///
+/// ```rust
+/// use rustynote::api::toc::table_of_contents;
+///
+/// assert_eq!(
+///     table_of_contents("## Usage\n### Install\n## FAQ"),
+///     "- [Usage](#usage)\n  - [Install](#install)\n- [FAQ](#faq)\n"
+/// );
+/// ```
+pub fn table_of_contents(markdown: &str) -> String {
+    let mut toc = String::new();
+    // Levels of the headings that enclose the current one.
+    let mut parents: Vec<u8> = Vec::new();
+    for heading in heading_anchors(markdown) {
+        while parents.last().is_some_and(|&level| level >= heading.level) {
+            parents.pop();
+        }
+        toc.push_str(&format!(
+            "{}- [{}](#{})\n",
+            "  ".repeat(parents.len()),
+            escape_label(&heading.text),
+            heading.anchor
+        ));
+        parents.push(heading.level);
+    }
+    toc
+}
+
+fn escape_label(text: &str) -> String {
+    text.replace('[', "\\[").replace(']', "\\]")
+}
+
+/// Gives every rendered heading the `id` of its anchor, so links like `[Usage](#usage)`
+/// jump to it.
+///
+/// `html` must be rendered from `markdown`, headings are matched in order.
+pub fn add_heading_ids(html: &str, markdown: &str) -> String {
+    let mut anchors = heading_anchors(markdown).into_iter();
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(position) = find_heading_start(rest) {
+        let Some(heading) = anchors.next() else {
+            break;
+        };
+        result.push_str(&rest[..position + 3]);
+        result.push_str(&format!(" id=\"{}\"", heading.anchor));
+        rest = &rest[position + 3..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+fn find_heading_start(html: &str) -> Option<usize> {
+    html.match_indices("<h").find_map(|(position, _)| {
+        let level = html.as_bytes().get(position + 2)?;
+        let next = html.as_bytes().get(position + 3)?;
+        ((b'1'..=b'6').contains(level) && matches!(next, b'>' | b' ')).then_some(position)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchors_follow_github() {
+        let headings = heading_anchors("# Hello, World!\n## Ünïcode Tïtle\n### a_b c-d");
+        let anchors: Vec<&str> = headings.iter().map(|h| h.anchor.as_str()).collect();
+        assert_eq!(anchors, ["hello-world", "ünïcode-tïtle", "a_b-c-d"]);
+    }
+
+    #[test]
+    fn test_headings_in_code_are_ignored() {
+        let headings = heading_anchors("```\n# not a heading\n```\n# Real");
+        assert_eq!(headings.len(), 1);
+        assert_eq!(headings[0].text, "Real");
+    }
+
+    #[test]
+    fn test_table_of_contents_nests_skipped_levels_by_one() {
+        assert_eq!(
+            table_of_contents("# Title\n### Deep\n## Mid\n# Next"),
+            "- [Title](#title)\n  - [Deep](#deep)\n  - [Mid](#mid)\n- [Next](#next)\n"
+        );
+    }
+
+    #[test]
+    fn test_table_of_contents_escapes_brackets() {
+        assert_eq!(
+            table_of_contents("# [Draft] Plan"),
+            "- [\\[Draft\\] Plan](#draft-plan)\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_table_of_contents() {
+        assert_eq!(table_of_contents("no headings"), "");
+    }
+
+    #[test]
+    fn test_add_heading_ids() {
+        let markdown = "# Trip\ntext\n## Packing";
+        let html = "<h1>Trip</h1>\n<p>text</p>\n<h2>Packing</h2>\n<hr />";
+        assert_eq!(
+            add_heading_ids(html, markdown),
+            "<h1 id=\"trip\">Trip</h1>\n<p>text</p>\n<h2 id=\"packing\">Packing</h2>\n<hr />"
+        );
+    }
+}
@@ -0,0 +1,511 @@
+use std::ops::Range;
+
+use crate::api::documents::{Document, DocumentLibrary};
+
+const OPEN: &str = "[[";
+const CLOSE: &str = "]]";
+/// How many note titles are suggested while typing a link.
+const MAX_SUGGESTIONS: usize = 8;
+/// Longest line shown as the context of a backlink.
+const MAX_CONTEXT: usize = 120;
+
+/// A `[[Note Title]]` or `[[Note Title|label]]` link in the markdown text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WikiLink {
+    pub target: String,
+    pub label: Option<String>,
+    /// Byte range of the whole link including the brackets.
+    pub range: Range<usize>,
+}
+
+impl WikiLink {
+    /// Whether the link points to a document called `name`, ignoring case.
+    pub fn points_to(&self, name: &str) -> bool {
+        self.target.to_lowercase() == name.trim().to_lowercase()
+    }
+}
+
+/// A note that links to the active document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backlink {
+    pub id: u64,
+    pub name: String,
+    /// The line with the first link.
+    pub context: String,
+}
+
+/// Finds all wiki links of a note. Links in code are ignored.
+///
+/// ```rust
+/// use rustynote::api::wikilinks::find_wikilinks;
+///
+/// let links = find_wikilinks("See [[Ideas]] and [[Plans|the plan]], not `[[code]]`.");
+/// assert_eq!(links.len(), 2);
+/// assert_eq!(links[1].target, "Plans");
+/// assert_eq!(links[1].label.as_deref(), Some("the plan"));
+/// ```
+pub fn find_wikilinks(markdown: &str) -> Vec<WikiLink> {
+    let mut links = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut offset = 0;
+
+    for line in markdown.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker))
+        {
+            fence = Some(marker);
+            continue;
+        }
+        if line.starts_with("    ") || line.starts_with('\t') {
+            continue;
+        }
+
+        links.extend(line_links(line.trim_end_matches('\n'), start));
+    }
+
+    links
+}
+
+fn line_links(line: &str, offset: usize) -> Vec<WikiLink> {
+    let mut links = Vec::new();
+    let mut position = 0;
+
+    while position < line.len() {
+        let rest = &line[position..];
+        if rest.starts_with('`') {
+            let ticks = rest.len() - rest.trim_start_matches('`').len();
+            let fence = &rest[..ticks];
+            position += match rest[ticks..].find(fence) {
+                Some(end) => ticks + end + ticks,
+                None => ticks,
+            };
+            continue;
+        }
+
+        if rest.starts_with(OPEN)
+            && let Some(link) = parse_link(&rest[OPEN.len()..])
+        {
+            let length = OPEN.len() + link.0 + CLOSE.len();
+            links.push(WikiLink {
+                target: link.1,
+                label: link.2,
+                range: offset + position..offset + position + length,
+            });
+            position += length;
+            continue;
+        }
+
+        position += rest.chars().next().map_or(1, char::len_utf8);
+    }
+
+    links
+}
+
+/// Reads `target|label]]` and returns the length of the inner text, the target and the
+/// label.
+fn parse_link(text: &str) -> Option<(usize, String, Option<String>)> {
+    let end = text.find(CLOSE)?;
+    let inner = &text[..end];
+    if inner.contains(['[', ']', '\n']) {
+        return None;
+    }
+
+    let (target, label) = match inner.split_once('|') {
+        Some((target, label)) => (target, Some(label.trim().to_string())),
+        None => (inner, None),
+    };
+    let target = target.trim();
+    (!target.is_empty()).then(|| (end, target.to_string(), label.filter(|l| !l.is_empty())))
+}
+
+/// Finds the document a link points to: a document with exactly this name, otherwise one
+/// whose name only differs in case.
+pub fn resolve<'a>(library: &'a DocumentLibrary, target: &str) -> Option<&'a Document> {
+    let target = target.trim();
+    library.find_by_name(target).or_else(|| {
+        let target = target.to_lowercase();
+        library
+            .documents
+            .iter()
+            .find(|document| document.name.to_lowercase() == target)
+    })
+}
+
+/// Turns the `[[links]]` left as text in rendered HTML into anchors with a
+/// `data-wikilink` attribute. Links to notes that don't exist get the `is-broken` class.
+/// Text in `<code>` is left alone.
+///
+/// ```rust
+/// use rustynote::api::wikilinks::link_wikilinks;
+///
+/// let html = link_wikilinks("<p>[[Ideas]] [[Gone|old]]</p>", |target| target == "Ideas");
+/// assert_eq!(
+///     html,
+///     "<p><a class=\"wikilink\" data-wikilink=\"Ideas\">Ideas</a> \
+///      <a class=\"wikilink is-broken\" data-wikilink=\"Gone\" title=\"No note is called Gone yet\">old</a></p>"
+/// );
+/// ```
+pub fn link_wikilinks(html: &str, exists: impl Fn(&str) -> bool) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+
+    loop {
+        let link = rest.find(OPEN);
+        let code = rest.find("<code");
+
+        match (link, code) {
+            (_, Some(code)) if link.is_none_or(|link| code < link) => {
+                let end = rest[code..]
+                    .find("</code>")
+                    .map_or(rest.len(), |end| code + end + "</code>".len());
+                result.push_str(&rest[..end]);
+                rest = &rest[end..];
+            }
+            (Some(link), _) => {
+                result.push_str(&rest[..link]);
+                let inner = &rest[link + OPEN.len()..];
+
+                match parse_link(inner).filter(|(length, ..)| !inner[..*length].contains('<')) {
+                    Some((length, target, label)) => {
+                        let unescaped = unescape(&target);
+                        let label = label.unwrap_or_else(|| target.clone());
+                        if exists(&unescaped) {
+                            result.push_str(&format!(
+                                "<a class=\"wikilink\" data-wikilink=\"{target}\">{label}</a>"
+                            ));
+                        } else {
+                            result.push_str(&format!(
+                                "<a class=\"wikilink is-broken\" data-wikilink=\"{target}\" \
+                                 title=\"No note is called {target} yet\">{label}</a>"
+                            ));
+                        }
+                        rest = &inner[length + CLOSE.len()..];
+                    }
+                    None => {
+                        result.push_str(OPEN);
+                        rest = inner;
+                    }
+                }
+            }
+            _ => break,
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Rewrites the links to a renamed note. Labels stay as they are.
+///
+/// ```rust
+/// use rustynote::api::wikilinks::rename_wikilinks;
+///
+/// assert_eq!(
+///     rename_wikilinks("[[old]] and [[Old|label]]", "Old", "New"),
+///     "[[New]] and [[New|label]]"
+/// );
+/// ```
+pub fn rename_wikilinks(markdown: &str, old_name: &str, new_name: &str) -> String {
+    let mut result = String::with_capacity(markdown.len());
+    let mut position = 0;
+
+    for link in find_wikilinks(markdown) {
+        if !link.points_to(old_name) {
+            continue;
+        }
+
+        result.push_str(&markdown[position..link.range.start]);
+        result.push_str(OPEN);
+        result.push_str(new_name);
+        if let Some(label) = &link.label {
+            result.push('|');
+            result.push_str(label);
+        }
+        result.push_str(CLOSE);
+        position = link.range.end;
+    }
+
+    result.push_str(&markdown[position..]);
+    result
+}
+
+/// Lists the notes linking to the active document, sorted by name. Encrypted notes are
+/// skipped, their text can't be read.
+pub fn backlinks(library: &DocumentLibrary) -> Vec<Backlink> {
+    let Some(active) = library.active() else {
+        return Vec::new();
+    };
+
+    let mut backlinks = library
+        .documents
+        .iter()
+        .filter(|document| document.id != active.id && document.sealed.is_none())
+        .filter_map(|document| {
+            let link = find_wikilinks(&document.content)
+                .into_iter()
+                .find(|link| link.points_to(&active.name))?;
+
+            Some(Backlink {
+                id: document.id,
+                name: document.name.clone(),
+                context: context(&document.content, &link.range),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    backlinks.sort_by_key(|backlink| backlink.name.to_lowercase());
+    backlinks
+}
+
+fn context(content: &str, range: &Range<usize>) -> String {
+    let start = content[..range.start]
+        .rfind('\n')
+        .map_or(0, |start| start + 1);
+    let end = content[range.end..]
+        .find('\n')
+        .map_or(content.len(), |end| range.end + end);
+    let line = content[start..end].trim();
+
+    match line.char_indices().nth(MAX_CONTEXT) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line.to_string(),
+    }
+}
+
+/// The part of a link typed so far, when the caret is inside an unclosed `[[`.
+///
+/// ```rust
+/// use rustynote::api::wikilinks::link_query;
+///
+/// assert_eq!(link_query("See [[Ide"), Some("Ide"));
+/// assert_eq!(link_query("See [[Ideas]] and"), None);
+/// ```
+pub fn link_query(before_caret: &str) -> Option<&str> {
+    let line = &before_caret[before_caret.rfind('\n').map_or(0, |start| start + 1)..];
+    let start = line.rfind(OPEN)? + OPEN.len();
+    let query = &line[start..];
+
+    (!query.contains([']', '|'])).then_some(query)
+}
+
+/// Titles of other notes for a partly typed link: names starting with the query come
+/// first, then names containing it. Without a query the recently modified notes are
+/// suggested.
+pub fn suggest_titles(library: &DocumentLibrary, query: &str) -> Vec<String> {
+    let query = query.trim().to_lowercase();
+    let mut candidates = library
+        .documents
+        .iter()
+        .filter(|document| document.id != library.active && !document.has_default_name())
+        .filter_map(|document| {
+            let name = document.name.to_lowercase();
+            let rank = if name.starts_with(&query) {
+                0
+            } else if name.contains(&query) {
+                1
+            } else {
+                return None;
+            };
+            Some((rank, document))
+        })
+        .collect::<Vec<_>>();
+
+    candidates.sort_by(|(rank_a, a), (rank_b, b)| {
+        rank_a
+            .cmp(rank_b)
+            .then(b.modified.total_cmp(&a.modified))
+            .then(a.name.cmp(&b.name))
+    });
+
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, document)| document.name.clone())
+        .collect()
+}
+
+/// Completes the link typed before `caret` with `title` and closes it. Returns the new
+/// text and the caret position after the link.
+///
+/// ```rust
+/// use rustynote::api::wikilinks::complete_link;
+///
+/// assert_eq!(complete_link("See [[Ide", 9, "Ideas"), Some((String::from("See [[Ideas]]"), 13)));
+/// ```
+pub fn complete_link(text: &str, caret: usize, title: &str) -> Option<(String, usize)> {
+    let query = link_query(&text[..caret])?;
+    let start = caret - query.len();
+    // Keep brackets that were typed or inserted already.
+    let after = &text[caret..];
+    let end = if after.starts_with(CLOSE) {
+        caret + CLOSE.len()
+    } else {
+        caret
+    };
+
+    let mut result = String::with_capacity(text.len() + title.len() + CLOSE.len());
+    result.push_str(&text[..start]);
+    result.push_str(title);
+    result.push_str(CLOSE);
+    let new_caret = result.len();
+    result.push_str(&text[end..]);
+
+    Some((result, new_caret))
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library(notes: &[(&str, &str)]) -> DocumentLibrary {
+        let mut library = DocumentLibrary::default();
+        let mut current = String::new();
+        for (index, (name, content)) in notes.iter().enumerate() {
+            library.create(name, content, &current, index as f64);
+            current = content.to_string();
+        }
+        library
+    }
+
+    #[test]
+    fn test_find_wikilinks_skips_code_and_invalid_links() {
+        let markdown =
+            "[[A]] [[]] [[ B | b ]]\n```\n[[C]]\n```\n    [[D]]\n``[[E]]`` [[F\n]] [[G]]";
+        let targets = find_wikilinks(markdown)
+            .into_iter()
+            .map(|link| link.target)
+            .collect::<Vec<_>>();
+
+        assert_eq!(targets, vec!["A", "B", "G"]);
+    }
+
+    #[test]
+    fn test_find_wikilinks_ranges() {
+        let markdown = "ü [[Ä]]\n[[B|c]]";
+        let links = find_wikilinks(markdown);
+
+        assert_eq!(&markdown[links[0].range.clone()], "[[Ä]]");
+        assert_eq!(&markdown[links[1].range.clone()], "[[B|c]]");
+    }
+
+    #[test]
+    fn test_link_wikilinks_leaves_code_alone() {
+        let html = "<pre><code>[[A]]</code></pre><p><code>[[A]]</code> [[A]] [[x<em>y</em>]]</p>";
+
+        assert_eq!(
+            link_wikilinks(html, |_| true),
+            "<pre><code>[[A]]</code></pre><p><code>[[A]]</code> \
+             <a class=\"wikilink\" data-wikilink=\"A\">A</a> [[x<em>y</em>]]</p>"
+        );
+    }
+
+    #[test]
+    fn test_link_wikilinks_resolves_unescaped_targets() {
+        let html = link_wikilinks("<p>[[Q&amp;A]]</p>", |target| target == "Q&A");
+
+        assert!(html.contains("class=\"wikilink\" data-wikilink=\"Q&amp;A\""));
+    }
+
+    #[test]
+    fn test_resolve_prefers_exact_name() {
+        let library = library(&[("notes", ""), ("Notes (2)", ""), ("Ideas", "")]);
+
+        assert_eq!(resolve(&library, "notes").unwrap().name, "notes");
+        assert_eq!(resolve(&library, " IDEAS ").unwrap().name, "Ideas");
+        assert!(resolve(&library, "Plans").is_none());
+    }
+
+    #[test]
+    fn test_rename_keeps_other_links_and_code() {
+        assert_eq!(
+            rename_wikilinks("[[Old]] [[Older]] `[[Old]]`", "old", "New"),
+            "[[New]] [[Older]] `[[Old]]`"
+        );
+    }
+
+    #[test]
+    fn test_backlinks() {
+        let library = library(&[
+            ("Ideas", "Nothing here"),
+            (
+                "Journal",
+                "Today:\n- worked on [[Plans|my plans]]\n- [[Plans]] again",
+            ),
+            ("Archive", "Old [[plans]]"),
+            ("Plans", "[[Plans]] links to itself"),
+        ]);
+
+        assert_eq!(
+            backlinks(&library),
+            vec![
+                Backlink {
+                    id: library.documents[2].id,
+                    name: String::from("Archive"),
+                    context: String::from("Old [[plans]]"),
+                },
+                Backlink {
+                    id: library.documents[1].id,
+                    name: String::from("Journal"),
+                    context: String::from("- worked on [[Plans|my plans]]"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_link_query() {
+        assert_eq!(link_query("[["), Some(""));
+        assert_eq!(link_query("[[A]]\n[[B"), Some("B"));
+        assert_eq!(link_query("[[A\nB"), None);
+        assert_eq!(link_query("[[A|label"), None);
+        assert_eq!(link_query("no link"), None);
+    }
+
+    #[test]
+    fn test_suggest_titles() {
+        let library = library(&[
+            ("Project Plans", ""),
+            ("Plans", ""),
+            ("Ideas", ""),
+            ("", ""),
+            ("Current", ""),
+        ]);
+
+        assert_eq!(
+            suggest_titles(&library, "pla"),
+            vec!["Plans", "Project Plans"]
+        );
+        assert_eq!(
+            suggest_titles(&library, ""),
+            vec!["Ideas", "Plans", "Project Plans"]
+        );
+    }
+
+    #[test]
+    fn test_complete_link_keeps_closing_brackets() {
+        assert_eq!(
+            complete_link("[[Pl]] rest", 4, "Plans"),
+            Some((String::from("[[Plans]] rest"), 9))
+        );
+        assert_eq!(complete_link("no link", 7, "Plans"), None);
+    }
+}
@@ -1,57 +1,216 @@
 use crate::{
-    api::download::download_file,
+    api::{
+        attachments::embed_attachments,
+        documents::DocumentLibrary,
+        download::{download_bytes, download_file},
+        download_options::{
+            DOWNLOAD_FORMATS, DownloadFormat, DownloadOptions, LINE_ENDINGS, TRAILING_NEWLINES,
+            file_name, suggested_file_name,
+        },
+        export::{document_title, export, export_document, html_document},
+        parser::Dialect,
+        preview_styles::PreviewStyle,
+        storage::use_persistent_store,
+        toc::add_heading_ids,
+    },
     components::{
         Color, Size,
         button::Button,
         icons::Icon,
+        select::Select,
         tooltip::{Tooltip, TooltipDirection},
     },
 };
-use leptos::prelude::*;
+use leptoaster::expect_toaster;
+use leptos::{html::Div, prelude::*};
+use leptos_use::on_click_outside;
 
+/// Opens the download dialog, which proposes a file name from the document and
+/// remembers the chosen format, line endings and trailing newline.
 #[component]
-pub fn DownloadButton(markdown: ReadSignal<String>) -> impl IntoView {
-    let download_markdown = move || {
-        let content = markdown.get();
-        download_file(content, "markdown.md");
+pub fn DownloadButton(
+    markdown: ReadSignal<String>,
+    documents: ReadSignal<DocumentLibrary>,
+    parser: ReadSignal<Dialect>,
+    preview_style: ReadSignal<PreviewStyle>,
+    custom_css: ReadSignal<String>,
+    download_options: RwSignal<DownloadOptions>,
+) -> impl IntoView {
+    let modal_visible = RwSignal::new(false);
+    let name = RwSignal::new(String::new());
+
+    let modal_area = NodeRef::<Div>::new();
+    let _ = on_click_outside(modal_area, move |_| modal_visible.set(false));
+
+    let open = move || {
+        let document_name = documents.with(|library| {
+            library
+                .active()
+                .filter(|document| !document.has_default_name())
+                .map(|document| document.name.clone())
+        });
+        name.set(suggested_file_name(
+            document_name.as_deref(),
+            &markdown.get(),
+        ));
+        modal_visible.set(true);
     };
 
-    view! {
-        <DesktopDownloadButton download_markdown=download_markdown />
-        <TouchDeviceDownloadButton download_markdown />
-    }
-}
+    let toaster = expect_toaster();
+    let store = use_persistent_store();
+
+    let download = move || {
+        let options = download_options.get();
+        let markdown = markdown.get();
+        let dialect = parser.get();
+        let markdown = store.with_value(|store| embed_attachments(store, &markdown, &dialect));
+        let file_name = file_name(&name.get(), options.format.extension());
+
+        let text = match options.format {
+            DownloadFormat::Markdown => markdown,
+            DownloadFormat::Html => {
+                let html = dialect.parse_markdown_to_html(&markdown);
+                let body = add_heading_ids(&html, &markdown);
+                html_document(
+                    &document_title(&markdown),
+                    &body,
+                    preview_style.get(),
+                    &custom_css.get(),
+                )
+            }
+            DownloadFormat::Export(format) => export(&markdown, &dialect, format),
+            DownloadFormat::Document(format) => {
+                match export_document(&markdown, &dialect, format) {
+                    Ok(document) => {
+                        download_bytes(&document, &file_name, format.mime_type());
+                        modal_visible.set(false);
+                    }
+                    Err(error) => toaster.error(&error),
+                }
+                return;
+            }
+        };
+
+        download_file(options.apply(&text), &file_name);
+        modal_visible.set(false);
+    };
+
+    let format_options = DOWNLOAD_FORMATS
+        .iter()
+        .map(DownloadFormat::to_string)
+        .collect::<Vec<String>>();
+    let line_ending_options = LINE_ENDINGS
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+    let trailing_newline_options = TRAILING_NEWLINES
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+    let is_binary = move || !download_options.read().format.is_text();
 
-#[component]
-fn DesktopDownloadButton(download_markdown: impl Fn() + Send + 'static) -> impl IntoView {
     view! {
         <div class="is-hidden-touch">
-            <Tooltip text="Download Markdown" direction=TooltipDirection::Right>
+            <Tooltip text="Download" direction=TooltipDirection::Right>
                 <Button
-                    aria_label=String::from("Download Markdown")
+                    aria_label=String::from("Download")
                     icon=Icon::Download
                     color=Color::White
                     size=Size::Small
-                    on_click=move || download_markdown()
+                    on_click=open
                 />
             </Tooltip>
         </div>
-    }
-}
-
-#[component]
-fn TouchDeviceDownloadButton(download_markdown: impl Fn() + Send + 'static) -> impl IntoView {
-    view! {
         <div class="is-hidden-desktop">
             <Button
-                aria_label=String::from("Download Markdown")
-                text="Download Markdown"
+                aria_label=String::from("Download")
+                text="Download"
                 icon=Icon::Download
                 color=Color::Transparent
                 has_smaller_padding=true
                 is_full_size=true
-                on_click=move || download_markdown()
+                on_click=open
             />
         </div>
+        <div class="modal" class:is-active=move || modal_visible.get()>
+            <div class="modal-background" />
+            <div class="modal-card container" node_ref=modal_area>
+                <header class="modal-card-head">
+                    <p class="modal-card-title">Download</p>
+                    <button class="delete" aria-label="close" on:click=move |_| modal_visible.set(false) />
+                </header>
+                <section class="modal-card-body px-5">
+                    <div class="field">
+                        <label class="label">Format</label>
+                        <Select
+                            options=format_options
+                            icon=Icon::FileExport
+                            prop_value=Signal::derive(move || download_options.read().format.to_string())
+                            on_change=move |label| {
+                                if let Ok(format) = label.parse() {
+                                    download_options.update(|options| options.format = format);
+                                }
+                            }
+                        />
+                    </div>
+                    <div class="field">
+                        <label class="label">File name</label>
+                        <div class="field has-addons">
+                            <div class="control is-expanded">
+                                <input
+                                    class="input"
+                                    type="text"
+                                    aria-label="File name"
+                                    prop:value=move || name.get()
+                                    on:input=move |ev| name.set(event_target_value(&ev))
+                                />
+                            </div>
+                            <div class="control">
+                                <span class="button is-static">
+                                    { move || format!(".{}", download_options.read().format.extension()) }
+                                </span>
+                            </div>
+                        </div>
+                    </div>
+                    <div class="field">
+                        <label class="label">Line endings</label>
+                        <Select
+                            options=line_ending_options
+                            prop_value=Signal::derive(move || download_options.read().line_ending.to_string())
+                            on_change=move |label| {
+                                if let Ok(line_ending) = label.parse() {
+                                    download_options.update(|options| options.line_ending = line_ending);
+                                }
+                            }
+                        />
+                    </div>
+                    <div class="field">
+                        <label class="label">Trailing newline</label>
+                        <Select
+                            options=trailing_newline_options
+                            prop_value=Signal::derive(move || download_options.read().trailing_newline.to_string())
+                            on_change=move |label| {
+                                if let Ok(policy) = label.parse() {
+                                    download_options.update(|options| options.trailing_newline = policy);
+                                }
+                            }
+                        />
+                    </div>
+                    <Show when=is_binary>
+                        <p class="help">Line endings and the trailing newline only apply to text formats.</p>
+                    </Show>
+                </section>
+                <footer class="modal-card-foot">
+                    <Button
+                        aria_label=String::from("Download File")
+                        text="Download"
+                        icon=Icon::Download
+                        size=Size::Small
+                        color=Color::Primary
+                        on_click=download
+                    />
+                </footer>
+            </div>
+        </div>
     }
 }
@@ -0,0 +1,74 @@
+use crate::components::{
+    Color, Size,
+    button::Button,
+    dropdown::{DropdownButton, DropdownDirection},
+    icons::Icon,
+    toolbar::actions::ActionId,
+};
+use leptos::prelude::*;
+
+const LEVEL_ACTIONS: [ActionId; 7] = [
+    ActionId::Paragraph,
+    ActionId::Heading1,
+    ActionId::Heading2,
+    ActionId::Heading3,
+    ActionId::Heading4,
+    ActionId::Heading5,
+    ActionId::Heading6,
+];
+
+const SECTION_ACTIONS: [ActionId; 3] = [
+    ActionId::PromoteSection,
+    ActionId::DemoteSection,
+    ActionId::ToggleHeadingStyle,
+];
+
+/// Dropdown that sets the heading level of the selected lines and restructures the
+/// section under the caret.
+#[component]
+pub fn HeadingActionsButton(
+    markdown: RwSignal<String>,
+    #[prop(default=DropdownDirection::Left)] direction: DropdownDirection,
+) -> impl IntoView {
+    let actions = move |actions: &'static [ActionId]| {
+        actions
+            .iter()
+            .map(|id| {
+                let action = id.action();
+                let text = match action.shortcut {
+                    Some(shortcut) => format!("{} ({shortcut})", action.label),
+                    None => action.label.to_string(),
+                };
+                view! {
+                    <div class="dropdown-item">
+                        <Button
+                            aria_label=action.label.to_string()
+                            text
+                            icon=action.icon
+                            color=Color::Transparent
+                            size=Size::Small
+                            has_smaller_padding=true
+                            is_full_size=true
+                            on_click=move || markdown.set(action.formatting.apply_text_formatting())
+                        />
+                    </div>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <DropdownButton
+            aria_label=String::from("Heading Actions")
+            icon=Icon::Heading
+            text=String::from("Headings")
+            color=Color::Primary
+            size=Size::Small
+            direction
+        >
+            { actions(&LEVEL_ACTIONS) }
+            <hr class="dropdown-divider" />
+            { actions(&SECTION_ACTIONS) }
+        </DropdownButton>
+    }
+}
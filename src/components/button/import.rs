@@ -0,0 +1,197 @@
+use gloo::file::FileList;
+use leptoaster::{ToasterContext, expect_toaster};
+use leptos::{html::Input, prelude::*, task::spawn_local};
+use web_sys::js_sys::Date;
+
+use crate::{
+    api::{
+        documents::DocumentLibrary,
+        encryption::EncryptedFile,
+        import::{ImportedFile, document_name, first_importable, read_text_file},
+    },
+    components::{
+        Color, Size,
+        button::Button,
+        confirmation::Confirmation,
+        icons::Icon,
+        tooltip::{Tooltip, TooltipDirection},
+    },
+};
+
+/// Opens a markdown or text file through the file picker.
+#[component]
+pub fn ImportButton(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+) -> impl IntoView {
+    let toaster = expect_toaster();
+    let pending = RwSignal::new(None::<ImportedFile>);
+    let input_ref = NodeRef::<Input>::new();
+
+    let open_picker = move || {
+        if let Some(input) = input_ref.get() {
+            input.click();
+        }
+    };
+
+    view! {
+        <input
+            class="is-hidden"
+            type="file"
+            accept=".md,.markdown,.txt,.rnote,text/markdown,text/plain"
+            node_ref=input_ref
+            on:change=move |ev| {
+                let input = event_target::<web_sys::HtmlInputElement>(&ev);
+                if !import_files(input.files(), pending, toaster.clone()) {
+                    toaster.warn("Please choose a .md, .markdown, .txt or .rnote file");
+                }
+                input.set_value("");
+            }
+        />
+        <div class="is-hidden-touch">
+            <Tooltip text="Open File" direction=TooltipDirection::Right>
+                <Button
+                    aria_label=String::from("Open File")
+                    icon=Icon::FolderOpen
+                    color=Color::White
+                    size=Size::Small
+                    on_click=open_picker
+                />
+            </Tooltip>
+        </div>
+        <div class="is-hidden-desktop">
+            <Button
+                aria_label=String::from("Open File")
+                text="Open File"
+                icon=Icon::FolderOpen
+                color=Color::Transparent
+                has_smaller_padding=true
+                is_full_size=true
+                on_click=open_picker
+            />
+        </div>
+        <ImportConfirmation markdown documents pending />
+    }
+}
+
+/// Reads the first markdown or text file of the list into `pending`.
+/// Returns `false` when the list contains no such file.
+pub fn import_files(
+    files: Option<web_sys::FileList>,
+    pending: RwSignal<Option<ImportedFile>>,
+    toaster: ToasterContext,
+) -> bool {
+    let Some(file) = files
+        .map(FileList::from)
+        .and_then(|files| first_importable(&files))
+    else {
+        return false;
+    };
+
+    spawn_local(async move {
+        match read_text_file(file).await {
+            Ok(imported) => pending.set(Some(imported)),
+            Err(error) => toaster.error(error.as_str()),
+        }
+    });
+
+    true
+}
+
+/// Asks whether an opened file replaces the current content or becomes a new document.
+/// An empty editor is replaced without asking. Encrypted notes always become a new,
+/// locked document.
+#[component]
+pub fn ImportConfirmation(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+    pending: RwSignal<Option<ImportedFile>>,
+) -> impl IntoView {
+    let visible = RwSignal::new(false);
+
+    let replace = move || {
+        if let Some(imported) = pending.get_untracked() {
+            markdown.set(imported.content);
+            pending.set(None);
+        }
+    };
+
+    let open_as_new_document = move || {
+        if let Some(imported) = pending.get_untracked() {
+            documents.update(|library| {
+                library.create(
+                    &document_name(&imported.name),
+                    &imported.content,
+                    &markdown.get_untracked(),
+                    Date::now(),
+                );
+            });
+            markdown.set(imported.content);
+            pending.set(None);
+        }
+        visible.set(false);
+    };
+
+    let toaster = expect_toaster();
+    let open_encrypted = move |imported: ImportedFile| {
+        match EncryptedFile::from_json(&imported.content) {
+            Ok(file) => {
+                documents.update(|library| {
+                    library.create_sealed(
+                        &file.name,
+                        file.sealed,
+                        &markdown.get_untracked(),
+                        Date::now(),
+                    );
+                });
+                markdown.set(String::new());
+            }
+            Err(error) => toaster.error(error),
+        }
+        pending.set(None);
+    };
+
+    Effect::new(move || {
+        let encrypted = pending.with(|imported| {
+            imported
+                .as_ref()
+                .filter(|imported| EncryptedFile::is_encrypted_file(&imported.name))
+                .cloned()
+        });
+
+        if let Some(imported) = encrypted {
+            open_encrypted(imported);
+        } else if pending.read().is_none() {
+            visible.set(false);
+        } else if markdown.read_untracked().trim().is_empty() {
+            replace();
+        } else {
+            visible.set(true);
+        }
+    });
+
+    view! {
+        <Confirmation
+            confirmation_text=Signal::derive(move || {
+                format!(
+                    "Replace the current content with \"{}\"?",
+                    pending.read().as_ref().map(|imported| imported.name.clone()).unwrap_or_default(),
+                )
+            })
+            confirm_label="Replace"
+            confirm_icon=Icon::FolderOpen
+            confirm_color=Color::Warning
+            on_confirmation=replace
+            visible
+        >
+            <Button
+                aria_label=String::from("Open as New Document")
+                text="New Document"
+                icon=Icon::NewFile
+                size=Size::Small
+                color=Color::Primary
+                on_click=open_as_new_document
+            />
+        </Confirmation>
+    }
+}
@@ -4,6 +4,12 @@ pub mod copy;
 pub mod delete;
 pub mod download;
 pub mod format_tables;
+pub mod heading_actions;
+pub mod import;
+pub mod print;
+pub mod save_file;
+pub mod share;
+pub mod table_actions;
 
 use crate::components::icons::Icon;
 use crate::components::{Color, Size, State};
@@ -0,0 +1,106 @@
+use gloo::utils::window;
+use leptos::{html::Div, prelude::*};
+use leptos_use::on_click_outside;
+
+use crate::{
+    api::print::{PAGE_BREAK_MARKER, PrintOptions},
+    components::{
+        Color, Size,
+        button::Button,
+        icons::Icon,
+        tooltip::{Tooltip, TooltipDirection},
+    },
+};
+
+/// Opens the print options and prints the rendered document, which also allows saving it as PDF.
+#[component]
+pub fn PrintButton(print_options: RwSignal<PrintOptions>) -> impl IntoView {
+    let modal_visible = RwSignal::new(false);
+
+    let modal_area = NodeRef::<Div>::new();
+    let _ = on_click_outside(modal_area, move |_| modal_visible.set(false));
+
+    let print = move || {
+        modal_visible.set(false);
+        let _ = window().print();
+    };
+
+    view! {
+        <div class="is-hidden-touch">
+            <Tooltip text="Print / Save as PDF" direction=TooltipDirection::Right>
+                <Button
+                    aria_label=String::from("Print / Save as PDF")
+                    icon=Icon::Print
+                    color=Color::White
+                    size=Size::Small
+                    on_click=move || modal_visible.set(true)
+                />
+            </Tooltip>
+        </div>
+        <div class="is-hidden-desktop">
+            <Button
+                aria_label=String::from("Print / Save as PDF")
+                text="Print / Save as PDF"
+                icon=Icon::Print
+                color=Color::Transparent
+                has_smaller_padding=true
+                is_full_size=true
+                on_click=move || modal_visible.set(true)
+            />
+        </div>
+        <div class="modal" class:is-active=move || modal_visible.get()>
+            <div class="modal-background" />
+            <div class="modal-card container" node_ref=modal_area>
+                <header class="modal-card-head">
+                    <p class="modal-card-title">Print / Save as PDF</p>
+                    <button class="delete" aria-label="close" on:click=move |_| modal_visible.set(false) />
+                </header>
+                <section class="modal-card-body px-5">
+                    <p class="mb-3">
+                        "Only the rendered document is printed. Put "
+                        <code>{ PAGE_BREAK_MARKER }</code>
+                        " on its own line to start a new page."
+                    </p>
+                    <div class="field">
+                        <label class="checkbox">
+                            <input
+                                type="checkbox"
+                                class="mr-2"
+                                prop:checked=move || print_options.read().break_on_rules
+                                on:change=move |ev| {
+                                    let checked = event_target_checked(&ev);
+                                    print_options.update(|options| options.break_on_rules = checked);
+                                }
+                            />
+                            "Start a new page at horizontal rules (" <code>"---"</code> ")"
+                        </label>
+                    </div>
+                    <div class="field">
+                        <label class="checkbox">
+                            <input
+                                type="checkbox"
+                                class="mr-2"
+                                prop:checked=move || print_options.read().header_footer
+                                on:change=move |ev| {
+                                    let checked = event_target_checked(&ev);
+                                    print_options.update(|options| options.header_footer = checked);
+                                }
+                            />
+                            "Show the title and page numbers on every page"
+                        </label>
+                    </div>
+                </section>
+                <footer class="modal-card-foot">
+                    <Button
+                        aria_label=String::from("Print")
+                        text="Print"
+                        icon=Icon::Print
+                        size=Size::Small
+                        color=Color::Primary
+                        on_click=print
+                    />
+                </footer>
+            </div>
+        </div>
+    }
+}
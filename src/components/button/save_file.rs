@@ -0,0 +1,102 @@
+use leptoaster::expect_toaster;
+use leptos::{prelude::*, task::spawn_local};
+
+use crate::{
+    api::{
+        documents::DocumentLibrary,
+        folder::{ExternalChange, FolderSync},
+    },
+    components::{
+        Color, Size,
+        button::Button,
+        icons::Icon,
+        tooltip::{Tooltip, TooltipDirection},
+    },
+};
+
+/// Writes the active document back to its file in the opened folder. Only shown for
+/// documents that were opened from a folder.
+#[component]
+pub fn SaveFileButton(
+    markdown: ReadSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+) -> impl IntoView {
+    let sync = expect_context::<FolderSync>();
+    let toaster = expect_toaster();
+
+    let linked = Signal::derive(move || {
+        documents.with(|library| {
+            library
+                .active()
+                .and_then(|document| Some((document.id, document.file.clone()?)))
+        })
+    });
+
+    let save = move || {
+        let Some((id, file)) = linked.get_untracked() else {
+            return;
+        };
+        let content = markdown.get_untracked();
+        let toaster = toaster.clone();
+
+        spawn_local(async move {
+            let disk = match sync.read(&file.path).await {
+                Ok(disk) => disk,
+                Err(error) => return toaster.error(error),
+            };
+
+            // Someone else changed the file since it was read, so don't overwrite it.
+            if disk.content != file.base && disk.content != content {
+                sync.change.set(Some(ExternalChange {
+                    document: id,
+                    path: file.path,
+                    file: disk,
+                }));
+                return toaster.warn(
+                    "The file was changed on disk. Choose how to combine the changes first.",
+                );
+            }
+
+            match sync.write(&file.path, &content, disk.format).await {
+                Ok(modified) => {
+                    documents.update(|library| library.update_link(id, &content, modified));
+                    toaster.success(format!("Saved {}", file.path));
+                }
+                Err(error) => toaster.error(error),
+            }
+        });
+    };
+
+    view! {
+        <Show when=move || linked.read().is_some()>
+            {
+                let save_on_desktop = save.clone();
+                let save = save.clone();
+                view! {
+            <div class="is-hidden-touch">
+                <Tooltip text="Save to File" direction=TooltipDirection::Right>
+                    <Button
+                        aria_label=String::from("Save to File")
+                        icon=Icon::Save
+                        color=Color::White
+                        size=Size::Small
+                        on_click=save_on_desktop
+                    />
+                </Tooltip>
+            </div>
+            <div class="is-hidden-desktop">
+                <Button
+                    aria_label=String::from("Save to File")
+                    text="Save to File"
+                    icon=Icon::Save
+                    color=Color::Transparent
+                    has_smaller_padding=true
+                    is_full_size=true
+                    on_click=save
+                />
+            </div>
+                }
+            }
+        </Show>
+    }
+}
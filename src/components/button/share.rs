@@ -0,0 +1,212 @@
+use gloo::{timers::future::TimeoutFuture, utils::window};
+use leptoaster::expect_toaster;
+use leptos::{html::Div, prelude::*, task::spawn_local};
+use leptos_use::{UseClipboardReturn, on_click_outside, use_clipboard};
+
+use crate::{
+    api::{
+        attachments::embed_attachments,
+        documents::DocumentLibrary,
+        encryption::{MIN_PASSPHRASE_LENGTH, Vault},
+        parser::Dialect,
+        share::{LONG_LINK_LENGTH, SharedNote},
+        storage::use_persistent_store,
+    },
+    components::{
+        Color, Size, State,
+        button::Button,
+        icons::Icon,
+        tooltip::{Tooltip, TooltipDirection},
+    },
+};
+
+/// Copies a link that contains the whole note, compressed into the URL fragment.
+/// The fragment never reaches a server. Encrypted notes are shared encrypted with a
+/// passphrase of their own, so the passphrase of the note stays private.
+#[component]
+pub fn ShareButton(
+    markdown: ReadSignal<String>,
+    documents: ReadSignal<DocumentLibrary>,
+    parser: ReadSignal<Dialect>,
+) -> impl IntoView {
+    let UseClipboardReturn {
+        is_supported, copy, ..
+    } = use_clipboard();
+
+    let toaster = expect_toaster();
+    let vault = expect_context::<Vault>();
+    let store = use_persistent_store();
+
+    let modal_visible = RwSignal::new(false);
+    let modal_area = NodeRef::<Div>::new();
+    let _ = on_click_outside(modal_area, move |_| modal_visible.set(false));
+
+    let passphrase = RwSignal::new(String::new());
+    let confirmation = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+    let sealing = RwSignal::new(false);
+
+    let close = move || {
+        modal_visible.set(false);
+        passphrase.set(String::new());
+        confirmation.set(String::new());
+        error.set(None);
+    };
+
+    let note = move || SharedNote {
+        name: documents.with_untracked(|library| {
+            library
+                .active()
+                .filter(|document| !document.has_default_name())
+                .map(|document| document.name.clone())
+        }),
+        dialect: parser.get_untracked(),
+        content: store.with_value(|store| {
+            embed_attachments(store, &markdown.get_untracked(), &parser.get_untracked())
+        }),
+    };
+
+    let copy_link = {
+        let toaster = toaster.clone();
+        move |fragment: String| {
+            let href = window().location().href().unwrap_or_default();
+            let base = href.split('#').next().unwrap_or_default();
+            let link = format!("{base}{fragment}");
+
+            copy(&link);
+            if link.len() > LONG_LINK_LENGTH {
+                toaster.warn("Link copied, but it is long and may be cut off by some apps");
+            } else {
+                toaster.success("Share link copied to clipboard!");
+            }
+        }
+    };
+
+    let share = {
+        let toaster = toaster.clone();
+        let copy_link = copy_link.clone();
+        move || {
+            if vault.is_active_locked() {
+                toaster.warn("Unlock the note to share it");
+            } else if vault.is_active_encrypted() {
+                modal_visible.set(true);
+            } else {
+                copy_link(note().to_fragment());
+            }
+        }
+    };
+
+    let share_sealed = move || {
+        if passphrase.read_untracked().as_str() != confirmation.read_untracked().as_str() {
+            error.set(Some(String::from("The passphrases don't match")));
+            return;
+        }
+        sealing.set(true);
+        let copy_link = copy_link.clone();
+        spawn_local(async move {
+            // Deriving the key blocks for a moment, so let the button show it first.
+            TimeoutFuture::new(0).await;
+            match note().to_sealed_fragment(&passphrase.get_untracked()) {
+                Ok(fragment) => {
+                    close();
+                    copy_link(fragment);
+                }
+                Err(message) => error.set(Some(message)),
+            }
+            sealing.set(false);
+        });
+    };
+
+    let share_on_desktop = share.clone();
+    let state = Signal::derive(move || {
+        if is_supported.get() {
+            State::Normal
+        } else {
+            State::Disabled
+        }
+    });
+
+    view! {
+        <div class="is-hidden-touch">
+            <Tooltip text=Signal::derive(move || if is_supported.get() { "Share Link" } else { "Your Browser does not support copying" }) direction=TooltipDirection::Right>
+                <Button
+                    aria_label=String::from("Share Link")
+                    icon=Icon::Share
+                    color=Color::White
+                    size=Size::Small
+                    state
+                    on_click=share_on_desktop
+                />
+            </Tooltip>
+        </div>
+        <div class="is-hidden-desktop">
+            <Button
+                aria_label=String::from("Share Link")
+                text="Share Link"
+                icon=Icon::Share
+                color=Color::Transparent
+                state
+                has_smaller_padding=true
+                is_full_size=true
+                on_click=share
+            />
+        </div>
+        <div class="modal" class:is-active=move || modal_visible.get()>
+            <div class="modal-background" />
+            <div class="modal-card container" node_ref=modal_area>
+                <header class="modal-card-head">
+                    <p class="modal-card-title">Share Encrypted Note</p>
+                    <button class="delete" aria-label="close" on:click=move |_| close() />
+                </header>
+                <section class="modal-card-body px-5">
+                    <p class="mb-4">
+                        "The link is encrypted with a passphrase just for sharing. Tell it to the "
+                        "recipients separately, the passphrase of the note stays private."
+                    </p>
+                    <div class="field">
+                        <label class="label">Share Passphrase</label>
+                        <div class="control">
+                            <input
+                                class="input"
+                                type="password"
+                                autocomplete="new-password"
+                                minlength=MIN_PASSPHRASE_LENGTH
+                                prop:value=passphrase
+                                on:input=move |ev| {
+                                    passphrase.set(event_target_value(&ev));
+                                    error.set(None);
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="field">
+                        <label class="label">Repeat Passphrase</label>
+                        <div class="control">
+                            <input
+                                class="input"
+                                type="password"
+                                autocomplete="new-password"
+                                prop:value=confirmation
+                                on:input=move |ev| {
+                                    confirmation.set(event_target_value(&ev));
+                                    error.set(None);
+                                }
+                            />
+                        </div>
+                        <p class="help is-danger">{ move || error.get() }</p>
+                    </div>
+                </section>
+                <footer class="modal-card-foot">
+                    <Button
+                        aria_label=String::from("Copy Encrypted Link")
+                        text=Signal::derive(move || Some(String::from(if sealing.get() { "Encrypting…" } else { "Copy Link" })))
+                        icon=Icon::Share
+                        size=Size::Small
+                        color=Color::Primary
+                        on_click=share_sealed
+                    />
+                </footer>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    api::{
+        download::download_file,
+        markdown_formatter::{
+            format::{Alignment, TableOperation, TextFormattingType},
+            table_under_cursor,
+        },
+    },
+    components::{
+        Color, Size,
+        button::Button,
+        dropdown::{DropdownButton, DropdownDirection},
+        icons::Icon,
+    },
+};
+use leptoaster::expect_toaster;
+use leptos::prelude::*;
+use leptos_use::{UseClipboardReturn, use_clipboard};
+
+const ROW_ACTIONS: [(&str, Icon, TableOperation); 5] = [
+    (
+        "Insert Row Above",
+        Icon::Plus,
+        TableOperation::InsertRowAbove,
+    ),
+    (
+        "Insert Row Below",
+        Icon::Plus,
+        TableOperation::InsertRowBelow,
+    ),
+    ("Move Row Up", Icon::ArrowUp, TableOperation::MoveRowUp),
+    (
+        "Move Row Down",
+        Icon::ArrowDown,
+        TableOperation::MoveRowDown,
+    ),
+    ("Delete Row", Icon::Delete, TableOperation::DeleteRow),
+];
+
+const COLUMN_ACTIONS: [(&str, Icon, TableOperation); 5] = [
+    (
+        "Insert Column Left",
+        Icon::Plus,
+        TableOperation::InsertColumnLeft,
+    ),
+    (
+        "Insert Column Right",
+        Icon::Plus,
+        TableOperation::InsertColumnRight,
+    ),
+    (
+        "Move Column Left",
+        Icon::ArrowLeft,
+        TableOperation::MoveColumnLeft,
+    ),
+    (
+        "Move Column Right",
+        Icon::ArrowRight,
+        TableOperation::MoveColumnRight,
+    ),
+    ("Delete Column", Icon::Delete, TableOperation::DeleteColumn),
+];
+
+const ALIGNMENT_ACTIONS: [(&str, Icon, TableOperation); 3] = [
+    (
+        "Align Left",
+        Icon::AlignLeft,
+        TableOperation::Align(Alignment::Left),
+    ),
+    (
+        "Align Center",
+        Icon::AlignCenter,
+        TableOperation::Align(Alignment::Center),
+    ),
+    (
+        "Align Right",
+        Icon::AlignRight,
+        TableOperation::Align(Alignment::Right),
+    ),
+];
+
+const NO_TABLE_MESSAGE: &str = "Place the caret inside a table to export it";
+
+/// Dropdown with the structural edits for the table under the caret.
+#[component]
+pub fn TableActionsButton(
+    markdown: RwSignal<String>,
+    #[prop(default=DropdownDirection::Left)] direction: DropdownDirection,
+) -> impl IntoView {
+    let actions = move |actions: &'static [(&'static str, Icon, TableOperation)]| {
+        actions
+            .iter()
+            .map(|(label, icon, operation)| {
+                view! {
+                    <div class="dropdown-item">
+                        <Button
+                            aria_label=label.to_string()
+                            text=label.to_string()
+                            icon=*icon
+                            color=Color::Transparent
+                            size=Size::Small
+                            has_smaller_padding=true
+                            is_full_size=true
+                            on_click=move || {
+                                markdown.set(
+                                    TextFormattingType::TableOperation(*operation)
+                                        .apply_text_formatting(),
+                                )
+                            }
+                        />
+                    </div>
+                }
+            })
+            .collect_view()
+    };
+
+    let UseClipboardReturn { copy, .. } = use_clipboard();
+    let toaster = expect_toaster();
+    let export_toaster = toaster.clone();
+
+    let copy_csv = move || match table_under_cursor() {
+        Some(table) => {
+            copy(&table.to_csv());
+            toaster.success("Table copied to clipboard as CSV!");
+        }
+        None => toaster.info(NO_TABLE_MESSAGE),
+    };
+
+    let download_csv = move || match table_under_cursor() {
+        Some(table) => download_file(table.to_csv(), "table.csv"),
+        None => export_toaster.info(NO_TABLE_MESSAGE),
+    };
+
+    view! {
+        <DropdownButton
+            aria_label=String::from("Table Actions")
+            icon=Icon::Table
+            text=String::from("Table")
+            color=Color::Primary
+            size=Size::Small
+            direction
+        >
+            { actions(&ROW_ACTIONS) }
+            <hr class="dropdown-divider" />
+            { actions(&COLUMN_ACTIONS) }
+            <hr class="dropdown-divider" />
+            { actions(&ALIGNMENT_ACTIONS) }
+            <hr class="dropdown-divider" />
+            <div class="dropdown-item">
+                <Button
+                    aria_label=String::from("Copy as CSV")
+                    text="Copy as CSV"
+                    icon=Icon::Copy
+                    color=Color::Transparent
+                    size=Size::Small
+                    has_smaller_padding=true
+                    is_full_size=true
+                    on_click=copy_csv
+                />
+            </div>
+            <div class="dropdown-item">
+                <Button
+                    aria_label=String::from("Download as CSV")
+                    text="Download as CSV"
+                    icon=Icon::Download
+                    color=Color::Transparent
+                    size=Size::Small
+                    has_smaller_padding=true
+                    is_full_size=true
+                    on_click=download_csv
+                />
+            </div>
+        </DropdownButton>
+    }
+}
@@ -3,51 +3,66 @@ use leptos_use::on_click_outside;
 
 use crate::components::{Color, Size, button::Button, icons::Icon};
 
+/// Asks before running an action. Without `visible` a delete button opens the
+/// confirmation, with it the caller decides when it is shown. `children` are extra
+/// actions shown between the confirm and the cancel button.
 #[component]
 pub fn Confirmation(
     #[prop(into)] confirmation_text: Signal<String>,
     on_confirmation: impl Fn() + 'static,
+    #[prop(into, default = String::from("Delete"))] confirm_label: String,
+    #[prop(default = Icon::Delete)] confirm_icon: Icon,
+    #[prop(default = Color::Danger)] confirm_color: Color,
+    #[prop(optional)] visible: Option<RwSignal<bool>>,
+    #[prop(optional)] children: Option<Children>,
 ) -> impl IntoView {
-    let dropdown_visible = RwSignal::new(false);
+    let has_trigger = visible.is_none();
+    let dropdown_visible = visible.unwrap_or_else(|| RwSignal::new(false));
 
     let dropdown_area = NodeRef::<Div>::new();
     let _ = on_click_outside(dropdown_area, move |_| dropdown_visible.set(false));
 
     view! {
         <div class="dropdown is-right" class:is-active=move || dropdown_visible.get()>
-            <div class="dropdown-trigger">
-                <Button
-                    aria_label=String::from("Delete")
-                    icon=Icon::Delete
-                    size=Size::Small
-                    color=Color::Danger
-                    on_click=move || dropdown_visible.set(!dropdown_visible.get())
-                />
-            </div>
+            <Show when=move || has_trigger>
+                <div class="dropdown-trigger">
+                    <Button
+                        aria_label=String::from("Delete")
+                        icon=Icon::Delete
+                        size=Size::Small
+                        color=Color::Danger
+                        on_click=move || dropdown_visible.set(!dropdown_visible.get())
+                    />
+                </div>
+            </Show>
             <div node_ref=dropdown_area class="dropdown-menu">
                 <div class="dropdown-content">
                     <div class="dropdown-item">
                         { confirmation_text }
                     </div>
-                    <div class="is-flex dropdown-item is-justify-content-space-between is-align-items-center">
+                    <div
+                        class="is-flex dropdown-item is-justify-content-space-between is-align-items-center"
+                        style="gap: 0.5rem"
+                    >
                         <Button
-                            aria_label=String::from("Confirm Deletion")
-                            text="Delete"
-                            icon=Icon::Delete
+                            aria_label=format!("Confirm {confirm_label}")
+                            text=confirm_label.clone()
+                            icon=confirm_icon
                             size=Size::Small
-                            color=Color::Danger
+                            color=confirm_color
                             on_click=move || {
                                 on_confirmation();
-                                dropdown_visible.set(!dropdown_visible.get());
+                                dropdown_visible.set(false);
                             }
                         />
+                        { children.map(|children| children()) }
                         <Button
                             aria_label=String::from("Cancel")
                             text="Cancel"
                             icon=Icon::Cross
                             size=Size::Small
                             color=Color::Light
-                            on_click=move || dropdown_visible.set(!dropdown_visible.get())
+                            on_click=move || dropdown_visible.set(false)
                         />
                     </div>
                 </div>
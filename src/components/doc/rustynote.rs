@@ -1,10 +1,20 @@
 use leptos::prelude::*;
 
-use crate::api::parser::Dialect;
+use crate::{
+    api::parser::Dialect,
+    components::toolbar::{
+        actions::{ACTIONS, ActionId},
+        layout::ToolbarLayout,
+    },
+};
 
 #[component]
 pub fn RustyNoteDoc() -> impl IntoView {
-    let parsed_doc = Dialect::GitHub.parse_markdown_to_html(RUSTYNOTE_DOC);
+    let parsed_doc = Dialect::GitHub.parse_markdown_to_html(&format!(
+        "{RUSTYNOTE_DOC}{}{}",
+        toolbar_doc(),
+        shortcuts_doc()
+    ));
 
     view! {
         <div class="content with-cropped-images">
@@ -28,15 +38,81 @@ It focuses on fast feedback: every formatting action updates the preview instant
 | **Selectable Mode**    | Switch between write mode, read mode, or a combined split view.                | ![Select Mode](resources/images/rustynote/mode.png)       |
 | **Selectable Dialect** | Choose between CommonMark or GitHub-Flavored Markdown for parsing.             | ![Select Dialect](resources/images/rustynote/dialect.png) |
 | **Theme Toggle**       | Work with your preferred color scheme.                                         | ![Theme Toggle](resources/images/rustynote/theme.png)     |
-| **Format Buttons**     | Use handy formatting buttons if you're new to or unsure about markdown syntax. | ![Format Buttons](resources/images/rustynote/format.png)  |
 | **Format Tables**      | Clean up messy GFM tables instantly with the “Format Tables” button.           | ![Format Tables](resources/images/rustynote/tables.png)   |
 | **Copy to Clipboard**  | Copy your markdown content with a single click.                                | ![Copy](resources/images/rustynote/copy.png)              |
 | **Download**           | Save your work as a markdown file directly to your device.                     | ![Download](resources/images/rustynote/download.png)      |
 | **Delete Button**      | Clear all content and start fresh with one click.                              | ![Delete](resources/images/rustynote/delete.png)          |
 
-### Shortcuts
-- **Ctrl + B** – **bold**
-- **Ctrl + H** – **heading**
-- **Ctrl + #** – **code block**
-- **Ctrl + M** – **monospace**
 "#;
+
+const EDITING_KEYS: &[(&str, &str)] = &[
+    ("Tab / Shift + Tab", "next / previous table cell"),
+    (
+        "Enter",
+        "new table row (leaves the table on an empty last row)",
+    ),
+    (
+        "Tab after a trigger like /adr",
+        "insert the snippet (edit snippets under Templates)",
+    ),
+    (
+        "/ at the start of a line",
+        "insert a block, filter by typing, e.g. /code rust",
+    ),
+    (
+        "``` or :emoji or ](# or ][",
+        "suggest code languages, emoji, heading anchors or reference labels, pick one with ↑ / ↓ and Enter or Tab",
+    ),
+    (
+        "[[",
+        "link to another note, pick a title with ↑ / ↓ and Enter",
+    ),
+];
+
+/// Describes the default toolbar, the buttons that can be added to it and the ones that
+/// need GitHub-Flavored Markdown.
+fn toolbar_doc() -> String {
+    let layout = ToolbarLayout::default();
+    let labels = |ids: Vec<ActionId>| {
+        ids.iter()
+            .map(|id| id.action().label)
+            .collect::<Vec<&str>>()
+            .join(", ")
+    };
+    let github_only = ACTIONS
+        .iter()
+        .filter(|action| action.requires_github)
+        .map(|action| action.id)
+        .collect();
+
+    format!(
+        "\n### Toolbar\n\
+         Use the formatting buttons if you're new to or unsure about markdown syntax, \
+         hovering a button shows what it does. \
+         Reorder, hide or add buttons and separators with the **Toolbar** button.\n\n\
+         - **Shown by default** – {}\n\
+         - **Can be added** – {}\n\
+         - **Need GitHub-Flavored Markdown** – {}\n",
+        labels(layout.groups().concat()),
+        labels(layout.hidden_actions()),
+        labels(github_only),
+    )
+}
+
+/// Lists the shortcuts of the registered actions followed by the editing keys.
+fn shortcuts_doc() -> String {
+    let action_shortcuts = ACTIONS.iter().filter_map(|action| {
+        action
+            .shortcut
+            .map(|shortcut| (shortcut.to_string(), action.label.to_lowercase()))
+    });
+    let editing_keys = EDITING_KEYS
+        .iter()
+        .map(|(keys, description)| (keys.to_string(), description.to_string()));
+
+    let mut doc = String::from("\n### Shortcuts\n");
+    for (keys, description) in action_shortcuts.chain(editing_keys) {
+        doc.push_str(&format!("- **{keys}** – {description}\n"));
+    }
+    doc
+}
@@ -0,0 +1,179 @@
+use leptos::{ev::SubmitEvent, html::Div, prelude::*};
+use leptos_use::on_click_outside;
+use web_sys::js_sys::Date;
+
+use crate::{
+    api::documents::DocumentLibrary,
+    components::{
+        Color, Size, button::Button, confirmation::Confirmation, icons::Icon, select::Select,
+    },
+};
+
+/// Switches between the stored documents, creates and deletes documents.
+#[component]
+pub fn DocumentSelect(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+) -> impl IntoView {
+    let open = move |name: String| {
+        let Some(id) = documents
+            .read_untracked()
+            .find_by_name(&name)
+            .map(|document| document.id)
+        else {
+            return;
+        };
+
+        let mut content = None;
+        documents.update(|library| {
+            content = library.open(id, &markdown.get_untracked(), Date::now());
+        });
+        if let Some(content) = content {
+            markdown.set(content);
+        }
+    };
+
+    let create = move || {
+        documents.update(|library| {
+            library.create("", "", &markdown.get_untracked(), Date::now());
+        });
+        markdown.set(String::new());
+    };
+
+    let delete = move || {
+        let mut content = String::new();
+        documents.update(|library| content = library.remove_active(Date::now()));
+        markdown.set(content);
+    };
+
+    view! {
+        <div class="is-flex is-align-items-center" style="gap: 0.5rem">
+            <Select
+                icon=Icon::Markdown
+                options=Signal::derive(move || documents.read().names())
+                prop_value=Signal::derive(move || {
+                    documents.read().active().map(|document| document.name.clone()).unwrap_or_default()
+                })
+                on_change=open
+            />
+            <RenameDocument markdown documents />
+            <Button
+                aria_label=String::from("New Document")
+                icon=Icon::NewFile
+                size=Size::Small
+                on_click=create
+            />
+            <Confirmation
+                confirmation_text="Are you sure? This will delete the whole document."
+                on_confirmation=delete
+            />
+        </div>
+    }
+}
+
+/// Renames the active document. Links to it in other notes are updated.
+#[component]
+fn RenameDocument(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+) -> impl IntoView {
+    let dropdown_visible = RwSignal::new(false);
+    let dropdown_area = NodeRef::<Div>::new();
+    let _ = on_click_outside(dropdown_area, move |_| dropdown_visible.set(false));
+
+    let name = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+
+    let toggle = move || {
+        if !dropdown_visible.get_untracked() {
+            name.set(
+                documents
+                    .read_untracked()
+                    .active()
+                    .map(|document| document.name.clone())
+                    .unwrap_or_default(),
+            );
+            error.set(None);
+        }
+        dropdown_visible.update(|visible| *visible = !*visible);
+    };
+
+    let rename = move || {
+        let mut result = Ok(String::new());
+        documents.update(|library| {
+            result = library.rename_active(
+                &name.get_untracked(),
+                &markdown.get_untracked(),
+                Date::now(),
+            );
+        });
+        match result {
+            Ok(content) => {
+                if content != markdown.get_untracked() {
+                    markdown.set(content);
+                }
+                dropdown_visible.set(false);
+            }
+            Err(message) => error.set(Some(message)),
+        }
+    };
+
+    view! {
+        <div class="dropdown is-right" class:is-active=move || dropdown_visible.get()>
+            <div class="dropdown-trigger">
+                <Button
+                    aria_label=String::from("Rename Document")
+                    icon=Icon::Rename
+                    size=Size::Small
+                    on_click=toggle
+                />
+            </div>
+            <div node_ref=dropdown_area class="dropdown-menu">
+                <form
+                    class="dropdown-content"
+                    on:submit=move |ev: SubmitEvent| {
+                        ev.prevent_default();
+                        rename();
+                    }
+                >
+                    <div class="dropdown-item">
+                        <input
+                            class="input is-small"
+                            class:is-danger=move || error.read().is_some()
+                            type="text"
+                            aria-label="Document name"
+                            prop:value=name
+                            on:input=move |ev| {
+                                name.set(event_target_value(&ev));
+                                error.set(None);
+                            }
+                        />
+                        <p class="help is-danger">{ move || error.get() }</p>
+                        <p class="help">"Links to this note are updated."</p>
+                    </div>
+                    <div
+                        class="is-flex dropdown-item is-justify-content-space-between is-align-items-center"
+                        style="gap: 0.5rem"
+                    >
+                        <Button
+                            aria_label=String::from("Confirm Rename")
+                            text="Rename"
+                            icon=Icon::Rename
+                            size=Size::Small
+                            color=Color::Primary
+                            on_click=rename
+                        />
+                        <Button
+                            aria_label=String::from("Cancel")
+                            text="Cancel"
+                            icon=Icon::Cross
+                            size=Size::Small
+                            color=Color::Light
+                            on_click=move || dropdown_visible.set(false)
+                        />
+                    </div>
+                </form>
+            </div>
+        </div>
+    }
+}
@@ -21,6 +21,9 @@ impl DropdownDirection {
 pub fn DropdownButton(
     aria_label: String,
     #[prop(into)] icon: Signal<Icon>,
+    #[prop(default=Signal::from(None), into)] text: Signal<Option<String>>,
+    #[prop(into, default=Signal::from(Color::Transparent))] color: Signal<Color>,
+    #[prop(into, default=Signal::from(Size::Normal))] size: Signal<Size>,
     #[prop(default=DropdownDirection::Left)] direction: DropdownDirection,
     children: Children,
 ) -> impl IntoView {
@@ -35,8 +38,9 @@ pub fn DropdownButton(
                 <Button
                     aria_label
                     icon
-                    size=Size::Normal
-                    color=Color::Transparent
+                    text
+                    size
+                    color
                     on_click=move || dropdown_visible.set(!dropdown_visible.get())
                 />
             </div>
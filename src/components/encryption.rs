@@ -0,0 +1,238 @@
+use gloo::timers::future::TimeoutFuture;
+use leptoaster::expect_toaster;
+use leptos::{html::Div, prelude::*, task::spawn_local};
+use leptos_use::on_click_outside;
+
+use crate::{
+    api::{
+        documents::DocumentLibrary,
+        download::download_file,
+        download_options::{file_name, slugify},
+        encryption::{AutoLock, ENCRYPTED_EXTENSION, EncryptedFile, MIN_PASSPHRASE_LENGTH, Vault},
+    },
+    components::{Color, Size, button::Button, icons::Icon, select::Select},
+};
+
+/// Encrypts the active document with a passphrase, locks encrypted documents and
+/// chooses when they are locked automatically.
+#[component]
+pub fn EncryptionButton(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+    #[prop(into, default = Signal::from(false))] is_dropdown_item: Signal<bool>,
+    #[prop(default = false)] fullsize_button: bool,
+) -> impl IntoView {
+    let vault = expect_context::<Vault>();
+    let toaster = expect_toaster();
+
+    let modal_visible = RwSignal::new(false);
+    let modal_area = NodeRef::<Div>::new();
+    let _ = on_click_outside(modal_area, move |_| modal_visible.set(false));
+
+    let passphrase = RwSignal::new(String::new());
+    let confirmation = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+    let encrypting = RwSignal::new(false);
+
+    let close = move || {
+        modal_visible.set(false);
+        passphrase.set(String::new());
+        confirmation.set(String::new());
+        error.set(None);
+    };
+
+    let encrypt = {
+        let toaster = toaster.clone();
+        move || {
+            if passphrase.read_untracked().as_str() != confirmation.read_untracked().as_str() {
+                error.set(Some(String::from("The passphrases don't match")));
+                return;
+            }
+            encrypting.set(true);
+            let toaster = toaster.clone();
+            spawn_local(async move {
+                // Deriving the key blocks for a moment, so let the button show it first.
+                TimeoutFuture::new(0).await;
+                match vault.encrypt_active(&passphrase.get_untracked()) {
+                    Ok(()) => {
+                        close();
+                        toaster.success("The note is encrypted");
+                    }
+                    Err(message) => error.set(Some(message)),
+                }
+                encrypting.set(false);
+            });
+        }
+    };
+
+    let lock = move || {
+        vault.lock_all();
+        close();
+    };
+
+    let decrypt = {
+        let toaster = toaster.clone();
+        move || {
+            vault.decrypt_active();
+            close();
+            toaster.info("The note is no longer encrypted");
+        }
+    };
+
+    // A fresh copy, the stored one may lag behind the last keystroke.
+    let download_encrypted = move || {
+        let Some(key) = vault.active_key() else {
+            return;
+        };
+        let name = documents.with_untracked(|library| {
+            library
+                .active()
+                .map(|document| document.name.clone())
+                .unwrap_or_default()
+        });
+
+        match key.seal(&markdown.get_untracked()) {
+            Ok(sealed) => {
+                let file = EncryptedFile {
+                    name: name.clone(),
+                    sealed,
+                };
+                download_file(
+                    file.to_json(),
+                    &file_name(&slugify(&name), ENCRYPTED_EXTENSION),
+                );
+            }
+            Err(message) => toaster.error(message),
+        }
+    };
+
+    let is_encrypted = Signal::derive(move || vault.is_active_encrypted());
+
+    view! {
+        <Button
+            aria_label=String::from("Encryption")
+            text="Encryption"
+            icon=Signal::derive(move || if is_encrypted.get() { Icon::Lock } else { Icon::Unlock })
+            color=Signal::derive(move || if is_dropdown_item.get() { Color::Transparent } else { Color::None })
+            size=Size::Normal
+            has_smaller_padding=is_dropdown_item
+            is_full_size=fullsize_button
+            on_click=move || modal_visible.set(true)
+        />
+        <div class="modal" class:is-active=move || modal_visible.get()>
+            <div class="modal-background" />
+            <div class="modal-card container" node_ref=modal_area>
+                <header class="modal-card-head">
+                    <p class="modal-card-title">Encryption</p>
+                    <button class="delete" aria-label="close" on:click=move |_| close() />
+                </header>
+                <section class="modal-card-body px-5">
+                    <Show
+                        when=move || is_encrypted.get()
+                        fallback=move || view! {
+                            <p class="mb-4">
+                                "Encrypt this note with a passphrase. Its text is only stored encrypted and "
+                                "has to be unlocked after a reload. The passphrase can't be recovered, "
+                                "without it the note is lost."
+                            </p>
+                            <div class="field">
+                                <label class="label">Passphrase</label>
+                                <div class="control">
+                                    <input
+                                        class="input"
+                                        type="password"
+                                        autocomplete="new-password"
+                                        minlength=MIN_PASSPHRASE_LENGTH
+                                        prop:value=passphrase
+                                        on:input=move |ev| {
+                                            passphrase.set(event_target_value(&ev));
+                                            error.set(None);
+                                        }
+                                    />
+                                </div>
+                            </div>
+                            <div class="field">
+                                <label class="label">Repeat Passphrase</label>
+                                <div class="control">
+                                    <input
+                                        class="input"
+                                        type="password"
+                                        autocomplete="new-password"
+                                        prop:value=confirmation
+                                        on:input=move |ev| {
+                                            confirmation.set(event_target_value(&ev));
+                                            error.set(None);
+                                        }
+                                    />
+                                </div>
+                                <p class="help is-danger">{ move || error.get() }</p>
+                            </div>
+                        }
+                    >
+                        <p class="mb-4">
+                            "This note is encrypted. Locking forgets the keys of all unlocked notes "
+                            "until their passphrase is entered again."
+                        </p>
+                        <div class="field">
+                            <label class="label">Lock Automatically</label>
+                            <Select
+                                icon=Icon::Lock
+                                options=AutoLock::CHOICES.map(|choice| choice.to_string()).to_vec()
+                                prop_value=Signal::derive(move || vault.auto_lock.get().to_string())
+                                on_change=move |value: String| {
+                                    vault.auto_lock.set(value.parse().unwrap_or_default());
+                                }
+                            />
+                        </div>
+                    </Show>
+                </section>
+                <footer class="modal-card-foot" style="gap: 0.5rem">
+                    <Show
+                        when=move || is_encrypted.get()
+                        fallback={
+                            let encrypt = encrypt.clone();
+                            move || {
+                                let encrypt = encrypt.clone();
+                                view! {
+                                    <Button
+                                        aria_label=String::from("Encrypt Note")
+                                        text=Signal::derive(move || Some(String::from(if encrypting.get() { "Encrypting…" } else { "Encrypt" })))
+                                        icon=Icon::Lock
+                                        size=Size::Small
+                                        color=Color::Primary
+                                        on_click=encrypt
+                                    />
+                                }
+                            }
+                        }
+                    >
+                        <Button
+                            aria_label=String::from("Lock Now")
+                            text="Lock Now"
+                            icon=Icon::Lock
+                            size=Size::Small
+                            color=Color::Primary
+                            on_click=lock
+                        />
+                        <Button
+                            aria_label=String::from("Download Encrypted")
+                            text="Download Encrypted"
+                            icon=Icon::Save
+                            size=Size::Small
+                            color=Color::Light
+                            on_click=download_encrypted.clone()
+                        />
+                        <Button
+                            aria_label=String::from("Remove Encryption")
+                            text="Remove Encryption"
+                            icon=Icon::Unlock
+                            size=Size::Small
+                            color=Color::Danger
+                            on_click=decrypt.clone()
+                        />
+                    </Show>
+                </footer>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,236 @@
+use leptoaster::expect_toaster;
+use leptos::{html::Div, prelude::*, task::spawn_local};
+use leptos_use::on_click_outside;
+use web_sys::js_sys::Date;
+
+use crate::{
+    api::{
+        documents::{DocumentLibrary, LinkedFile},
+        folder::{ExternalChange, FolderSync, file_tree, is_supported},
+        import::document_name,
+    },
+    components::{Color, Size, State, button::Button, icons::Icon},
+};
+
+/// Opens a folder of the device and lists its markdown files. Files open as documents
+/// that are saved back in place.
+#[component]
+pub fn FolderBrowser(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+    #[prop(into, default = Signal::from(false))] is_dropdown_item: Signal<bool>,
+    #[prop(default = false)] fullsize_button: bool,
+) -> impl IntoView {
+    let sync = expect_context::<FolderSync>();
+    let toaster = expect_toaster();
+
+    let modal_visible = RwSignal::new(false);
+    let modal_area = NodeRef::<Div>::new();
+    let _ = on_click_outside(modal_area, move |_| modal_visible.set(false));
+
+    let open_folder = {
+        let toaster = toaster.clone();
+        move || {
+            let toaster = toaster.clone();
+            spawn_local(async move {
+                if let Err(error) = sync.open().await {
+                    toaster.error(error);
+                }
+            });
+        }
+    };
+
+    let refresh = {
+        let toaster = toaster.clone();
+        move || {
+            let toaster = toaster.clone();
+            spawn_local(async move {
+                if let Err(error) = sync.refresh().await {
+                    toaster.error(error);
+                }
+            });
+        }
+    };
+
+    let open_file = move |path: String| {
+        let toaster = toaster.clone();
+        spawn_local(async move {
+            match open_linked_file(sync, markdown, documents, &path).await {
+                Ok(()) => modal_visible.set(false),
+                Err(error) => toaster.error(error),
+            }
+        });
+    };
+
+    let supported = is_supported();
+    let active_path = Signal::derive(move || {
+        documents
+            .read()
+            .active()
+            .and_then(|document| document.file.as_ref())
+            .map(|file| file.path.clone())
+    });
+
+    view! {
+        <Button
+            aria_label=String::from("Open Folder")
+            text="Folder"
+            icon=Icon::FolderTree
+            color=Signal::derive(move || if is_dropdown_item.get() { Color::Transparent } else { Color::None })
+            size=Size::Normal
+            has_smaller_padding=is_dropdown_item
+            is_full_size=fullsize_button
+            on_click=move || modal_visible.set(true)
+        />
+        <div class="modal" class:is-active=move || modal_visible.get()>
+            <div class="modal-background" />
+            <div class="modal-card container" node_ref=modal_area>
+                <header class="modal-card-head">
+                    <p class="modal-card-title">
+                        { move || sync.name.get().unwrap_or_else(|| String::from("Folder")) }
+                    </p>
+                    <button class="delete" aria-label="close" on:click=move |_| modal_visible.set(false) />
+                </header>
+                <section class="modal-card-body px-5">
+                    <Show
+                        when=move || !sync.files.read().is_empty()
+                        fallback=move || view! {
+                            <p>
+                                { move || if !supported {
+                                    "Your browser can't open folders. Use Open File and Download to work with single files instead."
+                                } else if sync.name.read().is_some() {
+                                    "The folder contains no markdown or text files."
+                                } else {
+                                    "Open a folder to edit its markdown files in place."
+                                } }
+                            </p>
+                        }
+                    >
+                        <div class="menu">
+                            <ul class="menu-list">
+                                {
+                                    let open_file = open_file.clone();
+                                    move || {
+                                        file_tree(&sync.files.read())
+                                            .into_iter()
+                                            .map(|entry| {
+                                                let indent = format!("padding-left: {}rem", 0.75 + entry.depth as f32 * 1.25);
+                                                match entry.path {
+                                                    Some(path) => {
+                                                        let open_file = open_file.clone();
+                                                        let is_active = {
+                                                            let path = path.clone();
+                                                            move || active_path.read().as_deref() == Some(path.as_str())
+                                                        };
+                                                        view! {
+                                                            <li>
+                                                                <a
+                                                                    style=indent
+                                                                    class:is-active=is_active
+                                                                    on:click=move |_| open_file(path.clone())
+                                                                >
+                                                                    <span class="icon"><i class=Icon::Markdown.as_fontawesome() /></span>
+                                                                    { entry.name }
+                                                                </a>
+                                                            </li>
+                                                        }.into_any()
+                                                    }
+                                                    None => view! {
+                                                        <li>
+                                                            <p class="has-text-weight-semibold py-1" style=indent>
+                                                                <span class="icon"><i class=Icon::FolderOpen.as_fontawesome() /></span>
+                                                                { entry.name }
+                                                            </p>
+                                                        </li>
+                                                    }.into_any(),
+                                                }
+                                            })
+                                            .collect_view()
+                                    }
+                                }
+                            </ul>
+                        </div>
+                    </Show>
+                </section>
+                <footer class="modal-card-foot" style="gap: 0.5rem">
+                    {supported.then(|| view! {
+                        <Button
+                            aria_label=String::from("Choose Folder")
+                            text="Open Folder"
+                            icon=Icon::FolderOpen
+                            size=Size::Small
+                            color=Color::Primary
+                            on_click=open_folder
+                        />
+                        <Button
+                            aria_label=String::from("Refresh Folder")
+                            text="Refresh"
+                            icon=Icon::Reset
+                            size=Size::Small
+                            color=Color::Light
+                            state=Signal::derive(move || if sync.name.read().is_some() { State::Normal } else { State::Disabled })
+                            on_click=refresh
+                        />
+                    })}
+                </footer>
+            </div>
+        </div>
+    }
+}
+
+/// Opens a file of the folder. A file that was opened before switches to its document,
+/// so the document model stays the single source for the editor.
+async fn open_linked_file(
+    sync: FolderSync,
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+    path: &str,
+) -> Result<(), String> {
+    let disk = sync.read(path).await?;
+
+    let linked = documents.with_untracked(|library| {
+        library
+            .find_by_path(path)
+            .map(|document| (document.id, document.file.clone()))
+    });
+
+    let Some((id, Some(file))) = linked else {
+        let name = document_name(path.rsplit('/').next().unwrap_or(path));
+        documents.update(|library| {
+            library.create_linked(
+                &name,
+                LinkedFile {
+                    path: path.to_string(),
+                    base: disk.content.clone(),
+                    disk_modified: disk.modified,
+                },
+                &markdown.get_untracked(),
+                Date::now(),
+            );
+        });
+        markdown.set(disk.content);
+        return Ok(());
+    };
+
+    let mut content = None;
+    documents.update(|library| content = library.open(id, &markdown.get_untracked(), Date::now()));
+    let content = content.unwrap_or_default();
+
+    if disk.content == file.base {
+        documents.update(|library| library.update_link(id, &file.base, disk.modified));
+        markdown.set(content);
+    } else if content == file.base {
+        // Nothing was edited here, so the file simply wins.
+        documents.update(|library| library.update_link(id, &disk.content, disk.modified));
+        markdown.set(disk.content);
+    } else {
+        markdown.set(content);
+        sync.change.set(Some(ExternalChange {
+            document: id,
+            path: path.to_string(),
+            file: disk,
+        }));
+    }
+
+    Ok(())
+}
@@ -1,22 +1,32 @@
 #[allow(dead_code)]
 #[derive(Copy, Clone)]
 pub enum Icon {
+    AlignCenter,
     AlignJusitify,
+    AlignLeft,
+    AlignRight,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
     Bars,
     Blockquote,
     Bold,
+    Calendar,
     Check,
     Checkbox,
     ChevronLeft,
     ChevronRight,
     Code,
     Computer,
+    Contents,
     Copy,
     Cross,
     CrossOut,
     Delete,
     Download,
     GitHub,
+    Hashtag,
     Help,
     Heading,
     Image,
@@ -25,15 +35,38 @@ pub enum Icon {
     Line,
     Link,
     Markdown,
+    Math,
     Monospace,
     Moon,
     OrderedList,
+    Palette,
+    Brush,
+    Css,
+    FileExport,
+    Print,
+    FolderOpen,
+    FolderTree,
+    Lock,
+    Unlock,
+    Merge,
+    NewFile,
+    Save,
+    Upload,
+    Plus,
     Read,
+    Rename,
+    Reset,
+    Search,
+    Separator,
+    Share,
+    Sliders,
+    Smile,
     Split,
     Subscript,
     Sun,
     Superscript,
     Table,
+    Template,
     Underline,
     UnorderedList,
     Write,
@@ -78,6 +111,39 @@ impl Icon {
             Self::Bars => String::from("fa-solid fa-bars"),
             Self::ChevronLeft => String::from("fa-solid fa-chevron-left"),
             Self::ChevronRight => String::from("fa-solid fa-chevron-right"),
+            Self::AlignLeft => String::from("fa-solid fa-align-left"),
+            Self::AlignCenter => String::from("fa-solid fa-align-center"),
+            Self::AlignRight => String::from("fa-solid fa-align-right"),
+            Self::ArrowUp => String::from("fa-solid fa-arrow-up"),
+            Self::ArrowDown => String::from("fa-solid fa-arrow-down"),
+            Self::ArrowLeft => String::from("fa-solid fa-arrow-left"),
+            Self::ArrowRight => String::from("fa-solid fa-arrow-right"),
+            Self::Plus => String::from("fa-solid fa-plus"),
+            Self::Palette => String::from("fa-solid fa-palette"),
+            Self::Brush => String::from("fa-solid fa-brush"),
+            Self::Css => String::from("fa-brands fa-css3-alt"),
+            Self::FileExport => String::from("fa-solid fa-file-export"),
+            Self::Print => String::from("fa-solid fa-print"),
+            Self::FolderOpen => String::from("fa-solid fa-folder-open"),
+            Self::FolderTree => String::from("fa-solid fa-folder-tree"),
+            Self::Lock => String::from("fa-solid fa-lock"),
+            Self::Unlock => String::from("fa-solid fa-lock-open"),
+            Self::Merge => String::from("fa-solid fa-code-merge"),
+            Self::NewFile => String::from("fa-solid fa-file-circle-plus"),
+            Self::Save => String::from("fa-solid fa-floppy-disk"),
+            Self::Upload => String::from("fa-solid fa-upload"),
+            Self::Sliders => String::from("fa-solid fa-sliders"),
+            Self::Reset => String::from("fa-solid fa-rotate-left"),
+            Self::Rename => String::from("fa-solid fa-i-cursor"),
+            Self::Template => String::from("fa-solid fa-file-lines"),
+            Self::Separator => String::from("fa-solid fa-grip-lines-vertical"),
+            Self::Share => String::from("fa-solid fa-share-nodes"),
+            Self::Search => String::from("fa-solid fa-magnifying-glass"),
+            Self::Hashtag => String::from("fa-solid fa-hashtag"),
+            Self::Calendar => String::from("fa-solid fa-calendar"),
+            Self::Math => String::from("fa-solid fa-square-root-variable"),
+            Self::Contents => String::from("fa-solid fa-list"),
+            Self::Smile => String::from("fa-regular fa-face-smile"),
         }
     }
 }
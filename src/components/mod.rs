@@ -2,12 +2,20 @@ pub mod button;
 pub mod confirmation;
 pub mod dialect;
 mod doc;
+pub mod documents;
 pub mod dropdown;
+pub mod encryption;
+pub mod folder;
 pub mod icons;
 pub mod logo;
+pub mod preview_style;
+pub mod search;
 pub mod select;
 pub mod tabs;
+pub mod templates;
 pub mod theme;
+pub mod theme_editor;
+pub mod toolbar;
 pub mod tooltip;
 
 pub use doc::modal::HelpModal;
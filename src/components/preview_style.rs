@@ -0,0 +1,62 @@
+use gloo::file::{File, futures::read_as_text};
+use leptoaster::expect_toaster;
+use leptos::{prelude::*, task::spawn_local};
+use web_sys::HtmlInputElement;
+
+use crate::{
+    api::preview_styles::{PREVIEW_STYLES, PreviewStyle},
+    components::{icons::Icon, select::Select},
+};
+
+/// Selects the preview style and loads a CSS file for the custom style.
+#[component]
+pub fn PreviewStyleSelect(
+    preview_style: RwSignal<PreviewStyle>,
+    custom_css: RwSignal<String>,
+) -> impl IntoView {
+    let toaster = expect_toaster();
+    let options: Vec<String> = PREVIEW_STYLES.iter().map(ToString::to_string).collect();
+
+    let load_css = move |ev: leptos::ev::Event| {
+        let input = event_target::<HtmlInputElement>(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        input.set_value("");
+
+        let toaster = toaster.clone();
+        spawn_local(async move {
+            match read_as_text(&File::from(file)).await {
+                Ok(css) => {
+                    custom_css.set(css);
+                    preview_style.set(PreviewStyle::Custom);
+                    toaster.success("Custom CSS loaded");
+                }
+                Err(error) => toaster.error(error.to_string().as_str()),
+            }
+        });
+    };
+
+    view! {
+        <div class="is-flex is-align-items-center" style="gap: 0.5rem">
+            <Select
+                icon=Icon::Brush
+                options
+                prop_value=Signal::derive(move || preview_style.get().to_string())
+                on_change=move |value: String| {
+                    preview_style.set(value.parse().unwrap_or_default());
+                }
+            />
+            <div class="file">
+                <label class="file-label" title="Load a CSS file for the custom preview style">
+                    <input class="file-input" type="file" accept=".css,text/css" on:change=load_css />
+                    <span class="file-cta">
+                        <span class="file-icon m-0">
+                            <i class=Icon::Css.as_fontawesome() />
+                        </span>
+                    </span>
+                </label>
+            </div>
+        </div>
+    }
+}
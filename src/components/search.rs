@@ -0,0 +1,209 @@
+use leptos::{
+    html::{Div, Input},
+    prelude::*,
+};
+use leptos_use::on_click_outside;
+use wasm_bindgen::JsValue;
+use web_sys::js_sys::Date;
+
+use crate::{
+    api::{
+        documents::DocumentLibrary,
+        parser::Dialect,
+        search::{ModifiedWithin, SearchFilter, SearchIndex},
+    },
+    components::{Color, Size, button::Button, icons::Icon, select::Select},
+};
+
+const ALL_TAGS: &str = "All tags";
+const ALL_DIALECTS: &str = "All dialects";
+
+/// Searches the text of all documents and filters them by tag, date and dialect.
+#[component]
+pub fn SearchButton(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+    #[prop(into, default = Signal::from(false))] is_dropdown_item: Signal<bool>,
+    #[prop(default = false)] fullsize_button: bool,
+) -> impl IntoView {
+    let modal_visible = RwSignal::new(false);
+    let modal_area = NodeRef::<Div>::new();
+    let input_ref = NodeRef::<Input>::new();
+    let _ = on_click_outside(modal_area, move |_| modal_visible.set(false));
+
+    let query = RwSignal::new(String::new());
+    let filter = RwSignal::new(SearchFilter::default());
+
+    // Only kept up to date while the search is open.
+    let index = Memo::new(move |_| {
+        if !modal_visible.get() {
+            return SearchIndex::default();
+        }
+        documents.with(|library| SearchIndex::build(library, &markdown.read()))
+    });
+    let results = Memo::new(move |_| {
+        index.with(|index| index.search(&query.read(), &filter.read(), Date::now()))
+    });
+
+    Effect::new(move || {
+        if modal_visible.get()
+            && let Some(input) = input_ref.get()
+        {
+            let _ = input.focus();
+        }
+    });
+
+    let open = move |id: u64| {
+        let mut content = None;
+        documents.update(|library| {
+            if id != library.active {
+                content = library.open(id, &markdown.get_untracked(), Date::now());
+            }
+        });
+        if let Some(content) = content {
+            markdown.set(content);
+        }
+        modal_visible.set(false);
+    };
+
+    let tag_options = Signal::derive(move || {
+        let mut options = vec![String::from(ALL_TAGS)];
+        options.extend(index.read().tags().into_iter().map(|tag| format!("#{tag}")));
+        options
+    });
+    let dialect_options = [ALL_DIALECTS, "Common", "GitHub"]
+        .map(String::from)
+        .to_vec();
+
+    view! {
+        <Button
+            aria_label=String::from("Search")
+            text="Search"
+            icon=Icon::Search
+            color=Signal::derive(move || if is_dropdown_item.get() { Color::Transparent } else { Color::None })
+            size=Size::Normal
+            has_smaller_padding=is_dropdown_item
+            is_full_size=fullsize_button
+            on_click=move || modal_visible.set(true)
+        />
+        <div class="modal" class:is-active=move || modal_visible.get()>
+            <div class="modal-background" />
+            <div class="modal-card container" node_ref=modal_area>
+                <header class="modal-card-head">
+                    <p class="modal-card-title">Search</p>
+                    <button class="delete" aria-label="close" on:click=move |_| modal_visible.set(false) />
+                </header>
+                <section class="modal-card-body px-5">
+                    <div class="field">
+                        <div class="control has-icons-left">
+                            <input
+                                class="input"
+                                type="search"
+                                placeholder="Search all documents"
+                                node_ref=input_ref
+                                prop:value=query
+                                on:input=move |ev| query.set(event_target_value(&ev))
+                            />
+                            <span class="icon is-left"><i class=Icon::Search.as_fontawesome() /></span>
+                        </div>
+                    </div>
+                    <div class="columns is-variable is-1 mb-2">
+                        <div class="column">
+                            <Select
+                                icon=Icon::Hashtag
+                                options=tag_options
+                                prop_value=Signal::derive(move || {
+                                    filter
+                                        .read()
+                                        .tag
+                                        .as_ref()
+                                        .map(|tag| format!("#{tag}"))
+                                        .unwrap_or_else(|| String::from(ALL_TAGS))
+                                })
+                                on_change=move |value: String| {
+                                    filter.update(|filter| {
+                                        filter.tag = value.strip_prefix('#').map(String::from);
+                                    });
+                                }
+                            />
+                        </div>
+                        <div class="column">
+                            <Select
+                                icon=Icon::Calendar
+                                options=ModifiedWithin::CHOICES.map(|choice| choice.to_string()).to_vec()
+                                prop_value=Signal::derive(move || filter.read().modified.to_string())
+                                on_change=move |value: String| {
+                                    filter.update(|filter| filter.modified = value.parse().unwrap_or_default());
+                                }
+                            />
+                        </div>
+                        <div class="column">
+                            <Select
+                                icon=Icon::Markdown
+                                options=dialect_options
+                                prop_value=Signal::derive(move || {
+                                    filter
+                                        .read()
+                                        .dialect
+                                        .as_ref()
+                                        .map(Dialect::to_string)
+                                        .unwrap_or_else(|| String::from(ALL_DIALECTS))
+                                })
+                                on_change=move |value: String| {
+                                    filter.update(|filter| filter.dialect = value.parse().ok());
+                                }
+                            />
+                        </div>
+                    </div>
+                    <Show
+                        when=move || !results.read().is_empty()
+                        fallback=|| view! { <p class="has-text-grey">"No documents found."</p> }
+                    >
+                        <div class="menu">
+                            <ul class="menu-list">
+                                {
+                                    move || {
+                                        results
+                                            .get()
+                                            .into_iter()
+                                            .map(|result| {
+                                                let id = result.id;
+                                                let is_active = move || documents.read().active == id;
+                                                view! {
+                                                    <li>
+                                                        <a class:is-active=is_active on:click=move |_| open(id)>
+                                                            <p class="is-flex is-justify-content-space-between" style="gap: 0.5rem">
+                                                                <span class="has-text-weight-semibold">{ result.name }</span>
+                                                                <span class="is-size-7">{ format_date(result.modified) }</span>
+                                                            </p>
+                                                            <p class="is-size-7" inner_html=result.snippet.to_html() />
+                                                            <p class="tags mt-1">
+                                                                {
+                                                                    result
+                                                                        .tags
+                                                                        .into_iter()
+                                                                        .map(|tag| view! { <span class="tag is-rounded">{ format!("#{tag}") }</span> })
+                                                                        .collect_view()
+                                                                }
+                                                            </p>
+                                                        </a>
+                                                    </li>
+                                                }
+                                            })
+                                            .collect_view()
+                                    }
+                                }
+                            </ul>
+                        </div>
+                    </Show>
+                </section>
+            </div>
+        </div>
+    }
+}
+
+/// The date in the format of the browser's locale.
+fn format_date(millis: f64) -> String {
+    let date = Date::new(&JsValue::from_f64(millis));
+    String::from(date.to_locale_date_string("default", &JsValue::UNDEFINED))
+}
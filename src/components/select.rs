@@ -3,7 +3,7 @@ use leptos::prelude::*;
 
 #[component]
 pub fn Select(
-    options: Vec<String>,
+    #[prop(into)] options: Signal<Vec<String>>,
     #[prop(into, optional)] icon: Option<Signal<Icon>>,
     #[prop(into)] prop_value: Signal<String>,
     on_change: impl Fn(String) + 'static,
@@ -19,7 +19,7 @@ pub fn Select(
                     }
                 >
                     <For
-                        each=move || options.clone()
+                        each=move || options.get()
                         key=|o| o.clone()
                         children=|o| {
                             view! {
@@ -0,0 +1,381 @@
+use gloo::timers::future::TimeoutFuture;
+use leptoaster::expect_toaster;
+use leptos::{html::Div, prelude::*, task::spawn_local};
+use leptos_use::on_click_outside;
+use web_sys::js_sys::Date;
+
+use crate::{
+    api::{
+        documents::DocumentLibrary,
+        markdown_formatter::place_caret,
+        templates::{Placeholders, Snippet, Template, TemplateLibrary, expand},
+    },
+    components::{
+        Color, Size,
+        button::Button,
+        confirmation::Confirmation,
+        icons::Icon,
+        select::Select,
+        tabs::{Tab, Tabs},
+    },
+};
+
+const NEW_TEMPLATE: &str = "New Template";
+const NEW_SNIPPET: &str = "New Snippet";
+const TEMPLATE_PREFIX: &str = "Template: ";
+const SNIPPET_PREFIX: &str = "Snippet: ";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Template,
+    Snippet,
+}
+
+/// Creates notes from templates and edits the templates and snippets of the user.
+#[component]
+pub fn TemplateButton(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+    #[prop(into, default = Signal::from(false))] is_dropdown_item: Signal<bool>,
+    #[prop(default = false)] fullsize_button: bool,
+) -> impl IntoView {
+    let templates = expect_context::<RwSignal<TemplateLibrary>>();
+    let toaster = expect_toaster();
+
+    let modal_visible = RwSignal::new(false);
+    let modal_area = NodeRef::<Div>::new();
+    let _ = on_click_outside(modal_area, move |_| modal_visible.set(false));
+    let active_tab = RwSignal::new(0);
+
+    // New note
+    let chosen = RwSignal::new(String::new());
+    let title = RwSignal::new(String::new());
+
+    let chosen_template = Memo::new(move |_| {
+        let library = templates.read();
+        library
+            .template(&chosen.read())
+            .or_else(|| library.templates().into_iter().next())
+            .unwrap_or_default()
+    });
+    let note_title = move || {
+        let title = title.get_untracked();
+        match title.trim() {
+            "" => chosen_template.read_untracked().name.clone(),
+            title => title.to_string(),
+        }
+    };
+    let preview = move || {
+        let name = match title.get().trim() {
+            "" => chosen_template.read().name.clone(),
+            title => title.to_string(),
+        };
+        expand(&chosen_template.read().content, &Placeholders::now(&name)).text
+    };
+
+    let create = move || {
+        let name = note_title();
+        let expansion = expand(
+            &chosen_template.read_untracked().content,
+            &Placeholders::now(&name),
+        );
+        documents.update(|library| {
+            library.create(
+                &name,
+                &expansion.text,
+                &markdown.get_untracked(),
+                Date::now(),
+            );
+        });
+        markdown.set(expansion.text.clone());
+        modal_visible.set(false);
+        title.set(String::new());
+
+        spawn_local(async move {
+            // Wait for the editor to show the new text.
+            TimeoutFuture::new(0).await;
+            place_caret(&expansion.text, expansion.cursor);
+        });
+    };
+
+    // Library
+    let kind = RwSignal::new(Kind::Template);
+    // Name or trigger of the stored entry that is edited, `None` for a new one.
+    let editing: RwSignal<Option<String>> = RwSignal::new(None);
+    let draft_key = RwSignal::new(String::new());
+    let draft_content = RwSignal::new(String::new());
+
+    let load = move |option: String| {
+        let library = templates.read_untracked();
+        if let Some(name) = option.strip_prefix(TEMPLATE_PREFIX)
+            && let Some(template) = library.template(name)
+        {
+            kind.set(Kind::Template);
+            editing.set(Some(template.name.clone()));
+            draft_key.set(template.name);
+            draft_content.set(template.content);
+        } else if let Some(trigger) = option.strip_prefix(SNIPPET_PREFIX)
+            && let Some(snippet) = library.snippet(trigger)
+        {
+            kind.set(Kind::Snippet);
+            editing.set(Some(snippet.trigger.clone()));
+            draft_key.set(snippet.trigger);
+            draft_content.set(snippet.content);
+        } else {
+            let is_snippet = option == NEW_SNIPPET;
+            kind.set(if is_snippet {
+                Kind::Snippet
+            } else {
+                Kind::Template
+            });
+            editing.set(None);
+            draft_key.set(String::from(if is_snippet { "/" } else { "" }));
+            draft_content.set(String::new());
+        }
+    };
+
+    let library_options = Signal::derive(move || {
+        let library = templates.read();
+        let mut options = vec![String::from(NEW_TEMPLATE), String::from(NEW_SNIPPET)];
+        options.extend(
+            library
+                .templates()
+                .into_iter()
+                .map(|template| format!("{TEMPLATE_PREFIX}{}", template.name)),
+        );
+        options.extend(
+            library
+                .snippets()
+                .into_iter()
+                .map(|snippet| format!("{SNIPPET_PREFIX}{}", snippet.trigger)),
+        );
+        options
+    });
+    let selected_option = Signal::derive(move || match (kind.get(), editing.get()) {
+        (Kind::Template, Some(name)) => format!("{TEMPLATE_PREFIX}{name}"),
+        (Kind::Snippet, Some(trigger)) => format!("{SNIPPET_PREFIX}{trigger}"),
+        (Kind::Template, None) => String::from(NEW_TEMPLATE),
+        (Kind::Snippet, None) => String::from(NEW_SNIPPET),
+    });
+    let is_custom = Signal::derive(move || {
+        editing.get().is_some_and(|key| {
+            let library = templates.read();
+            match kind.get() {
+                Kind::Template => library.is_custom_template(&key),
+                Kind::Snippet => library.is_custom_snippet(&key),
+            }
+        })
+    });
+
+    let save = move || {
+        let key = draft_key.get_untracked().trim().to_string();
+        let content = draft_content.get_untracked();
+        let kind = kind.get_untracked();
+
+        let validation = match kind {
+            Kind::Template => Template {
+                name: key.clone(),
+                content: content.clone(),
+            }
+            .validate(),
+            Kind::Snippet => Snippet {
+                trigger: key.clone(),
+                content: content.clone(),
+            }
+            .validate(),
+        };
+        if let Err(error) = validation {
+            toaster.error(error);
+            return;
+        }
+
+        templates.update(|library| {
+            let renamed = editing.get_untracked().filter(|old| *old != key);
+            match kind {
+                Kind::Template => {
+                    if let Some(old) = renamed {
+                        library.remove_template(&old);
+                    }
+                    library.save_template(Template {
+                        name: key.clone(),
+                        content,
+                    });
+                }
+                Kind::Snippet => {
+                    if let Some(old) = renamed {
+                        library.remove_snippet(&old);
+                    }
+                    library.save_snippet(Snippet {
+                        trigger: key.clone(),
+                        content,
+                    });
+                }
+            }
+        });
+        editing.set(Some(key.clone()));
+        toaster.success(format!("Saved \"{key}\""));
+    };
+
+    let delete = move || {
+        if let Some(key) = editing.get_untracked() {
+            templates.update(|library| match kind.get_untracked() {
+                Kind::Template => library.remove_template(&key),
+                Kind::Snippet => library.remove_snippet(&key),
+            });
+        }
+        load(String::from(match kind.get_untracked() {
+            Kind::Template => NEW_TEMPLATE,
+            Kind::Snippet => NEW_SNIPPET,
+        }));
+    };
+
+    let tabs = vec![
+        Tab {
+            name: String::from("New Note"),
+            icon: Icon::NewFile,
+        },
+        Tab {
+            name: String::from("Library"),
+            icon: Icon::Template,
+        },
+    ];
+
+    view! {
+        <Button
+            aria_label=String::from("Templates")
+            text="Templates"
+            icon=Icon::Template
+            color=Signal::derive(move || if is_dropdown_item.get() { Color::Transparent } else { Color::None })
+            size=Size::Normal
+            has_smaller_padding=is_dropdown_item
+            is_full_size=fullsize_button
+            on_click=move || modal_visible.set(true)
+        />
+        <div class="modal" class:is-active=move || modal_visible.get()>
+            <div class="modal-background" />
+            <div class="modal-card container" node_ref=modal_area>
+                <header class="modal-card-head">
+                    <p class="modal-card-title">Templates</p>
+                    <button class="delete" aria-label="close" on:click=move |_| modal_visible.set(false) />
+                </header>
+                <section class="modal-card-body px-5">
+                    <Tabs active_tab tabs />
+                    <Show
+                        when=move || active_tab.get() == 0
+                        fallback=move || view! {
+                            <div class="is-flex is-align-items-center mb-4" style="gap: 0.5rem">
+                                <Select
+                                    icon=Icon::Template
+                                    options=library_options
+                                    prop_value=selected_option
+                                    on_change=load
+                                />
+                                <Show when=move || is_custom.get()>
+                                    <Confirmation
+                                        confirmation_text=Signal::derive(move || {
+                                            format!("Delete \"{}\"?", editing.get().unwrap_or_default())
+                                        })
+                                        on_confirmation=delete
+                                    />
+                                </Show>
+                            </div>
+                            <div class="field">
+                                <label class="label">
+                                    { move || if kind.get() == Kind::Template { "Name" } else { "Trigger" } }
+                                </label>
+                                <div class="control">
+                                    <input
+                                        class="input"
+                                        type="text"
+                                        prop:value=draft_key
+                                        on:input=move |ev| draft_key.set(event_target_value(&ev))
+                                    />
+                                </div>
+                                <Show when=move || kind.get() == Kind::Snippet>
+                                    <p class="help">"Type the trigger in the editor and press Tab to insert the snippet."</p>
+                                </Show>
+                            </div>
+                            <div class="field">
+                                <label class="label">Content</label>
+                                <div class="control">
+                                    <textarea
+                                        class="textarea is-family-monospace"
+                                        rows="10"
+                                        prop:value=draft_content
+                                        on:input=move |ev| draft_content.set(event_target_value(&ev))
+                                    />
+                                </div>
+                                <p class="help">
+                                    "{{title}}, {{date}} and {{time}} are filled in, {{cursor}} marks where the caret is placed."
+                                </p>
+                            </div>
+                        }
+                    >
+                        <div class="field">
+                            <label class="label">Template</label>
+                            <Select
+                                icon=Icon::Template
+                                options=Signal::derive(move || {
+                                    templates.read().templates().into_iter().map(|template| template.name).collect::<Vec<_>>()
+                                })
+                                prop_value=Signal::derive(move || chosen_template.read().name.clone())
+                                on_change=move |value: String| chosen.set(value)
+                            />
+                        </div>
+                        <div class="field">
+                            <label class="label">Title</label>
+                            <div class="control">
+                                <input
+                                    class="input"
+                                    type="text"
+                                    placeholder=move || chosen_template.read().name.clone()
+                                    prop:value=title
+                                    on:input=move |ev| title.set(event_target_value(&ev))
+                                />
+                            </div>
+                        </div>
+                        <pre class="template-preview">{ preview }</pre>
+                    </Show>
+                </section>
+                <footer class="modal-card-foot" style="gap: 0.5rem">
+                    <Show
+                        when=move || active_tab.get() == 0
+                        fallback={
+                            let save = save.clone();
+                            move || {
+                                let save = save.clone();
+                                view! {
+                                    <Button
+                                        aria_label=String::from("Save Template")
+                                        text="Save"
+                                        icon=Icon::Save
+                                        size=Size::Small
+                                        color=Color::Primary
+                                        on_click=save
+                                    />
+                                    <Button
+                                        aria_label=String::from("Use Current Note")
+                                        text="Use Current Note"
+                                        icon=Icon::Copy
+                                        size=Size::Small
+                                        color=Color::Light
+                                        on_click=move || draft_content.set(markdown.get_untracked())
+                                    />
+                                }
+                            }
+                        }
+                    >
+                        <Button
+                            aria_label=String::from("Create Note from Template")
+                            text="Create Note"
+                            icon=Icon::NewFile
+                            size=Size::Small
+                            color=Color::Primary
+                            on_click=create
+                        />
+                    </Show>
+                </footer>
+            </div>
+        </div>
+    }
+}
@@ -1,7 +1,7 @@
 use std::{fmt, str::FromStr};
 
 use crate::{
-    api::local_storage::use_persistent_signal,
+    api::themes::{BaseScheme, CustomTheme, ThemeLibrary},
     components::{icons::Icon, select::Select},
 };
 use leptos::prelude::*;
@@ -13,6 +13,7 @@ pub enum Theme {
     Dark,
     #[default]
     System,
+    Custom(String),
 }
 
 impl fmt::Display for Theme {
@@ -24,6 +25,7 @@ impl fmt::Display for Theme {
                 Theme::Light => "Light",
                 Theme::Dark => "Dark",
                 Theme::System => "System",
+                Theme::Custom(name) => name,
             }
         )
     }
@@ -37,34 +39,63 @@ impl FromStr for Theme {
             "light" => Ok(Theme::Light),
             "dark" => Ok(Theme::Dark),
             "system" => Ok(Theme::System),
+            _ if !s.trim().is_empty() => Ok(Theme::Custom(s.to_string())),
             _ => Err(()),
         }
     }
 }
 
-#[component]
-pub fn ThemeSelect() -> impl IntoView {
-    let theme: RwSignal<Theme> = use_persistent_signal(String::from("theme"));
+/// Applies the selected theme to the document. Custom themes set their
+/// colours and fonts as CSS variables on top of their base scheme, a
+/// `preview` theme from the theme editor takes precedence while it is set.
+pub fn apply_theme(
+    theme: RwSignal<Theme>,
+    themes: RwSignal<ThemeLibrary>,
+    preview: RwSignal<Option<CustomTheme>>,
+) {
     let dark_preferred = use_preferred_dark();
 
     Effect::new(move || {
-        let theme = theme.get();
+        let custom_theme = match (preview.get(), &*theme.read()) {
+            (Some(draft), _) => Some(draft),
+            (None, Theme::Custom(name)) => themes.read().get(name).cloned(),
+            _ => None,
+        };
 
-        let is_dark = match theme {
-            Theme::Light => false,
-            Theme::Dark => true,
-            Theme::System => dark_preferred.get(),
+        let is_dark = match (&custom_theme, &*theme.read()) {
+            (Some(custom), _) => custom.base == BaseScheme::Dark,
+            (None, Theme::Light) => false,
+            (None, Theme::Dark) => true,
+            (None, _) => dark_preferred.get(),
         };
 
         let data_theme = if is_dark { "dark" } else { "light" };
         let document = use_document();
         let html = document.document_element().unwrap();
         html.set_attribute("data-theme", data_theme).unwrap();
+
+        match custom_theme {
+            Some(custom) => {
+                html.set_attribute("data-custom-theme", "").unwrap();
+                html.set_attribute("style", &custom.to_css_variables())
+                    .unwrap();
+            }
+            None => {
+                html.remove_attribute("data-custom-theme").unwrap();
+                html.remove_attribute("style").unwrap();
+            }
+        }
     });
+}
+
+#[component]
+pub fn ThemeSelect(theme: RwSignal<Theme>, themes: RwSignal<ThemeLibrary>) -> impl IntoView {
+    let dark_preferred = use_preferred_dark();
 
     let icon = Signal::derive(move || match theme.get() {
         Theme::Light => Icon::Sun,
         Theme::Dark => Icon::Moon,
+        Theme::Custom(_) => Icon::Palette,
         Theme::System => {
             if dark_preferred.get() {
                 Icon::Moon
@@ -74,13 +105,23 @@ pub fn ThemeSelect() -> impl IntoView {
         }
     });
 
+    let options = Signal::derive(move || {
+        let mut options = vec![
+            String::from("Light"),
+            String::from("Dark"),
+            String::from("System"),
+        ];
+        options.extend(themes.read().names());
+        options
+    });
+
     view! {
         <Select
             icon
-            options=vec![String::from("Light"), String::from("Dark"), String::from("System")]
+            options
             prop_value=Signal::derive(move || theme.get().to_string())
             on_change=move |value: String| {
-                theme.set(value.parse().unwrap());
+                theme.set(value.parse().unwrap_or_default());
             }
         />
     }
@@ -0,0 +1,324 @@
+use gloo::file::{File, futures::read_as_text};
+use leptoaster::expect_toaster;
+use leptos::{html::Div, prelude::*, task::spawn_local};
+use leptos_use::on_click_outside;
+use web_sys::HtmlInputElement;
+
+use crate::{
+    api::{
+        download::download_file,
+        themes::{BaseScheme, CustomTheme, ThemeLibrary},
+    },
+    components::{
+        Color, Size, button::Button, confirmation::Confirmation, icons::Icon, select::Select,
+        theme::Theme,
+    },
+};
+
+const NEW_THEME: &str = "New Theme";
+
+/// Button that opens a modal to create, edit, import and export custom themes.
+///
+/// While the modal is open the edited theme is written to `preview`, so every change is
+/// visible in the editor and the preview right away.
+#[component]
+pub fn ThemeEditor(
+    theme: RwSignal<Theme>,
+    themes: RwSignal<ThemeLibrary>,
+    preview: RwSignal<Option<CustomTheme>>,
+    #[prop(into, default = Signal::from(false))] is_dropdown_item: Signal<bool>,
+    #[prop(default = false)] fullsize_button: bool,
+) -> impl IntoView {
+    let toaster = expect_toaster();
+
+    let modal_visible = RwSignal::new(false);
+    let draft = RwSignal::new(CustomTheme::default());
+    // Name of the stored theme that is being edited, `None` for a new theme.
+    let editing: RwSignal<Option<String>> = RwSignal::new(None);
+
+    let modal_area = NodeRef::<Div>::new();
+    let _ = on_click_outside(modal_area, move |_| modal_visible.set(false));
+
+    Effect::new(move || {
+        if modal_visible.get() {
+            preview.set(Some(draft.get()));
+        } else {
+            preview.set(None);
+        }
+    });
+
+    let load = move |name: Option<String>| {
+        let stored = name
+            .as_ref()
+            .and_then(|name| themes.read_untracked().get(name).cloned());
+        editing.set(stored.as_ref().map(|stored| stored.name.clone()));
+        draft.set(stored.unwrap_or_default());
+    };
+
+    let open = move || {
+        match theme.get_untracked() {
+            Theme::Custom(name) => load(Some(name)),
+            _ => load(None),
+        }
+        modal_visible.set(true);
+    };
+
+    let save_toaster = toaster.clone();
+    let save = move || {
+        let new_theme = draft.get_untracked();
+        if let Err(error) = new_theme.validate() {
+            save_toaster.error(error.as_str());
+            return;
+        }
+
+        themes.update(|library| {
+            if let Some(old_name) = editing.get_untracked()
+                && old_name != new_theme.name
+            {
+                library.remove(&old_name);
+            }
+            library.save(new_theme.clone());
+        });
+        editing.set(Some(new_theme.name.clone()));
+        theme.set(Theme::Custom(new_theme.name.clone()));
+        save_toaster.success(format!("Saved theme \"{}\"", new_theme.name).as_str());
+    };
+
+    let delete = move || {
+        if let Some(name) = editing.get_untracked() {
+            themes.update(|library| library.remove(&name));
+            if theme.get_untracked() == Theme::Custom(name) {
+                theme.set(Theme::System);
+            }
+        }
+        load(None);
+    };
+
+    let import_toaster = toaster.clone();
+    let import = move |ev: leptos::ev::Event| {
+        let input = event_target::<HtmlInputElement>(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        input.set_value("");
+
+        let toaster = import_toaster.clone();
+        spawn_local(async move {
+            let file = File::from(file);
+            let imported = read_as_text(&file)
+                .await
+                .map_err(|error| error.to_string())
+                .and_then(|text| CustomTheme::from_file(&file.name(), &text));
+
+            match imported {
+                Ok(imported) => {
+                    editing.set(None);
+                    draft.set(imported);
+                    toaster.info("Theme imported, save it to keep it");
+                }
+                Err(error) => toaster.error(error.as_str()),
+            }
+        });
+    };
+
+    let theme_options = Signal::derive(move || {
+        let mut options = vec![String::from(NEW_THEME)];
+        options.extend(themes.read().names());
+        options
+    });
+
+    view! {
+        <Button
+            aria_label=String::from("Edit Themes")
+            text="Themes"
+            icon=Icon::Palette
+            color=Signal::derive(move || if is_dropdown_item.get() { Color::Transparent } else { Color::None })
+            size=Size::Normal
+            has_smaller_padding=is_dropdown_item
+            is_full_size=fullsize_button
+            on_click=open
+        />
+        <div class="modal" class:is-active=move || modal_visible.get()>
+            <div class="modal-background" />
+            <div class="modal-card container" node_ref=modal_area>
+                <header class="modal-card-head">
+                    <p class="modal-card-title">Theme Editor</p>
+                    <button class="delete" aria-label="close" on:click=move |_| modal_visible.set(false) />
+                </header>
+                <section class="modal-card-body px-5">
+                    <div class="is-flex is-align-items-center mb-4" style="gap: 0.5rem">
+                        <Select
+                            icon=Icon::Palette
+                            options=theme_options
+                            prop_value=Signal::derive(move || editing.get().unwrap_or_else(|| String::from(NEW_THEME)))
+                            on_change=move |value: String| {
+                                load((value != NEW_THEME).then_some(value));
+                            }
+                        />
+                        <Show when=move || editing.read().is_some()>
+                            <Confirmation
+                                confirmation_text=Signal::derive(move || {
+                                    format!("Delete the theme \"{}\"?", editing.get().unwrap_or_default())
+                                })
+                                on_confirmation=delete
+                            />
+                        </Show>
+                    </div>
+                    <ThemeField
+                        label="Name"
+                        value=Signal::derive(move || draft.read().name.clone())
+                        on_input=move |value| draft.update(|draft| draft.name = value)
+                    />
+                    <div class="field is-horizontal">
+                        <div class="field-label is-normal">
+                            <label class="label">Base</label>
+                        </div>
+                        <div class="field-body">
+                            <Select
+                                options=vec![String::from("Light"), String::from("Dark")]
+                                prop_value=Signal::derive(move || match draft.read().base {
+                                    BaseScheme::Light => String::from("Light"),
+                                    BaseScheme::Dark => String::from("Dark"),
+                                })
+                                on_change=move |value: String| {
+                                    let base = if value == "Dark" { BaseScheme::Dark } else { BaseScheme::Light };
+                                    draft.update(|draft| draft.base = base);
+                                }
+                            />
+                        </div>
+                    </div>
+                    <ThemeField
+                        label="Accent"
+                        input_type="color"
+                        value=Signal::derive(move || draft.read().accent.clone())
+                        on_input=move |value| draft.update(|draft| draft.accent = value)
+                    />
+                    <ThemeField
+                        label="Editor Background"
+                        input_type="color"
+                        value=Signal::derive(move || draft.read().editor_background.clone())
+                        on_input=move |value| draft.update(|draft| draft.editor_background = value)
+                    />
+                    <ThemeField
+                        label="Editor Text"
+                        input_type="color"
+                        value=Signal::derive(move || draft.read().editor_text.clone())
+                        on_input=move |value| draft.update(|draft| draft.editor_text = value)
+                    />
+                    <ThemeField
+                        label="Preview Background"
+                        input_type="color"
+                        value=Signal::derive(move || draft.read().preview_background.clone())
+                        on_input=move |value| draft.update(|draft| draft.preview_background = value)
+                    />
+                    <ThemeField
+                        label="Preview Text"
+                        input_type="color"
+                        value=Signal::derive(move || draft.read().preview_text.clone())
+                        on_input=move |value| draft.update(|draft| draft.preview_text = value)
+                    />
+                    <ThemeField
+                        label="Preview Font"
+                        value=Signal::derive(move || draft.read().font_family.clone())
+                        on_input=move |value| draft.update(|draft| draft.font_family = value)
+                    />
+                    <ThemeField
+                        label="Editor Font"
+                        value=Signal::derive(move || draft.read().editor_font_family.clone())
+                        on_input=move |value| draft.update(|draft| draft.editor_font_family = value)
+                    />
+                    <ThemeField
+                        label="Font Size"
+                        input_type="number"
+                        value=Signal::derive(move || draft.read().font_size.to_string())
+                        on_input=move |value: String| {
+                            if let Ok(font_size) = value.parse() {
+                                draft.update(|draft| draft.font_size = font_size);
+                            }
+                        }
+                    />
+                    <ThemeField
+                        label="Line Height"
+                        input_type="number"
+                        value=Signal::derive(move || draft.read().line_height.to_string())
+                        on_input=move |value: String| {
+                            if let Ok(line_height) = value.parse() {
+                                draft.update(|draft| draft.line_height = line_height);
+                            }
+                        }
+                    />
+                </section>
+                <footer class="modal-card-foot is-flex is-flex-wrap-wrap" style="gap: 0.5rem">
+                    <Button
+                        aria_label=String::from("Save Theme")
+                        text="Save"
+                        icon=Icon::Save
+                        size=Size::Small
+                        color=Color::Primary
+                        on_click=save
+                    />
+                    <Button
+                        aria_label=String::from("Export Theme as JSON")
+                        text="JSON"
+                        icon=Icon::Download
+                        size=Size::Small
+                        on_click=move || {
+                            let draft = draft.get_untracked();
+                            download_file(draft.to_json(), &draft.file_name("json"));
+                        }
+                    />
+                    <Button
+                        aria_label=String::from("Export Theme as TOML")
+                        text="TOML"
+                        icon=Icon::Download
+                        size=Size::Small
+                        on_click=move || {
+                            let draft = draft.get_untracked();
+                            download_file(draft.to_toml(), &draft.file_name("toml"));
+                        }
+                    />
+                    <div class="file is-small">
+                        <label class="file-label">
+                            <input class="file-input" type="file" accept=".json,.toml" on:change=import />
+                            <span class="file-cta">
+                                <span class="file-icon">
+                                    <i class=Icon::Upload.as_fontawesome() />
+                                </span>
+                                <span class="file-label">Import</span>
+                            </span>
+                        </label>
+                    </div>
+                </footer>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ThemeField(
+    label: &'static str,
+    #[prop(default = "text")] input_type: &'static str,
+    value: Signal<String>,
+    on_input: impl Fn(String) + 'static,
+) -> impl IntoView {
+    view! {
+        <div class="field is-horizontal">
+            <div class="field-label is-normal">
+                <label class="label">{ label }</label>
+            </div>
+            <div class="field-body">
+                <div class="field">
+                    <div class="control">
+                        <input
+                            class="input"
+                            type=input_type
+                            step="any"
+                            prop:value=move || value.get()
+                            on:input=move |ev| on_input(event_target_value(&ev))
+                        />
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
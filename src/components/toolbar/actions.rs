@@ -0,0 +1,389 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    api::markdown_formatter::{
+        Shortcut,
+        format::{HeadingOperation, MoveDirection, TextFormattingType},
+    },
+    components::icons::Icon,
+};
+
+/// Identifies a formatting command of the toolbar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ActionId {
+    Heading,
+    Paragraph,
+    Heading1,
+    Heading2,
+    Heading3,
+    Heading4,
+    Heading5,
+    Heading6,
+    PromoteSection,
+    DemoteSection,
+    ToggleHeadingStyle,
+    Bold,
+    Italic,
+    Strikethrough,
+    InlineCode,
+    UnorderedList,
+    OrderedList,
+    TaskList,
+    ToggleTask,
+    CodeBlock,
+    Blockquote,
+    Image,
+    Link,
+    HorizontalRule,
+    Table,
+    MoveUp,
+    MoveDown,
+}
+
+/// Everything the toolbars, the shortcuts and the documentation need to know about a command.
+pub struct Action {
+    pub id: ActionId,
+    pub label: &'static str,
+    /// Markdown syntax shown as tooltip.
+    pub syntax: &'static str,
+    pub icon: Icon,
+    pub shortcut: Option<Shortcut>,
+    pub requires_github: bool,
+    pub formatting: TextFormattingType,
+}
+
+pub const ACTIONS: &[Action] = &[
+    Action {
+        id: ActionId::Heading,
+        label: "Heading",
+        syntax: "# Heading",
+        icon: Icon::Heading,
+        shortcut: Some(Shortcut::ctrl("h")),
+        requires_github: false,
+        formatting: TextFormattingType::Heading,
+    },
+    Action {
+        id: ActionId::Paragraph,
+        label: "Paragraph",
+        syntax: "Paragraph",
+        icon: Icon::AlignJusitify,
+        shortcut: None,
+        requires_github: false,
+        formatting: TextFormattingType::HeadingOperation(HeadingOperation::SetLevel(0)),
+    },
+    Action {
+        id: ActionId::Heading1,
+        label: "Heading 1",
+        syntax: "# Heading 1",
+        icon: Icon::Heading,
+        shortcut: Some(Shortcut::ctrl("1")),
+        requires_github: false,
+        formatting: TextFormattingType::HeadingOperation(HeadingOperation::SetLevel(1)),
+    },
+    Action {
+        id: ActionId::Heading2,
+        label: "Heading 2",
+        syntax: "## Heading 2",
+        icon: Icon::Heading,
+        shortcut: Some(Shortcut::ctrl("2")),
+        requires_github: false,
+        formatting: TextFormattingType::HeadingOperation(HeadingOperation::SetLevel(2)),
+    },
+    Action {
+        id: ActionId::Heading3,
+        label: "Heading 3",
+        syntax: "### Heading 3",
+        icon: Icon::Heading,
+        shortcut: Some(Shortcut::ctrl("3")),
+        requires_github: false,
+        formatting: TextFormattingType::HeadingOperation(HeadingOperation::SetLevel(3)),
+    },
+    Action {
+        id: ActionId::Heading4,
+        label: "Heading 4",
+        syntax: "#### Heading 4",
+        icon: Icon::Heading,
+        shortcut: Some(Shortcut::ctrl("4")),
+        requires_github: false,
+        formatting: TextFormattingType::HeadingOperation(HeadingOperation::SetLevel(4)),
+    },
+    Action {
+        id: ActionId::Heading5,
+        label: "Heading 5",
+        syntax: "##### Heading 5",
+        icon: Icon::Heading,
+        shortcut: Some(Shortcut::ctrl("5")),
+        requires_github: false,
+        formatting: TextFormattingType::HeadingOperation(HeadingOperation::SetLevel(5)),
+    },
+    Action {
+        id: ActionId::Heading6,
+        label: "Heading 6",
+        syntax: "###### Heading 6",
+        icon: Icon::Heading,
+        shortcut: Some(Shortcut::ctrl("6")),
+        requires_github: false,
+        formatting: TextFormattingType::HeadingOperation(HeadingOperation::SetLevel(6)),
+    },
+    Action {
+        id: ActionId::PromoteSection,
+        label: "Promote Section",
+        syntax: "## Section → # Section",
+        icon: Icon::ArrowUp,
+        shortcut: Some(Shortcut::ctrl("[")),
+        requires_github: false,
+        formatting: TextFormattingType::HeadingOperation(HeadingOperation::Promote),
+    },
+    Action {
+        id: ActionId::DemoteSection,
+        label: "Demote Section",
+        syntax: "# Section → ## Section",
+        icon: Icon::ArrowDown,
+        shortcut: Some(Shortcut::ctrl("]")),
+        requires_github: false,
+        formatting: TextFormattingType::HeadingOperation(HeadingOperation::Demote),
+    },
+    Action {
+        id: ActionId::ToggleHeadingStyle,
+        label: "Toggle ATX / Setext",
+        syntax: "# Heading ↔ Heading ===",
+        icon: Icon::Underline,
+        shortcut: None,
+        requires_github: false,
+        formatting: TextFormattingType::HeadingOperation(HeadingOperation::ToggleStyle),
+    },
+    Action {
+        id: ActionId::Bold,
+        label: "Bold",
+        syntax: "**Bold**",
+        icon: Icon::Bold,
+        shortcut: Some(Shortcut::ctrl("b")),
+        requires_github: false,
+        formatting: TextFormattingType::Inline {
+            prefix: "**",
+            suffix: "**",
+        },
+    },
+    Action {
+        id: ActionId::Italic,
+        label: "Italic",
+        syntax: "_Italic_",
+        icon: Icon::Italic,
+        shortcut: None,
+        requires_github: false,
+        formatting: TextFormattingType::Inline {
+            prefix: "_",
+            suffix: "_",
+        },
+    },
+    Action {
+        id: ActionId::Strikethrough,
+        label: "Strikethrough",
+        syntax: "~~Strikethrough~~",
+        icon: Icon::CrossOut,
+        shortcut: None,
+        requires_github: true,
+        formatting: TextFormattingType::Inline {
+            prefix: "~~",
+            suffix: "~~",
+        },
+    },
+    Action {
+        id: ActionId::InlineCode,
+        label: "Inline Code",
+        syntax: "`Inline Code`",
+        icon: Icon::Monospace,
+        shortcut: Some(Shortcut::ctrl("m")),
+        requires_github: false,
+        formatting: TextFormattingType::Inline {
+            prefix: "`",
+            suffix: "`",
+        },
+    },
+    Action {
+        id: ActionId::UnorderedList,
+        label: "Unordered List",
+        syntax: "- Unordered List",
+        icon: Icon::UnorderedList,
+        shortcut: None,
+        requires_github: false,
+        formatting: TextFormattingType::LinePrefix { prefix: "- " },
+    },
+    Action {
+        id: ActionId::OrderedList,
+        label: "Ordered List",
+        syntax: "1. Ordered List",
+        icon: Icon::OrderedList,
+        shortcut: None,
+        requires_github: false,
+        formatting: TextFormattingType::OrderedList,
+    },
+    Action {
+        id: ActionId::TaskList,
+        label: "Task List",
+        syntax: "- [ ] Task List",
+        icon: Icon::Checkbox,
+        shortcut: None,
+        requires_github: true,
+        formatting: TextFormattingType::LinePrefix { prefix: "- [ ] " },
+    },
+    Action {
+        id: ActionId::ToggleTask,
+        label: "Check Task",
+        syntax: "- [x] Check Task",
+        icon: Icon::Check,
+        shortcut: Some(Shortcut::ctrl("Enter")),
+        requires_github: true,
+        formatting: TextFormattingType::Task,
+    },
+    Action {
+        id: ActionId::CodeBlock,
+        label: "Code Block",
+        syntax: "```Code Block```",
+        icon: Icon::Code,
+        shortcut: Some(Shortcut::ctrl("#")),
+        requires_github: false,
+        formatting: TextFormattingType::CodeBlock,
+    },
+    Action {
+        id: ActionId::Blockquote,
+        label: "Blockquote",
+        syntax: "> Blockquote",
+        icon: Icon::Blockquote,
+        shortcut: None,
+        requires_github: false,
+        formatting: TextFormattingType::LinePrefix { prefix: "> " },
+    },
+    Action {
+        id: ActionId::Image,
+        label: "Image",
+        syntax: "![Image](url)",
+        icon: Icon::Image,
+        shortcut: None,
+        requires_github: false,
+        formatting: TextFormattingType::Inline {
+            prefix: "![",
+            suffix: "](url)",
+        },
+    },
+    Action {
+        id: ActionId::Link,
+        label: "Link",
+        syntax: "[Link](url)",
+        icon: Icon::Link,
+        shortcut: None,
+        requires_github: false,
+        formatting: TextFormattingType::Inline {
+            prefix: "[",
+            suffix: "](url)",
+        },
+    },
+    Action {
+        id: ActionId::HorizontalRule,
+        label: "Horizontal Rule",
+        syntax: "--- Horizontal Rule",
+        icon: Icon::Line,
+        shortcut: None,
+        requires_github: false,
+        formatting: TextFormattingType::HorizontalRule,
+    },
+    Action {
+        id: ActionId::Table,
+        label: "Table",
+        syntax: "| Table |",
+        icon: Icon::Table,
+        shortcut: None,
+        requires_github: true,
+        formatting: TextFormattingType::Table,
+    },
+    Action {
+        id: ActionId::MoveUp,
+        label: "Move Up",
+        syntax: "Move the line, list item or heading section up",
+        icon: Icon::ArrowUp,
+        shortcut: Some(Shortcut::new("ArrowUp", false, true, false)),
+        requires_github: false,
+        formatting: TextFormattingType::MoveLines(MoveDirection::Up),
+    },
+    Action {
+        id: ActionId::MoveDown,
+        label: "Move Down",
+        syntax: "Move the line, list item or heading section down",
+        icon: Icon::ArrowDown,
+        shortcut: Some(Shortcut::new("ArrowDown", false, true, false)),
+        requires_github: false,
+        formatting: TextFormattingType::MoveLines(MoveDirection::Down),
+    },
+];
+
+impl ActionId {
+    /// Looks up the registry entry of this command.
+    pub fn action(self) -> &'static Action {
+        ACTIONS
+            .iter()
+            .find(|action| action.id == self)
+            .expect("every action id is registered")
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            ActionId::Heading => "heading",
+            ActionId::Paragraph => "paragraph",
+            ActionId::Heading1 => "heading-1",
+            ActionId::Heading2 => "heading-2",
+            ActionId::Heading3 => "heading-3",
+            ActionId::Heading4 => "heading-4",
+            ActionId::Heading5 => "heading-5",
+            ActionId::Heading6 => "heading-6",
+            ActionId::PromoteSection => "promote-section",
+            ActionId::DemoteSection => "demote-section",
+            ActionId::ToggleHeadingStyle => "toggle-heading-style",
+            ActionId::Bold => "bold",
+            ActionId::Italic => "italic",
+            ActionId::Strikethrough => "strikethrough",
+            ActionId::InlineCode => "inline-code",
+            ActionId::UnorderedList => "unordered-list",
+            ActionId::OrderedList => "ordered-list",
+            ActionId::TaskList => "task-list",
+            ActionId::ToggleTask => "toggle-task",
+            ActionId::CodeBlock => "code-block",
+            ActionId::Blockquote => "blockquote",
+            ActionId::Image => "image",
+            ActionId::Link => "link",
+            ActionId::HorizontalRule => "horizontal-rule",
+            ActionId::Table => "table",
+            ActionId::MoveUp => "move-up",
+            ActionId::MoveDown => "move-down",
+        }
+    }
+}
+
+impl fmt::Display for ActionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for ActionId {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ACTIONS
+            .iter()
+            .map(|action| action.id)
+            .find(|id| id.key() == s)
+            .ok_or(())
+    }
+}
+
+/// Key bindings of all actions that define a shortcut.
+pub fn shortcut_bindings() -> Vec<(Shortcut, TextFormattingType)> {
+    ACTIONS
+        .iter()
+        .filter_map(|action| {
+            action
+                .shortcut
+                .map(|shortcut| (shortcut, action.formatting))
+        })
+        .collect()
+}
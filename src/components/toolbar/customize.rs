@@ -0,0 +1,144 @@
+use leptos::{html::Div, prelude::*};
+use leptos_use::on_click_outside;
+
+use crate::components::{
+    Color, Size, State,
+    button::Button,
+    icons::Icon,
+    toolbar::layout::{ToolbarItem, ToolbarLayout},
+};
+
+/// Button that opens a modal to reorder, hide and add toolbar buttons.
+#[component]
+pub fn ToolbarCustomizer(
+    toolbar: RwSignal<ToolbarLayout>,
+    #[prop(into, default = Signal::from(false))] is_dropdown_item: Signal<bool>,
+    #[prop(default = false)] fullsize_button: bool,
+) -> impl IntoView {
+    let modal_visible = RwSignal::new(false);
+
+    let modal_area = NodeRef::<Div>::new();
+    let _ = on_click_outside(modal_area, move |_| modal_visible.set(false));
+
+    let items = move || {
+        toolbar
+            .read()
+            .items
+            .iter()
+            .copied()
+            .enumerate()
+            .collect::<Vec<(usize, ToolbarItem)>>()
+    };
+
+    view! {
+        <Button
+            aria_label=String::from("Customize Toolbar")
+            text="Toolbar"
+            icon=Icon::Sliders
+            color=Signal::derive(move || if is_dropdown_item.get() { Color::Transparent } else { Color::None })
+            size=Size::Normal
+            has_smaller_padding=is_dropdown_item
+            is_full_size=fullsize_button
+            on_click=move || modal_visible.set(true)
+        />
+        <div class="modal" class:is-active=move || modal_visible.get()>
+            <div class="modal-background" />
+            <div class="modal-card container" node_ref=modal_area>
+                <header class="modal-card-head">
+                    <p class="modal-card-title">Customize Toolbar</p>
+                    <button class="delete" aria-label="close" on:click=move |_| modal_visible.set(false) />
+                </header>
+                <section class="modal-card-body px-5">
+                    <For
+                        each=items
+                        key=|item| *item
+                        children=move |(index, item)| {
+                            let is_last = Signal::derive(move || index + 1 == toolbar.read().items.len());
+                            view! {
+                                <div class="is-flex is-justify-content-space-between is-align-items-center py-1">
+                                    <ToolbarItemLabel item />
+                                    <div class="buttons has-addons m-0">
+                                        <Button
+                                            aria_label=String::from("Move Up")
+                                            icon=Icon::ArrowUp
+                                            size=Size::Small
+                                            state=Signal::derive(move || if index == 0 { State::Disabled } else { State::Normal })
+                                            on_click=move || toolbar.update(|layout| layout.move_item(index, true))
+                                        />
+                                        <Button
+                                            aria_label=String::from("Move Down")
+                                            icon=Icon::ArrowDown
+                                            size=Size::Small
+                                            state=Signal::derive(move || if is_last.get() { State::Disabled } else { State::Normal })
+                                            on_click=move || toolbar.update(|layout| layout.move_item(index, false))
+                                        />
+                                        <Button
+                                            aria_label=String::from("Hide")
+                                            icon=Icon::Cross
+                                            size=Size::Small
+                                            on_click=move || toolbar.update(|layout| layout.remove(index))
+                                        />
+                                    </div>
+                                </div>
+                            }
+                        }
+                    />
+                    <hr />
+                    <p class="has-text-weight-semibold mb-2">Add</p>
+                    <div class="buttons">
+                        <For
+                            each=move || toolbar.read().hidden_actions()
+                            key=|id| *id
+                            children=move |id| {
+                                let action = id.action();
+                                view! {
+                                    <Button
+                                        aria_label=format!("Add {}", action.label)
+                                        text=action.label
+                                        icon=action.icon
+                                        size=Size::Small
+                                        on_click=move || toolbar.update(|layout| layout.add(ToolbarItem::Action(id)))
+                                    />
+                                }
+                            }
+                        />
+                        <Button
+                            aria_label=String::from("Add Separator")
+                            text="Separator"
+                            icon=Icon::Separator
+                            size=Size::Small
+                            on_click=move || toolbar.update(|layout| layout.add(ToolbarItem::Separator))
+                        />
+                    </div>
+                </section>
+                <footer class="modal-card-foot">
+                    <Button
+                        aria_label=String::from("Reset Toolbar")
+                        text="Reset to Default"
+                        icon=Icon::Reset
+                        size=Size::Small
+                        color=Color::Primary
+                        on_click=move || toolbar.set(ToolbarLayout::default())
+                    />
+                </footer>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ToolbarItemLabel(item: ToolbarItem) -> impl IntoView {
+    let (icon, label) = match item {
+        ToolbarItem::Action(id) => (id.action().icon, id.action().label),
+        ToolbarItem::Separator => (Icon::Separator, "Separator"),
+    };
+
+    view! {
+        <span class="is-flex is-align-items-center">
+            <span class="icon mr-2">
+                <i class=icon.as_fontawesome() />
+            </span>
+            <span>{ label }</span>
+        </span>
+    }
+}
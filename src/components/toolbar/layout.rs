@@ -0,0 +1,244 @@
+use std::{fmt, str::FromStr};
+
+use crate::components::toolbar::actions::{ACTIONS, ActionId};
+
+const SEPARATOR: &str = "|";
+
+/// Entry of a customised toolbar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ToolbarItem {
+    Action(ActionId),
+    Separator,
+}
+
+/// Order and visibility of the toolbar buttons, persisted as `heading,|,bold,...`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToolbarLayout {
+    pub items: Vec<ToolbarItem>,
+}
+
+impl Default for ToolbarLayout {
+    fn default() -> Self {
+        use ActionId::*;
+
+        let groups: &[&[ActionId]] = &[
+            &[Heading],
+            &[Bold, Italic, Strikethrough, InlineCode],
+            &[UnorderedList, OrderedList, TaskList],
+            &[CodeBlock, Blockquote],
+            &[Image, Link],
+            &[HorizontalRule, Table],
+        ];
+
+        let mut items = Vec::new();
+        for (index, group) in groups.iter().enumerate() {
+            if index > 0 {
+                items.push(ToolbarItem::Separator);
+            }
+            items.extend(group.iter().map(|id| ToolbarItem::Action(*id)));
+        }
+
+        ToolbarLayout { items }
+    }
+}
+
+impl ToolbarLayout {
+    /// Splits the visible actions into the groups between separators, skipping empty groups.
+    pub fn groups(&self) -> Vec<Vec<ActionId>> {
+        self.items
+            .split(|item| *item == ToolbarItem::Separator)
+            .map(|group| {
+                group
+                    .iter()
+                    .filter_map(|item| match item {
+                        ToolbarItem::Action(id) => Some(*id),
+                        ToolbarItem::Separator => None,
+                    })
+                    .collect::<Vec<ActionId>>()
+            })
+            .filter(|group| !group.is_empty())
+            .collect()
+    }
+
+    /// All registered actions that aren't part of the toolbar.
+    pub fn hidden_actions(&self) -> Vec<ActionId> {
+        ACTIONS
+            .iter()
+            .map(|action| action.id)
+            .filter(|id| !self.items.contains(&ToolbarItem::Action(*id)))
+            .collect()
+    }
+
+    /// Swaps the item at `index` with its neighbour in the given direction.
+    pub fn move_item(&mut self, index: usize, up: bool) {
+        let target = if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1)
+        };
+
+        if let Some(target) = target
+            && index < self.items.len()
+            && target < self.items.len()
+        {
+            self.items.swap(index, target);
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.items.len() {
+            self.items.remove(index);
+        }
+    }
+
+    /// Appends the item; actions already on the toolbar aren't added twice.
+    pub fn add(&mut self, item: ToolbarItem) {
+        if item == ToolbarItem::Separator || !self.items.contains(&item) {
+            self.items.push(item);
+        }
+    }
+}
+
+impl fmt::Display for ToolbarLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items: Vec<String> = self
+            .items
+            .iter()
+            .map(|item| match item {
+                ToolbarItem::Action(id) => id.to_string(),
+                ToolbarItem::Separator => SEPARATOR.to_string(),
+            })
+            .collect();
+
+        write!(f, "{}", items.join(","))
+    }
+}
+
+impl FromStr for ToolbarLayout {
+    type Err = ();
+
+    /// Unknown actions are dropped so layouts survive removed commands.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Err(());
+        }
+
+        let mut layout = ToolbarLayout { items: Vec::new() };
+        for key in s.split(',').map(str::trim) {
+            if key == SEPARATOR {
+                layout.add(ToolbarItem::Separator);
+            } else if let Ok(id) = key.parse::<ActionId>() {
+                layout.add(ToolbarItem::Action(id));
+            }
+        }
+
+        Ok(layout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_layout_round_trip() {
+        let layout = ToolbarLayout::default();
+
+        assert_eq!(layout.to_string().parse::<ToolbarLayout>(), Ok(layout));
+    }
+
+    #[test]
+    fn test_default_layout_groups() {
+        let groups = ToolbarLayout::default().groups();
+
+        assert_eq!(groups.len(), 6);
+        assert_eq!(groups[0], vec![ActionId::Heading]);
+        assert_eq!(groups[5], vec![ActionId::HorizontalRule, ActionId::Table]);
+    }
+
+    #[test]
+    fn test_default_layout_hides_toggle_task_heading_and_move_commands() {
+        use ActionId::*;
+
+        assert_eq!(
+            ToolbarLayout::default().hidden_actions(),
+            vec![
+                Paragraph,
+                Heading1,
+                Heading2,
+                Heading3,
+                Heading4,
+                Heading5,
+                Heading6,
+                PromoteSection,
+                DemoteSection,
+                ToggleHeadingStyle,
+                ToggleTask,
+                MoveUp,
+                MoveDown,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_skips_unknown_and_duplicate_actions() {
+        let layout: ToolbarLayout = "bold,unknown,|,bold,link".parse().unwrap();
+
+        assert_eq!(
+            layout.items,
+            vec![
+                ToolbarItem::Action(ActionId::Bold),
+                ToolbarItem::Separator,
+                ToolbarItem::Action(ActionId::Link),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_layout_fails() {
+        assert!("".parse::<ToolbarLayout>().is_err());
+    }
+
+    #[test]
+    fn test_groups_skip_empty_groups() {
+        let layout: ToolbarLayout = "|,|,bold,|,|,italic,|".parse().unwrap();
+
+        assert_eq!(
+            layout.groups(),
+            vec![vec![ActionId::Bold], vec![ActionId::Italic]]
+        );
+    }
+
+    #[test]
+    fn test_move_item() {
+        let mut layout: ToolbarLayout = "bold,italic,link".parse().unwrap();
+
+        layout.move_item(0, false);
+        assert_eq!(layout.to_string(), "italic,bold,link");
+
+        layout.move_item(2, true);
+        assert_eq!(layout.to_string(), "italic,link,bold");
+
+        layout.move_item(0, true);
+        layout.move_item(2, false);
+        assert_eq!(layout.to_string(), "italic,link,bold");
+    }
+
+    #[test]
+    fn test_remove_and_add() {
+        let mut layout: ToolbarLayout = "bold,|,italic".parse().unwrap();
+
+        layout.remove(1);
+        layout.add(ToolbarItem::Action(ActionId::Italic));
+        layout.add(ToolbarItem::Action(ActionId::Table));
+
+        assert_eq!(layout.to_string(), "bold,italic,table");
+    }
+
+    #[test]
+    fn test_every_action_key_parses() {
+        for action in ACTIONS {
+            assert_eq!(action.id.to_string().parse::<ActionId>(), Ok(action.id));
+        }
+    }
+}
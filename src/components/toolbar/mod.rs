@@ -0,0 +1,131 @@
+use leptos::prelude::*;
+
+use crate::{
+    Mode,
+    api::parser::Dialect,
+    components::{Color, Size, State, button::Button, tooltip::Tooltip},
+};
+
+pub mod actions;
+pub mod customize;
+pub mod layout;
+
+use actions::ActionId;
+use layout::ToolbarLayout;
+
+/// Arrangement of the toolbar for the different screen sizes.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ToolbarVariant {
+    /// Grouped buttons with syntax tooltips.
+    Desktop,
+    /// Grouped buttons spread over the full width, tooltips only explain disabled buttons.
+    Tablet,
+    /// A single column for the mobile sidebar.
+    Mobile,
+}
+
+/// Formatting toolbar generated from the action registry and the user's layout.
+#[component]
+pub fn Toolbar(
+    markdown: RwSignal<String>,
+    parser: RwSignal<Dialect>,
+    mode: RwSignal<Mode>,
+    toolbar: RwSignal<ToolbarLayout>,
+    variant: ToolbarVariant,
+) -> impl IntoView {
+    let groups = move || toolbar.read().groups();
+
+    match variant {
+        ToolbarVariant::Mobile => view! {
+            <div class="is-flex is-flex-direction-column is-justify-content-space-evenly full-height">
+                <For
+                    each=move || groups().into_iter().flatten()
+                    key=|id| *id
+                    children=move |id| view! { <ToolbarButton markdown parser mode id variant /> }
+                />
+            </div>
+        }
+        .into_any(),
+        _ => view! {
+            <div
+                class="is-flex"
+                class:is-justify-content-space-between=variant == ToolbarVariant::Tablet
+                style="gap: 0.5rem"
+            >
+                <For
+                    each=groups
+                    key=|group| group.clone()
+                    children=move |group| view! {
+                        <div class="buttons has-addons m-0">
+                            { group.into_iter().map(|id| view! { <ToolbarButton markdown parser mode id variant /> }).collect_view() }
+                        </div>
+                    }
+                />
+            </div>
+        }
+        .into_any(),
+    }
+}
+
+#[component]
+fn ToolbarButton(
+    markdown: RwSignal<String>,
+    parser: RwSignal<Dialect>,
+    mode: RwSignal<Mode>,
+    id: ActionId,
+    variant: ToolbarVariant,
+) -> impl IntoView {
+    let action = id.action();
+
+    let disabled_reason = move || {
+        if let Mode::Read = mode.get() {
+            Some("❌ The Format Buttons are not available in Read Mode")
+        } else if let Dialect::Common = parser.get()
+            && action.requires_github
+        {
+            Some("❌ Please Enable GitHub extension")
+        } else {
+            None
+        }
+    };
+
+    let state = Signal::derive(move || {
+        if disabled_reason().is_some() {
+            State::Disabled
+        } else {
+            State::Normal
+        }
+    });
+
+    let tooltip = Signal::derive(move || match (disabled_reason(), variant) {
+        (Some(reason), _) => String::from(reason),
+        (None, ToolbarVariant::Desktop) => match action.shortcut {
+            Some(shortcut) => format!("{} ({shortcut})", action.syntax),
+            None => String::from(action.syntax),
+        },
+        (None, _) => String::new(),
+    });
+
+    let button = move || {
+        view! {
+            <Button
+                aria_label=String::from(action.label)
+                icon=action.icon
+                color=Color::White
+                size=Size::Small
+                state
+                on_click=move || markdown.set(action.formatting.apply_text_formatting())
+            />
+        }
+    };
+
+    match variant {
+        ToolbarVariant::Mobile => button().into_any(),
+        _ => view! {
+            <Tooltip text=tooltip>
+                { button() }
+            </Tooltip>
+        }
+        .into_any(),
+    }
+}
@@ -1,9 +1,28 @@
 use crate::{
-    api::{local_storage::use_persistent_signal, parser::Dialect},
-    page::{editor::MarkdownEditor, header::Header},
+    api::{
+        documents::DocumentLibrary,
+        download_options::DownloadOptions,
+        encryption::{AutoLock, Vault, provide_vault},
+        folder::FolderSync,
+        parser::Dialect,
+        preview_styles::PreviewStyle,
+        print::PrintOptions,
+        pwa::register_service_worker,
+        storage::{
+            PersistentStore, provide_persistent_store, use_persistent_signal,
+            use_persistent_signal_with,
+        },
+        templates::TemplateLibrary,
+    },
+    components::toolbar::layout::ToolbarLayout,
+    page::{
+        editor::MarkdownEditor, file_changes::ExternalChangeDialog, header::Header,
+        locked_note::LockedNoteDialog, print::PrintDocument, shared_note::SharedNoteDialog,
+    },
 };
-use leptoaster::{Toaster, provide_toaster};
-use leptos::prelude::*;
+use leptoaster::{Toaster, expect_toaster, provide_toaster};
+use leptos::{prelude::*, task::spawn_local};
+use web_sys::js_sys::Date;
 
 pub mod api;
 mod components;
@@ -16,20 +35,120 @@ pub enum Mode {
     Read,
 }
 
+/// Opens the store first, as IndexedDB can only be read asynchronously.
 #[component]
 pub fn App() -> impl IntoView {
+    provide_toaster();
+    register_service_worker(expect_toaster());
+
+    let store = StoredValue::new_local(None::<PersistentStore>);
+    let ready = RwSignal::new(false);
+    let toaster = expect_toaster();
+    spawn_local(async move {
+        store.set_value(Some(PersistentStore::open(toaster).await));
+        ready.set(true);
+    });
+
+    view! {
+        <Show when=move || ready.get()>
+            {
+                move || {
+                    if let Some(store) = store.get_value() {
+                        provide_persistent_store(store);
+                    }
+                    view! { <Workspace /> }
+                }
+            }
+        </Show>
+    }
+}
+
+#[component]
+fn Workspace() -> impl IntoView {
     let mode = RwSignal::new(Mode::Split);
-    let markdown: RwSignal<String> = use_persistent_signal("markdown_content".to_string());
+    let documents: RwSignal<DocumentLibrary> = use_persistent_signal("documents".to_string());
+    // The text of an encrypted document only ever reaches the store in its sealed form.
+    let markdown: RwSignal<String> =
+        use_persistent_signal_with("markdown_content".to_string(), move |content: &String| {
+            if documents.with(|library| {
+                library
+                    .active()
+                    .is_some_and(|document| document.sealed.is_some())
+            }) {
+                String::new()
+            } else {
+                content.clone()
+            }
+        });
+    documents.update(|library| library.ensure_active(&markdown.get_untracked(), Date::now()));
 
     let parser: RwSignal<Dialect> = use_persistent_signal("markdown_parser".to_string());
+    // Each document keeps the dialect it was edited with, which is restored when it is
+    // opened again.
+    Effect::new(move |previous: Option<u64>| {
+        let dialect = parser.get();
+        let (active, stored) = documents.with(|library| {
+            (
+                library.active,
+                library
+                    .active()
+                    .and_then(|document| document.dialect.clone()),
+            )
+        });
+        match stored {
+            Some(stored) if previous != Some(active) => parser.set(stored),
+            Some(stored) if stored == dialect => {}
+            _ => documents.update(|library| library.set_dialect(dialect)),
+        }
+        active
+    });
 
-    provide_toaster();
+    let toolbar: RwSignal<ToolbarLayout> = use_persistent_signal("toolbar_layout".to_string());
+
+    let preview_style: RwSignal<PreviewStyle> = use_persistent_signal("preview_style".to_string());
+    let custom_css: RwSignal<String> = use_persistent_signal("preview_custom_css".to_string());
+
+    let print_options: RwSignal<PrintOptions> = use_persistent_signal("print_options".to_string());
+    let download_options: RwSignal<DownloadOptions> =
+        use_persistent_signal("download_options".to_string());
+
+    let auto_lock: RwSignal<AutoLock> = use_persistent_signal("auto_lock".to_string());
+
+    let templates: RwSignal<TemplateLibrary> = use_persistent_signal("templates".to_string());
+    provide_context(templates);
+
+    provide_context(FolderSync::default());
+    provide_vault(Vault::new(markdown, documents, auto_lock), expect_toaster());
 
     view! {
         <div class="is-flex is-flex-direction-column page-height page-background-color">
             <Toaster />
-            <Header markdown mode parser />
-            <MarkdownEditor mode markdown parser />
+            <Header
+                markdown
+                documents
+                mode
+                parser
+                toolbar
+                preview_style
+                custom_css
+                print_options
+                download_options
+            />
+            <MarkdownEditor
+                mode
+                markdown
+                documents
+                parser
+                toolbar
+                preview_style
+                custom_css
+                print_options
+                download_options
+            />
         </div>
+        <PrintDocument markdown parser preview_style custom_css print_options />
+        <SharedNoteDialog markdown documents parser />
+        <ExternalChangeDialog markdown documents />
+        <LockedNoteDialog markdown documents />
     }
 }
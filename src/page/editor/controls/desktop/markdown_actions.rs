@@ -1,13 +1,43 @@
 use leptos::prelude::*;
 
-use crate::components::button::{copy::CopyButton, delete::DeleteButton, download::DownloadButton};
+use crate::api::{
+    documents::DocumentLibrary, download_options::DownloadOptions, parser::Dialect,
+    preview_styles::PreviewStyle, print::PrintOptions,
+};
+use crate::components::button::{
+    copy::CopyButton, delete::DeleteButton, download::DownloadButton, import::ImportButton,
+    print::PrintButton, save_file::SaveFileButton, share::ShareButton,
+};
 
 #[component]
-pub fn ActionButtons(markdown: RwSignal<String>) -> impl IntoView {
+pub fn ActionButtons(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+    parser: RwSignal<Dialect>,
+    preview_style: RwSignal<PreviewStyle>,
+    custom_css: RwSignal<String>,
+    print_options: RwSignal<PrintOptions>,
+    download_options: RwSignal<DownloadOptions>,
+) -> impl IntoView {
     view! {
         <div class="buttons">
             <CopyButton markdown=markdown.read_only() />
-            <DownloadButton markdown=markdown.read_only() />
+            <ImportButton markdown documents />
+            <SaveFileButton markdown=markdown.read_only() documents />
+            <DownloadButton
+                markdown=markdown.read_only()
+                documents=documents.read_only()
+                parser=parser.read_only()
+                preview_style=preview_style.read_only()
+                custom_css=custom_css.read_only()
+                download_options
+            />
+            <ShareButton
+                markdown=markdown.read_only()
+                documents=documents.read_only()
+                parser=parser.read_only()
+            />
+            <PrintButton print_options />
             <DeleteButton markdown />
         </div>
     }
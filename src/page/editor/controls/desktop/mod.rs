@@ -1,25 +1,47 @@
 use crate::Mode;
 use crate::{
-    api::parser::Dialect,
-    page::editor::controls::desktop::{
-        format_buttons::EditTextButtons, markdown_actions::ActionButtons,
+    api::{
+        documents::DocumentLibrary, download_options::DownloadOptions, parser::Dialect,
+        preview_styles::PreviewStyle, print::PrintOptions,
     },
+    components::{
+        button::{
+            format_tables::FormatTablesButton, heading_actions::HeadingActionsButton,
+            table_actions::TableActionsButton,
+        },
+        dropdown::DropdownDirection,
+        toolbar::{Toolbar, ToolbarVariant, layout::ToolbarLayout},
+        tooltip::TooltipDirection,
+    },
+    page::editor::controls::desktop::markdown_actions::ActionButtons,
 };
 use leptos::prelude::*;
 
-mod format_buttons;
 mod markdown_actions;
 
 #[component]
 pub fn DesktopControls(
     markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
     parser: RwSignal<Dialect>,
     mode: RwSignal<Mode>,
+    toolbar: RwSignal<ToolbarLayout>,
+    preview_style: RwSignal<PreviewStyle>,
+    custom_css: RwSignal<String>,
+    print_options: RwSignal<PrintOptions>,
+    download_options: RwSignal<DownloadOptions>,
 ) -> impl IntoView {
     view! {
         <div class="is-flex is-justify-content-space-between is-hidden-touch pt-3 px-5">
-            <EditTextButtons markdown parser mode />
-            <ActionButtons markdown />
+            <div class="is-flex" style="gap: 0.5rem">
+                <Toolbar markdown parser mode toolbar variant=ToolbarVariant::Desktop />
+                <HeadingActionsButton markdown direction=DropdownDirection::Right />
+                <TableActionsButton markdown direction=DropdownDirection::Right />
+                <FormatTablesButton markdown tooltip_direction=TooltipDirection::Left />
+            </div>
+            <ActionButtons
+                markdown documents parser preview_style custom_css print_options download_options
+            />
         </div>
     }
 }
@@ -4,7 +4,11 @@ use crate::Mode;
 use crate::components::State;
 use crate::components::{
     Color, Size,
-    button::{Button, format_tables::FormatTablesButton},
+    button::{
+        Button, format_tables::FormatTablesButton, heading_actions::HeadingActionsButton,
+        table_actions::TableActionsButton,
+    },
+    dropdown::DropdownDirection,
     icons::Icon,
     tooltip::{Tooltip, TooltipDirection},
 };
@@ -36,7 +40,11 @@ pub fn MobileControls(
                     on_click=move || sidebar_open.set(!sidebar_open.get())
                 />
             </Tooltip>
-            <FormatTablesButton markdown tooltip_direction=TooltipDirection::Right />
+            <div class="is-flex">
+                <HeadingActionsButton markdown direction=DropdownDirection::Right />
+                <TableActionsButton markdown direction=DropdownDirection::Right />
+                <FormatTablesButton markdown tooltip_direction=TooltipDirection::Right />
+            </div>
         </div>
     }
 }
@@ -1,7 +1,11 @@
 use leptos::prelude::*;
 
 use crate::Mode;
-use crate::api::parser::Dialect;
+use crate::api::{
+    documents::DocumentLibrary, download_options::DownloadOptions, parser::Dialect,
+    preview_styles::PreviewStyle, print::PrintOptions,
+};
+use crate::components::toolbar::layout::ToolbarLayout;
 use crate::page::editor::controls::{
     desktop::DesktopControls, mobile::MobileControls, tablet::TabletControls,
 };
@@ -13,14 +17,22 @@ pub mod tablet;
 #[component]
 pub fn Controls(
     markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
     parser: RwSignal<Dialect>,
     sidebar_open: RwSignal<bool>,
     mode: RwSignal<Mode>,
+    toolbar: RwSignal<ToolbarLayout>,
+    preview_style: RwSignal<PreviewStyle>,
+    custom_css: RwSignal<String>,
+    print_options: RwSignal<PrintOptions>,
+    download_options: RwSignal<DownloadOptions>,
 ) -> impl IntoView {
     view! {
         <div>
-            <DesktopControls markdown parser mode />
-            <TabletControls markdown parser mode />
+            <DesktopControls
+                markdown documents parser mode toolbar preview_style custom_css print_options download_options
+            />
+            <TabletControls markdown parser mode toolbar />
         </div>
         <MobileControls markdown sidebar_open mode />
     }
@@ -2,25 +2,31 @@ use leptos::prelude::*;
 
 use crate::Mode;
 use crate::api::parser::Dialect;
-use crate::{
-    components::{button::format_tables::FormatTablesButton, tooltip::TooltipDirection},
-    page::editor::controls::tablet::format_buttons::EditTextButtons,
+use crate::components::{
+    button::{
+        format_tables::FormatTablesButton, heading_actions::HeadingActionsButton,
+        table_actions::TableActionsButton,
+    },
+    dropdown::DropdownDirection,
+    toolbar::{Toolbar, ToolbarVariant, layout::ToolbarLayout},
+    tooltip::TooltipDirection,
 };
 
-mod format_buttons;
-
 #[component]
 pub fn TabletControls(
     markdown: RwSignal<String>,
     parser: RwSignal<Dialect>,
     mode: RwSignal<Mode>,
+    toolbar: RwSignal<ToolbarLayout>,
 ) -> impl IntoView {
     view! {
         <div class="is-hidden-desktop is-hidden-mobile px-5">
             <div class="is-flex is-justify-content-end py-3">
+                <HeadingActionsButton markdown direction=DropdownDirection::Right />
+                <TableActionsButton markdown direction=DropdownDirection::Right />
                 <FormatTablesButton markdown tooltip_direction=TooltipDirection::Right />
             </div>
-            <EditTextButtons markdown parser mode />
+            <Toolbar markdown parser mode toolbar variant=ToolbarVariant::Tablet />
         </div>
     }
 }
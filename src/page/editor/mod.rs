@@ -1,24 +1,35 @@
 use leptos::prelude::*;
-use leptos_use::use_media_query;
 
 use crate::Mode;
+use crate::api::documents::DocumentLibrary;
+use crate::api::download_options::DownloadOptions;
 use crate::api::markdown_formatter::setup_shortcuts;
 use crate::api::parser::Dialect;
+use crate::api::preview_styles::PreviewStyle;
+use crate::api::print::PrintOptions;
+use crate::components::toolbar::{actions::shortcut_bindings, layout::ToolbarLayout};
 use crate::page::editor::controls::Controls;
 use crate::page::editor::read_section::ReadSection;
 use crate::page::editor::write_section::WriteSection;
 
 mod controls;
 mod read_section;
+mod status_bar;
 mod write_section;
 
 #[component]
 pub fn MarkdownEditor(
     markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
     mode: RwSignal<Mode>,
     parser: RwSignal<Dialect>,
+    toolbar: RwSignal<ToolbarLayout>,
+    preview_style: RwSignal<PreviewStyle>,
+    custom_css: RwSignal<String>,
+    print_options: RwSignal<PrintOptions>,
+    download_options: RwSignal<DownloadOptions>,
 ) -> impl IntoView {
-    setup_shortcuts(markdown);
+    setup_shortcuts(markdown, shortcut_bindings());
 
     let mobile_sidebar_open = RwSignal::new(false);
 
@@ -33,7 +44,18 @@ pub fn MarkdownEditor(
     // let is_mobile = use_media_query("(max-width: 768px)");
 
     view! {
-        <Controls markdown parser sidebar_open=mobile_sidebar_open mode />
+        <Controls
+            markdown
+            documents
+            parser
+            sidebar_open=mobile_sidebar_open
+            mode
+            toolbar
+            preview_style
+            custom_css
+            print_options
+            download_options
+        />
 
         <main
             class="columns is-mobile is-flex-grow-1 px-5 m-0" style="overflow: hidden;"
@@ -48,13 +70,20 @@ pub fn MarkdownEditor(
 
             { move || (mode.get() == Mode::Write || mode.get() == Mode::Split).then(||
                 view! {
-                    <WriteSection markdown parser mode mobile_sidebar_open />
+                    <WriteSection markdown documents parser mode mobile_sidebar_open toolbar />
                 }
             )}
 
             { move || (mode.get() == Mode::Read || mode.get() == Mode::Split).then(||
                 view! {
-                    <ReadSection markdown=markdown.read_only() parser=parser.read_only() mode=mode.read_only() />
+                    <ReadSection
+                        markdown
+                        documents
+                        parser=parser.read_only()
+                        mode=mode.read_only()
+                        preview_style=preview_style.read_only()
+                        custom_css=custom_css.read_only()
+                    />
                 }
             )}
 
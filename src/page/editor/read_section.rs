@@ -1,29 +1,175 @@
 use crate::Mode;
-use crate::api::parser::Dialect;
+use crate::api::{
+    attachments::embed_attachment_urls,
+    documents::DocumentLibrary,
+    parser::Dialect,
+    preview_styles::PreviewStyle,
+    storage::use_persistent_store,
+    tasks::{add_heading_progress, document_progress, enable_task_checkboxes, toggle_task},
+    toc::add_heading_ids,
+    wikilinks::{backlinks, link_wikilinks, resolve},
+};
+use crate::components::icons::Icon;
 use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::js_sys::{Date, decode_uri_component};
 
 #[component]
 pub fn ReadSection(
-    markdown: ReadSignal<String>,
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
     parser: ReadSignal<Dialect>,
     mode: ReadSignal<Mode>,
+    preview_style: ReadSignal<PreviewStyle>,
+    custom_css: ReadSignal<String>,
 ) -> impl IntoView {
+    let store = use_persistent_store();
     let parsed_markdown = Signal::derive(move || {
         let markdown = markdown.read();
         let parser = parser.read();
-        parser.parse_markdown_to_html(&markdown)
+        let html = documents.with(|library| {
+            let html = parser.parse_markdown_to_html_with(&markdown, |arena, root| {
+                add_heading_progress(arena, root);
+                store.with_value(|store| embed_attachment_urls(store, root));
+            });
+            let html = add_heading_ids(&html, &markdown);
+            link_wikilinks(&html, |target| resolve(library, target).is_some())
+        });
+        enable_task_checkboxes(&html)
     });
 
+    let preview_class = move || {
+        let style = preview_style.get();
+        format!(
+            "card preview full-height p-5 {} {}",
+            if style.uses_app_styles() {
+                "content"
+            } else {
+                ""
+            },
+            style.class()
+        )
+    };
+
+    let stylesheet =
+        Signal::derive(move || preview_style.get().preview_stylesheet(&custom_css.read()));
+
+    let progress = Signal::derive(move || document_progress(&markdown.read(), &parser.read()));
+
+    let links = Memo::new(move |_| backlinks(&documents.read()));
+
+    // Opens the linked note, a broken link creates it.
+    let open_note = move |target: &str| {
+        let mut content = None;
+        documents.update(|library| {
+            let current = markdown.get_untracked();
+            content = match resolve(library, target).map(|document| document.id) {
+                Some(id) if id == library.active => None,
+                Some(id) => library.open(id, &current, Date::now()),
+                None => {
+                    library.create(target, "", &current, Date::now());
+                    Some(String::new())
+                }
+            };
+        });
+        if let Some(content) = content {
+            markdown.set(content);
+        }
+    };
+
+    let handle_click = move |ev: web_sys::MouseEvent| {
+        let element = ev
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::Element>().ok());
+
+        if let Some(target) = element
+            .as_ref()
+            .and_then(|element| element.closest("[data-wikilink]").ok().flatten())
+            .and_then(|link| link.get_attribute("data-wikilink"))
+        {
+            ev.prevent_default();
+            open_note(&target);
+            return;
+        }
+
+        // Links to headings scroll the preview instead of changing the URL.
+        if let Some(anchor) = element
+            .as_ref()
+            .and_then(|element| element.closest("a[href^='#']").ok().flatten())
+            .and_then(|link| link.get_attribute("href"))
+        {
+            ev.prevent_default();
+            let id = decode_uri_component(&anchor[1..])
+                .map(String::from)
+                .unwrap_or_else(|_| anchor[1..].to_string());
+            if let Some(heading) = document().get_element_by_id(&id) {
+                heading.scroll_into_view();
+            }
+            return;
+        }
+
+        let index = element
+            .and_then(|element| element.get_attribute("data-task"))
+            .and_then(|index| index.parse::<usize>().ok());
+
+        if let Some(index) = index
+            && let Some(new_text) =
+                toggle_task(&markdown.get_untracked(), &parser.get_untracked(), index)
+        {
+            markdown.set(new_text);
+        }
+    };
+
     view! {
         <div
             class="column m-0 pr-0"
             class:pl-0=mode.get() != Mode::Split
             style="overflow-x: auto; max-width: 100%; text-overflow: break-word;"
         >
-            <div
-                class="card content full-height p-5"
-                inner_html=move || parsed_markdown.get()
-            />
+            <style>{ stylesheet }</style>
+            <div class=preview_class>
+                <Show when=move || progress.read().total != 0>
+                    <div class="is-flex is-align-items-center mb-4">
+                        <progress
+                            class="progress is-primary is-small mb-0 mr-3"
+                            max="1"
+                            value=move || progress.get().ratio()
+                        />
+                        <span class="tag task-progress">{ move || progress.get().to_string() }</span>
+                    </div>
+                </Show>
+                <div
+                    on:click=handle_click
+                    inner_html=move || parsed_markdown.get()
+                />
+                <Show when=move || !links.read().is_empty()>
+                    <div class="backlinks mt-5 pt-3">
+                        <p class="heading">
+                            <span class="icon"><i class=Icon::Link.as_fontawesome() /></span>
+                            "Linked from"
+                        </p>
+                        <ul>
+                            {
+                                move || {
+                                    links
+                                        .get()
+                                        .into_iter()
+                                        .map(|link| {
+                                            let name = link.name.clone();
+                                            view! {
+                                                <li class="mb-1">
+                                                    <a class="wikilink" on:click=move |_| open_note(&name)>{ link.name }</a>
+                                                    <p class="is-size-7 has-text-grey">{ link.context }</p>
+                                                </li>
+                                            }
+                                        })
+                                        .collect_view()
+                                }
+                            }
+                        </ul>
+                    </div>
+                </Show>
+            </div>
         </div>
     }
 }
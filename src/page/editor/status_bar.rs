@@ -0,0 +1,51 @@
+use leptos::prelude::*;
+
+use crate::api::parser::Dialect;
+use crate::api::statistics::{CaretPosition, Statistics};
+
+/// Shows the size of the document, the caret position and the size of the selection.
+#[component]
+pub fn StatusBar(
+    markdown: RwSignal<String>,
+    parser: ReadSignal<Dialect>,
+    caret: ReadSignal<CaretPosition>,
+    selection: ReadSignal<Option<Statistics>>,
+) -> impl IntoView {
+    let statistics = Memo::new(move |_| Statistics::of(&markdown.read(), &parser.read()));
+
+    view! {
+        <div class="status-bar is-flex is-flex-wrap-wrap is-justify-content-space-between is-size-7 px-2 pt-1">
+            <span>
+                { move || format!("Ln {}, Col {}", caret.get().line, caret.get().column) }
+                { move || selection.get().map(|selected| format!(
+                    " · Selected: {}, {}, {}",
+                    count(selected.words, "word"),
+                    count(selected.characters, "character"),
+                    count(selected.lines, "line"),
+                )) }
+            </span>
+            <span>
+                { move || {
+                    let statistics = statistics.get();
+                    [
+                        count(statistics.words, "word"),
+                        count(statistics.characters, "character"),
+                        count(statistics.lines, "line"),
+                        count(statistics.paragraphs, "paragraph"),
+                        count(statistics.headings, "heading"),
+                        format!("{} min read", statistics.reading_minutes()),
+                    ]
+                    .join(" · ")
+                } }
+            </span>
+        </div>
+    }
+}
+
+fn count(amount: usize, noun: &str) -> String {
+    if amount == 1 {
+        format!("{amount} {noun}")
+    } else {
+        format!("{amount} {noun}s")
+    }
+}
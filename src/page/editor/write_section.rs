@@ -1,17 +1,45 @@
-use leptos::{html, prelude::*};
+use gloo::file::FileList;
+use leptoaster::{ToasterContext, expect_toaster};
+use leptos::{html, prelude::*, task::spawn_local};
 use wasm_bindgen::JsCast;
 
 use crate::Mode;
-use crate::api::markdown_formatter::handle_enter_for_lists;
+use crate::api::attachments::{contains_images, read_image_files, store_attachment};
+use crate::api::completion::completions as find_completions;
+use crate::api::completion::{Completion, CompletionKind};
+use crate::api::documents::DocumentLibrary;
+use crate::api::encryption::Vault;
+use crate::api::markdown_formatter::{
+    Block, SlashCommand, apply_slash_command, caret_position, complete_at_caret, complete_wikilink,
+    completion_query_at_caret, convert_pasted_table, filter_slash_commands, format::MarkdownTable,
+    handle_enter_for_lists, handle_snippet, handle_tab, insert_at_cursor, selection_start,
+    selection_statistics, slash_query_at_caret, wikilink_query,
+};
 use crate::api::parser::Dialect;
+use crate::api::statistics::{CaretPosition, Statistics};
+use crate::api::storage::{PersistentStore, use_persistent_store};
+use crate::api::templates::{Placeholders, TemplateLibrary};
+use crate::api::wikilinks::suggest_titles;
+use crate::components::{
+    Color, Size,
+    button::{
+        Button,
+        import::{ImportConfirmation, import_files},
+    },
+    icons::Icon,
+    toolbar::layout::ToolbarLayout,
+};
 use crate::page::editor::controls::mobile::format_buttons::MobileSidebar;
+use crate::page::editor::status_bar::StatusBar;
 
 #[component]
 pub fn WriteSection(
     markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
     parser: RwSignal<Dialect>,
     mode: RwSignal<Mode>,
     mobile_sidebar_open: RwSignal<bool>,
+    toolbar: RwSignal<ToolbarLayout>,
 ) -> impl IntoView {
     let textarea_ref: NodeRef<html::Textarea> = NodeRef::new();
     let scroll = RwSignal::new(0.0);
@@ -34,16 +62,176 @@ pub fn WriteSection(
         }
     };
 
+    let toaster = expect_toaster();
+    let drop_toaster = toaster.clone();
+    let store = use_persistent_store();
+    let vault = expect_context::<Vault>();
+    let pasted_table = RwSignal::new(None::<(usize, String)>);
+    let pending_import = RwSignal::new(None);
+
+    let caret = RwSignal::new(CaretPosition::default());
+    let selected = RwSignal::new(None::<Statistics>);
+    let update_caret = move || {
+        let (position, selection) = selection_statistics(&parser.get_untracked());
+        caret.set(position);
+        selected.set(selection);
+    };
+
+    // Note titles offered while a `[[link]]` is typed. Escape hides them until the next
+    // input.
+    let suggestions = RwSignal::new(Vec::<String>::new());
+    let highlighted = RwSignal::new(0_usize);
+    let dismissed = RwSignal::new(false);
+    let update_suggestions = move || {
+        let titles = if dismissed.get_untracked() {
+            Vec::new()
+        } else {
+            wikilink_query()
+                .map(|query| suggest_titles(&documents.read_untracked(), &query))
+                .unwrap_or_default()
+        };
+        if titles != *suggestions.read_untracked() {
+            highlighted.set(0);
+            suggestions.set(titles);
+        }
+    };
+
+    // Blocks offered while a `/command` starts the line. The menu stays where the `/`
+    // was typed.
+    let slash_commands = RwSignal::new(Vec::<&'static SlashCommand>::new());
+    let slash_highlighted = RwSignal::new(0_usize);
+    let slash_position = RwSignal::new((0.0, 0.0));
+    let update_slash_menu = move || {
+        let commands = if dismissed.get_untracked() {
+            Vec::new()
+        } else {
+            slash_query_at_caret()
+                .map(|query| filter_slash_commands(&query.filter))
+                .unwrap_or_default()
+        };
+        if commands != *slash_commands.read_untracked() {
+            if slash_commands.read_untracked().is_empty()
+                && let Some(position) = caret_position()
+            {
+                slash_position.set(position);
+            }
+            slash_highlighted.set(0);
+            slash_commands.set(commands);
+        }
+    };
+    let accept_slash_command = move |index: usize| {
+        let block = slash_commands.read_untracked()[index].block;
+        if let Some(new_text) = apply_slash_command(block) {
+            markdown.set(new_text);
+        }
+        slash_commands.set(Vec::new());
+    };
+    let slash_entries = Signal::derive(move || {
+        slash_commands
+            .read()
+            .iter()
+            .map(|command| MenuEntry {
+                icon: block_icon(command.block),
+                label: command.label.to_string(),
+                detail: String::new(),
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // Fence languages, emoji shortcodes, heading anchors and reference labels for the
+    // word at the caret.
+    let completions = RwSignal::new(Vec::<Completion>::new());
+    let completion_kind = RwSignal::new(CompletionKind::Language);
+    let completion_highlighted = RwSignal::new(0_usize);
+    let completion_position = RwSignal::new((0.0, 0.0));
+    let update_completions = move || {
+        let query = completion_query_at_caret()
+            .filter(|_| !dismissed.get_untracked())
+            // Shortcodes only turn into emoji with GitHub-Flavored Markdown.
+            .filter(|query| {
+                query.kind != CompletionKind::Emoji || parser.get_untracked() == Dialect::GitHub
+            });
+        let found = query
+            .as_ref()
+            .map(|query| find_completions(&markdown.read_untracked(), query))
+            .unwrap_or_default();
+        if found != *completions.read_untracked() {
+            if completions.read_untracked().is_empty()
+                && let Some(position) = caret_position()
+            {
+                completion_position.set(position);
+            }
+            if let Some(query) = query {
+                completion_kind.set(query.kind);
+            }
+            completion_highlighted.set(0);
+            completions.set(found);
+        }
+    };
+    let accept_completion = move |index: usize| {
+        let completion = completions.read_untracked()[index].clone();
+        if let Some(new_text) = complete_at_caret(&completion) {
+            markdown.set(new_text);
+        }
+        completions.set(Vec::new());
+    };
+    let completion_entries = Signal::derive(move || {
+        let icon = completion_icon(completion_kind.get());
+        completions
+            .read()
+            .iter()
+            .map(|completion| MenuEntry {
+                icon,
+                label: completion.label.clone(),
+                detail: completion.detail.clone(),
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let templates = expect_context::<RwSignal<TemplateLibrary>>();
+    let expand_snippet = move || {
+        let title = documents
+            .read_untracked()
+            .active()
+            .map(|document| document.name.clone())
+            .unwrap_or_default();
+        handle_snippet(&templates.read_untracked(), &Placeholders::now(&title))
+    };
+
+    let accept_suggestion = move |title: String| {
+        if let Some(new_text) = complete_wikilink(&title) {
+            markdown.set(new_text);
+        }
+        suggestions.set(Vec::new());
+    };
+
     view! {
-        <MobileSidebar markdown parser sidebar_open=mobile_sidebar_open />
+        <MobileSidebar markdown parser mode toolbar sidebar_open=mobile_sidebar_open />
         <div class="column is-narrow is-hidden is-hidden-mobile">
             <LineColumn markdown scroll=scroll.read_only() />
         </div>
         <div
-            class="column pl-0 editor"
+            class="column pl-0 editor is-flex is-flex-direction-column"
             class:has-sidebar=move || mobile_sidebar_open.get()
             class:pr-0=mode.get() != Mode::Split
         >
+            <PastedTableOffer markdown pasted_table />
+            <div class="is-flex is-justify-content-end">
+                <ImportConfirmation markdown documents pending=pending_import />
+            </div>
+            <LinkSuggestions suggestions highlighted on_select=accept_suggestion />
+            <CaretMenu
+                entries=slash_entries
+                highlighted=slash_highlighted
+                position=slash_position
+                on_select=accept_slash_command
+            />
+            <CaretMenu
+                entries=completion_entries
+                highlighted=completion_highlighted
+                position=completion_position
+                on_select=accept_completion
+            />
             <textarea
                 id="markdown-textarea"
                 class="textarea has-fixed-size card is-family-monospace full-height p-5"
@@ -53,9 +241,66 @@ pub fn WriteSection(
                 on:input=move |ev| {
                     markdown.set(event_target_value(&ev));
                     sync_scroll_to_caret();
+                    update_caret();
+                    dismissed.set(false);
+                    update_suggestions();
+                    update_slash_menu();
+                    update_completions();
+                }
+                on:keyup=move |_| {
+                    update_caret();
+                    update_suggestions();
+                    update_slash_menu();
+                    update_completions();
+                }
+                on:mouseup=move |_| {
+                    update_caret();
+                    update_suggestions();
+                    update_slash_menu();
+                    update_completions();
+                }
+                on:select=move |_| update_caret()
+                on:blur=move |_| {
+                    suggestions.set(Vec::new());
+                    slash_commands.set(Vec::new());
+                    completions.set(Vec::new());
                 }
                 on:keydown=move |ev: web_sys::KeyboardEvent| {
-                    if ev.key() == "Enter" {
+                    let key = ev.key();
+                    let menus = [
+                        // Tab is left to the snippets, `/table` + Tab still expands one.
+                        (slash_commands.read_untracked().len(), slash_highlighted, false),
+                        (completions.read_untracked().len(), completion_highlighted, true),
+                        (suggestions.read_untracked().len(), highlighted, true),
+                    ];
+                    for (menu, (count, highlight, accepts_tab)) in menus.into_iter().enumerate() {
+                        // Alt + arrows move lines, even while a menu is open.
+                        let Some(action) = menu_key(&key, count, highlight, accepts_tab)
+                            .filter(|_| !ev.alt_key())
+                        else {
+                            continue;
+                        };
+                        ev.prevent_default();
+                        let index = highlight.get_untracked();
+                        match (action, menu) {
+                            (MenuKey::Moved, _) => {}
+                            (MenuKey::Accept, 0) => accept_slash_command(index),
+                            (MenuKey::Accept, 1) => accept_completion(index),
+                            (MenuKey::Accept, _) => {
+                                let title = suggestions.read_untracked()[index].clone();
+                                accept_suggestion(title);
+                            }
+                            (MenuKey::Dismiss, _) => {
+                                dismissed.set(true);
+                                slash_commands.set(Vec::new());
+                                completions.set(Vec::new());
+                                suggestions.set(Vec::new());
+                            }
+                        }
+                        return;
+                    }
+
+                    if ev.key() == "Enter" && !ev.ctrl_key() {
                         ev.prevent_default();
                         markdown.set(handle_enter_for_lists());
                         if let Some(textarea) = textarea_ref.get() {
@@ -63,6 +308,49 @@ pub fn WriteSection(
                             scroll.set(textarea.scroll_top().into());
                         }
                         sync_scroll_to_caret();
+                    } else if ev.key() == "Tab"
+                        && !ev.shift_key()
+                        && let Some(new_text) = expand_snippet()
+                    {
+                        ev.prevent_default();
+                        markdown.set(new_text);
+                    } else if ev.key() == "Tab"
+                        && let Some(new_text) = handle_tab(ev.shift_key())
+                    {
+                        ev.prevent_default();
+                        markdown.set(new_text);
+                    }
+                }
+                on:paste=move |ev: web_sys::ClipboardEvent| {
+                    let files = ev.clipboard_data().and_then(|data| data.files());
+                    if insert_images(files, markdown, store.get_value(), vault, toaster.clone()) {
+                        ev.prevent_default();
+                        return;
+                    }
+
+                    let text = ev
+                        .clipboard_data()
+                        .and_then(|data| data.get_data("text/plain").ok())
+                        .filter(|text| MarkdownTable::from_delimited(text).is_some());
+                    pasted_table.set(text.map(|text| (selection_start(), text)));
+                }
+                on:dragover=move |ev: web_sys::DragEvent| ev.prevent_default()
+                on:drop=move |ev: web_sys::DragEvent| {
+                    let files = ev.data_transfer().and_then(|data| data.files());
+                    if insert_images(
+                        files,
+                        markdown,
+                        store.get_value(),
+                        vault,
+                        drop_toaster.clone(),
+                    ) {
+                        ev.prevent_default();
+                        return;
+                    }
+
+                    let files = ev.data_transfer().and_then(|data| data.files());
+                    if import_files(files, pending_import, drop_toaster.clone()) {
+                        ev.prevent_default();
                     }
                 }
                 on:scroll=move |ev| {
@@ -74,10 +362,273 @@ pub fn WriteSection(
                     }
                 }
             />
+            <StatusBar markdown parser=parser.read_only() caret=caret.read_only() selection=selected.read_only() />
         </div>
     }
 }
 
+/// Stores pasted or dropped images as attachments and references them at the caret.
+/// Returns `false` when there was no image to insert.
+///
+/// Attachments are stored unencrypted, so encrypted notes refuse images instead of
+/// leaking them next to the sealed text.
+fn insert_images(
+    files: Option<web_sys::FileList>,
+    markdown: RwSignal<String>,
+    store: PersistentStore,
+    vault: Vault,
+    toaster: ToasterContext,
+) -> bool {
+    let Some(files) = files.map(FileList::from) else {
+        return false;
+    };
+
+    if !contains_images(&files) {
+        return false;
+    }
+
+    if vault.is_active_encrypted() {
+        toaster.warn("Images can't be added to encrypted notes, they would be stored unencrypted");
+        return true;
+    }
+
+    spawn_local(async move {
+        for attachment in read_image_files(files).await {
+            match store_attachment(&store, &attachment).await {
+                Ok(()) => markdown.set(insert_at_cursor(&attachment.to_markdown())),
+                Err(error) => toaster.error(error.as_str()),
+            }
+        }
+    });
+
+    true
+}
+
+/// Offers to turn the spreadsheet data that was just pasted into a markdown table.
+#[component]
+fn PastedTableOffer(
+    markdown: RwSignal<String>,
+    pasted_table: RwSignal<Option<(usize, String)>>,
+) -> impl IntoView {
+    let toaster = expect_toaster();
+
+    let convert = move || {
+        if let Some((start, text)) = pasted_table.get_untracked() {
+            match convert_pasted_table(start, &text) {
+                Some(new_text) => markdown.set(new_text),
+                None => toaster.warn("The pasted data was edited and can't be converted anymore"),
+            }
+        }
+        pasted_table.set(None);
+    };
+
+    view! {
+        <Show when=move || pasted_table.read().is_some()>
+            <div class="notification is-primary is-light is-flex is-align-items-center is-justify-content-space-between py-2 px-4 mb-2">
+                <span>"The pasted data looks like a table."</span>
+                <div class="buttons">
+                    <Button
+                        aria_label=String::from("Convert to Table")
+                        text="Convert to Table"
+                        icon=Icon::Table
+                        color=Color::Primary
+                        size=Size::Small
+                        on_click=convert.clone()
+                    />
+                    <Button
+                        aria_label=String::from("Dismiss")
+                        icon=Icon::Cross
+                        color=Color::Transparent
+                        size=Size::Small
+                        on_click=move || pasted_table.set(None)
+                    />
+                </div>
+            </div>
+        </Show>
+    }
+}
+
+/// Lists note titles for the `[[link]]` at the caret. The arrow keys move the
+/// highlight, Enter or Tab insert it.
+#[component]
+fn LinkSuggestions(
+    suggestions: RwSignal<Vec<String>>,
+    highlighted: RwSignal<usize>,
+    on_select: impl Fn(String) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    view! {
+        <Show when=move || !suggestions.read().is_empty()>
+            <div class="box link-suggestions is-flex is-flex-wrap-wrap is-align-items-center py-2 px-3 mb-2">
+                <span class="icon has-text-grey"><i class=Icon::Link.as_fontawesome() /></span>
+                {
+                    move || {
+                        suggestions
+                            .get()
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, title)| {
+                                view! {
+                                    <button
+                                        class="button is-small is-rounded"
+                                        class:is-primary=move || highlighted.get() == index
+                                        // Keep the focus in the editor.
+                                        on:mousedown=move |ev| {
+                                            ev.prevent_default();
+                                            on_select(title.clone());
+                                        }
+                                    >
+                                        { title.clone() }
+                                    </button>
+                                }
+                            })
+                            .collect_view()
+                    }
+                }
+            </div>
+        </Show>
+    }
+}
+
+/// What a key does to an open menu at the caret.
+enum MenuKey {
+    Moved,
+    Accept,
+    Dismiss,
+}
+
+/// Moves the highlight of a menu with `count` entries for the arrow keys. Returns
+/// `None` for keys the menu doesn't use, or when it is closed.
+fn menu_key(
+    key: &str,
+    count: usize,
+    highlighted: RwSignal<usize>,
+    accepts_tab: bool,
+) -> Option<MenuKey> {
+    if count == 0 {
+        return None;
+    }
+    match key {
+        "ArrowDown" => {
+            highlighted.update(|index| *index = (*index + 1) % count);
+            Some(MenuKey::Moved)
+        }
+        "ArrowUp" => {
+            highlighted.update(|index| *index = (*index + count - 1) % count);
+            Some(MenuKey::Moved)
+        }
+        "Enter" => Some(MenuKey::Accept),
+        "Tab" if accepts_tab => Some(MenuKey::Accept),
+        "Escape" => Some(MenuKey::Dismiss),
+        _ => None,
+    }
+}
+
+#[derive(Clone)]
+struct MenuEntry {
+    icon: Icon,
+    label: String,
+    detail: String,
+}
+
+/// A menu right below the caret, for slash commands and completions. The arrow keys
+/// move the highlight, a click or Enter picks the entry.
+#[component]
+fn CaretMenu(
+    #[prop(into)] entries: Signal<Vec<MenuEntry>>,
+    highlighted: RwSignal<usize>,
+    position: RwSignal<(f64, f64)>,
+    on_select: impl Fn(usize) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let menu = NodeRef::<html::Div>::new();
+
+    // Keeps the highlighted entry visible in the scrolled menu.
+    Effect::new(move |_| {
+        let index = highlighted.get();
+        let Some(menu) = menu.get() else {
+            return;
+        };
+        let Some(item) = menu
+            .children()
+            .item(index as u32)
+            .and_then(|item| item.dyn_into::<web_sys::HtmlElement>().ok())
+        else {
+            return;
+        };
+
+        let top = item.offset_top();
+        let bottom = top + item.offset_height();
+        if top < menu.scroll_top() {
+            menu.set_scroll_top(top);
+        } else if bottom > menu.scroll_top() + menu.client_height() {
+            menu.set_scroll_top(bottom - menu.client_height());
+        }
+    });
+
+    let style = move || {
+        let (left, top) = position.get();
+        format!("left: min({left:.0}px, calc(100% - 16rem)); top: {top:.0}px;")
+    };
+
+    view! {
+        <Show when=move || !entries.read().is_empty()>
+            <div class="box caret-menu p-1" style=style node_ref=menu>
+                {
+                    move || {
+                        entries
+                            .get()
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, entry)| {
+                                view! {
+                                    <a
+                                        class="dropdown-item is-flex is-align-items-center"
+                                        class:is-active=move || highlighted.get() == index
+                                        // Keep the focus in the editor.
+                                        on:mousedown=move |ev| {
+                                            ev.prevent_default();
+                                            on_select(index);
+                                        }
+                                    >
+                                        <span class="icon mr-2">
+                                            <i class=entry.icon.as_fontawesome() />
+                                        </span>
+                                        <span class="caret-menu-label">{ entry.label }</span>
+                                        <span class="caret-menu-detail has-text-grey ml-auto pl-3">{ entry.detail }</span>
+                                    </a>
+                                }
+                            })
+                            .collect_view()
+                    }
+                }
+            </div>
+        </Show>
+    }
+}
+
+fn completion_icon(kind: CompletionKind) -> Icon {
+    match kind {
+        CompletionKind::Language => Icon::Code,
+        CompletionKind::Emoji => Icon::Smile,
+        CompletionKind::Anchor => Icon::Hashtag,
+        CompletionKind::Reference => Icon::Link,
+    }
+}
+
+fn block_icon(block: Block) -> Icon {
+    match block {
+        Block::Heading(_) => Icon::Heading,
+        Block::BulletList => Icon::UnorderedList,
+        Block::OrderedList => Icon::OrderedList,
+        Block::Checkbox => Icon::Checkbox,
+        Block::Quote => Icon::Blockquote,
+        Block::CodeBlock => Icon::Code,
+        Block::Table => Icon::Table,
+        Block::HorizontalRule => Icon::Line,
+        Block::Math => Icon::Math,
+        Block::TableOfContents => Icon::Contents,
+    }
+}
+
 #[component]
 pub fn LineColumn(markdown: RwSignal<String>, scroll: ReadSignal<f64>) -> impl IntoView {
     let line_count = Signal::derive(move || {
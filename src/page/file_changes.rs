@@ -0,0 +1,184 @@
+use gloo::timers::callback::Interval;
+use leptoaster::expect_toaster;
+use leptos::{ev::focus, html::Div, prelude::*, task::spawn_local};
+use leptos_use::{on_click_outside, use_event_listener, use_window};
+
+use crate::{
+    api::{
+        documents::DocumentLibrary,
+        folder::{ExternalChange, FolderSync},
+        merge::merge,
+    },
+    components::{Color, Size, button::Button, icons::Icon},
+};
+
+/// How often the file of the active document is checked for changes on disk.
+const CHECK_INTERVAL_MS: u32 = 3_000;
+
+/// Watches the file of the active document and asks what to do when it changed on disk
+/// while it was edited here. Unedited documents are reloaded without asking.
+#[component]
+pub fn ExternalChangeDialog(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+) -> impl IntoView {
+    let sync = expect_context::<FolderSync>();
+    let toaster = expect_toaster();
+    let checking = StoredValue::new(false);
+
+    let check = {
+        let toaster = toaster.clone();
+        move || {
+            let linked = documents.with_untracked(|library| {
+                library
+                    .active()
+                    .and_then(|document| Some((document.id, document.file.clone()?)))
+            });
+            let Some((id, file)) = linked else {
+                return;
+            };
+            if checking.get_value()
+                || sync.change.read_untracked().is_some()
+                || !sync.is_available(&file.path)
+            {
+                return;
+            }
+
+            checking.set_value(true);
+            let toaster = toaster.clone();
+            spawn_local(async move {
+                if sync.modified(&file.path).await.ok() != Some(file.disk_modified)
+                    && let Ok(disk) = sync.read(&file.path).await
+                {
+                    if disk.content == file.base {
+                        // Only touched, e.g. saved again without changes.
+                        documents
+                            .update(|library| library.update_link(id, &file.base, disk.modified));
+                    } else if markdown.get_untracked() == file.base {
+                        documents.update(|library| {
+                            library.update_link(id, &disk.content, disk.modified)
+                        });
+                        markdown.set(disk.content);
+                        toaster.info(format!("Reloaded {}, it was changed on disk", file.path));
+                    } else {
+                        sync.change.set(Some(ExternalChange {
+                            document: id,
+                            path: file.path,
+                            file: disk,
+                        }));
+                    }
+                }
+                checking.set_value(false);
+            });
+        }
+    };
+
+    Interval::new(CHECK_INTERVAL_MS, check.clone()).forget();
+    let _ = use_event_listener(use_window(), focus, move |_| check());
+
+    // A change only concerns the document it was found in.
+    let change = Signal::derive(move || {
+        let active = documents.with(|library| library.active().map(|document| document.id));
+        sync.change
+            .get()
+            .filter(|change| Some(change.document) == active)
+    });
+
+    let close = move || sync.change.set(None);
+    let modal_area = NodeRef::<Div>::new();
+    let _ = on_click_outside(modal_area, move |_| close());
+
+    let base = move || {
+        documents.with_untracked(|library| {
+            library
+                .active()
+                .and_then(|document| document.file.as_ref())
+                .map(|file| file.base.clone())
+                .unwrap_or_default()
+        })
+    };
+
+    let reload = move || {
+        if let Some(change) = change.get_untracked() {
+            documents.update(|library| {
+                library.update_link(change.document, &change.file.content, change.file.modified)
+            });
+            markdown.set(change.file.content);
+        }
+        close();
+    };
+
+    let combine = move || {
+        if let Some(change) = change.get_untracked() {
+            let merged = merge(&base(), &markdown.get_untracked(), &change.file.content);
+            documents.update(|library| {
+                library.update_link(change.document, &change.file.content, change.file.modified)
+            });
+            markdown.set(merged.text);
+            if merged.conflicts > 0 {
+                toaster.warn(format!(
+                    "{} conflicting change(s) are marked in the text. Resolve them before saving.",
+                    merged.conflicts
+                ));
+            }
+        }
+        close();
+    };
+
+    // The disk version becomes the base, so the next save overwrites it.
+    let keep_mine = move || {
+        if let Some(change) = change.get_untracked() {
+            documents.update(|library| {
+                library.update_link(change.document, &change.file.content, change.file.modified)
+            });
+        }
+        close();
+    };
+
+    view! {
+        <div class="modal" class:is-active=move || change.read().is_some()>
+            <div class="modal-background" />
+            <div class="modal-card container" node_ref=modal_area>
+                <header class="modal-card-head">
+                    <p class="modal-card-title">"File Changed on Disk"</p>
+                    <button class="delete" aria-label="close" on:click=move |_| close() />
+                </header>
+                <section class="modal-card-body px-5">
+                    <p>
+                        <code>{ move || change.get().map(|change| change.path).unwrap_or_default() }</code>
+                        " was changed outside the editor while you edited it here."
+                    </p>
+                    <p class="mt-3">
+                        "Reload it and lose your edits, merge both versions, or keep your version and overwrite the file on the next save."
+                    </p>
+                </section>
+                <footer class="modal-card-foot" style="gap: 0.5rem">
+                    <Button
+                        aria_label=String::from("Merge Changes")
+                        text="Merge"
+                        icon=Icon::Merge
+                        size=Size::Small
+                        color=Color::Primary
+                        on_click=combine
+                    />
+                    <Button
+                        aria_label=String::from("Reload from Disk")
+                        text="Reload"
+                        icon=Icon::Reset
+                        size=Size::Small
+                        color=Color::Light
+                        on_click=reload
+                    />
+                    <Button
+                        aria_label=String::from("Keep My Version")
+                        text="Keep Mine"
+                        icon=Icon::Save
+                        size=Size::Small
+                        color=Color::Light
+                        on_click=keep_mine
+                    />
+                </footer>
+            </div>
+        </div>
+    }
+}
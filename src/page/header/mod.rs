@@ -2,6 +2,11 @@ mod logo;
 mod mode_switcher;
 mod settings;
 
+use crate::api::{
+    documents::DocumentLibrary, download_options::DownloadOptions, preview_styles::PreviewStyle,
+    print::PrintOptions,
+};
+use crate::components::toolbar::layout::ToolbarLayout;
 use crate::page::header::{
     logo::LogoWithTitle, mode_switcher::ModeSwitcher, settings::HeaderSettings,
 };
@@ -10,8 +15,14 @@ use leptos::prelude::*;
 #[component]
 pub fn Header(
     markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
     mode: RwSignal<crate::Mode>,
     parser: RwSignal<crate::Dialect>,
+    toolbar: RwSignal<ToolbarLayout>,
+    preview_style: RwSignal<PreviewStyle>,
+    custom_css: RwSignal<String>,
+    print_options: RwSignal<PrintOptions>,
+    download_options: RwSignal<DownloadOptions>,
 ) -> impl IntoView {
     view! {
         <header class="columns is-mobile header has-shadow-bottom m-0">
@@ -24,7 +35,9 @@ pub fn Header(
             </div>
 
             <div class="column is-flex is-justify-content-end is-align-items-center">
-                <HeaderSettings markdown parser />
+                <HeaderSettings
+                    markdown documents parser toolbar preview_style custom_css print_options download_options
+                />
             </div>
         </header>
     }
@@ -1,38 +1,112 @@
 use leptos::prelude::*;
 
-use crate::api::parser::Dialect;
+use crate::api::{
+    documents::DocumentLibrary,
+    download_options::DownloadOptions,
+    parser::Dialect,
+    preview_styles::PreviewStyle,
+    print::PrintOptions,
+    storage::use_persistent_signal,
+    themes::{CustomTheme, ThemeLibrary},
+};
 use crate::components::{
     HelpModal,
-    button::{copy::CopyButton, delete::DeleteButton, download::DownloadButton},
+    button::{
+        copy::CopyButton, delete::DeleteButton, download::DownloadButton, import::ImportButton,
+        print::PrintButton, save_file::SaveFileButton, share::ShareButton,
+    },
     dialect::DialectSelect,
+    documents::DocumentSelect,
     dropdown::{DropdownButton, DropdownDirection},
+    encryption::EncryptionButton,
+    folder::FolderBrowser,
     icons::Icon,
-    theme::ThemeSelect,
+    preview_style::PreviewStyleSelect,
+    search::SearchButton,
+    templates::TemplateButton,
+    theme::{Theme, ThemeSelect, apply_theme},
+    theme_editor::ThemeEditor,
+    toolbar::{customize::ToolbarCustomizer, layout::ToolbarLayout},
 };
 
 #[component]
-pub fn HeaderSettings(markdown: RwSignal<String>, parser: RwSignal<Dialect>) -> impl IntoView {
+pub fn HeaderSettings(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+    parser: RwSignal<Dialect>,
+    toolbar: RwSignal<ToolbarLayout>,
+    preview_style: RwSignal<PreviewStyle>,
+    custom_css: RwSignal<String>,
+    print_options: RwSignal<PrintOptions>,
+    download_options: RwSignal<DownloadOptions>,
+) -> impl IntoView {
+    let theme: RwSignal<Theme> = use_persistent_signal(String::from("theme"));
+    let themes: RwSignal<ThemeLibrary> = use_persistent_signal(String::from("custom_themes"));
+    let preview = RwSignal::new(None);
+    apply_theme(theme, themes, preview);
+
     view! {
-        <DesktopSettings parser />
-        <TouchDeviceSettings markdown parser />
+        <DesktopSettings markdown documents parser toolbar theme themes preview preview_style custom_css />
+        <TouchDeviceSettings
+            markdown documents parser toolbar theme themes preview preview_style custom_css print_options
+            download_options
+        />
     }
 }
 
 #[component]
-fn DesktopSettings(parser: RwSignal<Dialect>) -> impl IntoView {
+fn DesktopSettings(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+    parser: RwSignal<Dialect>,
+    toolbar: RwSignal<ToolbarLayout>,
+    theme: RwSignal<Theme>,
+    themes: RwSignal<ThemeLibrary>,
+    preview: RwSignal<Option<CustomTheme>>,
+    preview_style: RwSignal<PreviewStyle>,
+    custom_css: RwSignal<String>,
+) -> impl IntoView {
     view! {
         <div class="is-flex is-align-items-center is-hidden-touch">
+            <DocumentSelect markdown documents />
+            <div class="px-1" />
+            <SearchButton markdown documents />
+            <div class="px-1" />
+            <TemplateButton markdown documents />
+            <div class="px-1" />
+            <FolderBrowser markdown documents />
+            <div class="px-1" />
+            <EncryptionButton markdown documents />
+            <div class="px-1" />
             <DialectSelect parser />
             <div class="px-1" />
-            <ThemeSelect />
+            <ThemeSelect theme themes />
+            <div class="px-1" />
+            <PreviewStyleSelect preview_style custom_css />
+            <div class="px-1" />
+            <ThemeEditor theme themes preview />
             <div class="px-1" />
             <HelpModal />
+            <div class="px-1" />
+            <ToolbarCustomizer toolbar />
         </div>
     }
 }
 
 #[component]
-fn TouchDeviceSettings(markdown: RwSignal<String>, parser: RwSignal<Dialect>) -> impl IntoView {
+fn TouchDeviceSettings(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+    parser: RwSignal<Dialect>,
+    toolbar: RwSignal<ToolbarLayout>,
+    theme: RwSignal<Theme>,
+    themes: RwSignal<ThemeLibrary>,
+    preview: RwSignal<Option<CustomTheme>>,
+    preview_style: RwSignal<PreviewStyle>,
+    custom_css: RwSignal<String>,
+    print_options: RwSignal<PrintOptions>,
+    download_options: RwSignal<DownloadOptions>,
+) -> impl IntoView {
     view! {
         <div class="is-flex is-align-items-center is-hidden-desktop">
             <DropdownButton
@@ -40,24 +114,80 @@ fn TouchDeviceSettings(markdown: RwSignal<String>, parser: RwSignal<Dialect>) ->
                 icon=Icon::Bars
                 direction=DropdownDirection::Right
             >
+                <div class="dropdown-item">
+                    <DocumentSelect markdown documents />
+                </div>
+                <hr class="dropdown-divider" />
+                <div class="dropdown-item">
+                    <SearchButton markdown documents is_dropdown_item=true fullsize_button=true />
+                </div>
+                <hr class="dropdown-divider" />
+                <div class="dropdown-item">
+                    <TemplateButton markdown documents is_dropdown_item=true fullsize_button=true />
+                </div>
+                <hr class="dropdown-divider" />
+                <div class="dropdown-item">
+                    <FolderBrowser markdown documents is_dropdown_item=true fullsize_button=true />
+                </div>
+                <hr class="dropdown-divider" />
+                <div class="dropdown-item">
+                    <EncryptionButton markdown documents is_dropdown_item=true fullsize_button=true />
+                </div>
+                <hr class="dropdown-divider" />
                 <div class="dropdown-item">
                     <DialectSelect parser />
                 </div>
                 <hr class="dropdown-divider" />
                 <div class="dropdown-item">
-                    <ThemeSelect />
+                    <ThemeSelect theme themes />
+                </div>
+                <hr class="dropdown-divider" />
+                <div class="dropdown-item">
+                    <PreviewStyleSelect preview_style custom_css />
+                </div>
+                <hr class="dropdown-divider" />
+                <div class="dropdown-item">
+                    <ThemeEditor theme themes preview is_dropdown_item=true fullsize_button=true />
                 </div>
                 <hr class="dropdown-divider" />
                 <div class="dropdown-item">
                     <HelpModal is_dropdown_item=true fullsize_button=true />
                 </div>
                 <hr class="dropdown-divider" />
+                <div class="dropdown-item">
+                    <ToolbarCustomizer toolbar is_dropdown_item=true fullsize_button=true />
+                </div>
+                <hr class="dropdown-divider" />
                 <div class="dropdown-item">
                     <CopyButton markdown=markdown.read_only() />
                 </div>
                 <hr class="dropdown-divider" />
                 <div class="dropdown-item">
-                    <DownloadButton markdown=markdown.read_only() />
+                    <ImportButton markdown documents />
+                    <SaveFileButton markdown=markdown.read_only() documents />
+                </div>
+                <hr class="dropdown-divider" />
+                <div class="dropdown-item">
+                    <DownloadButton
+                        markdown=markdown.read_only()
+                        documents=documents.read_only()
+                        parser=parser.read_only()
+                        preview_style=preview_style.read_only()
+                        custom_css=custom_css.read_only()
+                        download_options
+                    />
+                </div>
+                <hr class="dropdown-divider" />
+                <div class="dropdown-item">
+                    <ShareButton
+                        markdown=markdown.read_only()
+                        documents=documents.read_only()
+                        parser=parser.read_only()
+                    />
+                </div>
+                <hr class="dropdown-divider" />
+                <div class="dropdown-item">
+                    <PrintButton print_options />
                 </div>
                 <hr class="dropdown-divider" />
                 <div class="dropdown-item">
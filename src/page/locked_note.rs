@@ -0,0 +1,109 @@
+use gloo::timers::future::TimeoutFuture;
+use leptos::{ev::SubmitEvent, html::Input, prelude::*, task::spawn_local};
+
+use crate::{
+    api::{documents::DocumentLibrary, encryption::Vault},
+    components::{Color, Size, button::Button, documents::DocumentSelect, icons::Icon},
+};
+
+/// Covers the editor while the active document is encrypted and locked. The user can
+/// unlock it or switch to another document.
+#[component]
+pub fn LockedNoteDialog(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+) -> impl IntoView {
+    let vault = expect_context::<Vault>();
+    let passphrase = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+    let unlocking = RwSignal::new(false);
+    let input_ref = NodeRef::<Input>::new();
+
+    let is_locked = Signal::derive(move || vault.is_active_locked());
+
+    Effect::new(move || {
+        if is_locked.get() {
+            passphrase.set(String::new());
+            error.set(None);
+            if let Some(input) = input_ref.get() {
+                let _ = input.focus();
+            }
+        }
+    });
+
+    let unlock = move || {
+        if unlocking.get_untracked() {
+            return;
+        }
+        unlocking.set(true);
+        spawn_local(async move {
+            // Deriving the key blocks for a moment, so let the button show it first.
+            TimeoutFuture::new(0).await;
+            match vault.unlock_active(&passphrase.get_untracked()) {
+                Ok(()) => passphrase.set(String::new()),
+                Err(message) => error.set(Some(message)),
+            }
+            unlocking.set(false);
+        });
+    };
+
+    let name = move || {
+        documents
+            .read()
+            .active()
+            .map(|document| document.name.clone())
+            .unwrap_or_default()
+    };
+
+    view! {
+        <div class="modal" class:is-active=move || is_locked.get()>
+            <div class="modal-background" />
+            <div class="modal-card container">
+                <header class="modal-card-head">
+                    <span class="icon mr-2"><i class=Icon::Lock.as_fontawesome() /></span>
+                    <p class="modal-card-title">{ name }</p>
+                </header>
+                <section class="modal-card-body px-5">
+                    <form on:submit=move |ev: SubmitEvent| {
+                        ev.prevent_default();
+                        unlock();
+                    }>
+                        <div class="field">
+                            <label class="label" for="unlock-passphrase">"This note is encrypted"</label>
+                            <div class="control">
+                                <input
+                                    id="unlock-passphrase"
+                                    class="input"
+                                    class:is-danger=move || error.read().is_some()
+                                    type="password"
+                                    autocomplete="current-password"
+                                    placeholder="Passphrase"
+                                    node_ref=input_ref
+                                    prop:value=passphrase
+                                    on:input=move |ev| {
+                                        passphrase.set(event_target_value(&ev));
+                                        error.set(None);
+                                    }
+                                />
+                            </div>
+                            <p class="help is-danger">{ move || error.get() }</p>
+                        </div>
+                    </form>
+                    <hr />
+                    <p class="mb-2">"Or open another document:"</p>
+                    <DocumentSelect markdown documents />
+                </section>
+                <footer class="modal-card-foot" style="gap: 0.5rem">
+                    <Button
+                        aria_label=String::from("Unlock Note")
+                        text=Signal::derive(move || Some(String::from(if unlocking.get() { "Unlocking…" } else { "Unlock" })))
+                        icon=Icon::Unlock
+                        size=Size::Small
+                        color=Color::Primary
+                        on_click=unlock
+                    />
+                </footer>
+            </div>
+        </div>
+    }
+}
@@ -1,2 +1,6 @@
 pub mod editor;
+pub mod file_changes;
 pub mod header;
+pub mod locked_note;
+pub mod print;
+pub mod shared_note;
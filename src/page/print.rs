@@ -0,0 +1,58 @@
+use leptos::{ev::Custom, html::Div, prelude::*};
+use leptos_use::{use_event_listener, use_window};
+
+use crate::api::{
+    attachments::embed_attachments,
+    export::document_title,
+    parser::Dialect,
+    preview_styles::PreviewStyle,
+    print::{PrintOptions, page_stylesheet, render_for_print},
+    storage::use_persistent_store,
+};
+
+/// The parsed document as it is printed. It is hidden on screen and filled right before
+/// the browser prints, so printing with the browser menu works as well.
+#[component]
+pub fn PrintDocument(
+    markdown: RwSignal<String>,
+    parser: RwSignal<Dialect>,
+    preview_style: RwSignal<PreviewStyle>,
+    custom_css: RwSignal<String>,
+    print_options: RwSignal<PrintOptions>,
+) -> impl IntoView {
+    let document_ref = NodeRef::<Div>::new();
+    let store = use_persistent_store();
+
+    let _ = use_event_listener(
+        use_window(),
+        Custom::<web_sys::Event>::new("beforeprint"),
+        move |_| {
+            let Some(element) = document_ref.get_untracked() else {
+                return;
+            };
+
+            let markdown = markdown.get_untracked();
+            let options = print_options.get_untracked();
+            let style = preview_style.get_untracked();
+
+            let dialect = parser.get_untracked();
+            let embedded = store.with_value(|store| embed_attachments(store, &markdown, &dialect));
+            let body = render_for_print(&embedded, &dialect, options);
+
+            element.set_inner_html(&format!(
+                "<style>{}\n{}</style><article class=\"{} {}\">{}</article>",
+                page_stylesheet(&document_title(&markdown), options),
+                style.preview_stylesheet(&custom_css.get_untracked()),
+                if style.uses_app_styles() {
+                    "content"
+                } else {
+                    ""
+                },
+                style.class(),
+                body,
+            ));
+        },
+    );
+
+    view! { <div class="print-document" node_ref=document_ref /> }
+}
@@ -0,0 +1,231 @@
+use gloo::utils::window;
+use leptoaster::expect_toaster;
+use leptos::{
+    ev::{SubmitEvent, hashchange},
+    html::Div,
+    prelude::*,
+};
+use leptos_use::{on_click_outside, use_event_listener, use_window};
+use wasm_bindgen::JsValue;
+use web_sys::js_sys::Date;
+
+use crate::{
+    api::{
+        documents::DocumentLibrary,
+        encryption::{NoteKey, Sealed, Vault},
+        export::document_title,
+        parser::Dialect,
+        share::SharedNote,
+    },
+    components::{Color, Size, button::Button, icons::Icon},
+};
+
+/// Shows a note that was opened through a share link. It is read-only until the user
+/// opens it as a new document, so the stored content is never replaced. Encrypted
+/// links ask for the passphrase first and stay encrypted when they are kept.
+#[component]
+pub fn SharedNoteDialog(
+    markdown: RwSignal<String>,
+    documents: RwSignal<DocumentLibrary>,
+    parser: RwSignal<Dialect>,
+) -> impl IntoView {
+    let toaster = expect_toaster();
+    let vault = expect_context::<Vault>();
+    let shared = RwSignal::new(None::<SharedNote>);
+    let sealed = RwSignal::new(None::<Sealed>);
+    let key = StoredValue::new_local(None::<NoteKey>);
+    let passphrase = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+
+    let read_fragment = move || {
+        let location = window().location();
+        let hash = location.hash().unwrap_or_default();
+        let result = match SharedNote::from_fragment(&hash) {
+            Some(result) => result.map(Some),
+            None => match SharedNote::from_sealed_fragment(&hash) {
+                Some(result) => result.map(|share| {
+                    sealed.set(Some(share));
+                    None
+                }),
+                None => return,
+            },
+        };
+        key.set_value(None);
+
+        // Drop the fragment so reloading doesn't open the note again.
+        let url = format!(
+            "{}{}",
+            location.pathname().unwrap_or_default(),
+            location.search().unwrap_or_default()
+        );
+        if let Ok(history) = window().history() {
+            let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&url));
+        }
+
+        match result {
+            Ok(note) => shared.set(note),
+            Err(error) => toaster.error(&error),
+        }
+    };
+
+    let close = move || {
+        shared.set(None);
+        sealed.set(None);
+        key.set_value(None);
+        passphrase.set(String::new());
+        error.set(None);
+    };
+
+    let unlock = move || {
+        let Some(share) = sealed.get_untracked() else {
+            return;
+        };
+        let opened = NoteKey::for_sealed(&passphrase.get_untracked(), &share)
+            .and_then(|share_key| Ok((SharedNote::open_sealed(&share, &share_key)?, share_key)));
+        match opened {
+            Ok((note, share_key)) => {
+                key.set_value(Some(share_key));
+                shared.set(Some(note));
+                sealed.set(None);
+                passphrase.set(String::new());
+            }
+            Err(message) => error.set(Some(message)),
+        }
+    };
+
+    read_fragment();
+    let _ = use_event_listener(use_window(), hashchange, {
+        let read_fragment = read_fragment.clone();
+        move |_| read_fragment()
+    });
+
+    let modal_area = NodeRef::<Div>::new();
+    let _ = on_click_outside(modal_area, move |_| close());
+
+    let title = move || {
+        shared.with(|note| {
+            note.as_ref()
+                .map(|note| {
+                    note.name
+                        .clone()
+                        .unwrap_or_else(|| document_title(&note.content))
+                })
+                .unwrap_or_default()
+        })
+    };
+
+    let rendered = move || {
+        shared.with(|note| {
+            note.as_ref()
+                .map(|note| note.dialect.parse_markdown_to_html(&note.content))
+                .unwrap_or_default()
+        })
+    };
+
+    let open_as_new_document = move || {
+        if let Some(note) = shared.get_untracked() {
+            let name = note
+                .name
+                .clone()
+                .unwrap_or_else(|| document_title(&note.content));
+            let current = markdown.get_untracked();
+            match key.get_value() {
+                Some(share_key) => {
+                    let Ok(sealed_note) = share_key.seal(&note.content) else {
+                        return;
+                    };
+                    documents.update(|library| {
+                        library.create_sealed(&name, sealed_note, &current, Date::now());
+                    });
+                    vault.insert(
+                        documents.with_untracked(|library| library.active),
+                        share_key,
+                    );
+                    markdown.set(String::new());
+                }
+                None => {
+                    documents.update(|library| {
+                        library.create(&name, &note.content, &current, Date::now());
+                    });
+                    markdown.set(note.content);
+                }
+            }
+            parser.set(note.dialect);
+        }
+        close();
+    };
+
+    let is_locked = move || sealed.read().is_some();
+
+    view! {
+        <div class="modal" class:is-active=move || shared.read().is_some() || is_locked()>
+            <div class="modal-background" />
+            <div class="modal-card container" node_ref=modal_area>
+                <header class="modal-card-head">
+                    <p class="modal-card-title">{ move || if is_locked() { String::from("Encrypted Note") } else { title() } }</p>
+                    <span class="tag mr-3">Shared, read-only</span>
+                    <button class="delete" aria-label="close" on:click=move |_| close() />
+                </header>
+                <section class="modal-card-body px-5">
+                    <Show when=is_locked fallback=move || view! { <div class="content" inner_html=rendered /> }>
+                        <form on:submit=move |ev: SubmitEvent| {
+                            ev.prevent_default();
+                            unlock();
+                        }>
+                            <div class="field">
+                                <label class="label">"The shared note is encrypted"</label>
+                                <div class="control">
+                                    <input
+                                        class="input"
+                                        class:is-danger=move || error.read().is_some()
+                                        type="password"
+                                        autocomplete="off"
+                                        placeholder="Passphrase"
+                                        prop:value=passphrase
+                                        on:input=move |ev| {
+                                            passphrase.set(event_target_value(&ev));
+                                            error.set(None);
+                                        }
+                                    />
+                                </div>
+                                <p class="help is-danger">{ move || error.get() }</p>
+                            </div>
+                        </form>
+                    </Show>
+                </section>
+                <footer class="modal-card-foot" style="gap: 0.5rem">
+                    <Show
+                        when=is_locked
+                        fallback=move || view! {
+                            <Button
+                                aria_label=String::from("Open as New Document")
+                                text="Open as New Document"
+                                icon=Icon::NewFile
+                                size=Size::Small
+                                color=Color::Primary
+                                on_click=open_as_new_document
+                            />
+                        }
+                    >
+                        <Button
+                            aria_label=String::from("Unlock Shared Note")
+                            text="Unlock"
+                            icon=Icon::Unlock
+                            size=Size::Small
+                            color=Color::Primary
+                            on_click=unlock
+                        />
+                    </Show>
+                    <Button
+                        aria_label=String::from("Close")
+                        text="Close"
+                        icon=Icon::Cross
+                        size=Size::Small
+                        color=Color::Light
+                        on_click=close
+                    />
+                </footer>
+            </div>
+        </div>
+    }
+}